@@ -1,9 +1,73 @@
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use release_kit_core::config::parse_album_toml;
+use release_kit_core::types::{Album, LossyFormat, SiteLayout, format_duration};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
 
-use super::template::{detect_cover_art, generate_html, generate_player_js};
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::TagType;
+
+use super::dry_run::DryRun;
+use super::feed::generate_feed;
+use super::sync_durations::probe_duration;
+use super::template::{CoverImage, LinerNotes, generate_html, generate_player_js, theme_warnings};
+use release_kit_core::artwork::detect_cover_art;
+
+/// Outcome of a successful build
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    /// Non-fatal problems (e.g. missing audio files), also printed as they occur
+    pub warnings: Vec<String>,
+    /// Audio copies that had embedded tags removed (`strip_metadata` only)
+    pub stripped_audio: usize,
+}
+
+impl BuildReport {
+    fn warn(&mut self, msg: String) {
+        eprintln!("   ⚠ Warning: {}", msg);
+        self.warnings.push(msg);
+    }
+}
+
+/// Where a build will be served from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeployEnvironment {
+    /// The public site; indexing follows `site.allow_indexing`
+    #[default]
+    Production,
+    /// A branch or preview deploy, never indexed by search engines
+    Preview,
+}
+
+/// How `build_static_site` builds; `Default` is a plain local build
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuildOptions<'a> {
+    /// Enable verbose logging
+    pub verbose: bool,
+    /// Optional CDN URL for audio files (skips audio copy if provided)
+    pub audio_base_url: Option<&'a str>,
+    /// Path prefix when deployed under a subdirectory, or "" for root
+    pub base_path: &'a str,
+    /// Also emit a WebP version of the cover art
+    pub modern_images: bool,
+    /// Remove embedded tags from the copied audio (sources are untouched)
+    pub strip_metadata: bool,
+    /// Preview builds get `noindex` whatever `site.allow_indexing` says
+    pub environment: DeployEnvironment,
+    /// Encode FLAC/WAV tracks to the lossy `download_formats` with ffmpeg
+    /// (local audio only; the player streams the first one)
+    pub transcode: bool,
+    /// Keep `draft = true` tracks (always on for `preview`)
+    pub include_drafts: bool,
+    /// Replaces `[rss] since` for this build (`build --since`)
+    pub since: Option<NaiveDate>,
+}
 
 /// Build static site (internal implementation)
 ///
@@ -14,14 +78,25 @@ use super::template::{detect_cover_art, generate_html, generate_player_js};
 ///
 /// * `path` - Source album directory
 /// * `output` - Output directory for built site
-/// * `verbose` - Enable verbose logging
-/// * `audio_base_url` - Optional CDN URL for audio files (skips audio copy if provided)
+/// * `options` - Build options
 pub fn build_static_site(
     path: &Path,
     output: &Path,
-    verbose: bool,
-    audio_base_url: Option<&str>,
-) -> Result<()> {
+    options: &BuildOptions,
+) -> Result<BuildReport> {
+    let BuildOptions {
+        verbose,
+        audio_base_url,
+        base_path,
+        modern_images,
+        strip_metadata,
+        environment,
+        transcode,
+        include_drafts,
+        since,
+    } = *options;
+    let mut report = BuildReport::default();
+
     // Validate album directory exists
     if !path.exists() {
         anyhow::bail!("Album directory does not exist: {}", path.display());
@@ -37,23 +112,57 @@ pub fn build_static_site(
         );
     }
 
-    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    let mut album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    if environment == DeployEnvironment::Preview {
+        album.site.allow_indexing = false;
+    }
+    if since.is_some() {
+        album.rss.since = since;
+    }
+    let drafts = if include_drafts {
+        0
+    } else {
+        album.remove_drafts()
+    };
+    check_audio_file_names(&album, transcode)?;
 
     if verbose {
         println!("✓ Loaded: {}", album.metadata.title);
         println!("  Artist: {}", album.metadata.artist);
         println!("  Tracks: {}", album.tracks.len());
+        if drafts > 0 {
+            println!(
+                "  Left out {} draft track(s) (--include-drafts keeps them)",
+                drafts
+            );
+        }
         println!();
     }
 
+    // Fill in missing durations from the audio (album.toml is left untouched)
+    let probed = probe_durations(path, &mut album, &mut report);
+    if verbose && probed > 0 {
+        println!("⏱️  Detected {} track duration(s) from audio", probed);
+    }
+
+    if album.site.peak_bars {
+        let measured = measure_peaks(path, &mut album, "ffmpeg", &mut report);
+        if verbose {
+            println!("📊 Measured peaks of {} track(s)", measured);
+        }
+    }
+
     // Create output directory structure
     if verbose {
         println!("📁 Creating output directory structure...");
     }
+    let layout = album.site.layout;
     fs::create_dir_all(output).context("Failed to create output directory")?;
-    fs::create_dir_all(output.join("audio")).context("Failed to create audio directory")?;
-    fs::create_dir_all(output.join("artwork")).context("Failed to create artwork directory")?;
-    fs::create_dir_all(output.join("notes")).context("Failed to create notes directory")?;
+    if layout == SiteLayout::Nested {
+        fs::create_dir_all(output.join("audio")).context("Failed to create audio directory")?;
+        fs::create_dir_all(output.join("artwork")).context("Failed to create artwork directory")?;
+        fs::create_dir_all(output.join("notes")).context("Failed to create notes directory")?;
+    }
     if verbose {
         println!("   ✓ Created directories");
     }
@@ -68,21 +177,85 @@ pub fn build_static_site(
             println!("🎵 Copying audio files...");
         }
         let mut copied_audio = 0;
-        for track in &album.tracks {
+        for (track, filename) in album.tracks.iter().zip(album.audio_file_names()) {
             let src = path.join(&track.file);
-            let filename = track.file.file_name().context("Invalid track filename")?;
-            let dst = output.join("audio").join(filename);
+            let dst = output.join(layout.asset_path("audio", &filename));
 
             if src.exists() {
-                fs::copy(&src, &dst)
-                    .with_context(|| format!("Failed to copy {}", src.display()))?;
+                if let Some(parent) = dst.parent() {
+                    fs::create_dir_all(parent).context("Failed to create audio directory")?;
+                }
+                if strip_metadata {
+                    if strip_audio_tags(&src, &dst)? {
+                        report.stripped_audio += 1;
+                    }
+                } else {
+                    fs::copy(&src, &dst)
+                        .with_context(|| format!("Failed to copy {}", src.display()))?;
+                }
                 copied_audio += 1;
             } else {
-                eprintln!("   ⚠ Warning: Audio file not found: {}", src.display());
+                report.warn(format!("Audio file not found: {}", src.display()));
             }
         }
         if verbose {
             println!("   ✓ Copied {} audio files", copied_audio);
+            if strip_metadata {
+                println!(
+                    "   ✓ Stripped metadata from {} audio files",
+                    report.stripped_audio
+                );
+            }
+        }
+
+        if transcode {
+            if verbose {
+                println!("🎚️  Transcoding audio...");
+            }
+            let encoded = transcode_audio(path, output, &mut album, "ffmpeg", strip_metadata)?;
+            if verbose {
+                println!("   ✓ Encoded {} lossy variants", encoded);
+            }
+        }
+
+        if let Some(format) = album.distribution.stream_copy_format() {
+            if verbose {
+                println!("🎚️  Encoding {} stream copies...", format.extension());
+            }
+            let copies =
+                encode_stream_copies(path, &album, format, "ffmpeg", strip_metadata, |name| {
+                    output.join(layout.asset_path("audio", name))
+                })?;
+            if verbose {
+                println!(
+                    "   ✓ Encoded {} stream copies",
+                    copies.iter().flatten().count()
+                );
+            }
+            for (track, copy) in album.tracks.iter_mut().zip(copies) {
+                track.stream_copy = copy;
+            }
+        }
+    }
+
+    // With the audio on a CDN, the deploy uploaded the stream copies beside it
+    if audio_base_url.is_some()
+        && let Some(format) = album.distribution.stream_copy_format()
+    {
+        let filenames = album.audio_file_names();
+        for (track, filename) in album.tracks.iter_mut().zip(filenames) {
+            track.stream_copy = Some(format.stream_file_name(&filename));
+        }
+    }
+
+    // Generate preview clips (streamed instead of full tracks when downloads are paid)
+    if album.distribution.is_paid() && album.tracks.iter().any(|t| t.preview_clip.is_some()) {
+        if verbose {
+            println!("✂️  Generating preview clips...");
+        }
+        let clips = generate_preview_clips(path, output, &album, "ffmpeg")?;
+        if verbose {
+            println!("   ✓ Generated {} preview clips", clips);
         }
     }
 
@@ -97,8 +270,8 @@ pub fn build_static_site(
             let entry = entry?;
             let src_path = entry.path();
             if src_path.is_file() {
-                let filename = src_path.file_name().unwrap();
-                let dst_path = output.join("artwork").join(filename);
+                let filename = src_path.file_name().unwrap().to_string_lossy();
+                let dst_path = output.join(layout.asset_path("artwork", &filename));
                 fs::copy(&src_path, &dst_path)
                     .with_context(|| format!("Failed to copy artwork {}", src_path.display()))?;
                 copied_artwork += 1;
@@ -109,6 +282,46 @@ pub fn build_static_site(
         println!("   ✓ Copied {} artwork files", copied_artwork);
     }
 
+    // Copy merch images
+    for item in &album.merch.items {
+        let (Some(image), Some(filename)) = (&item.image, item.image_file_name()) else {
+            continue;
+        };
+        let src_path = path.join(image);
+        if !src_path.is_file() {
+            report.warn(format!("Merch image not found: {}", src_path.display()));
+            continue;
+        }
+        let dst_path = output.join(layout.asset_path("merch", &filename));
+        if let Some(parent) = dst_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        fs::copy(&src_path, &dst_path)
+            .with_context(|| format!("Failed to copy merch image {}", src_path.display()))?;
+    }
+
+    // Copy gallery images
+    for (image, filename) in album
+        .artwork
+        .gallery
+        .iter()
+        .zip(album.artwork.gallery_file_names())
+    {
+        let src_path = path.join(image);
+        if !src_path.is_file() {
+            report.warn(format!("Gallery image not found: {}", src_path.display()));
+            continue;
+        }
+        let dst_path = output.join(layout.asset_path("gallery", &filename));
+        if let Some(parent) = dst_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        fs::copy(&src_path, &dst_path)
+            .with_context(|| format!("Failed to copy gallery image {}", src_path.display()))?;
+    }
+
     // Copy liner notes
     if verbose {
         println!("📝 Copying liner notes...");
@@ -120,8 +333,8 @@ pub fn build_static_site(
             let entry = entry?;
             let src_path = entry.path();
             if src_path.is_file() {
-                let filename = src_path.file_name().unwrap();
-                let dst_path = output.join("notes").join(filename);
+                let filename = src_path.file_name().unwrap().to_string_lossy();
+                let dst_path = output.join(layout.asset_path("notes", &filename));
                 fs::copy(&src_path, &dst_path).with_context(|| {
                     format!("Failed to copy liner notes {}", src_path.display())
                 })?;
@@ -133,17 +346,133 @@ pub fn build_static_site(
         println!("   ✓ Copied {} liner note files", copied_notes);
     }
 
+    // Assemble the full-album download
+    if album.distribution.download_enabled {
+        if verbose {
+            println!("🗜️  Assembling download zip...");
+        }
+        let entries = write_download_zip(path, output, &album, strip_metadata)?;
+        if verbose {
+            println!(
+                "   ✓ Wrote {} ({} files)",
+                album.download_zip_path(),
+                entries
+            );
+        }
+    }
+
     // Generate index.html
     if verbose {
         println!("📄 Generating index.html...");
     }
-    let cover_art = detect_cover_art(&path.join("artwork"));
-    let html = generate_html(&album, cover_art.as_deref(), false, audio_base_url);
+    let cover_path = detect_cover_art(&path.join("artwork"));
+    let cover_art = cover_path
+        .as_ref()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+
+    // Read the cover's size for width/height attributes and a srcset
+    let mut cover = cover_art.as_ref().map(CoverImage::new);
+    if let (Some(cover), Some(cover_path)) = (&mut cover, &cover_path) {
+        match image::image_dimensions(cover_path) {
+            Ok((width, height)) => {
+                cover.dimensions = Some((width, height));
+                for warning in cover_size_warnings(width, height) {
+                    report.warn(warning);
+                }
+                match write_cover_variants(cover_path, output, layout, width) {
+                    Ok(variants) => {
+                        if verbose && !variants.is_empty() {
+                            let names: Vec<&str> =
+                                variants.iter().map(|(name, _)| name.as_str()).collect();
+                            println!("   ✓ Generated {}", names.join(", "));
+                        }
+                        cover.variants = variants;
+                    }
+                    Err(e) => report.warn(format!("Cover resizing failed: {:#}", e)),
+                }
+            }
+            Err(e) => report.warn(format!("Could not read cover art dimensions: {}", e)),
+        }
+    }
+
+    // Convert cover to WebP (falls back to the original on failure)
+    let modern_cover = match (&cover_path, modern_images) {
+        (Some(cover), true) => {
+            if verbose {
+                println!("🖼️  Converting cover art to WebP...");
+            }
+            match convert_cover_to_webp(cover, output, layout) {
+                Ok(Some(webp)) => {
+                    if verbose {
+                        println!("   ✓ Generated {}", webp);
+                    }
+                    Some(webp)
+                }
+                Ok(None) => {
+                    if verbose {
+                        println!("   ✓ WebP not smaller than original, keeping original only");
+                    }
+                    None
+                }
+                Err(e) => {
+                    report.warn(format!("WebP conversion failed: {:#}", e));
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    for warning in theme_warnings(&album.site) {
+        report.warn(warning);
+    }
+    if let Some(cover) = &mut cover {
+        cover.webp = modern_cover;
+    }
+    let (notes, notes_warnings) = LinerNotes::read(path, &album);
+    for warning in notes_warnings {
+        report.warn(warning);
+    }
+    let html = generate_html(
+        &album,
+        cover.as_ref(),
+        Some(&notes),
+        false,
+        audio_base_url,
+        base_path,
+    );
     fs::write(output.join("index.html"), html).context("Failed to write index.html")?;
     if verbose {
         println!("   ✓ Generated index.html");
     }
 
+    // Generate feed.xml
+    if album.rss.enabled {
+        if verbose {
+            println!("📡 Generating feed.xml...");
+        }
+        let feed = generate_feed(
+            &album,
+            path,
+            output,
+            cover_art.as_deref(),
+            audio_base_url,
+            base_path,
+        );
+        fs::write(output.join("feed.xml"), feed).context("Failed to write feed.xml")?;
+        if verbose {
+            println!("   ✓ Generated feed.xml");
+        }
+    }
+
+    // Keep search engines out (Pages and Netlify read _headers)
+    if !album.site.allow_indexing {
+        fs::write(output.join("_headers"), NOINDEX_HEADERS).context("Failed to write _headers")?;
+        if verbose {
+            println!("   ✓ Generated _headers (noindex)");
+        }
+    }
+
     // Generate player.js
     if verbose {
         println!("🎮 Generating player.js...");
@@ -154,20 +483,780 @@ pub fn build_static_site(
         println!("   ✓ Generated player.js");
     }
 
+    Ok(report)
+}
+
+/// `_headers` rules sending `X-Robots-Tag: noindex` with every response
+const NOINDEX_HEADERS: &str = "/*\n  X-Robots-Tag: noindex\n";
+
+/// Ensure no two tracks copy or upload to the same audio file
+///
+/// `audio_file_names` numbers tracks that share a basename, but its names
+/// can still meet: a numbered name against a track really called that
+/// (`disc1/intro.flac` and `01-intro.flac`), paths that the flat layout
+/// folds together (`disc1/a.flac` and `disc1-a.flac`), or names differing
+/// only in case, which macOS and Windows treat as one file. The files a
+/// build derives from the audio are checked too: `transcode` variants
+/// (`01-a.flac` and `01-a.wav` both encode to `01-a.mp3`), stream copies and
+/// preview clips. Checked before anything is written, rather than letting
+/// one track overwrite another.
+pub(crate) fn check_audio_file_names(album: &Album, transcode: bool) -> Result<()> {
+    let layout = album.site.layout;
+    let filenames = album.audio_file_names();
+
+    // Every audio file the build writes: (track index, what it is, file name)
+    let mut outputs: Vec<(usize, Option<String>, String)> = Vec::new();
+    for (i, filename) in filenames.iter().enumerate() {
+        outputs.push((i, None, filename.clone()));
+    }
+    if transcode {
+        for (i, filename) in filenames.iter().enumerate() {
+            if !is_lossless(&album.tracks[i].file) {
+                continue;
+            }
+            for format in album.distribution.lossy_formats() {
+                let variant = Path::new(filename)
+                    .with_extension(format.extension())
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                outputs.push((i, Some(format!("{} variant", format.extension())), variant));
+            }
+        }
+    }
+    if let Some(format) = album.distribution.stream_copy_format() {
+        for (i, filename) in filenames.iter().enumerate() {
+            outputs.push((
+                i,
+                Some("stream copy".to_string()),
+                format.stream_file_name(filename),
+            ));
+        }
+    }
+    if album.distribution.is_paid() {
+        for (i, track) in album.tracks.iter().enumerate() {
+            if track.preview_clip.is_some() {
+                outputs.push((
+                    i,
+                    Some("preview clip".to_string()),
+                    track.preview_file_name(),
+                ));
+            }
+        }
+    }
+
+    let describe = |i: usize, kind: &Option<String>| {
+        let track = &album.tracks[i];
+        match kind {
+            Some(kind) => format!("\"{}\" ({}, {})", track.title, track.file.display(), kind),
+            None => format!("\"{}\" ({})", track.title, track.file.display()),
+        }
+    };
+    let mut seen: BTreeMap<String, (usize, &Option<String>)> = BTreeMap::new();
+    let mut conflicts = Vec::new();
+    for (i, kind, filename) in &outputs {
+        let asset = layout.asset_path("audio", filename);
+        match seen.get(&asset.to_lowercase()) {
+            Some(&(first, first_kind)) => conflicts.push(format!(
+                "{} and {} both become {}",
+                describe(first, first_kind),
+                describe(*i, kind),
+                asset
+            )),
+            None => {
+                seen.insert(asset.to_lowercase(), (*i, kind));
+            }
+        }
+    }
+    if !conflicts.is_empty() {
+        anyhow::bail!(
+            "Track audio files collide:\n  {}\nRename one of each pair so every track gets its own file",
+            conflicts.join("\n  ")
+        );
+    }
     Ok(())
 }
 
+/// Configured durations further than this from the audio are reported
+const DURATION_MISMATCH_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// Probe each track's local audio for its duration
+///
+/// Missing durations are filled in from the audio. Configured durations are
+/// kept, but a warning is reported when they are more than
+/// `DURATION_MISMATCH_TOLERANCE` off. Audio that isn't present locally keeps
+/// its configured value (the copy step warns about it); audio that exists
+/// but can't be read is reported as a warning.
+///
+/// # Returns
+///
+/// Number of durations detected
+fn probe_durations(path: &Path, album: &mut Album, report: &mut BuildReport) -> usize {
+    let mut probed = 0;
+    for (i, track) in album.tracks.iter_mut().enumerate() {
+        let audio_path = path.join(&track.file);
+        if !audio_path.exists() {
+            continue;
+        }
+        let actual = match probe_duration(&audio_path) {
+            Ok(duration) => duration,
+            Err(e) => {
+                report.warn(format!(
+                    "Could not detect duration of {}: {:#}",
+                    audio_path.display(),
+                    e
+                ));
+                continue;
+            }
+        };
+        match track.duration {
+            None => {
+                track.duration = Some(actual);
+                probed += 1;
+            }
+            Some(configured) if configured.abs_diff(actual) > DURATION_MISMATCH_TOLERANCE => {
+                report.warn(format!(
+                    "Track {} ({}) duration mismatch: album.toml says {}, audio is {}",
+                    i + 1,
+                    track.title,
+                    format_duration(Some(configured)),
+                    format_duration(Some(actual))
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    probed
+}
+
+/// Bars in a `[site] peak_bars` chart
+const PEAK_BUCKETS: usize = 32;
+
+/// Sample rate audio is decoded at for peaks; plenty for a few dozen bars
+const PEAK_SAMPLE_RATE: u32 = 2000;
+
+/// Fill in each track's `peaks` from its local audio, decoded with ffmpeg
+///
+/// The bars are decoration, so audio that is missing or can't be decoded
+/// (or no ffmpeg at all) is reported as a warning and that track keeps a
+/// plain progress bar.
+///
+/// # Returns
+///
+/// Number of tracks measured
+fn measure_peaks(path: &Path, album: &mut Album, ffmpeg: &str, report: &mut BuildReport) -> usize {
+    let mut measured = 0;
+    for track in &mut album.tracks {
+        let src = path.join(&track.file);
+        if !src.exists() {
+            continue;
+        }
+        let output = Command::new(ffmpeg)
+            .args(["-loglevel", "error", "-i"])
+            .arg(&src)
+            .args(["-vn", "-ac", "1", "-ar"])
+            .arg(PEAK_SAMPLE_RATE.to_string())
+            .args(["-f", "s16le", "-"])
+            .output();
+        let samples = match output {
+            Ok(output) if output.status.success() => output.stdout,
+            Ok(output) => {
+                report.warn(format!(
+                    "Could not measure peaks of {}: {}",
+                    src.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+                continue;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                report.warn(
+                    "ffmpeg was not found on PATH, so [site] peak_bars has no peaks to draw"
+                        .to_string(),
+                );
+                return measured;
+            }
+            Err(e) => {
+                report.warn(format!("Failed to run ffmpeg: {}", e));
+                return measured;
+            }
+        };
+        let samples: Vec<i16> = samples
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        track.peaks = Some(coarse_peaks(&samples, PEAK_BUCKETS));
+        measured += 1;
+    }
+    measured
+}
+
+/// Loudest sample of each of `buckets` equal slices, scaled 0-9 against the
+/// loudest slice, so quiet masters still show their shape
+///
+/// Audio shorter than `buckets` samples gets one level per sample; silence
+/// is all zeros.
+pub(crate) fn coarse_peaks(samples: &[i16], buckets: usize) -> Vec<u8> {
+    if samples.is_empty() || buckets == 0 {
+        return Vec::new();
+    }
+    let len = samples.len();
+    let buckets = buckets.min(len);
+    let maxima: Vec<u16> = (0..buckets)
+        .map(|b| {
+            samples[b * len / buckets..(b + 1) * len / buckets]
+                .iter()
+                .map(|s| s.unsigned_abs())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let loudest = maxima.iter().copied().max().unwrap_or(0);
+    if loudest == 0 {
+        return vec![0; maxima.len()];
+    }
+    maxima
+        .iter()
+        .map(|&max| ((u32::from(max) * 9 + u32::from(loudest) / 2) / u32::from(loudest)) as u8)
+        .collect()
+}
+
+/// Render a preview clip for each track that has one into the site's audio location
+///
+/// # Arguments
+///
+/// * `ffmpeg` - ffmpeg executable to run
+///
+/// # Returns
+///
+/// Number of clips generated
+fn generate_preview_clips(
+    path: &Path,
+    output: &Path,
+    album: &Album,
+    ffmpeg: &str,
+) -> Result<usize> {
+    let mut generated = 0;
+    for track in &album.tracks {
+        let Some(clip) = track.preview_clip else {
+            continue;
+        };
+        let src = path.join(&track.file);
+        let dst = output.join(
+            album
+                .site
+                .layout
+                .asset_path("audio", &track.preview_file_name()),
+        );
+
+        let status = Command::new(ffmpeg)
+            .args(["-y", "-loglevel", "error", "-ss"])
+            .arg(format!("{:.3}", clip.start.as_secs_f64()))
+            .arg("-t")
+            .arg(format!("{:.3}", clip.length().as_secs_f64()))
+            .arg("-i")
+            .arg(&src)
+            .args(["-vn", "-codec:a", "libmp3lame", "-b:a", "128k"])
+            .arg(&dst)
+            .status()
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    anyhow::anyhow!(
+                        "ffmpeg is required to generate preview clips (preview_clip is set and downloads are paid)"
+                    )
+                } else {
+                    anyhow::anyhow!("Failed to run ffmpeg: {}", e)
+                }
+            })?;
+
+        if !status.success() {
+            anyhow::bail!(
+                "ffmpeg failed to create preview clip for '{}' from {}",
+                track.title,
+                src.display()
+            );
+        }
+        generated += 1;
+    }
+
+    Ok(generated)
+}
+
+/// Encode each FLAC/WAV track to every lossy format in `download_formats`
+///
+/// Variants are written next to the copied audio with the format's extension
+/// (`01-track.flac` -> `01-track.mp3`). The first format becomes each track's
+/// `stream_file`, which the player streams in place of the original.
+///
+/// # Arguments
+///
+/// * `ffmpeg` - ffmpeg executable to run
+/// * `strip_metadata` - Leave embedded tags out of the variants
+///
+/// # Returns
+///
+/// Number of variants encoded
+fn transcode_audio(
+    path: &Path,
+    output: &Path,
+    album: &mut Album,
+    ffmpeg: &str,
+    strip_metadata: bool,
+) -> Result<usize> {
+    let formats = album.distribution.lossy_formats();
+    if formats.is_empty() {
+        return Ok(0);
+    }
+    let layout = album.site.layout;
+    let filenames = album.audio_file_names();
+
+    let mut encoded = 0;
+    for (track, filename) in album.tracks.iter_mut().zip(filenames) {
+        let src = path.join(&track.file);
+        if !is_lossless(&src) || !src.exists() {
+            continue;
+        }
+
+        for format in &formats {
+            let variant = Path::new(&filename)
+                .with_extension(format.extension())
+                .to_string_lossy()
+                .replace('\\', "/");
+            let dst = output.join(layout.asset_path("audio", &variant));
+            let status =
+                ffmpeg_encode(ffmpeg, &src, &dst, *format, strip_metadata).map_err(|e| {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        anyhow::anyhow!(
+                            "ffmpeg is required for --transcode but was not found on PATH\nInstall it (e.g. 'brew install ffmpeg' or 'apt install ffmpeg') or build without --transcode"
+                        )
+                    } else {
+                        anyhow::anyhow!("Failed to run ffmpeg: {}", e)
+                    }
+                })?;
+
+            if !status.success() {
+                anyhow::bail!(
+                    "ffmpeg failed to encode '{}' to {} from {}",
+                    track.title,
+                    format.extension(),
+                    src.display()
+                );
+            }
+            if track.stream_file.is_none() {
+                track.stream_file = Some(variant);
+            }
+            encoded += 1;
+        }
+    }
+
+    Ok(encoded)
+}
+
+/// Whether `file` is FLAC or WAV, the formats `transcode` encodes from
+fn is_lossless(file: &Path) -> bool {
+    file.extension()
+        .map(|ext| {
+            let ext = ext.to_string_lossy().to_ascii_lowercase();
+            ext == "flac" || ext == "wav"
+        })
+        .unwrap_or(false)
+}
+
+/// Encode `src` to `format` at `dst` with ffmpeg, overwriting it
+fn ffmpeg_encode(
+    ffmpeg: &str,
+    src: &Path,
+    dst: &Path,
+    format: LossyFormat,
+    strip_metadata: bool,
+) -> std::io::Result<std::process::ExitStatus> {
+    let (codec, kbps) = match format {
+        LossyFormat::Mp3 { kbps } => ("libmp3lame", kbps),
+        LossyFormat::Ogg { kbps } => ("libvorbis", kbps),
+        LossyFormat::Opus { kbps } => ("libopus", kbps),
+    };
+
+    let mut command = Command::new(ffmpeg);
+    command
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(src)
+        .args(["-vn", "-codec:a", codec, "-b:a"])
+        .arg(format!("{}k", kbps));
+    if strip_metadata {
+        command.args(["-map_metadata", "-1"]);
+    }
+    command.arg(dst).status()
+}
+
+/// Encode every track's `[distribution] stream_format` copy
+///
+/// Each copy is written to `dst(name)`, `name` being the track's
+/// `LossyFormat::stream_file_name`. Tracks whose audio is missing are
+/// skipped; the audio copy or upload reports them.
+///
+/// # Arguments
+///
+/// * `path` - Album directory
+/// * `ffmpeg` - ffmpeg executable to run
+/// * `strip_metadata` - Leave embedded tags out of the copies
+/// * `dst` - Where the copy with a given name goes
+///
+/// # Returns
+///
+/// Per track, the name of its copy, or `None` when it was skipped
+pub(crate) fn encode_stream_copies(
+    path: &Path,
+    album: &Album,
+    format: LossyFormat,
+    ffmpeg: &str,
+    strip_metadata: bool,
+    dst: impl Fn(&str) -> PathBuf,
+) -> Result<Vec<Option<String>>> {
+    let mut copies = Vec::with_capacity(album.tracks.len());
+    for (track, filename) in album.tracks.iter().zip(album.audio_file_names()) {
+        let src = path.join(&track.file);
+        if !src.exists() {
+            copies.push(None);
+            continue;
+        }
+
+        let name = format.stream_file_name(&filename);
+        let dst = dst(&name);
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent).context("Failed to create audio directory")?;
+        }
+        let status = ffmpeg_encode(ffmpeg, &src, &dst, format, strip_metadata).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow::anyhow!(
+                    "ffmpeg is required for [distribution] stream_format but was not found on PATH\nInstall it (e.g. 'brew install ffmpeg' or 'apt install ffmpeg') or remove stream_format"
+                )
+            } else {
+                anyhow::anyhow!("Failed to run ffmpeg: {}", e)
+            }
+        })?;
+        if !status.success() {
+            anyhow::bail!(
+                "ffmpeg failed to encode the {} stream copy of '{}' from {}",
+                format.extension(),
+                track.title,
+                src.display()
+            );
+        }
+        copies.push(Some(name));
+    }
+    Ok(copies)
+}
+
+/// Write the full-album download zip to `Album::download_zip_path`
+///
+/// Holds every track under its built audio filename, plus the album and
+/// track liner notes under their album paths. Audio comes from the built
+/// copies when the build made them (so transcodes are left out and
+/// `strip_metadata` is already applied), otherwise from the album directory.
+/// Missing files are skipped; the copy step already warned about them.
+///
+/// # Returns
+///
+/// Number of files in the zip
+fn write_download_zip(
+    path: &Path,
+    output: &Path,
+    album: &Album,
+    strip_metadata: bool,
+) -> Result<usize> {
+    use zip::CompressionMethod;
+    use zip::write::SimpleFileOptions;
+
+    let zip_path = output.join(album.download_zip_path());
+    if let Some(parent) = zip_path.parent() {
+        fs::create_dir_all(parent).context("Failed to create download directory")?;
+    }
+    let file = fs::File::create(&zip_path)
+        .with_context(|| format!("Failed to create {}", zip_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    // Audio is already compressed; notes are text
+    let stored = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(true);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let stripped = tempfile::TempDir::new().context("Failed to create temporary directory")?;
+    let layout = album.site.layout;
+
+    let mut entries = 0;
+    for (track, filename) in album.tracks.iter().zip(album.audio_file_names()) {
+        let built = output.join(layout.asset_path("audio", &filename));
+        let source = if built.exists() {
+            built
+        } else if strip_metadata {
+            let src = path.join(&track.file);
+            if !src.exists() {
+                continue;
+            }
+            let dst = stripped.path().join(entries.to_string());
+            strip_audio_tags(&src, &dst)?;
+            dst
+        } else {
+            path.join(&track.file)
+        };
+        if !source.exists() {
+            continue;
+        }
+        zip.start_file(filename.as_str(), stored)?;
+        let mut audio = fs::File::open(&source)
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        std::io::copy(&mut audio, &mut zip)
+            .with_context(|| format!("Failed to add {} to the download zip", filename))?;
+        entries += 1;
+    }
+
+    let mut notes: Vec<&PathBuf> = album
+        .metadata
+        .liner_notes
+        .iter()
+        .chain(album.tracks.iter().filter_map(|t| t.liner_notes.as_ref()))
+        .collect();
+    notes.sort();
+    notes.dedup();
+    for note in notes {
+        let src = path.join(note);
+        if !src.is_file() {
+            continue;
+        }
+        let name = note
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        zip.start_file(name, deflated)?;
+        zip.write_all(
+            &fs::read(&src).with_context(|| format!("Failed to read {}", src.display()))?,
+        )?;
+        entries += 1;
+    }
+
+    zip.finish().context("Failed to write download zip")?;
+    Ok(entries)
+}
+
+/// Copy an audio file with every embedded tag removed
+///
+/// Covers ID3, Vorbis comments (including FLAC picture blocks), MP4 and
+/// RIFF INFO tags: whatever lofty finds in the file. The source is left as is.
+///
+/// # Returns
+///
+/// Whether the file had any tags to remove
+pub(crate) fn strip_audio_tags(src: &Path, dst: &Path) -> Result<bool> {
+    fs::copy(src, dst).with_context(|| format!("Failed to copy {}", src.display()))?;
+
+    let tag_types: Vec<TagType> = Probe::open(dst)
+        .and_then(|probe| probe.guess_file_type()?.read())
+        .with_context(|| format!("Failed to read tags from {}", src.display()))?
+        .tags()
+        .iter()
+        .map(|tag| tag.tag_type())
+        .collect();
+    for tag_type in &tag_types {
+        tag_type
+            .remove_from_path(dst)
+            .with_context(|| format!("Failed to strip metadata from {}", src.display()))?;
+    }
+
+    Ok(!tag_types.is_empty())
+}
+
+/// Write a WebP copy of the cover art into the site's artwork location
+///
+/// The image crate only encodes lossless WebP, which can be larger than a
+/// JPEG original; in that case the WebP is discarded so the `<picture>`
+/// element never points browsers at a heavier file.
+///
+/// # Returns
+///
+/// Filename of the written WebP (e.g. `cover.webp`), or `None` if it was not
+/// smaller than the original
+fn convert_cover_to_webp(
+    cover: &Path,
+    output: &Path,
+    layout: SiteLayout,
+) -> Result<Option<String>> {
+    let stem = cover
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cover".to_string());
+    let webp_name = format!("{}.webp", stem);
+    let webp_path = output.join(layout.asset_path("artwork", &webp_name));
+
+    let img = image::open(cover)
+        .with_context(|| format!("Failed to read cover art {}", cover.display()))?;
+    img.save_with_format(&webp_path, image::ImageFormat::WebP)
+        .with_context(|| format!("Failed to write {}", webp_path.display()))?;
+
+    let original_size = fs::metadata(cover)?.len();
+    let webp_size = fs::metadata(&webp_path)?.len();
+    if webp_size >= original_size {
+        fs::remove_file(&webp_path)?;
+        return Ok(None);
+    }
+
+    Ok(Some(webp_name))
+}
+
+/// Widths of the downscaled cover copies written for `srcset`
+const COVER_VARIANT_WIDTHS: [u32; 2] = [300, 600];
+
+/// Warnings for a cover that will look soft on high-density screens or be
+/// cropped in the square frame
+fn cover_size_warnings(width: u32, height: u32) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if width < 600 || height < 600 {
+        warnings.push(format!(
+            "Cover art is {}x{}; use at least 600x600 so it stays sharp on high-density screens",
+            width, height
+        ));
+    }
+    if width != height {
+        warnings.push(format!(
+            "Cover art is {}x{}, not square; it will be cropped to fit",
+            width, height
+        ));
+    }
+    warnings
+}
+
+/// Write downscaled copies of the cover art next to the original
+///
+/// One copy per entry in `COVER_VARIANT_WIDTHS` the cover is wider than,
+/// named `<stem>-<width>.<ext>`. Returns (filename, width), smallest first.
+fn write_cover_variants(
+    cover: &Path,
+    output: &Path,
+    layout: SiteLayout,
+    width: u32,
+) -> Result<Vec<(String, u32)>> {
+    let targets: Vec<u32> = COVER_VARIANT_WIDTHS
+        .into_iter()
+        .filter(|target| width > *target)
+        .collect();
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let stem = cover
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "cover".to_string());
+    let ext = cover
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| "png".to_string());
+    let img = image::open(cover)
+        .with_context(|| format!("Failed to read cover art {}", cover.display()))?;
+
+    let mut variants = Vec::new();
+    for target in targets {
+        let height = (u64::from(img.height()) * u64::from(target) / u64::from(img.width())).max(1);
+        let name = format!("{}-{}.{}", stem, target, ext);
+        let dst = output.join(layout.asset_path("artwork", &name));
+        img.resize_exact(target, height as u32, image::imageops::FilterType::Lanczos3)
+            .save(&dst)
+            .with_context(|| format!("Failed to write {}", dst.display()))?;
+        variants.push((name, target));
+    }
+    Ok(variants)
+}
+
 /// Build static site for deployment (command interface)
-pub async fn run(path: PathBuf, output: PathBuf) -> Result<()> {
+///
+/// With `check`, the full build runs into a temporary directory that is
+/// removed afterwards, so CI can verify a build would succeed without
+/// producing artifacts. A dry run builds the same way, then lists every file
+/// it would have written to `output`.
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    path: PathBuf,
+    output: Option<PathBuf>,
+    base_path: Option<String>,
+    modern_images: bool,
+    check: bool,
+    strip_metadata: bool,
+    transcode: bool,
+    include_drafts: bool,
+    since: Option<NaiveDate>,
+    dry_run: DryRun,
+) -> Result<()> {
+    let options = BuildOptions {
+        base_path: base_path.as_deref().unwrap_or(""),
+        modern_images,
+        strip_metadata,
+        transcode,
+        include_drafts,
+        since,
+        ..Default::default()
+    };
+
+    if check {
+        println!("🔍 Checking build (no output will be written)...");
+        println!("   Source: {}", path.display());
+        println!();
+
+        let report = check_build(&path, &options)?;
+
+        println!();
+        if report.warnings.is_empty() {
+            println!("✅ Build check passed");
+        } else {
+            println!(
+                "✅ Build check passed with {} warning(s)",
+                report.warnings.len()
+            );
+        }
+        return Ok(());
+    }
+
+    let output = output.context("--output is required unless --check is given")?;
+
+    if dry_run.is_enabled() {
+        println!("🔨 Building static site (dry run)...");
+        println!("   Source: {}", path.display());
+        println!("   Output: {}", output.display());
+        println!();
+
+        let sink = tempfile::TempDir::new().context("Failed to create temporary directory")?;
+        build_static_site(&path, sink.path(), &options)?;
+        let files = dry_run.report_staged(sink.path(), &output)?;
+
+        println!();
+        println!(
+            "✅ Dry run complete - {} file(s) would be written, nothing was",
+            files
+        );
+        return Ok(());
+    }
+
     println!("🔨 Building static site...");
     println!("   Source: {}", path.display());
     println!("   Output: {}", output.display());
+    if !options.base_path.is_empty() {
+        println!("   Base path: {}", options.base_path);
+    }
     println!();
 
-    build_static_site(&path, &output, true, None)?;
+    let report = build_static_site(
+        &path,
+        &output,
+        &BuildOptions {
+            verbose: true,
+            ..options
+        },
+    )?;
 
     println!();
     println!("✅ Build complete!");
+    if strip_metadata {
+        println!(
+            "   Stripped metadata: {} audio file(s)",
+            report.stripped_audio
+        );
+    }
     println!("   Output: {}", output.display());
     println!();
     println!("To test locally:");
@@ -176,3 +1265,1329 @@ pub async fn run(path: PathBuf, output: PathBuf) -> Result<()> {
 
     Ok(())
 }
+
+/// Run the full build against a throwaway directory and return its report
+fn check_build(path: &Path, options: &BuildOptions) -> Result<BuildReport> {
+    let sink = tempfile::TempDir::new().context("Failed to create temporary directory")?;
+    build_static_site(path, sink.path(), options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{album_toml, write_wav};
+    use tempfile::TempDir;
+
+    /// Write a minimal album.toml with the given `[[track]]` entries
+    fn write_album_toml(dir: &Path, tracks: &str) {
+        fs::write(dir.join("album.toml"), album_toml(tracks)).unwrap();
+    }
+
+    #[test]
+    fn test_build_renders_liner_notes() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("notes")).unwrap();
+        fs::write(
+            src.path().join("notes/first.md"),
+            "Tracked **to tape**.\n\n<script>alert(1)</script>",
+        )
+        .unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-first.flac"
+title = "First"
+liner_notes = "notes/first.md"
+
+[[track]]
+file = "audio/02-second.flac"
+title = "Second"
+liner_notes = "notes/missing.md"
+"#,
+        );
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains("<strong>to tape</strong>"));
+        assert!(!html.contains("alert(1)"));
+        assert!(
+            report
+                .warnings
+                .iter()
+                .any(|w| w.contains("Could not read liner notes notes/missing.md"))
+        );
+    }
+
+    #[test]
+    fn test_build_copies_merch_images() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir_all(src.path().join("merch")).unwrap();
+        fs::write(src.path().join("merch/vinyl.jpg"), b"jpeg").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[merch.items]]
+name = "Vinyl"
+url = "https://shop.example.com/lp"
+image = "merch/vinyl.jpg"
+
+[[merch.items]]
+name = "CD"
+url = "https://shop.example.com/cd"
+image = "merch/cd.jpg"
+"#,
+        );
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        assert_eq!(
+            fs::read(out.path().join("merch/vinyl.jpg")).unwrap(),
+            b"jpeg"
+        );
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert!(report.warnings[0].contains("Merch image not found"));
+        assert!(report.warnings[0].contains("cd.jpg"));
+    }
+
+    #[test]
+    fn test_build_leaves_out_drafts_unless_included() {
+        let src = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-done.flac"), b"done").unwrap();
+        fs::write(src.path().join("audio/02-wip.flac"), b"wip").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-done.flac"
+title = "Finished Song"
+
+[[track]]
+file = "audio/02-wip.flac"
+title = "Unfinished Song"
+draft = true
+"#,
+        );
+
+        // A normal build (and so every deploy)
+        let out = TempDir::new().unwrap();
+        build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains("Finished Song"));
+        assert!(!html.contains("Unfinished Song"));
+        assert!(out.path().join("audio/01-done.flac").exists());
+        assert!(!out.path().join("audio/02-wip.flac").exists());
+        let feed = fs::read_to_string(out.path().join("feed.xml")).unwrap();
+        assert!(!feed.contains("Unfinished Song"));
+
+        // What `preview` and `--include-drafts` build
+        let out = TempDir::new().unwrap();
+        build_static_site(
+            src.path(),
+            out.path(),
+            &BuildOptions {
+                include_drafts: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains("Unfinished Song"));
+        assert!(out.path().join("audio/02-wip.flac").exists());
+    }
+
+    #[test]
+    fn test_build_since_overrides_rss_since() {
+        let src = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-song.flac"), b"song").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-song.flac"
+title = "Song"
+"#,
+        );
+        let feed_with_since = |since: Option<&str>| {
+            let out = TempDir::new().unwrap();
+            build_static_site(
+                src.path(),
+                out.path(),
+                &BuildOptions {
+                    since: since.map(|date| date.parse().unwrap()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            fs::read_to_string(out.path().join("feed.xml")).unwrap()
+        };
+
+        // Released 2025-11-15
+        assert!(feed_with_since(None).contains("<item>"));
+        assert!(feed_with_since(Some("2025-11-15")).contains("<item>"));
+        assert!(!feed_with_since(Some("2025-11-16")).contains("<item>"));
+    }
+
+    #[test]
+    fn test_build_copies_gallery_images() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir_all(src.path().join("photos")).unwrap();
+        fs::write(src.path().join("photos/studio.jpg"), b"jpeg").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[artwork]
+gallery = ["photos/studio.jpg", "photos/missing.png"]
+"#,
+        );
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        assert_eq!(
+            fs::read(out.path().join("gallery/studio.jpg")).unwrap(),
+            b"jpeg"
+        );
+        assert!(
+            fs::read_to_string(out.path().join("index.html"))
+                .unwrap()
+                .contains(r#"src="/gallery/studio.jpg""#)
+        );
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert!(report.warnings[0].contains("Gallery image not found"));
+        assert!(report.warnings[0].contains("missing.png"));
+    }
+
+    #[test]
+    fn test_build_disambiguates_identical_basenames() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir_all(src.path().join("audio/disc1")).unwrap();
+        fs::create_dir_all(src.path().join("audio/disc2")).unwrap();
+        fs::write(src.path().join("audio/disc1/track.flac"), b"first").unwrap();
+        fs::write(src.path().join("audio/disc2/track.flac"), b"second").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/disc1/track.flac"
+title = "First"
+
+[[track]]
+file = "audio/disc2/track.flac"
+title = "Second"
+"#,
+        );
+
+        build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        let audio_out = out.path().join("audio");
+        assert_eq!(fs::read(audio_out.join("01-track.flac")).unwrap(), b"first");
+        assert_eq!(
+            fs::read(audio_out.join("02-track.flac")).unwrap(),
+            b"second"
+        );
+        assert!(!audio_out.join("track.flac").exists());
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"data-src="/audio/01-track.flac""#));
+        assert!(html.contains(r#"data-src="/audio/02-track.flac""#));
+
+        // [rss] enabled: the feed encloses the renamed files too
+        let feed = fs::read_to_string(out.path().join("feed.xml")).unwrap();
+        assert!(feed.contains(r#"url="https://test.example.com/audio/01-track.flac""#));
+        assert!(feed.contains(r#"url="https://test.example.com/audio/02-track.flac""#));
+    }
+
+    #[test]
+    fn test_build_rejects_colliding_audio_file_names() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir_all(src.path().join("audio/disc1")).unwrap();
+        fs::create_dir_all(src.path().join("audio/disc2")).unwrap();
+        fs::write(src.path().join("audio/disc1/intro.flac"), b"first").unwrap();
+        fs::write(src.path().join("audio/disc2/intro.flac"), b"second").unwrap();
+        fs::write(src.path().join("audio/01-intro.flac"), b"third").unwrap();
+        // disc1/intro.flac is numbered 01-intro.flac, the third track's own name
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/disc1/intro.flac"
+title = "First"
+
+[[track]]
+file = "audio/disc2/intro.flac"
+title = "Second"
+
+[[track]]
+file = "audio/01-intro.flac"
+title = "Third"
+"#,
+        );
+
+        let err = build_static_site(src.path(), out.path(), &BuildOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Track audio files collide"), "{}", err);
+        assert!(
+            err.contains(
+                r#""First" (audio/disc1/intro.flac) and "Third" (audio/01-intro.flac) both become audio/01-intro.flac"#
+            ),
+            "{}",
+            err
+        );
+        assert!(!err.contains("Second"), "{}", err);
+        // Nothing was copied before the check
+        assert!(!out.path().join("audio").exists());
+
+        // Names differing only in case are one file on macOS and Windows
+        let mut album = parse_album_toml(src.path().join("album.toml")).unwrap();
+        album.tracks[2].file = "audio/Intro.flac".into();
+        album.tracks.remove(1);
+        album.tracks[0].file = "audio/intro.flac".into();
+        let err = check_audio_file_names(&album, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("both become audio/Intro.flac"), "{}", err);
+    }
+
+    #[test]
+    fn test_build_rejects_colliding_derived_audio_files() {
+        let album = |tracks: &str, distribution: &str| {
+            let toml = album_toml(tracks)
+                .replace("download_enabled = false", "download_enabled = true")
+                .replace(
+                    r#"download_formats = ["flac"]"#,
+                    &format!("download_formats = [\"flac\", \"mp3\"]\n{}", distribution),
+                );
+            release_kit_core::config::parse_album_toml_str(&toml).unwrap()
+        };
+        let two_takes = r#"
+[[track]]
+file = "audio/01-a.flac"
+title = "Album Take"
+
+[[track]]
+file = "audio/01-a.wav"
+title = "Demo Take"
+"#;
+        let masters = album(two_takes, "");
+
+        // The sources differ, but --transcode encodes both to 01-a.mp3
+        check_audio_file_names(&masters, false).unwrap();
+        let err = check_audio_file_names(&masters, true)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(
+                r#""Album Take" (audio/01-a.flac, mp3 variant) and "Demo Take" (audio/01-a.wav, mp3 variant) both become audio/01-a.mp3"#
+            ),
+            "{}",
+            err
+        );
+
+        // Or onto a track that really is 01-a.mp3
+        let variant_and_track = album(
+            r#"
+[[track]]
+file = "audio/01-a.mp3"
+title = "Radio Edit"
+
+[[track]]
+file = "audio/01-a.flac"
+title = "Album Take"
+"#,
+            "",
+        );
+        let err = check_audio_file_names(&variant_and_track, true)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(
+                r#""Radio Edit" (audio/01-a.mp3) and "Album Take" (audio/01-a.flac, mp3 variant) both become audio/01-a.mp3"#
+            ),
+            "{}",
+            err
+        );
+
+        // Stream copies are named after the audio the same way
+        let streamed = album(two_takes, "stream_format = \"opus-96\"");
+        let err = check_audio_file_names(&streamed, false)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("(audio/01-a.wav, stream copy) both become audio/01-a.stream.opus"),
+            "{}",
+            err
+        );
+
+        // Preview clips are named after the title, so two "Intro"s share one
+        let previews = album(
+            r#"
+[[track]]
+file = "audio/01-intro.flac"
+title = "Intro"
+preview_clip = "0:00-0:30"
+
+[[track]]
+file = "audio/02-intro.flac"
+title = "Intro"
+preview_clip = "0:00-0:30"
+"#,
+            "download_price = 5.0",
+        );
+        let err = check_audio_file_names(&previews, false)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("both become audio/intro-preview.mp3"),
+            "{}",
+            err
+        );
+
+        // A transcode that collides fails the build before anything is written
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-a.flac"), b"flac").unwrap();
+        write_wav(&src.path().join("audio/01-a.wav"), 1);
+        fs::write(
+            src.path().join("album.toml"),
+            album_toml(two_takes).replace(
+                r#"download_formats = ["flac"]"#,
+                r#"download_formats = ["flac", "mp3"]"#,
+            ),
+        )
+        .unwrap();
+        let err = build_static_site(
+            src.path(),
+            out.path(),
+            &BuildOptions {
+                transcode: true,
+                ..Default::default()
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("both become audio/01-a.mp3"), "{}", err);
+        assert!(!out.path().join("audio").exists());
+    }
+
+    #[test]
+    fn test_build_preserves_nested_audio_paths() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir_all(src.path().join("audio/disc1")).unwrap();
+        fs::create_dir_all(src.path().join("audio/disc2")).unwrap();
+        fs::write(src.path().join("audio/disc1/track.flac"), b"first").unwrap();
+        fs::write(src.path().join("audio/disc2/track.flac"), b"second").unwrap();
+        let toml_path = src.path().join("album.toml");
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/disc1/track.flac"
+title = "First"
+
+[[track]]
+file = "audio/disc2/track.flac"
+title = "Second"
+"#,
+        );
+        let toml = fs::read_to_string(&toml_path).unwrap().replace(
+            "accent_color = \"#ff6b35\"",
+            "accent_color = \"#ff6b35\"\npreserve_audio_paths = true",
+        );
+        fs::write(&toml_path, toml).unwrap();
+
+        let album = parse_album_toml(&toml_path).unwrap();
+        assert_eq!(
+            album.audio_file_names(),
+            vec!["disc1/track.flac", "disc2/track.flac"]
+        );
+
+        build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        let audio_out = out.path().join("audio");
+        assert_eq!(
+            fs::read(audio_out.join("disc1/track.flac")).unwrap(),
+            b"first"
+        );
+        assert_eq!(
+            fs::read(audio_out.join("disc2/track.flac")).unwrap(),
+            b"second"
+        );
+        assert!(!audio_out.join("01-track.flac").exists());
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"data-src="/audio/disc1/track.flac""#));
+        assert!(html.contains(r#"data-src="/audio/disc2/track.flac""#));
+
+        // R2 keys come from the same names
+        let manifest =
+            crate::commands::deploy::DeployManifest::from_album(src.path(), &album).unwrap();
+        let keys: Vec<_> = manifest.tracks.iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["audio/disc1/track.flac", "audio/disc2/track.flac"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_generate_preview_clips_runs_ffmpeg() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::create_dir_all(out.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-track.flac"), b"audio").unwrap();
+
+        // Stand-in ffmpeg that records its arguments into the output file (last arg)
+        let fake_ffmpeg = src.path().join("ffmpeg");
+        fs::write(
+            &fake_ffmpeg,
+            "#!/bin/sh\nfor last; do :; done\necho \"$@\" > \"$last\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&fake_ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+preview_clip = "0:30-1:00"
+"#,
+        );
+        let album = parse_album_toml(src.path().join("album.toml")).unwrap();
+
+        let generated = generate_preview_clips(
+            src.path(),
+            out.path(),
+            &album,
+            fake_ffmpeg.to_str().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(generated, 1);
+        let args = fs::read_to_string(out.path().join("audio/track-one-preview.mp3")).unwrap();
+        assert!(args.contains("-ss 30.000 -t 30.000"), "{}", args);
+        assert!(args.contains("01-track.flac"), "{}", args);
+    }
+
+    #[test]
+    fn test_generate_preview_clips_missing_ffmpeg() {
+        let src = TempDir::new().unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+preview_clip = "0:30-1:00"
+"#,
+        );
+        let album = parse_album_toml(src.path().join("album.toml")).unwrap();
+
+        let err =
+            generate_preview_clips(src.path(), src.path(), &album, "release-kit-no-such-ffmpeg")
+                .unwrap_err();
+        assert!(err.to_string().contains("ffmpeg is required"));
+    }
+
+    #[test]
+    fn test_coarse_peaks_scales_to_loudest_bucket() {
+        // Quiet, loud, silent and half-loud quarters
+        let mut samples = vec![100i16, -100];
+        samples.extend([-1000, 1000]);
+        samples.extend([0, 0]);
+        samples.extend([500, -500]);
+        assert_eq!(coarse_peaks(&samples, 4), vec![1, 9, 0, 5]);
+
+        // i16::MIN doesn't overflow, silence is flat
+        assert_eq!(coarse_peaks(&[i16::MIN, 0], 2), vec![9, 0]);
+        assert_eq!(coarse_peaks(&[0; 10], 5), vec![0; 5]);
+        assert!(coarse_peaks(&[], 5).is_empty());
+        assert_eq!(coarse_peaks(&[1; 100], PEAK_BUCKETS).len(), PEAK_BUCKETS);
+        assert_eq!(coarse_peaks(&[1, 2], PEAK_BUCKETS), vec![5, 9]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_measure_peaks_decodes_with_ffmpeg() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-track.flac"), b"flac").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+
+[[track]]
+file = "audio/02-missing.flac"
+title = "Missing"
+"#,
+        );
+        // Stand-in ffmpeg that "decodes" to two s16le samples: 1000 and -2000
+        let fake_ffmpeg = src.path().join("ffmpeg");
+        fs::write(&fake_ffmpeg, "#!/bin/sh\nprintf '\\350\\003\\060\\370'\n").unwrap();
+        fs::set_permissions(&fake_ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut album = parse_album_toml(src.path().join("album.toml")).unwrap();
+        let mut report = BuildReport::default();
+
+        let measured = measure_peaks(
+            src.path(),
+            &mut album,
+            fake_ffmpeg.to_str().unwrap(),
+            &mut report,
+        );
+        assert_eq!(measured, 1);
+        assert_eq!(album.tracks[0].peaks, Some(vec![5, 9]));
+        assert_eq!(album.tracks[1].peaks, None);
+        assert!(report.warnings.is_empty());
+
+        // No ffmpeg: a warning, not a failed build
+        album.tracks[0].peaks = None;
+        let measured = measure_peaks(
+            src.path(),
+            &mut album,
+            "release-kit-no-such-ffmpeg",
+            &mut report,
+        );
+        assert_eq!(measured, 0);
+        assert_eq!(album.tracks[0].peaks, None);
+        assert!(report.warnings[0].contains("peak_bars"));
+    }
+
+    /// Album with a WAV, a FLAC and an MP3 track, offered as MP3 and OGG downloads
+    fn write_transcode_album(dir: &Path) {
+        fs::create_dir_all(dir.join("audio")).unwrap();
+        write_wav(&dir.join("audio/01-first.wav"), 1);
+        fs::write(dir.join("audio/02-second.flac"), b"flac").unwrap();
+        fs::write(dir.join("audio/03-third.mp3"), b"mp3").unwrap();
+        write_album_toml(
+            dir,
+            r#"
+[[track]]
+file = "audio/01-first.wav"
+title = "First"
+
+[[track]]
+file = "audio/02-second.flac"
+title = "Second"
+
+[[track]]
+file = "audio/03-third.mp3"
+title = "Third"
+"#,
+        );
+        let toml = fs::read_to_string(dir.join("album.toml")).unwrap().replace(
+            r#"download_formats = ["flac"]"#,
+            r#"download_formats = ["flac", "mp3-320", "ogg"]"#,
+        );
+        fs::write(dir.join("album.toml"), toml).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_transcode_audio_runs_ffmpeg_per_format() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(out.path().join("audio")).unwrap();
+        write_transcode_album(src.path());
+
+        // Stand-in ffmpeg that records its arguments into the output file (last arg)
+        let fake_ffmpeg = src.path().join("ffmpeg");
+        fs::write(
+            &fake_ffmpeg,
+            "#!/bin/sh\nfor last; do :; done\necho \"$@\" > \"$last\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&fake_ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut album = parse_album_toml(src.path().join("album.toml")).unwrap();
+        let encoded = transcode_audio(
+            src.path(),
+            out.path(),
+            &mut album,
+            fake_ffmpeg.to_str().unwrap(),
+            false,
+        )
+        .unwrap();
+
+        // Lossless tracks only, one variant per lossy format
+        assert_eq!(encoded, 4);
+        let mp3 = fs::read_to_string(out.path().join("audio/01-first.mp3")).unwrap();
+        assert!(
+            mp3.contains("01-first.wav -vn -codec:a libmp3lame -b:a 320k"),
+            "{}",
+            mp3
+        );
+        let ogg = fs::read_to_string(out.path().join("audio/02-second.ogg")).unwrap();
+        assert!(ogg.contains("-codec:a libvorbis -b:a 192k"), "{}", ogg);
+        assert!(out.path().join("audio/02-second.mp3").exists());
+        assert!(!out.path().join("audio/03-third.ogg").exists());
+
+        let streamed: Vec<_> = album.tracks.iter().map(|t| t.stream_file.clone()).collect();
+        assert_eq!(
+            streamed,
+            vec![
+                Some("01-first.mp3".to_string()),
+                Some("02-second.mp3".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transcode_audio_missing_ffmpeg() {
+        let src = TempDir::new().unwrap();
+        write_transcode_album(src.path());
+        let mut album = parse_album_toml(src.path().join("album.toml")).unwrap();
+
+        let err = transcode_audio(
+            src.path(),
+            src.path(),
+            &mut album,
+            "release-kit-no-such-ffmpeg",
+            false,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("ffmpeg is required for --transcode")
+        );
+        assert!(err.to_string().contains("build without --transcode"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_encode_stream_copies_runs_ffmpeg() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-first.flac"), b"flac").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-first.flac"
+title = "First"
+
+[[track]]
+file = "audio/02-missing.flac"
+title = "Missing"
+"#,
+        );
+        let toml = fs::read_to_string(src.path().join("album.toml"))
+            .unwrap()
+            .replace(
+                r#"download_formats = ["flac"]"#,
+                "download_formats = [\"flac\"]\nstream_format = \"opus-96\"",
+            );
+        fs::write(src.path().join("album.toml"), toml).unwrap();
+
+        // Stand-in ffmpeg that records its arguments into the output file (last arg)
+        let fake_ffmpeg = src.path().join("ffmpeg");
+        fs::write(
+            &fake_ffmpeg,
+            "#!/bin/sh\nfor last; do :; done\necho \"$@\" > \"$last\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&fake_ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let album = parse_album_toml(src.path().join("album.toml")).unwrap();
+        let format = album.distribution.stream_copy_format().unwrap();
+        let copies = encode_stream_copies(
+            src.path(),
+            &album,
+            format,
+            fake_ffmpeg.to_str().unwrap(),
+            true,
+            |name| out.path().join("audio").join(name),
+        )
+        .unwrap();
+
+        assert_eq!(copies, vec![Some("01-first.stream.opus".to_string()), None]);
+        let opus = fs::read_to_string(out.path().join("audio/01-first.stream.opus")).unwrap();
+        assert!(
+            opus.contains("01-first.flac -vn -codec:a libopus -b:a 96k -map_metadata -1"),
+            "{}",
+            opus
+        );
+        // The master is left as it is, for the downloads
+        assert_eq!(
+            fs::read(src.path().join("audio/01-first.flac")).unwrap(),
+            b"flac"
+        );
+
+        let err = encode_stream_copies(
+            src.path(),
+            &album,
+            format,
+            "release-kit-no-such-ffmpeg",
+            false,
+            |name| out.path().join(name),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("[distribution] stream_format"));
+    }
+
+    #[test]
+    fn test_build_transcode_with_ffmpeg() {
+        if Command::new("ffmpeg").arg("-version").output().is_err() {
+            eprintln!("skipping: ffmpeg not on PATH");
+            return;
+        }
+
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        write_wav(&src.path().join("audio/01-first.wav"), 1);
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-first.wav"
+title = "First"
+"#,
+        );
+        let toml = fs::read_to_string(src.path().join("album.toml"))
+            .unwrap()
+            .replace(r#"["flac"]"#, r#"["flac", "mp3-128"]"#);
+        fs::write(src.path().join("album.toml"), toml).unwrap();
+
+        build_static_site(
+            src.path(),
+            out.path(),
+            &BuildOptions {
+                transcode: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mp3 = out.path().join("audio/01-first.mp3");
+        assert!(
+            Probe::open(&mp3)
+                .unwrap()
+                .guess_file_type()
+                .unwrap()
+                .file_type()
+                == Some(lofty::file::FileType::Mpeg)
+        );
+        assert!(out.path().join("audio/01-first.wav").exists());
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"data-src="/audio/01-first.mp3""#));
+    }
+
+    #[test]
+    fn test_build_writes_download_zip() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::create_dir_all(src.path().join("notes")).unwrap();
+        fs::write(src.path().join("audio/01-first.flac"), b"first").unwrap();
+        fs::write(src.path().join("audio/02-second.flac"), b"second").unwrap();
+        fs::write(src.path().join("notes/first.md"), b"# First").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-first.flac"
+title = "First"
+liner_notes = "notes/first.md"
+
+[[track]]
+file = "audio/02-second.flac"
+title = "Second"
+"#,
+        );
+        let toml = fs::read_to_string(src.path().join("album.toml"))
+            .unwrap()
+            .replace("download_enabled = false", "download_enabled = true");
+        fs::write(src.path().join("album.toml"), toml).unwrap();
+
+        build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        let zip = fs::File::open(out.path().join("download/test-album.zip")).unwrap();
+        let mut archive = zip::ZipArchive::new(zip).unwrap();
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        assert_eq!(
+            names,
+            vec!["01-first.flac", "02-second.flac", "notes/first.md"]
+        );
+        let mut contents = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("02-second.flac").unwrap(),
+            &mut contents,
+        )
+        .unwrap();
+        assert_eq!(contents, "second");
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"href="/download/test-album.zip""#));
+    }
+
+    #[test]
+    fn test_build_modern_images_emits_webp_picture() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir(src.path().join("artwork")).unwrap();
+        // Noisy PNG so lossless WebP beats deflate
+        let cover = image::RgbImage::from_fn(64, 64, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 4) as u8, ((x * y) % 256) as u8])
+        });
+        cover.save(src.path().join("artwork/cover.png")).unwrap();
+        write_album_toml(src.path(), "");
+
+        build_static_site(
+            src.path(),
+            out.path(),
+            &BuildOptions {
+                modern_images: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(out.path().join("artwork/cover.webp").exists());
+        assert!(out.path().join("artwork/cover.png").exists());
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"<picture><source srcset="/artwork/cover.webp" type="image/webp"><img src="/artwork/cover.png" width="64" height="64" alt="Album cover" class="cover-art"></picture>"#));
+        assert!(html.contains(r#"class="player-album-art"></picture>"#));
+    }
+
+    #[test]
+    fn test_build_cover_dimensions_and_downscaled_variants() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir(src.path().join("artwork")).unwrap();
+        image::RgbImage::new(1000, 1000)
+            .save(src.path().join("artwork/cover.png"))
+            .unwrap();
+        write_album_toml(src.path(), "");
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+        assert_eq!(
+            image::image_dimensions(out.path().join("artwork/cover-600.png")).unwrap(),
+            (600, 600)
+        );
+        assert_eq!(
+            image::image_dimensions(out.path().join("artwork/cover-300.png")).unwrap(),
+            (300, 300)
+        );
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"<img src="/artwork/cover.png" srcset="/artwork/cover-300.png 300w, /artwork/cover-600.png 600w, /artwork/cover.png 1000w" sizes="300px" width="1000" height="1000" alt="Album cover" class="cover-art">"#));
+    }
+
+    #[test]
+    fn test_build_warns_on_small_or_non_square_cover() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir(src.path().join("artwork")).unwrap();
+        image::RgbImage::new(400, 300)
+            .save(src.path().join("artwork/cover.png"))
+            .unwrap();
+        write_album_toml(src.path(), "");
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+        assert_eq!(report.warnings.len(), 2, "{:?}", report.warnings);
+        assert!(report.warnings[0].contains("at least 600x600"));
+        assert!(report.warnings[1].contains("not square"));
+
+        // Only the 300px variant is smaller than the original
+        assert!(out.path().join("artwork/cover-300.png").exists());
+        assert!(!out.path().join("artwork/cover-600.png").exists());
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"width="400" height="300""#));
+    }
+
+    #[test]
+    fn test_build_without_modern_images_has_no_picture() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir(src.path().join("artwork")).unwrap();
+        image::RgbImage::new(8, 8)
+            .save(src.path().join("artwork/cover.png"))
+            .unwrap();
+        write_album_toml(src.path(), "");
+
+        build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        assert!(!out.path().join("artwork/cover.webp").exists());
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(!html.contains("<picture><source"));
+    }
+
+    #[test]
+    fn test_check_build_writes_nothing_but_reports_missing_audio() {
+        let src = TempDir::new().unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/missing.flac"
+title = "Missing"
+"#,
+        );
+        let before: Vec<_> = fs::read_dir(src.path()).unwrap().flatten().collect();
+
+        let report = check_build(src.path(), &BuildOptions::default()).unwrap();
+
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("Audio file not found"));
+        assert!(report.warnings[0].contains("missing.flac"));
+
+        // Source directory is untouched (no build output written next to it)
+        let after: Vec<_> = fs::read_dir(src.path()).unwrap().flatten().collect();
+        assert_eq!(before.len(), after.len());
+    }
+
+    #[tokio::test]
+    async fn test_build_dry_run_creates_no_files() {
+        let src = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-track.flac"), b"audio").unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "Track"
+"#,
+        );
+        let out = TempDir::new().unwrap();
+        let output = out.path().join("dist");
+
+        run(
+            src.path().to_path_buf(),
+            Some(output.clone()),
+            None,
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            DryRun::new(true),
+        )
+        .await
+        .unwrap();
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_check_build_fails_on_invalid_album() {
+        let src = TempDir::new().unwrap();
+        assert!(check_build(src.path(), &BuildOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_build_flat_layout_puts_assets_at_root() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::create_dir_all(src.path().join("artwork")).unwrap();
+        fs::create_dir_all(src.path().join("notes")).unwrap();
+        fs::write(src.path().join("audio/01-track.flac"), b"audio").unwrap();
+        fs::write(src.path().join("artwork/cover.png"), b"png").unwrap();
+        fs::write(src.path().join("notes/liner.md"), b"notes").unwrap();
+        let toml_path = src.path().join("album.toml");
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "One"
+"#,
+        );
+        let toml = fs::read_to_string(&toml_path).unwrap().replace(
+            "accent_color = \"#ff6b35\"",
+            "accent_color = \"#ff6b35\"\nlayout = \"flat\"",
+        );
+        fs::write(&toml_path, toml).unwrap();
+
+        build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+
+        assert_eq!(
+            fs::read(out.path().join("audio-01-track.flac")).unwrap(),
+            b"audio"
+        );
+        assert_eq!(
+            fs::read(out.path().join("artwork-cover.png")).unwrap(),
+            b"png"
+        );
+        assert_eq!(
+            fs::read(out.path().join("notes-liner.md")).unwrap(),
+            b"notes"
+        );
+        for dir in ["audio", "artwork", "notes"] {
+            assert!(!out.path().join(dir).exists(), "{} should not exist", dir);
+        }
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"data-src="/audio-01-track.flac""#));
+        assert!(html.contains(r#"src="/artwork-cover.png""#));
+        assert!(!html.contains("/audio/"));
+        assert!(!html.contains("/artwork/"));
+    }
+
+    #[test]
+    fn test_build_probes_missing_durations() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        write_wav(&src.path().join("audio/01-probed.wav"), 3);
+        write_wav(&src.path().join("audio/02-configured.wav"), 3);
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-probed.wav"
+title = "Probed"
+
+[[track]]
+file = "audio/02-configured.wav"
+title = "Configured"
+duration = "4:10"
+"#,
+        );
+        let toml_before = fs::read_to_string(src.path().join("album.toml")).unwrap();
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+        assert_eq!(
+            report.warnings,
+            vec!["Track 2 (Configured) duration mismatch: album.toml says 4:10, audio is 0:03"]
+        );
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"<span class="track-duration">0:03</span>"#));
+        // Configured durations are kept, even when they disagree with the audio
+        assert!(html.contains(r#"<span class="track-duration">4:10</span>"#));
+        assert!(!html.contains("--:--"));
+
+        assert_eq!(
+            fs::read_to_string(src.path().join("album.toml")).unwrap(),
+            toml_before
+        );
+    }
+
+    /// Write a FLAC of the given length: STREAMINFO only, no audio frames
+    fn write_flac(path: &Path, seconds: u64) {
+        let sample_rate: u64 = 8000;
+        let mut flac = b"fLaC".to_vec();
+        flac.extend_from_slice(&[0x80, 0x00, 0x00, 0x22]); // last block, STREAMINFO, 34 bytes
+        flac.extend_from_slice(&4096u16.to_be_bytes()); // min block size
+        flac.extend_from_slice(&4096u16.to_be_bytes()); // max block size
+        flac.extend_from_slice(&[0; 6]); // min/max frame size unknown
+        // sample rate (20 bits), channels - 1 (3), bits per sample - 1 (5), total samples (36)
+        let packed = (sample_rate << 44) | (15 << 36) | (sample_rate * seconds);
+        flac.extend_from_slice(&packed.to_be_bytes());
+        flac.extend_from_slice(&[0; 16]); // MD5 unknown
+        fs::write(path, flac).unwrap();
+    }
+
+    #[test]
+    fn test_build_fills_flac_durations_and_warns_on_mismatch() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        write_flac(&src.path().join("audio/01-missing.flac"), 125);
+        write_flac(&src.path().join("audio/02-close.flac"), 60);
+        write_flac(&src.path().join("audio/03-wrong.flac"), 60);
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-missing.flac"
+title = "Missing"
+
+[[track]]
+file = "audio/02-close.flac"
+title = "Close Enough"
+duration = "1:02"
+
+[[track]]
+file = "audio/03-wrong.flac"
+title = "Wrong"
+duration = "1:03"
+"#,
+        );
+
+        let report = build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+        // Track 2 is within the 2 second tolerance
+        assert_eq!(
+            report.warnings,
+            vec!["Track 3 (Wrong) duration mismatch: album.toml says 1:03, audio is 1:00"]
+        );
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"<span class="track-duration">2:05</span>"#));
+        assert!(html.contains(r#"<span class="track-duration">1:02</span>"#));
+        assert!(html.contains(r#"<span class="track-duration">1:03</span>"#));
+    }
+
+    #[test]
+    fn test_build_keeps_configured_duration_without_local_audio() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-on-cdn.flac"
+title = "On CDN"
+duration = "4:10"
+
+[[track]]
+file = "audio/02-unknown.flac"
+title = "Unknown"
+"#,
+        );
+
+        build_static_site(
+            src.path(),
+            out.path(),
+            &BuildOptions {
+                audio_base_url: Some("https://cdn.example.com"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"<span class="track-duration">4:10</span>"#));
+        assert!(html.contains(r#"<span class="track-duration">--:--</span>"#));
+    }
+
+    /// Tag types lofty finds in an audio file
+    fn tag_types(path: &Path) -> Vec<TagType> {
+        Probe::open(path)
+            .unwrap()
+            .read()
+            .unwrap()
+            .tags()
+            .iter()
+            .map(|tag| tag.tag_type())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_preview_environment_is_noindex() {
+        const TRACKS: &str = r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "Track"
+"#;
+        const NOINDEX_META: &str = r#"<meta name="robots" content="noindex">"#;
+
+        let src = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::write(src.path().join("audio/01-track.flac"), b"audio").unwrap();
+        write_album_toml(src.path(), TRACKS);
+
+        let staging = TempDir::new().unwrap();
+        build_static_site(
+            src.path(),
+            staging.path(),
+            &BuildOptions {
+                environment: DeployEnvironment::Preview,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let html = fs::read_to_string(staging.path().join("index.html")).unwrap();
+        assert!(html.contains(NOINDEX_META));
+        assert_eq!(
+            fs::read_to_string(staging.path().join("_headers")).unwrap(),
+            "/*\n  X-Robots-Tag: noindex\n"
+        );
+
+        let production = TempDir::new().unwrap();
+        build_static_site(src.path(), production.path(), &BuildOptions::default()).unwrap();
+        let html = fs::read_to_string(production.path().join("index.html")).unwrap();
+        assert!(!html.contains(NOINDEX_META));
+        assert!(!production.path().join("_headers").exists());
+
+        // Production follows site.allow_indexing
+        write_album_toml(src.path(), &format!("allow_indexing = false\n{}", TRACKS));
+        let unlisted = TempDir::new().unwrap();
+        build_static_site(src.path(), unlisted.path(), &BuildOptions::default()).unwrap();
+        let html = fs::read_to_string(unlisted.path().join("index.html")).unwrap();
+        assert!(html.contains(NOINDEX_META));
+        assert!(unlisted.path().join("_headers").exists());
+    }
+
+    #[test]
+    fn test_build_strip_metadata_leaves_source_tags() {
+        use lofty::config::WriteOptions;
+        use lofty::tag::Tag;
+
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        let source = src.path().join("audio/01-tagged.wav");
+        write_wav(&source, 1);
+        for tag_type in [TagType::RiffInfo, TagType::Id3v2] {
+            let mut tag = Tag::new(tag_type);
+            tag.set_comment("Recorded at 12 Home Street".to_string());
+            tag.save_to_path(&source, WriteOptions::default()).unwrap();
+        }
+        write_album_toml(
+            src.path(),
+            r#"
+[[track]]
+file = "audio/01-tagged.wav"
+title = "Tagged"
+"#,
+        );
+
+        let report = build_static_site(
+            src.path(),
+            out.path(),
+            &BuildOptions {
+                strip_metadata: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(report.stripped_audio, 1);
+
+        let copy = out.path().join("audio/01-tagged.wav");
+        assert!(tag_types(&copy).is_empty());
+        assert!(probe_duration(&copy).is_ok());
+        assert_eq!(tag_types(&source), vec![TagType::RiffInfo, TagType::Id3v2]);
+    }
+}
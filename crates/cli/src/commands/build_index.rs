@@ -0,0 +1,209 @@
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::build::{BuildOptions, build_static_site};
+use super::dry_run::DryRun;
+
+/// Outcome of building one album of a discography
+#[derive(Debug)]
+struct AlbumBuild {
+    /// Album directory name, also its subdirectory of the output
+    name: String,
+    result: Result<()>,
+}
+
+/// Build every album under a directory, each into its own subdirectory.
+///
+/// Albums are the immediate subdirectories of `path` that contain an
+/// album.toml; each is built to `<output>/<dir-name>/` with a matching base
+/// path. Up to `parallel_builds` albums build at once. A failing album does
+/// not stop the others: all failures are reported once every build is done.
+///
+/// # Arguments
+///
+/// * `path` - Directory containing one subdirectory per album
+/// * `output` - Output directory for the built sites
+/// * `parallel_builds` - Maximum number of albums built at once
+/// * `dry_run` - Build into a scratch directory and list what would be written
+pub async fn run(
+    path: PathBuf,
+    output: PathBuf,
+    parallel_builds: usize,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("🔨 Building discography...");
+    println!("   Source: {}", path.display());
+    println!("   Output: {}", output.display());
+    println!();
+
+    let albums = discover_albums(&path)?;
+    if albums.is_empty() {
+        anyhow::bail!(
+            "No album directories (with album.toml) found in {}",
+            path.display()
+        );
+    }
+    println!(
+        "   Found {} album(s), building up to {} at once",
+        albums.len(),
+        parallel_builds.max(1)
+    );
+    println!();
+
+    // A dry run builds into a scratch directory, then lists what it holds
+    let sink = if dry_run.is_enabled() {
+        Some(tempfile::TempDir::new().context("Failed to create temporary directory")?)
+    } else {
+        None
+    };
+    let target = sink.as_ref().map_or(output.as_path(), |sink| sink.path());
+    let builds = build_albums(albums, target, parallel_builds).await;
+
+    let mut failed = 0;
+    for build in &builds {
+        match &build.result {
+            Ok(()) => println!("   ✓ {}", build.name),
+            Err(e) => {
+                failed += 1;
+                eprintln!("   ✗ {}: {:#}", build.name, e);
+            }
+        }
+    }
+
+    println!();
+    if failed > 0 {
+        anyhow::bail!("{} of {} album(s) failed to build", failed, builds.len());
+    }
+    if let Some(sink) = &sink {
+        let files = dry_run.report_staged(sink.path(), &output)?;
+        println!();
+        println!(
+            "✅ Dry run complete - {} file(s) would be written, nothing was",
+            files
+        );
+        return Ok(());
+    }
+    println!(
+        "✅ Built {} album(s) into {}",
+        builds.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Immediate subdirectories of `path` containing an album.toml, sorted
+pub(crate) fn discover_albums(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut albums: Vec<PathBuf> = fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("album.toml").is_file())
+        .collect();
+    albums.sort();
+    Ok(albums)
+}
+
+/// Build albums on a bounded pool of blocking tasks
+///
+/// # Returns
+///
+/// One outcome per album, in the order given
+async fn build_albums(
+    albums: Vec<PathBuf>,
+    output: &Path,
+    parallel_builds: usize,
+) -> Vec<AlbumBuild> {
+    let mut builds: Vec<(usize, AlbumBuild)> = stream::iter(albums.into_iter().enumerate())
+        .map(|(index, album)| {
+            let name = album
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let album_output = output.join(&name);
+            async move {
+                let base_path = format!("/{}", name);
+                let result = tokio::task::spawn_blocking(move || {
+                    build_static_site(
+                        &album,
+                        &album_output,
+                        &BuildOptions {
+                            base_path: &base_path,
+                            ..Default::default()
+                        },
+                    )
+                    .map(|_| ())
+                })
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("Build task panicked: {}", e)));
+                (index, AlbumBuild { name, result })
+            }
+        })
+        .buffer_unordered(parallel_builds.max(1))
+        .collect()
+        .await;
+
+    builds.sort_by_key(|(index, _)| *index);
+    builds.into_iter().map(|(_, build)| build).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::album_toml;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_build_albums_reports_failures_without_aborting() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+
+        let good = src.path().join("first-album");
+        fs::create_dir_all(good.join("audio")).unwrap();
+        fs::write(good.join("audio/01-track.flac"), b"flac").unwrap();
+        fs::write(
+            good.join("album.toml"),
+            album_toml(
+                r#"
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+"#,
+            ),
+        )
+        .unwrap();
+
+        let broken = src.path().join("broken-album");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join("album.toml"), "[album]\ntitle = ").unwrap();
+
+        // Not an album: ignored
+        fs::create_dir_all(src.path().join("scratch")).unwrap();
+
+        let albums = discover_albums(src.path()).unwrap();
+        assert_eq!(albums, vec![broken.clone(), good.clone()]);
+
+        let builds = build_albums(albums, out.path(), 2).await;
+        assert_eq!(builds.len(), 2);
+        assert_eq!(builds[0].name, "broken-album");
+        assert!(builds[0].result.is_err());
+        assert_eq!(builds[1].name, "first-album");
+        assert!(builds[1].result.is_ok());
+
+        let html = fs::read_to_string(out.path().join("first-album/index.html")).unwrap();
+        assert!(html.contains(r#"data-src="/first-album/audio/01-track.flac""#));
+        assert!(out.path().join("first-album/audio/01-track.flac").exists());
+
+        let err = run(
+            src.path().to_path_buf(),
+            out.path().to_path_buf(),
+            2,
+            DryRun::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("1 of 2 album(s) failed to build"));
+    }
+}
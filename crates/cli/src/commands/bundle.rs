@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use release_kit_core::artwork::detect_cover_art;
+use release_kit_core::config::parse_album_toml;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::dry_run::DryRun;
+use super::template::{CoverImage, LinerNotes, generate_html, generate_player_js, html_escape};
+
+/// Bundles larger than this are awkward to email or attach
+const LARGE_BUNDLE_BYTES: usize = 25 * 1024 * 1024;
+
+/// A generated single-file bundle
+struct Bundle {
+    html: String,
+    /// Tracks too large to embed, as paths relative to the bundle (`audio/<name>`)
+    referenced_audio: Vec<String>,
+    embedded_audio: usize,
+}
+
+/// Produce a self-contained single HTML file for the album.
+///
+/// CSS is already inline in the template; the player JavaScript and cover art
+/// are inlined, and audio files up to `max_inline_audio_mb` are embedded as
+/// base64 data URIs. Larger tracks are referenced relative to the bundle as
+/// `audio/<name>`.
+///
+/// # Arguments
+///
+/// * `path` - Path to album directory containing album.toml
+/// * `output` - Path of the HTML file to write
+/// * `max_inline_audio_mb` - Largest audio file to embed, in megabytes
+/// * `dry_run` - Render the bundle but don't write it
+pub async fn run(
+    path: PathBuf,
+    output: PathBuf,
+    max_inline_audio_mb: u64,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("📦 Bundling album into a single HTML file...");
+    println!("   Source: {}", path.display());
+    println!("   Output: {}", output.display());
+    println!();
+
+    let bundle = bundle_album(&path, max_inline_audio_mb.saturating_mul(1024 * 1024))?;
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        dry_run
+            .create_dir_all(parent)
+            .context("Failed to create output directory")?;
+    }
+    dry_run
+        .write(&output, &bundle.html)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!("   ✓ Embedded {} audio file(s)", bundle.embedded_audio);
+    if !bundle.referenced_audio.is_empty() {
+        println!(
+            "   ⚠ {} track(s) over {} MB are referenced, not embedded:",
+            bundle.referenced_audio.len(),
+            max_inline_audio_mb
+        );
+        for name in &bundle.referenced_audio {
+            println!("      {}", name);
+        }
+        println!("     Ship them at these paths next to the bundle");
+    }
+    if bundle.html.len() > LARGE_BUNDLE_BYTES {
+        println!(
+            "   ⚠ Bundle is {:.1} MB - too large for most email attachments",
+            bundle.html.len() as f64 / (1024.0 * 1024.0)
+        );
+    }
+
+    println!();
+    if dry_run.is_enabled() {
+        println!("✅ Dry run complete - nothing was written");
+    } else {
+        println!("✅ Bundle complete: {}", output.display());
+    }
+
+    Ok(())
+}
+
+/// Render the album page with all assets inlined where possible
+fn bundle_album(path: &Path, max_inline_audio_bytes: u64) -> Result<Bundle> {
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nRun 'release-kit init {}' first",
+            path.display(),
+            path.display()
+        );
+    }
+
+    let mut album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    // A bundle has no deployed URL, so link previews get no absolute links
+    album.site.domain.clear();
+
+    let cover_path = detect_cover_art(&path.join("artwork"));
+    let cover_art = cover_path
+        .as_ref()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+
+    // The cover is inlined as-is, so only its dimensions carry over
+    let cover = cover_art.as_ref().map(|name| CoverImage {
+        dimensions: cover_path
+            .as_deref()
+            .and_then(|p| image::image_dimensions(p).ok()),
+        ..CoverImage::new(name)
+    });
+    // Missing notes files are `validate`'s to report; the page just leaves them out
+    let (notes, _) = LinerNotes::read(path, &album);
+    let mut html = generate_html(&album, cover.as_ref(), Some(&notes), false, None, "");
+
+    // Inline the player script
+    html = html.replace(
+        r#"<script src="/player.js"></script>"#,
+        &format!("<script>\n{}\n</script>", generate_player_js()),
+    );
+
+    // Inline the cover art (header and player)
+    let layout = album.site.layout;
+    if let (Some(cover_path), Some(cover_name)) = (&cover_path, &cover_art) {
+        let data_uri = data_uri(cover_path)?;
+        html = html.replace(
+            &format!(
+                r#"src="/{}""#,
+                html_escape(&layout.asset_path("artwork", cover_name))
+            ),
+            &format!(r#"src="{}""#, data_uri),
+        );
+    }
+
+    // Embed small audio, reference the rest relative to the bundle
+    let mut referenced_audio = Vec::new();
+    let mut embedded_audio = 0;
+    for (track, filename) in album.tracks.iter().zip(album.audio_file_names()) {
+        let src = path.join(&track.file);
+        let asset_path = layout.asset_path("audio", &filename);
+        let original = format!(r#"data-src="/{}""#, html_escape(&asset_path));
+
+        let size = fs::metadata(&src).map(|m| m.len()).ok();
+        let replacement = match size {
+            Some(size) if size <= max_inline_audio_bytes => {
+                embedded_audio += 1;
+                format!(r#"data-src="{}""#, data_uri(&src)?)
+            }
+            _ => {
+                let replacement = format!(r#"data-src="{}""#, html_escape(&asset_path));
+                referenced_audio.push(asset_path);
+                replacement
+            }
+        };
+        html = html.replace(&original, &replacement);
+    }
+
+    Ok(Bundle {
+        html,
+        referenced_audio,
+        embedded_audio,
+    })
+}
+
+/// Encode a file as a base64 `data:` URI
+fn data_uri(file: &Path) -> Result<String> {
+    let bytes = fs::read(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let mime = mime_guess::from_path(file).first_or_octet_stream();
+    Ok(format!("data:{};base64,{}", mime, BASE64.encode(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::album_toml;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_bundle_is_single_file_with_inlined_player() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(src.path().join("audio")).unwrap();
+        fs::create_dir_all(src.path().join("artwork")).unwrap();
+        fs::write(src.path().join("audio/01-short.mp3"), b"tiny").unwrap();
+        fs::File::create(src.path().join("audio/02-long.flac"))
+            .unwrap()
+            .set_len(2 * 1024 * 1024)
+            .unwrap();
+        fs::write(src.path().join("artwork/cover.png"), b"png").unwrap();
+        fs::write(
+            src.path().join("album.toml"),
+            album_toml(
+                r#"
+[[track]]
+file = "audio/01-short.mp3"
+title = "Short Clip"
+
+[[track]]
+file = "audio/02-long.flac"
+title = "Long Track"
+"#,
+            ),
+        )
+        .unwrap();
+
+        let output = out.path().join("album.html");
+        run(
+            src.path().to_path_buf(),
+            output.clone(),
+            1,
+            DryRun::default(),
+        )
+        .await
+        .unwrap();
+
+        // Exactly one file written
+        let files: Vec<_> = fs::read_dir(out.path()).unwrap().flatten().collect();
+        assert_eq!(files.len(), 1);
+
+        let html = fs::read_to_string(&output).unwrap();
+        assert!(!html.contains(r#"<script src="#));
+        assert!(html.contains("class AnalogOscilloscope"));
+        assert!(html.contains(r#"src="data:image/png;base64,cG5n""#));
+        assert!(!html.contains("/artwork/cover.png"));
+
+        // Small audio embedded, large audio referenced relative to the bundle
+        assert!(html.contains(r#"data-src="data:audio/mpeg;base64,dGlueQ==""#));
+        assert!(html.contains(r#"data-src="audio/02-long.flac""#));
+    }
+}
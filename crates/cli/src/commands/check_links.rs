@@ -0,0 +1,260 @@
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use release_kit_core::config::parse_album_toml;
+use release_kit_core::types::Album;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::deploy::{derive_project_name, load_config, r2_public_base_url, user_agent};
+
+/// URLs checked at once
+const LINK_CHECK_CONCURRENCY: usize = 8;
+
+/// Give up on a URL that hasn't answered in this long
+const LINK_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a HEAD request to one URL got back
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum LinkStatus {
+    /// HTTP status code (after redirects)
+    Status(u16),
+    /// No response at all (DNS, TLS, timeout...)
+    Unreachable(String),
+}
+
+impl LinkStatus {
+    fn is_ok(&self) -> bool {
+        matches!(self, LinkStatus::Status(status) if *status < 400)
+    }
+}
+
+/// Counts over a `check_links` run
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct LinkSummary {
+    pub ok: usize,
+    /// 4xx/5xx responses and unreachable URLs
+    pub broken: usize,
+}
+
+impl LinkSummary {
+    fn of(results: &[(String, LinkStatus)]) -> Self {
+        let ok = results.iter().filter(|(_, status)| status.is_ok()).count();
+        Self {
+            ok,
+            broken: results.len() - ok,
+        }
+    }
+}
+
+/// Check that a published album is reachable: the site index, feed.xml and
+/// every track's audio URL.
+///
+/// The URLs are the ones `publish` deploys to: the project's pages.dev site,
+/// and audio under the R2 public URL (the bucket's custom domain when a base
+/// domain is configured, else `pub-<account>.r2.dev`) or `[hosting.s3]`'s.
+/// Each gets a HEAD request; any 4xx/5xx or unreachable URL fails the check.
+///
+/// # Arguments
+///
+/// * `path` - Path to album directory containing album.toml
+pub async fn run(path: PathBuf) -> Result<()> {
+    println!("🔗 Checking deployed links...\n");
+
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nRun 'release-kit init {}' first",
+            path.display(),
+            path.display()
+        );
+    }
+
+    // Drafts are only deployed with --include-drafts
+    let mut album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    album.remove_drafts();
+    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+
+    let audio_base_url = match &album.hosting.s3 {
+        Some(s3) => s3.public_base_url(),
+        None => {
+            let config = load_config()?.context(
+                "No Cloudflare configuration found.\nRun 'release-kit deploy configure' first",
+            )?;
+            r2_public_base_url(&config.cloudflare, &project_name)
+        }
+    };
+    let site_url = format!("https://{}.pages.dev", project_name);
+
+    println!("   Project: {}", project_name);
+    println!("   Site: {}", site_url);
+    println!("   Audio: {}", audio_base_url);
+    println!();
+
+    let urls = link_urls(&album, &site_url, &audio_base_url);
+    let client = reqwest::Client::builder()
+        .user_agent(user_agent())
+        .timeout(LINK_CHECK_TIMEOUT)
+        .build()
+        .context("Failed to create HTTP client")?;
+    let results = check_links(&client, &urls).await;
+
+    for (url, status) in &results {
+        match status {
+            LinkStatus::Status(code) if status.is_ok() => println!("   ✓ {} {}", code, url),
+            LinkStatus::Status(code) => println!("   ✗ {} {}", code, url),
+            LinkStatus::Unreachable(reason) => println!("   ✗ unreachable {} ({})", url, reason),
+        }
+    }
+
+    let summary = LinkSummary::of(&results);
+    println!();
+    if summary.broken > 0 {
+        anyhow::bail!("{} of {} link(s) are broken", summary.broken, results.len());
+    }
+    println!("✅ All {} link(s) resolve", summary.ok);
+
+    Ok(())
+}
+
+/// Site index, feed (when enabled) and one audio URL per track
+fn link_urls(album: &Album, site_url: &str, audio_base_url: &str) -> Vec<String> {
+    let site_url = site_url.trim_end_matches('/');
+    let audio_base_url = audio_base_url.trim_end_matches('/');
+
+    let mut urls = vec![format!("{}/", site_url)];
+    if album.rss.enabled {
+        urls.push(format!("{}/feed.xml", site_url));
+    }
+    urls.extend(
+        album
+            .audio_file_names()
+            .iter()
+            .map(|name| format!("{}/{}", audio_base_url, album.audio_key(name))),
+    );
+    urls
+}
+
+/// HEAD every URL, a few at once
+///
+/// # Returns
+///
+/// Each URL with its status, in the order given
+pub(crate) async fn check_links(
+    client: &reqwest::Client,
+    urls: &[String],
+) -> Vec<(String, LinkStatus)> {
+    futures::stream::iter(urls)
+        .map(|url| async move {
+            let status = match client.head(url).send().await {
+                Ok(response) => LinkStatus::Status(response.status().as_u16()),
+                Err(e) => LinkStatus::Unreachable(e.to_string()),
+            };
+            (url.clone(), status)
+        })
+        .buffered(LINK_CHECK_CONCURRENCY)
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::album_toml;
+    use release_kit_core::config::parse_album_toml_str;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve 200 for the given paths and 404 for everything else
+    async fn mock_server(found: &'static [&'static str]) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                tokio::spawn(async move {
+                    let mut received = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !received.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => received.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let head = String::from_utf8_lossy(&received).to_string();
+                    let path = head.split(' ').nth(1).unwrap_or_default();
+                    let status = if found.contains(&path) {
+                        "200 OK"
+                    } else {
+                        "404 Not Found"
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        status
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        base
+    }
+
+    const TRACKS: &str = r#"
+[[track]]
+file = "audio/01-first.flac"
+title = "First"
+
+[[track]]
+file = "audio/02-second.flac"
+title = "Second"
+"#;
+
+    #[test]
+    fn test_link_urls_cover_site_feed_and_tracks() {
+        let album = parse_album_toml_str(&album_toml(TRACKS)).unwrap();
+        assert_eq!(
+            link_urls(
+                &album,
+                "https://test-artist-test-album.pages.dev",
+                "https://pub-abc.r2.dev/"
+            ),
+            vec![
+                "https://test-artist-test-album.pages.dev/",
+                "https://test-artist-test-album.pages.dev/feed.xml",
+                "https://pub-abc.r2.dev/audio/01-first.flac",
+                "https://pub-abc.r2.dev/audio/02-second.flac",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_links_counts_broken_urls() {
+        let base = mock_server(&["/", "/feed.xml", "/audio/01-first.flac"]).await;
+        let album = parse_album_toml_str(&album_toml(TRACKS)).unwrap();
+        let urls = link_urls(&album, &base, &base);
+
+        let results = check_links(&reqwest::Client::new(), &urls).await;
+
+        let statuses: Vec<&LinkStatus> = results.iter().map(|(_, status)| status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &LinkStatus::Status(200),
+                &LinkStatus::Status(200),
+                &LinkStatus::Status(200),
+                &LinkStatus::Status(404),
+            ]
+        );
+        assert_eq!(results[3].0, format!("{}/audio/02-second.flac", base));
+        assert_eq!(LinkSummary::of(&results), LinkSummary { ok: 3, broken: 1 });
+
+        // Nothing listening at all
+        let results = check_links(
+            &reqwest::Client::new(),
+            &["http://127.0.0.1:1/".to_string()],
+        )
+        .await;
+        assert!(matches!(results[0].1, LinkStatus::Unreachable(_)));
+        assert_eq!(LinkSummary::of(&results), LinkSummary { ok: 0, broken: 1 });
+    }
+}
@@ -1,22 +1,94 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use release_kit_core::config::parse_album_toml;
+use release_kit_core::types::{Album, S3Config};
+use release_kit_deployer::ipfs::{IpfsConfig, IpfsDeployer};
+use release_kit_deployer::netlify::{NetlifyConfig, NetlifyDeployer};
+use release_kit_deployer::static_site::StaticDeployer;
+use release_kit_deployer::{DeployEvent, Deployer, DeploymentResult, ProgressFn};
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use s3::Bucket as S3Bucket;
 use s3::Region as S3Region;
 use s3::creds::Credentials as S3Credentials;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tempfile::TempDir;
 
-use super::build::build_static_site;
+use super::build::{
+    BuildOptions, DeployEnvironment, build_static_site, check_audio_file_names,
+    encode_stream_copies, strip_audio_tags,
+};
+use super::build_index::discover_albums;
+use super::dry_run::DryRun;
 
 // Constants
 const DEFAULT_BRANCH: &str = "main";
+/// Pages response-header rules, uploaded with the deployment instead of as an asset
+const PAGES_HEADERS_FILE: &str = "_headers";
 const DNS_RECORD_TYPE: &str = "CNAME";
 const HTTP_TIMEOUT_SECS: u64 = 300; // 5 minutes for large uploads
+/// Tries per Cloudflare API call on rate limits and server errors
+const API_MAX_ATTEMPTS: u32 = 5;
+/// Environment variable replacing `API_MAX_ATTEMPTS`
+const API_MAX_ATTEMPTS_ENV: &str = "RELEASE_KIT_API_MAX_ATTEMPTS";
+/// Longest `Retry-After` wait honored between tries
+const MAX_RETRY_AFTER_SECS: u64 = 60;
+const CLOUDFLARE_API_BASE: &str = "https://api.cloudflare.com/client/v4";
+/// Environment variable replacing the `User-Agent` sent with API and storage requests
+const USER_AGENT_ENV: &str = "RELEASE_KIT_USER_AGENT";
+
+/// `User-Agent` for Cloudflare API, R2/S3 and link-check requests:
+/// `release-kit/<version>`, unless `RELEASE_KIT_USER_AGENT` sets another
+pub(crate) fn user_agent() -> String {
+    user_agent_from(std::env::var(USER_AGENT_ENV).ok())
+}
+
+fn user_agent_from(configured: Option<String>) -> String {
+    configured
+        .map(|agent| agent.trim().to_string())
+        .filter(|agent| !agent.is_empty())
+        .unwrap_or_else(|| format!("release-kit/{}", env!("CARGO_PKG_VERSION")))
+}
+
+/// Tries per Cloudflare API call: `API_MAX_ATTEMPTS`, unless
+/// `RELEASE_KIT_API_MAX_ATTEMPTS` sets another count (at least 1)
+fn api_max_attempts() -> Result<u32> {
+    api_max_attempts_from(std::env::var(API_MAX_ATTEMPTS_ENV).ok())
+}
+
+fn api_max_attempts_from(configured: Option<String>) -> Result<u32> {
+    match configured.as_deref().map(str::trim) {
+        None | Some("") => Ok(API_MAX_ATTEMPTS),
+        Some(value) => match value.parse::<u32>() {
+            Ok(attempts) if attempts > 0 => Ok(attempts),
+            _ => anyhow::bail!(
+                "{} must be a whole number of at least 1, got '{}'",
+                API_MAX_ATTEMPTS_ENV,
+                value
+            ),
+        },
+    }
+}
+
+/// Key of the manifest recording what the last publish uploaded, before the
+/// album's `r2_prefix` (see `manifest_key`)
+pub(crate) const DEPLOY_MANIFEST_KEY: &str = "release-kit/manifest.json";
+
+/// Bucket key of `album`'s deploy manifest, under its `r2_prefix` like its
+/// audio, so albums sharing a bucket keep a manifest each
+pub(crate) fn manifest_key(album: &Album) -> String {
+    album.bucket_key(DEPLOY_MANIFEST_KEY)
+}
+
+/// Whether `key` is a deploy manifest, at the bucket root or under a prefix
+pub(crate) fn is_manifest_key(key: &str) -> bool {
+    key.strip_suffix(DEPLOY_MANIFEST_KEY)
+        .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with('/'))
+}
 
 /// Global configuration for deployments
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,25 +108,76 @@ pub struct CloudflareConfig {
     pub r2_secret_access_key: String,
 }
 
-/// Get path to global config file
-fn config_path() -> Result<PathBuf> {
+/// Get path to a file in the global config directory (~/.release-kit)
+fn config_dir_file(name: &str) -> Result<PathBuf> {
     let home = std::env::var("HOME")
         .or_else(|_| std::env::var("USERPROFILE"))
         .context("Could not determine home directory")?;
     let config_dir = PathBuf::from(home).join(".release-kit");
     fs::create_dir_all(&config_dir)?;
-    Ok(config_dir.join("config.toml"))
+    Ok(config_dir.join(name))
+}
+
+/// Get path to global config file
+fn config_path() -> Result<PathBuf> {
+    config_dir_file("config.toml")
+}
+
+/// Load a per-target config file from ~/.release-kit
+///
+/// `example` is shown, indented, when the file is missing.
+fn load_target_config<T: serde::de::DeserializeOwned>(
+    file_name: &str,
+    target: &str,
+    example: &str,
+) -> Result<T> {
+    let path = config_dir_file(file_name)?;
+    if !path.exists() {
+        anyhow::bail!(
+            "No {} configuration found.\nCreate {} with:\n\n{}",
+            target,
+            path.display(),
+            example
+        );
+    }
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {} config file", target))?;
+    toml::from_str(&contents).with_context(|| format!("Failed to parse {} config file", target))
+}
+
+/// Load Netlify config from ~/.release-kit/netlify.toml
+fn load_netlify_config() -> Result<NetlifyConfig> {
+    load_target_config(
+        "netlify.toml",
+        "Netlify",
+        "  api_token = \"<personal access token>\"\n  site_id = \"<site id or name.netlify.app>\"",
+    )
+}
+
+/// Load IPFS pinning config from ~/.release-kit/ipfs.toml
+fn load_ipfs_config() -> Result<IpfsConfig> {
+    load_target_config(
+        "ipfs.toml",
+        "IPFS",
+        "  api_token = \"<pinning service JWT>\"\n  # pinning_api = \"https://api.pinata.cloud\"\n  # gateway = \"dweb.link\"",
+    )
 }
 
 /// Load global config
-fn load_config() -> Result<Option<GlobalConfig>> {
+pub(crate) fn load_config() -> Result<Option<GlobalConfig>> {
     let path = config_path()?;
     if !path.exists() {
         return Ok(None);
     }
-    let contents = fs::read_to_string(&path).context("Failed to read config file")?;
-    let config: GlobalConfig = toml::from_str(&contents).context("Failed to parse config file")?;
-    Ok(Some(config))
+    load_config_file(&path).map(Some)
+}
+
+/// Load a config file in the global config's format (e.g. another account's)
+pub(crate) fn load_config_file(path: &Path) -> Result<GlobalConfig> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))
 }
 
 /// Save global config with secure permissions
@@ -88,7 +211,7 @@ fn save_config(config: &GlobalConfig) -> Result<()> {
 /// Derive project name from album metadata
 /// Format: {artist-slug}-{album-slug}
 /// Example: "Artist Name" + "My Album" -> "artist-name-my-album"
-fn derive_project_name(artist: &str, album: &str) -> String {
+pub(crate) fn derive_project_name(artist: &str, album: &str) -> String {
     let slugify = |s: &str| -> String {
         s.to_lowercase()
             .chars()
@@ -114,14 +237,321 @@ fn derive_project_name(artist: &str, album: &str) -> String {
     format!("{}-{}", slugify(artist), slugify(album))
 }
 
+// ============================================================================
+// Deploy Manifest
+// ============================================================================
+
+/// Snapshot of a published album, stored in R2 alongside the audio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DeployManifest {
+    pub title: String,
+    pub artist: String,
+    pub release_date: String,
+    pub tracks: Vec<ManifestTrack>,
+}
+
+/// A single uploaded track in the deploy manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ManifestTrack {
+    pub title: String,
+    /// R2 object key (e.g. "audio/01-track.flac")
+    pub key: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl DeployManifest {
+    /// Build a manifest from the local album, hashing each audio file
+    ///
+    /// Tracks whose audio file is missing are left out, matching what publish uploads.
+    pub fn from_album(path: &Path, album: &Album) -> Result<Self> {
+        use sha2::{Digest, Sha256};
+
+        let mut tracks = Vec::new();
+        for (track, filename) in album.tracks.iter().zip(album.audio_file_names()) {
+            let audio_file = path.join(&track.file);
+            if !audio_file.exists() {
+                continue;
+            }
+            let bytes = fs::read(&audio_file)
+                .with_context(|| format!("Failed to read file: {}", audio_file.display()))?;
+            tracks.push(ManifestTrack {
+                title: track.title.clone(),
+                key: album.audio_key(&filename),
+                size: bytes.len() as u64,
+                sha256: format!("{:x}", Sha256::digest(&bytes)),
+            });
+        }
+
+        Ok(Self {
+            title: album.metadata.title.clone(),
+            artist: album.metadata.artist.clone(),
+            release_date: album.metadata.release_date.to_string(),
+            tracks,
+        })
+    }
+
+    /// The manifest the last publish of `album` saved in `bucket`, if there is one
+    pub async fn fetch(bucket: &S3Bucket, album: &Album) -> Option<Self> {
+        match bucket.get_object(manifest_key(album)).await {
+            Ok(response) if response.status_code() == 200 => {
+                serde_json::from_slice(response.as_slice()).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Open the R2 bucket with the configured S3-compatible credentials
+pub(crate) fn r2_bucket(config: &CloudflareConfig, bucket_name: &str) -> Result<Box<S3Bucket>> {
+    let credentials = S3Credentials::new(
+        Some(&config.r2_access_key_id),
+        Some(&config.r2_secret_access_key),
+        None,
+        None,
+        None,
+    )?;
+
+    let region = S3Region::R2 {
+        account_id: config.account_id.clone(),
+    };
+
+    s3_bucket(bucket_name, region, credentials)
+}
+
+/// Open the `[hosting.s3]` bucket with credentials from the environment
+///
+/// Reads `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY`, falling back to the
+/// default profile in ~/.aws/credentials.
+pub(crate) fn custom_s3_bucket(config: &S3Config) -> Result<Box<S3Bucket>> {
+    let credentials = S3Credentials::from_env()
+        .or_else(|_| S3Credentials::from_profile(None))
+        .context(
+            "No S3 credentials found for [hosting.s3].\n\
+             Set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY, or add a default profile to ~/.aws/credentials",
+        )?;
+
+    s3_bucket(&config.bucket, s3_region(config), credentials)
+}
+
+/// Region for an arbitrary S3-compatible endpoint
+fn s3_region(config: &S3Config) -> S3Region {
+    S3Region::Custom {
+        region: config.region.clone(),
+        endpoint: config.endpoint.trim_end_matches('/').to_string(),
+    }
+}
+
+/// Open a bucket on any S3-compatible service
+///
+/// Path-style addressing is used everywhere: R2, MinIO, B2 and Wasabi all
+/// accept it, and it avoids per-bucket DNS for local endpoints.
+pub(crate) fn s3_bucket(
+    bucket_name: &str,
+    region: S3Region,
+    credentials: S3Credentials,
+) -> Result<Box<S3Bucket>> {
+    let mut bucket = S3Bucket::new(bucket_name, region, credentials)?.with_path_style();
+    bucket.add_header("User-Agent", &user_agent());
+    Ok(bucket)
+}
+
+// ============================================================================
+// Pages Asset Upload
+// ============================================================================
+
+/// Upper bound on the file bytes in a single Pages upload batch
+const MAX_BATCH_BYTES: u64 = 40 * 1024 * 1024;
+/// Upper bound on the number of files in a single Pages upload batch
+const MAX_BATCH_FILES: usize = 1000;
+
+/// A file from the build directory, hashed for the Pages manifest
+#[derive(Debug, Clone)]
+struct UploadFile {
+    /// Path relative to the build directory, with `/` separators
+    relative_path: String,
+    path: PathBuf,
+    /// Content hash (32 hex chars, as Pages expects for asset keys)
+    hash: String,
+    size: u64,
+    content_type: String,
+}
+
+/// Walk the build directory and hash every file without loading it whole
+fn collect_upload_files(build_dir: &Path) -> Result<Vec<UploadFile>> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+    use walkdir::WalkDir;
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(build_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let relative_path = path
+            .strip_prefix(build_dir)
+            .context("Failed to get relative path")?
+            .to_str()
+            .with_context(|| format!("{} has a file name that isn't valid UTF-8", path.display()))?
+            .replace('\\', "/"); // Normalize path separators
+
+        let mut file = fs::File::open(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        let mut buf = [0u8; 64 * 1024];
+        let mut size = 0u64;
+        loop {
+            let n = file.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            size += n as u64;
+        }
+        // Include the extension so identical bytes served with different types stay distinct
+        if let Some(ext) = path.extension() {
+            hasher.update(ext.to_string_lossy().as_bytes());
+        }
+        let hash = format!("{:x}", hasher.finalize())[..32].to_string();
+
+        let content_type = content_type_for(path);
+
+        files.push(UploadFile {
+            relative_path,
+            path: path.to_path_buf(),
+            hash,
+            size,
+            content_type,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Content type for an upload: by extension, falling back to the file's magic bytes
+///
+/// Extensionless or unrecognized files are sniffed with `infer`, so a FLAC
+/// master saved without `.flac` is still served as `audio/flac`.
+pub(crate) fn content_type_for(path: &Path) -> String {
+    if let Some(mime) = mime_guess::from_path(path).first() {
+        return mime.to_string();
+    }
+    match infer::get_from_path(path).ok().flatten() {
+        Some(kind) => normalize_sniffed_type(kind.mime_type()).to_string(),
+        None => "application/octet-stream".to_string(),
+    }
+}
+
+/// Map `infer`'s legacy `x-` audio types to the registered ones mime_guess uses
+fn normalize_sniffed_type(mime: &str) -> &str {
+    match mime {
+        "audio/x-flac" => "audio/flac",
+        "audio/x-wav" => "audio/wav",
+        "audio/x-aiff" => "audio/aiff",
+        other => other,
+    }
+}
+
+/// Deployment manifest: `"/path"` to asset hash for every file in the site
+fn pages_manifest(files: &[UploadFile]) -> std::collections::BTreeMap<String, &str> {
+    files
+        .iter()
+        .map(|f| (format!("/{}", f.relative_path), f.hash.as_str()))
+        .collect()
+}
+
+/// Name the files in an upload batch for error messages (the first few, then a count)
+fn describe_batch(batch: &[UploadFile]) -> String {
+    const SHOWN: usize = 3;
+    let names: Vec<&str> = batch
+        .iter()
+        .take(SHOWN)
+        .map(|f| f.relative_path.as_str())
+        .collect();
+    match batch.len().saturating_sub(SHOWN) {
+        0 => names.join(", "),
+        more => format!("{} and {} more", names.join(", "), more),
+    }
+}
+
+/// Group files into batches bounded by total size and file count
+///
+/// A file larger than `max_bytes` gets a batch of its own.
+fn batch_upload_files<'a>(
+    files: &[&'a UploadFile],
+    max_bytes: u64,
+    max_files: usize,
+) -> Vec<Vec<&'a UploadFile>> {
+    let mut batches = Vec::new();
+    let mut current: Vec<&UploadFile> = Vec::new();
+    let mut current_bytes = 0;
+
+    for &file in files {
+        if !current.is_empty()
+            && (current_bytes + file.size > max_bytes || current.len() >= max_files)
+        {
+            batches.push(std::mem::take(&mut current));
+            current_bytes = 0;
+        }
+        current_bytes += file.size;
+        current.push(file);
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+
+    batches
+}
+
 // ============================================================================
 // Cloudflare API Client
 // ============================================================================
 
+#[cfg(test)]
+thread_local! {
+    /// `CloudflareClient`s created on this thread, so tests can check a
+    /// code path stays offline
+    static CLIENTS_CREATED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
 /// Cloudflare API client
 struct CloudflareClient {
     client: reqwest::Client,
     account_id: String,
+    api_base: String,
+    /// Tries per API call before a 429/5xx response is returned as final
+    max_attempts: u32,
+}
+
+/// Whether a Cloudflare API status is worth retrying: rate limited, or a
+/// server error when the request is `replayable`
+///
+/// A 5xx can come back after Cloudflare already acted on the request, so only
+/// requests that are safe to send twice are retried on one.
+fn is_retryable_status(status: reqwest::StatusCode, replayable: bool) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || (replayable && status.is_server_error())
+}
+
+/// Wait asked for by a `Retry-After: <seconds>` header, capped at
+/// `MAX_RETRY_AFTER_SECS` (HTTP dates are ignored in favor of the backoff)
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let seconds: u64 = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds.min(MAX_RETRY_AFTER_SECS)))
+}
+
+/// Backoff before retry `attempt` + 1: 1s, 2s, 4s, ... up to 30s
+fn retry_backoff(attempt: u32) -> Duration {
+    Duration::from_secs((1u64 << (attempt - 1).min(5)).min(30))
 }
 
 /// Cloudflare API response wrapper
@@ -150,6 +580,26 @@ struct PagesProject {
     created_on: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     production_branch: Option<String>,
+    /// The production deployment being served (not always the newest, after a rollback)
+    #[serde(default, skip_serializing)]
+    canonical_deployment: Option<CanonicalDeployment>,
+}
+
+/// `canonical_deployment` of a Pages project
+#[derive(Debug, Deserialize)]
+struct CanonicalDeployment {
+    id: String,
+}
+
+/// One deployment in a Pages project's history
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct ProjectDeployment {
+    id: String,
+    #[serde(default)]
+    short_id: Option<String>,
+    /// "production" or "preview"
+    environment: String,
+    created_on: String,
 }
 
 /// DNS Zone info
@@ -172,6 +622,74 @@ struct DnsRecord {
     proxied: bool,
 }
 
+/// Body of an R2 CORS update allowing `GET`/`HEAD` from `origins`
+fn r2_cors_request(origins: &[String]) -> serde_json::Value {
+    serde_json::json!({
+        "rules": [{
+            "allowed": {
+                "methods": ["GET", "HEAD"],
+                "origins": origins,
+                "headers": ["content-type"],
+            },
+            "MaxAgeSeconds": 3600,
+        }]
+    })
+}
+
+/// Origins the album's site is served from: `site.domain`, the project's
+/// pages.dev URL and its preview/deployment subdomains, and the custom
+/// subdomain when there is a base domain
+fn site_origins(album: &Album, project_name: &str, base_domain: Option<&str>) -> Vec<String> {
+    let mut origins = vec![
+        format!("https://{}", album.site.domain.trim_end_matches('/')),
+        format!("https://{}.pages.dev", project_name),
+        format!("https://*.{}.pages.dev", project_name),
+    ];
+    if let (Some(subdomain), Some(base_domain)) = (&album.hosting.cloudflare.subdomain, base_domain)
+    {
+        origins.push(format!("https://{}.{}", subdomain, base_domain));
+    }
+    origins.dedup();
+    origins
+}
+
+/// Origins the R2 audio bucket allows: the site's own plus
+/// `[hosting.cloudflare] cors_origins`, or just `*` if that lists it
+fn r2_cors_origins(album: &Album, project_name: &str, base_domain: Option<&str>) -> Vec<String> {
+    let extra = &album.hosting.cloudflare.cors_origins;
+    if extra.iter().any(|origin| origin == "*") {
+        return vec!["*".to_string()];
+    }
+    let mut origins = site_origins(album, project_name, base_domain);
+    for origin in extra {
+        let origin = origin.trim_end_matches('/').to_string();
+        if !origins.contains(&origin) {
+            origins.push(origin);
+        }
+    }
+    origins
+}
+
+/// R2 bucket CORS policy, as returned by the API
+#[derive(Debug, Deserialize)]
+struct R2CorsPolicy {
+    #[serde(default)]
+    rules: Vec<R2CorsRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct R2CorsRule {
+    allowed: R2CorsAllowed,
+}
+
+#[derive(Debug, Deserialize)]
+struct R2CorsAllowed {
+    #[serde(default)]
+    methods: Vec<String>,
+    #[serde(default)]
+    origins: Vec<String>,
+}
+
 /// R2 Bucket info
 #[derive(Debug, Deserialize, Serialize)]
 struct R2Bucket {
@@ -180,6 +698,18 @@ struct R2Bucket {
     creation_date: Option<String>,
 }
 
+/// Workers KV namespace
+#[derive(Debug, Deserialize)]
+struct KvNamespace {
+    id: String,
+    title: String,
+}
+
+/// Title of the KV namespace the streaming worker counts plays in
+fn stats_namespace_title(project_name: &str) -> String {
+    format!("{}-stats", project_name)
+}
+
 /// R2 Custom Domain
 #[derive(Debug, Deserialize, Serialize)]
 struct R2CustomDomain {
@@ -190,8 +720,97 @@ struct R2CustomDomain {
 }
 
 impl CloudflareClient {
+    /// Get a JWT for the Pages assets upload API
+    async fn get_pages_upload_token(&self, project_name: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct UploadToken {
+            jwt: String,
+        }
+
+        let url = format!(
+            "{}/accounts/{}/pages/projects/{}/upload-token",
+            self.api_base, self.account_id, project_name
+        );
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
+        let cf_response: CloudflareResponse<UploadToken> = response.json().await?;
+
+        if !cf_response.success {
+            if let Some(error) = cf_response.errors.first() {
+                anyhow::bail!("Cloudflare API error: {}", error.message);
+            }
+            anyhow::bail!("Unknown Cloudflare API error");
+        }
+
+        Ok(cf_response
+            .result
+            .context("No upload token returned from API")?
+            .jwt)
+    }
+
+    /// POST to a Pages assets endpoint (`check-missing`, `upload`, `upsert-hashes`)
+    async fn pages_assets_request<T: serde::de::DeserializeOwned>(
+        &self,
+        jwt: &str,
+        endpoint: &str,
+        body: &serde_json::Value,
+    ) -> Result<T> {
+        let url = format!("{}/pages/assets/{}", self.api_base, endpoint);
+        // Assets are keyed by content hash, so sending a call twice changes nothing
+        let response = self
+            .replayable_request_with_retry(|| self.client.post(&url).bearer_auth(jwt).json(body))
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("Pages {} failed ({}): {}", endpoint, status, response_text);
+        }
+
+        let cf_response: CloudflareResponse<T> = serde_json::from_str(&response_text)
+            .with_context(|| format!("Failed to parse Pages {} response", endpoint))?;
+        if !cf_response.success {
+            if let Some(error) = cf_response.errors.first() {
+                anyhow::bail!("Pages {} failed: {}", endpoint, error.message);
+            }
+            anyhow::bail!("Pages {} failed with unknown error", endpoint);
+        }
+
+        cf_response
+            .result
+            .with_context(|| format!("No result from Pages {}", endpoint))
+    }
+
+    /// Upload one batch of assets, reading file contents only for this batch
+    async fn upload_pages_batch(&self, jwt: &str, batch: Vec<UploadFile>) -> Result<()> {
+        use base64::Engine;
+
+        let mut payload = Vec::with_capacity(batch.len());
+        for file in batch {
+            let bytes = tokio::fs::read(&file.path)
+                .await
+                .with_context(|| format!("Failed to read file: {}", file.path.display()))?;
+            payload.push(serde_json::json!({
+                "key": file.hash,
+                "value": base64::engine::general_purpose::STANDARD.encode(bytes),
+                "metadata": { "contentType": file.content_type },
+                "base64": true,
+            }));
+        }
+
+        self.pages_assets_request::<serde_json::Value>(
+            jwt,
+            "upload",
+            &serde_json::Value::Array(payload),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Create new Cloudflare API client
     fn new(api_token: &str, account_id: &str) -> Result<Self> {
+        #[cfg(test)]
+        CLIENTS_CREATED.with(|count| count.set(count.get() + 1));
+
         let mut headers = HeaderMap::new();
         headers.insert(
             AUTHORIZATION,
@@ -200,23 +819,99 @@ impl CloudflareClient {
 
         let client = reqwest::Client::builder()
             .default_headers(headers)
+            .user_agent(user_agent())
             .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
             .build()?;
 
         Ok(Self {
             client,
             account_id: account_id.to_string(),
+            api_base: CLOUDFLARE_API_BASE.to_string(),
+            max_attempts: api_max_attempts()?,
         })
     }
 
+    /// Change how many times each API call is tried (at least once)
+    #[cfg(test)]
+    fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Send a request, retrying rate limits (429), connection failures and,
+    /// for idempotent methods (GET, PUT, DELETE, ...), server errors (5xx)
+    /// with exponential backoff
+    ///
+    /// A POST that creates something is not retried on a 5xx: Cloudflare may
+    /// have created it before failing, and a second try would then fail with
+    /// "already exists" or make a duplicate.
+    async fn request_with_retry<F>(&self, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        self.send_with_retry(build, false).await
+    }
+
+    /// `request_with_retry` for a POST that is safe to send twice, so server
+    /// errors are retried too
+    async fn replayable_request_with_retry<F>(&self, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        self.send_with_retry(build, true).await
+    }
+
+    /// `build` is called once per attempt, since a request is consumed by
+    /// sending it. A `Retry-After` header (in seconds) replaces the backoff for
+    /// that wait. After the last attempt the response is returned whatever its
+    /// status, so callers still report Cloudflare's error.
+    async fn send_with_retry<F>(&self, build: F, replayable: bool) -> Result<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let mut attempt = 1;
+        loop {
+            let last = attempt >= self.max_attempts;
+            let request = build().build()?;
+            let replayable = replayable || request.method().is_idempotent();
+            let wait = match self.client.execute(request).await {
+                Ok(response) if last || !is_retryable_status(response.status(), replayable) => {
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    let wait = retry_after(&response).unwrap_or_else(|| retry_backoff(attempt));
+                    eprintln!(
+                        "   ⏳ Cloudflare API returned {}, retrying in {}s ({}/{})",
+                        response.status(),
+                        wait.as_secs(),
+                        attempt,
+                        self.max_attempts
+                    );
+                    wait
+                }
+                Err(e) if !last && e.is_connect() => retry_backoff(attempt),
+                Err(e) => return Err(e.into()),
+            };
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        }
+    }
+
+    /// Point the client at a different API base (a mock server in tests)
+    #[cfg(test)]
+    fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
+    }
+
     /// Get Pages project by name
     async fn get_pages_project(&self, project_name: &str) -> Result<Option<PagesProject>> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/pages/projects/{}",
-            self.account_id, project_name
+            "{}/accounts/{}/pages/projects/{}",
+            self.api_base, self.account_id, project_name
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
 
         if response.status() == 404 {
             return Ok(None);
@@ -237,8 +932,8 @@ impl CloudflareClient {
     /// Create Pages project
     async fn create_pages_project(&self, project_name: &str) -> Result<PagesProject> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/pages/projects",
-            self.account_id
+            "{}/accounts/{}/pages/projects",
+            self.api_base, self.account_id
         );
 
         #[derive(Serialize)]
@@ -252,7 +947,9 @@ impl CloudflareClient {
             production_branch: DEFAULT_BRANCH.to_string(),
         };
 
-        let response = self.client.post(&url).json(&request).send().await?;
+        let response = self
+            .request_with_retry(|| self.client.post(&url).json(&request))
+            .await?;
         let cf_response: CloudflareResponse<PagesProject> = response.json().await?;
 
         if !cf_response.success {
@@ -265,61 +962,130 @@ impl CloudflareClient {
         cf_response.result.context("No project returned from API")
     }
 
-    /// Upload static site files to Pages project (Direct Upload)
-    async fn upload_deployment(&self, project_name: &str, build_dir: &Path) -> Result<String> {
-        use std::collections::HashMap;
-        use walkdir::WalkDir;
+    /// Upload the built site and create a Pages deployment
+    ///
+    /// Files are hashed by streaming them from disk, then only assets Pages does
+    /// not already have are uploaded, in size-bounded batches with at most
+    /// `concurrency` batches in flight. The deployment itself is created from the
+    /// manifest alone, so no single request holds the whole site in memory.
+    async fn upload_deployment(
+        &self,
+        project_name: &str,
+        build_dir: &Path,
+        branch: Option<&str>,
+        concurrency: usize,
+        progress: ProgressFn<'_>,
+    ) -> Result<String> {
+        use futures::{StreamExt, TryStreamExt};
+
+        // _headers is deployment config, sent with the deployment rather than as an asset
+        let (headers_files, files): (Vec<UploadFile>, Vec<UploadFile>) =
+            collect_upload_files(build_dir)?
+                .into_iter()
+                .partition(|f| f.relative_path == PAGES_HEADERS_FILE);
+        let jwt = self.get_pages_upload_token(project_name).await?;
+
+        // Skip assets Pages already stores from previous deployments
+        let hashes: Vec<&str> = files.iter().map(|f| f.hash.as_str()).collect();
+        let missing: std::collections::HashSet<String> = self
+            .pages_assets_request::<Vec<String>>(
+                &jwt,
+                "check-missing",
+                &serde_json::json!({ "hashes": hashes }),
+            )
+            .await?
+            .into_iter()
+            .collect();
+        let to_upload: Vec<&UploadFile> =
+            files.iter().filter(|f| missing.contains(&f.hash)).collect();
+
+        // Owned batches keep the upload stream's futures `Send` for `Deployer` callers
+        let batches: Vec<Vec<UploadFile>> =
+            batch_upload_files(&to_upload, MAX_BATCH_BYTES, MAX_BATCH_FILES)
+                .into_iter()
+                .map(|batch| batch.into_iter().cloned().collect())
+                .collect();
+        let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        let total_files = to_upload.len();
+        let total_bytes: u64 = to_upload.iter().map(|f| f.size).sum();
+        println!(
+            "   ℹ️  Uploading {} of {} files ({:.1} MB) in {} batch(es)",
+            total_files,
+            files.len(),
+            mb(total_bytes),
+            batches.len()
+        );
 
-        // Build manifest of all files with their hashes
-        let mut manifest = HashMap::new();
-        let mut form = reqwest::multipart::Form::new();
+        let total = batches.len();
+        let mut done = 0;
+        let (mut files_done, mut bytes_done) = (0, 0);
+        progress(DeployEvent::UploadingSite { done, total });
+        futures::stream::iter(batches)
+            .map(|batch| {
+                let (count, bytes) = (batch.len(), batch.iter().map(|f| f.size).sum::<u64>());
+                let names = describe_batch(&batch);
+                let jwt = &jwt;
+                async move {
+                    self.upload_pages_batch(jwt, batch)
+                        .await
+                        .with_context(|| format!("Failed to upload {}", names))
+                        .map(|()| (count, bytes))
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .try_for_each(|(count, bytes)| {
+                done += 1;
+                files_done += count;
+                bytes_done += bytes;
+                println!(
+                    "      ✓ {}/{} files, {:.1}/{:.1} MB",
+                    files_done,
+                    total_files,
+                    mb(bytes_done),
+                    mb(total_bytes)
+                );
+                progress(DeployEvent::UploadingSite { done, total });
+                futures::future::ready(Ok(()))
+            })
+            .await?;
 
-        for entry in WalkDir::new(build_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-        {
-            let path = entry.path();
-            let relative_path = path
-                .strip_prefix(build_dir)
-                .context("Failed to get relative path")?
-                .to_string_lossy()
-                .replace('\\', "/"); // Normalize path separators
-
-            // Read file and calculate SHA256 hash
-            let file_bytes = std::fs::read(path)
-                .with_context(|| format!("Failed to read file: {}", path.display()))?;
-
-            // Calculate SHA256 hash for manifest
-            use sha2::{Digest, Sha256};
-            let hash = format!("{:x}", Sha256::digest(&file_bytes));
-
-            manifest.insert(relative_path.clone(), hash);
-
-            // Add file to multipart form
-            let mime_type = mime_guess::from_path(path)
-                .first_or_octet_stream()
-                .to_string();
-
-            form = form.part(
-                relative_path.clone(),
-                reqwest::multipart::Part::bytes(file_bytes)
-                    .file_name(relative_path.clone())
-                    .mime_str(&mime_type)?,
-            );
-        }
+        self.pages_assets_request::<serde_json::Value>(
+            &jwt,
+            "upsert-hashes",
+            &serde_json::json!({ "hashes": hashes }),
+        )
+        .await?;
 
-        // Add manifest as JSON field
-        let manifest_json = serde_json::to_string(&manifest)?;
-        form = form.text("manifest", manifest_json);
+        let manifest_json = serde_json::to_string(&pages_manifest(&files))?;
+        let headers = if headers_files.is_empty() {
+            None
+        } else {
+            Some(fs::read(build_dir.join(PAGES_HEADERS_FILE)).context("Failed to read _headers")?)
+        };
+        // A multipart form is consumed by sending, so each attempt builds its own
+        let form = || {
+            let mut form = reqwest::multipart::Form::new().text("manifest", manifest_json.clone());
+            if let Some(branch) = branch {
+                form = form.text("branch", branch.to_string());
+            }
+            if let Some(headers) = &headers {
+                form = form.part(
+                    PAGES_HEADERS_FILE,
+                    reqwest::multipart::Part::bytes(headers.clone()).file_name(PAGES_HEADERS_FILE),
+                );
+            }
+            form
+        };
 
         // Upload via Cloudflare Pages Direct Upload API
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/pages/projects/{}/deployments",
-            self.account_id, project_name
+            "{}/accounts/{}/pages/projects/{}/deployments",
+            self.api_base, self.account_id, project_name
         );
 
-        let response = self.client.post(&url).multipart(form).send().await?;
+        let response = self
+            .request_with_retry(|| self.client.post(&url).multipart(form()))
+            .await?;
 
         let status = response.status();
         let response_text = response.text().await?;
@@ -350,11 +1116,11 @@ impl CloudflareClient {
     /// Delete Pages project
     async fn delete_pages_project(&self, project_name: &str) -> Result<()> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/pages/projects/{}",
-            self.account_id, project_name
+            "{}/accounts/{}/pages/projects/{}",
+            self.api_base, self.account_id, project_name
         );
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.request_with_retry(|| self.client.delete(&url)).await?;
         let cf_response: CloudflareResponse<serde_json::Value> = response.json().await?;
 
         if !cf_response.success {
@@ -367,12 +1133,18 @@ impl CloudflareClient {
         Ok(())
     }
 
-    /// Get DNS zone by domain name
-    async fn get_dns_zone(&self, domain: &str) -> Result<Option<DnsZone>> {
-        let url = format!("https://api.cloudflare.com/client/v4/zones?name={}", domain);
+    /// List a Pages project's deployments, most recent first
+    async fn list_deployments(&self, project_name: &str) -> Result<Vec<ProjectDeployment>> {
+        let url = format!(
+            "{}/accounts/{}/pages/projects/{}/deployments",
+            self.api_base, self.account_id, project_name
+        );
 
-        let response = self.client.get(&url).send().await?;
-        let cf_response: CloudflareResponse<Vec<DnsZone>> = response.json().await?;
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
+        let cf_response: CloudflareResponse<Vec<ProjectDeployment>> = response
+            .json()
+            .await
+            .context("Failed to parse deployment list")?;
 
         if !cf_response.success {
             if let Some(error) = cf_response.errors.first() {
@@ -381,37 +1153,83 @@ impl CloudflareClient {
             anyhow::bail!("Unknown Cloudflare API error");
         }
 
-        Ok(cf_response.result.and_then(|mut zones| zones.pop()))
+        Ok(cf_response.result.unwrap_or_default())
     }
 
-    /// Get existing DNS record by name
-    async fn get_dns_record(&self, zone_id: &str, name: &str) -> Result<Option<DnsRecord>> {
+    /// Make an earlier production deployment the live one again
+    async fn rollback_deployment(
+        &self,
+        project_name: &str,
+        deployment_id: &str,
+    ) -> Result<ProjectDeployment> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records?name={}",
-            zone_id, name
+            "{}/accounts/{}/pages/projects/{}/deployments/{}/rollback",
+            self.api_base, self.account_id, project_name, deployment_id
         );
 
-        let response = self.client.get(&url).send().await?;
-        let cf_response: CloudflareResponse<Vec<DnsRecord>> = response.json().await?;
+        // Rolling back to the same deployment twice leaves it live either way
+        let response = self
+            .replayable_request_with_retry(|| self.client.post(&url))
+            .await?;
+        let cf_response: CloudflareResponse<ProjectDeployment> = response
+            .json()
+            .await
+            .context("Failed to parse rollback response")?;
 
         if !cf_response.success {
-            return Ok(None);
+            if let Some(error) = cf_response.errors.first() {
+                anyhow::bail!("Cloudflare API error: {}", error.message);
+            }
+            anyhow::bail!("Unknown Cloudflare API error");
         }
 
-        Ok(cf_response.result.and_then(|mut records| records.pop()))
+        cf_response
+            .result
+            .context("No deployment returned from API")
     }
 
-    /// Create DNS CNAME record
-    async fn create_dns_record(
-        &self,
-        zone_id: &str,
+    /// Get DNS zone by domain name
+    async fn get_dns_zone(&self, domain: &str) -> Result<Option<DnsZone>> {
+        let url = format!("{}/zones?name={}", self.api_base, domain);
+
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
+        let cf_response: CloudflareResponse<Vec<DnsZone>> = response.json().await?;
+
+        if !cf_response.success {
+            if let Some(error) = cf_response.errors.first() {
+                anyhow::bail!("Cloudflare API error: {}", error.message);
+            }
+            anyhow::bail!("Unknown Cloudflare API error");
+        }
+
+        Ok(cf_response.result.and_then(|mut zones| zones.pop()))
+    }
+
+    /// Get existing DNS record by name
+    async fn get_dns_record(&self, zone_id: &str, name: &str) -> Result<Option<DnsRecord>> {
+        let url = format!(
+            "{}/zones/{}/dns_records?name={}",
+            self.api_base, zone_id, name
+        );
+
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
+        let cf_response: CloudflareResponse<Vec<DnsRecord>> = response.json().await?;
+
+        if !cf_response.success {
+            return Ok(None);
+        }
+
+        Ok(cf_response.result.and_then(|mut records| records.pop()))
+    }
+
+    /// Create DNS CNAME record
+    async fn create_dns_record(
+        &self,
+        zone_id: &str,
         name: &str,
         target: &str,
     ) -> Result<DnsRecord> {
-        let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records",
-            zone_id
-        );
+        let url = format!("{}/zones/{}/dns_records", self.api_base, zone_id);
 
         let record = DnsRecord {
             id: None,
@@ -421,7 +1239,9 @@ impl CloudflareClient {
             proxied: true, // Enable Cloudflare proxy for HTTPS
         };
 
-        let response = self.client.post(&url).json(&record).send().await?;
+        let response = self
+            .request_with_retry(|| self.client.post(&url).json(&record))
+            .await?;
         let cf_response: CloudflareResponse<DnsRecord> = response.json().await?;
 
         if !cf_response.success {
@@ -439,11 +1259,11 @@ impl CloudflareClient {
     /// Get R2 bucket by name
     async fn get_r2_bucket(&self, bucket_name: &str) -> Result<Option<R2Bucket>> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/r2/buckets/{}",
-            self.account_id, bucket_name
+            "{}/accounts/{}/r2/buckets/{}",
+            self.api_base, self.account_id, bucket_name
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
 
         if response.status() == 404 {
             return Ok(None);
@@ -461,13 +1281,35 @@ impl CloudflareClient {
         Ok(cf_response.result)
     }
 
-    /// Create R2 bucket
-    async fn create_r2_bucket(&self, bucket_name: &str) -> Result<R2Bucket> {
+    /// Get an R2 bucket's CORS policy, or `None` if it has none
+    async fn get_r2_bucket_cors(&self, bucket_name: &str) -> Result<Option<R2CorsPolicy>> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/r2/buckets",
-            self.account_id
+            "{}/accounts/{}/r2/buckets/{}/cors",
+            self.api_base, self.account_id, bucket_name
         );
 
+        let response = self.request_with_retry(|| self.client.get(&url)).await?;
+
+        if response.status() == 404 {
+            return Ok(None);
+        }
+
+        let cf_response: CloudflareResponse<R2CorsPolicy> = response.json().await?;
+
+        if !cf_response.success {
+            if let Some(error) = cf_response.errors.first() {
+                anyhow::bail!("Cloudflare API error: {}", error.message);
+            }
+            anyhow::bail!("Unknown Cloudflare API error");
+        }
+
+        Ok(cf_response.result)
+    }
+
+    /// Create R2 bucket
+    async fn create_r2_bucket(&self, bucket_name: &str) -> Result<R2Bucket> {
+        let url = format!("{}/accounts/{}/r2/buckets", self.api_base, self.account_id);
+
         #[derive(Serialize)]
         struct CreateBucketRequest {
             name: String,
@@ -477,7 +1319,9 @@ impl CloudflareClient {
             name: bucket_name.to_string(),
         };
 
-        let response = self.client.post(&url).json(&request).send().await?;
+        let response = self
+            .request_with_retry(|| self.client.post(&url).json(&request))
+            .await?;
         let cf_response: CloudflareResponse<R2Bucket> = response.json().await?;
 
         if !cf_response.success {
@@ -510,7 +1354,7 @@ impl CloudflareClient {
             account_id: self.account_id.clone(),
         };
 
-        let bucket = S3Bucket::new(bucket_name, region, credentials)?.with_path_style();
+        let bucket = s3_bucket(bucket_name, region, credentials)?;
 
         // List all objects in the bucket
         println!("      Listing bucket: {}", bucket_name);
@@ -610,11 +1454,11 @@ impl CloudflareClient {
     /// Delete R2 bucket
     async fn delete_r2_bucket(&self, bucket_name: &str) -> Result<()> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/r2/buckets/{}",
-            self.account_id, bucket_name
+            "{}/accounts/{}/r2/buckets/{}",
+            self.api_base, self.account_id, bucket_name
         );
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.request_with_retry(|| self.client.delete(&url)).await?;
         let cf_response: CloudflareResponse<serde_json::Value> = response.json().await?;
 
         if !cf_response.success {
@@ -627,45 +1471,78 @@ impl CloudflareClient {
         Ok(())
     }
 
-    /// Configure R2 bucket for public access with CORS
-    async fn configure_r2_public_access(&self, bucket_name: &str) -> Result<()> {
-        // Set CORS policy to allow browser access
+    /// Find a Workers KV namespace by title
+    async fn get_kv_namespace(&self, title: &str) -> Result<Option<KvNamespace>> {
+        const PER_PAGE: usize = 100;
+        for page in 1.. {
+            let url = format!(
+                "{}/accounts/{}/storage/kv/namespaces?per_page={}&page={}",
+                self.api_base, self.account_id, PER_PAGE, page
+            );
+
+            let response = self.request_with_retry(|| self.client.get(&url)).await?;
+            let cf_response: CloudflareResponse<Vec<KvNamespace>> = response.json().await?;
+
+            if !cf_response.success {
+                if let Some(error) = cf_response.errors.first() {
+                    anyhow::bail!("Cloudflare API error: {}", error.message);
+                }
+                anyhow::bail!("Unknown Cloudflare API error");
+            }
+
+            let namespaces = cf_response.result.unwrap_or_default();
+            let last_page = namespaces.len() < PER_PAGE;
+            if let Some(namespace) = namespaces.into_iter().find(|n| n.title == title) {
+                return Ok(Some(namespace));
+            }
+            if last_page {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Create a Workers KV namespace
+    async fn create_kv_namespace(&self, title: &str) -> Result<KvNamespace> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/r2/buckets/{}/cors",
-            self.account_id, bucket_name
+            "{}/accounts/{}/storage/kv/namespaces",
+            self.api_base, self.account_id
         );
 
-        #[derive(Serialize)]
-        struct CorsAllowed {
-            methods: Vec<String>,
-            origins: Vec<String>,
-            headers: Vec<String>,
-        }
+        let request = serde_json::json!({ "title": title });
+        let response = self
+            .request_with_retry(|| self.client.post(&url).json(&request))
+            .await?;
+        let cf_response: CloudflareResponse<KvNamespace> = response.json().await?;
 
-        #[derive(Serialize)]
-        struct CorsRule {
-            allowed: CorsAllowed,
-            #[serde(rename = "MaxAgeSeconds")]
-            max_age_seconds: u32,
+        if !cf_response.success {
+            if let Some(error) = cf_response.errors.first() {
+                anyhow::bail!("Cloudflare API error: {}", error.message);
+            }
+            anyhow::bail!("Unknown Cloudflare API error");
         }
 
-        #[derive(Serialize)]
-        struct CorsConfig {
-            rules: Vec<CorsRule>,
-        }
+        cf_response
+            .result
+            .context("No KV namespace returned from API")
+    }
 
-        let config = CorsConfig {
-            rules: vec![CorsRule {
-                allowed: CorsAllowed {
-                    methods: vec!["GET".to_string(), "HEAD".to_string()],
-                    origins: vec!["*".to_string()],
-                    headers: vec!["content-type".to_string()],
-                },
-                max_age_seconds: 3600,
-            }],
-        };
+    /// Configure R2 bucket for public access with CORS, allowing `origins`
+    async fn configure_r2_public_access(
+        &self,
+        bucket_name: &str,
+        origins: &[String],
+    ) -> Result<()> {
+        // Set CORS policy to allow browser access
+        let url = format!(
+            "{}/accounts/{}/r2/buckets/{}/cors",
+            self.api_base, self.account_id, bucket_name
+        );
 
-        let response = self.client.put(&url).json(&config).send().await?;
+        let request = r2_cors_request(origins);
+        let response = self
+            .request_with_retry(|| self.client.put(&url).json(&request))
+            .await?;
         let cf_response: CloudflareResponse<serde_json::Value> = response.json().await?;
 
         if !cf_response.success {
@@ -686,8 +1563,8 @@ impl CloudflareClient {
         zone_id: &str,
     ) -> Result<()> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/r2/buckets/{}/domains/custom",
-            self.account_id, bucket_name
+            "{}/accounts/{}/r2/buckets/{}/domains/custom",
+            self.api_base, self.account_id, bucket_name
         );
 
         let request = R2CustomDomain {
@@ -696,7 +1573,9 @@ impl CloudflareClient {
             zone_id: zone_id.to_string(),
         };
 
-        let response = self.client.post(&url).json(&request).send().await?;
+        let response = self
+            .request_with_retry(|| self.client.post(&url).json(&request))
+            .await?;
         let cf_response: CloudflareResponse<serde_json::Value> = response.json().await?;
 
         if !cf_response.success {
@@ -815,7 +1694,10 @@ fn validate_r2_access_key(key: &str) -> Result<()> {
 }
 
 /// Configure Cloudflare credentials and base domain
-pub async fn configure() -> Result<()> {
+///
+/// A dry run still asks every question, then shows where the answers would
+/// be saved instead of saving them.
+pub async fn configure(dry_run: DryRun) -> Result<()> {
     println!("🔧 Configuring Cloudflare deployment...\n");
 
     // Load existing config if any
@@ -963,6 +1845,14 @@ pub async fn configure() -> Result<()> {
     };
 
     // Save config
+    if !dry_run.allows(format_args!(
+        "save the configuration to {}",
+        config_path()?.display()
+    )) {
+        println!();
+        println!("✅ Dry run complete - configuration not saved");
+        return Ok(());
+    }
     save_config(&config)?;
 
     println!();
@@ -985,9 +1875,150 @@ pub async fn configure() -> Result<()> {
     Ok(())
 }
 
+/// Tracks larger than `limit_mb`, as "path (size MB)"
+///
+/// Missing files are ignored here; the upload loop reports them.
+fn oversized_tracks(path: &Path, album: &Album, limit_mb: u64) -> Vec<String> {
+    let limit_bytes = limit_mb.saturating_mul(1024 * 1024);
+    album
+        .tracks
+        .iter()
+        .filter_map(|track| {
+            let audio_file = path.join(&track.file);
+            let size = fs::metadata(&audio_file).ok()?.len();
+            (size > limit_bytes).then(|| {
+                format!(
+                    "{} ({:.1} MB)",
+                    audio_file.display(),
+                    size as f64 / (1024.0 * 1024.0)
+                )
+            })
+        })
+        .collect()
+}
+
+/// Ensure no track exceeds the upload size limit
+fn check_track_sizes(path: &Path, album: &Album, max_file_size_mb: u64) -> Result<()> {
+    let oversized = oversized_tracks(path, album, max_file_size_mb);
+    if !oversized.is_empty() {
+        anyhow::bail!(
+            "Track file(s) exceed the {} MB upload limit:\n  {}\nCompress the audio or raise the limit with --max-file-size-mb",
+            max_file_size_mb,
+            oversized.join("\n  ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Largest single file Cloudflare Pages accepts
+const PAGES_MAX_FILE_MB: u64 = 25;
+
+/// Ensure every track fits in a Pages deployment (`--audio-mode local`)
+fn check_bundled_track_sizes(path: &Path, album: &Album) -> Result<()> {
+    let oversized = oversized_tracks(path, album, PAGES_MAX_FILE_MB);
+    if !oversized.is_empty() {
+        anyhow::bail!(
+            "Track file(s) exceed the {} MB Cloudflare Pages file limit, so they can't be bundled with --audio-mode local:\n  {}\nCompress the audio or serve it from R2 (--audio-mode cdn)",
+            PAGES_MAX_FILE_MB,
+            oversized.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// Where a Pages deploy's audio is served from
+enum PagesAudio {
+    /// Uploaded to a bucket and served from its public base URL
+    Bucket {
+        bucket: Box<S3Bucket>,
+        /// Public base URL of `bucket`, baked into the built site
+        base_url: String,
+    },
+    /// Copied into the Pages deployment itself (`--audio-mode local`)
+    Bundled,
+    /// Already uploaded by an earlier publish; only linked (`--cover-only`)
+    Existing { base_url: String },
+}
+
+/// How `publish` deploys to Cloudflare Pages
+#[derive(Debug, Clone, Default)]
+pub struct PublishOptions {
+    /// Skip the confirmation prompt
+    pub force: bool,
+    /// Max concurrent uploads (default: 3)
+    pub concurrency: Option<usize>,
+    /// Refuse to upload any track larger than this many megabytes
+    pub max_file_size_mb: u64,
+    /// Upload audio with embedded tags removed
+    pub strip_metadata: bool,
+    /// Pages branch to deploy to (the production branch when `None`)
+    pub branch: Option<String>,
+    /// Put the audio in the Pages deployment instead of R2
+    pub bundle_audio: bool,
+    /// Print the Cloudflare calls `publish` would make, and make none
+    pub dry_run: DryRun,
+    /// Rebuild and redeploy the site only, against audio already in the bucket
+    pub cover_only: bool,
+    /// Ignore an interrupted publish's progress and upload everything again
+    pub restart: bool,
+    /// Deploy `draft = true` tracks too
+    pub include_drafts: bool,
+    /// Upload every track, even ones unchanged since the last publish
+    pub reupload: bool,
+    /// Delete audio objects album.toml no longer names, once the site is live
+    pub prune_removed: bool,
+}
+
+impl PublishOptions {
+    /// Any branch but the production one is a preview deploy
+    fn environment(&self) -> DeployEnvironment {
+        match &self.branch {
+            Some(branch) if branch != DEFAULT_BRANCH => DeployEnvironment::Preview,
+            _ => DeployEnvironment::Production,
+        }
+    }
+}
+
 /// Publish album to Cloudflare Pages
-pub async fn publish(path: PathBuf, force: bool, concurrency: Option<usize>) -> Result<()> {
-    println!("🚀 Publishing album to Cloudflare Pages...\n");
+///
+/// With `bundle_audio`, the audio goes into the Pages deployment instead of
+/// R2 (for small releases, or when R2 isn't available); every track must be
+/// under the 25 MB Pages file limit.
+///
+/// With `dry_run`, everything local still happens (album checks, a build to a
+/// temporary directory) but no API client is created: the planned calls are
+/// printed instead.
+///
+/// With `cover_only`, storage setup and audio uploads are skipped: the site
+/// is rebuilt (picking up new artwork) and deployed against the audio a
+/// previous publish left in the bucket.
+///
+/// Progress is checkpointed to `.release-kit/publish-progress.toml` in the
+/// album directory, so re-running after a failure skips the audio already
+/// uploaded and, when the rebuilt site is unchanged, the site deploy. The
+/// file is removed once a publish completes; `restart` ignores it.
+pub async fn publish(path: PathBuf, options: PublishOptions) -> Result<()> {
+    let PublishOptions {
+        force,
+        concurrency,
+        max_file_size_mb,
+        strip_metadata,
+        ref branch,
+        bundle_audio,
+        dry_run,
+        cover_only,
+        restart,
+        include_drafts,
+        reupload,
+        prune_removed,
+    } = options;
+
+    if dry_run.is_enabled() {
+        println!("🚀 Publishing album to Cloudflare Pages (dry run)...\n");
+    } else {
+        println!("🚀 Publishing album to Cloudflare Pages...\n");
+    }
 
     // Validate and load album config
     let album_toml_path = path.join("album.toml");
@@ -999,9 +2030,29 @@ pub async fn publish(path: PathBuf, force: bool, concurrency: Option<usize>) ->
         );
     }
 
-    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    let mut album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    // Drafts left out of this deploy keep their audio, so collect keys before dropping them
+    let referenced = prune_removed.then(|| referenced_audio_keys(&album));
+    if !include_drafts {
+        album.remove_drafts();
+    }
     let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
 
+    let environment = options.environment();
+    if prune_removed {
+        if bundle_audio {
+            anyhow::bail!("--prune-removed needs audio in a bucket, not --audio-mode local");
+        }
+        // Like teardown, leave a user-managed bucket alone
+        if album.hosting.s3.is_some() {
+            anyhow::bail!("--prune-removed only prunes R2, not the [hosting.s3] bucket");
+        }
+        // Previews share the production bucket
+        if environment == DeployEnvironment::Preview {
+            anyhow::bail!("--prune-removed is only supported for production deploys");
+        }
+    }
+
     // Validate project name is not empty or invalid
     if project_name.is_empty() || project_name == "-" {
         anyhow::bail!(
@@ -1011,19 +2062,69 @@ pub async fn publish(path: PathBuf, force: bool, concurrency: Option<usize>) ->
         );
     }
 
-    // Get subdomain from album config if specified
-    let subdomain = album.hosting.cloudflare.subdomain.clone();
+    // Catch accidentally-huge masters and colliding audio names before touching R2
+    if !cover_only {
+        check_track_sizes(&path, &album, max_file_size_mb)?;
+    }
+    check_audio_file_names(&album, false)?;
+    if bundle_audio {
+        check_bundled_track_sizes(&path, &album)?;
+    }
+
+    let is_preview = environment == DeployEnvironment::Preview;
+
+    // Get subdomain from album config if specified (previews keep the pages.dev URL)
+    let subdomain = album
+        .hosting
+        .cloudflare
+        .subdomain
+        .clone()
+        .filter(|_| !is_preview);
 
     println!("📋 Deployment Plan:");
     println!("   Album: {}", album.metadata.title);
     println!("   Artist: {}", album.artist.name);
     println!("   Project: {}", project_name);
     println!("   Target: Cloudflare Pages (Free Tier)");
+    if let Some(branch) = branch {
+        if is_preview {
+            println!("   Branch: {} (preview, not indexed)", branch);
+        } else {
+            println!("   Branch: {}", branch);
+        }
+    }
     if let Some(ref sub) = subdomain {
         println!("   Subdomain: {}", sub);
     }
+    if bundle_audio {
+        println!("   Audio: bundled into the Pages deployment");
+    } else if cover_only {
+        println!("   Audio: unchanged (--cover-only redeploys the site only)");
+    } else if let Some(s3) = &album.hosting.s3 {
+        println!("   Audio: {} (bucket {})", s3.endpoint, s3.bucket);
+    }
     println!();
 
+    if dry_run.is_enabled() {
+        let config = load_config()?;
+        let steps = dry_run_steps(
+            &path,
+            &album,
+            config.as_ref().map(|c| &c.cloudflare),
+            &options,
+            subdomain.as_deref(),
+        )?;
+        println!("🧪 Dry run - these Cloudflare calls would be made:");
+        for (i, step) in steps.iter().enumerate() {
+            println!("   {}. {}", i + 1, step);
+        }
+        if config.is_none() {
+            println!("\n   ℹ️  No Cloudflare configuration found; <account-id> is a placeholder");
+        }
+        println!("\n✅ Dry run complete - nothing was changed");
+        return Ok(());
+    }
+
     // Load global config
     let config = load_config()?
         .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
@@ -1059,182 +2160,1183 @@ pub async fn publish(path: PathBuf, force: bool, concurrency: Option<usize>) ->
         println!();
     }
 
-    // R2 audio storage (always enabled)
-    // R2 bucket name: {project-name}-audio
-    //
-    // Memory Requirements:
-    // - Each audio file is loaded entirely into memory during upload
-    // - For large albums with high-quality audio (e.g., 10 FLAC files @ 200MB each),
-    //   peak memory usage can reach 600MB+ (3 concurrent uploads × 200MB per file)
-    // - Adjust concurrency (-c flag) if running on memory-constrained systems
-    // - Example: Use `-c 1` for single-file uploads to minimize memory usage
-    let bucket_name = format!("{}-audio", project_name);
-
-    println!("📦 Setting up R2 audio storage...");
+    // Pick up where an interrupted publish of this project stopped
+    let checkpoint = Checkpoint::open(&path, &project_name, restart);
+    if let Some(summary) = checkpoint.resume_summary() {
+        println!("↻ Resuming an interrupted publish ({})", summary);
+        println!("   Use --restart to start over");
+        println!();
+    }
 
-    // Check if R2 bucket exists
-    let bucket_exists = match client.get_r2_bucket(&bucket_name).await? {
-        Some(_) => {
-            println!("   ✓ R2 bucket exists: {}", bucket_name);
-            true
-        }
-        None => {
-            println!("   ℹ️  Creating R2 bucket: {}", bucket_name);
-            client.create_r2_bucket(&bucket_name).await?;
-            println!("   ✓ R2 bucket created");
-            false
-        }
+    // Audio storage: bundled, [hosting.s3] if configured, otherwise the album's R2 bucket
+    let audio = if bundle_audio {
+        PagesAudio::Bundled
+    } else if cover_only {
+        let base_url = match &album.hosting.s3 {
+            Some(s3) => s3.public_base_url(),
+            None => {
+                let bucket_name = format!("{}-audio", project_name);
+                if client.get_r2_bucket(&bucket_name).await?.is_none() {
+                    anyhow::bail!(
+                        "R2 bucket {} not found - run publish without --cover-only first",
+                        bucket_name
+                    );
+                }
+                signed_audio_base_url(&album)
+                    .unwrap_or_else(|| r2_public_base_url(&config.cloudflare, &project_name))
+            }
+        };
+        println!("🎵 Keeping the audio already at: {}", base_url);
+        println!();
+        PagesAudio::Existing { base_url }
+    } else {
+        let (bucket, base_url) = match &album.hosting.s3 {
+            Some(s3) => {
+                println!("📦 Using S3-compatible audio storage...");
+                println!("   Endpoint: {}", s3.endpoint);
+                println!("   Bucket: {}", s3.bucket);
+                (custom_s3_bucket(s3)?, s3.public_base_url())
+            }
+            None => prepare_r2_audio(&client, &config, &album, &project_name)
+                .await
+                .context(
+                    "R2 audio setup failed (small albums can skip R2 with --audio-mode local)",
+                )?,
+        };
+        println!("   ✓ Audio will be served from: {}", base_url);
+        println!();
+        PagesAudio::Bucket { bucket, base_url }
     };
 
-    // Upload audio files to R2 with retry logic
-    println!("   📤 Uploading audio files to R2...");
-    let audio_dir = path.join("audio");
-    if !audio_dir.exists() {
-        anyhow::bail!("Audio directory not found: {}", audio_dir.display());
+    if album.hosting.cloudflare.stream_worker_url.is_some() && !cover_only {
+        let namespace = ensure_stats_namespace(&client, &project_name).await?;
+        let settings = stream_worker_settings(
+            &album,
+            &project_name,
+            config.cloudflare.base_domain.as_deref(),
+            &namespace,
+        );
+        println!("📊 Streaming worker settings (add to its wrangler.toml):");
+        println!("{}", settings);
     }
 
-    // Create rust-s3 bucket configuration for R2
-    let credentials = S3Credentials::new(
-        Some(&config.cloudflare.r2_access_key_id),
-        Some(&config.cloudflare.r2_secret_access_key),
-        None,
-        None,
-        None,
-    )?;
+    let custom_domain = subdomain.zip(config.cloudflare.base_domain.clone());
+
+    // R2 buckets only: teardown deletes what's recorded here
+    let (state_bucket, cdn_url) = match &audio {
+        PagesAudio::Bucket { bucket, base_url } => (
+            album.hosting.s3.is_none().then(|| bucket.name()),
+            Some(base_url.clone()),
+        ),
+        PagesAudio::Bundled => (None, None),
+        PagesAudio::Existing { base_url } => (
+            album
+                .hosting
+                .s3
+                .is_none()
+                .then(|| format!("{}-audio", project_name)),
+            Some(base_url.clone()),
+        ),
+    };
 
-    let region = S3Region::R2 {
-        account_id: config.cloudflare.account_id.clone(),
+    let deployment = PagesDeployment {
+        client: &client,
+        path: &path,
+        album: &album,
+        project_name: &project_name,
+        project_exists,
+        audio,
+        concurrency: concurrency.unwrap_or(3),
+        custom_domain: custom_domain.clone(),
+        strip_metadata,
+        branch: branch.as_deref(),
+        environment,
+        include_drafts,
+        reupload,
+        checkpoint: Some(&checkpoint),
     };
+    let result = deployment.deploy().await?;
+    checkpoint.finish();
 
-    let bucket = S3Bucket::new(&bucket_name, region, credentials)?.with_path_style();
+    // The new site is live, so nothing links to the removed tracks any more
+    if let (Some(referenced), PagesAudio::Bucket { bucket, .. }) = (&referenced, &deployment.audio)
+    {
+        println!("🧹 Pruning removed tracks...");
+        let orphans = orphaned_audio_keys(bucket, &album, referenced).await?;
+        prune_audio(bucket, &orphans, force).await?;
+        println!();
+    }
 
-    // Create semaphore to limit concurrent uploads (default: 3)
-    let max_concurrent_uploads = concurrency.unwrap_or(3);
-    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(max_concurrent_uploads));
-    println!("   ℹ️  Max concurrent uploads: {}", max_concurrent_uploads);
+    let state = DeployState {
+        project: project_name.clone(),
+        bucket: state_bucket,
+        cdn_url,
+        deployed_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    };
+    if let Err(e) = state.save(&path) {
+        eprintln!("   ⚠️  Failed to save deploy state: {:#}", e);
+    }
 
-    // Collect upload tasks
-    let mut upload_tasks = Vec::new();
+    println!("✅ Deployment complete!");
+    println!("   Live URL: {}", result.site_url);
+    if let Some(feed_url) = &result.feed_url {
+        println!("   RSS feed: {}", feed_url);
+    }
+    if let Some((subdomain, base_domain)) = &custom_domain {
+        println!(
+            "   Custom domain: https://{}.{} (DNS propagation may take a few minutes)",
+            subdomain, base_domain
+        );
+    }
 
-    for track in &album.tracks {
-        let audio_file = path.join(&track.file);
-        if !audio_file.exists() {
-            eprintln!(
-                "   ⚠️  Warning: Audio file not found: {}",
-                audio_file.display()
-            );
-            continue;
-        }
+    Ok(())
+}
 
-        let filename = audio_file
-            .file_name()
-            .context("Invalid audio filename")?
-            .to_str()
-            .context("Invalid UTF-8 in filename")?
-            .to_string();
+/// Object keys of every track's audio (and stream copy) in album.toml
+///
+/// Everything else under the album's `audio/` prefix is left over from
+/// tracks since removed. `album` should still have its drafts; the keys are
+/// taken both with and without them, since colliding basenames are numbered
+/// by track position and leaving a draft out can renumber them.
+fn referenced_audio_keys(album: &Album) -> BTreeSet<String> {
+    let mut published = album.clone();
+    published.remove_drafts();
+
+    let stream_format = album.distribution.stream_copy_format();
+    [album, &published]
+        .into_iter()
+        .flat_map(|album| {
+            album
+                .audio_file_names()
+                .into_iter()
+                .flat_map(|name| {
+                    let stream_copy = stream_format.map(|format| format.stream_file_name(&name));
+                    std::iter::once(name).chain(stream_copy)
+                })
+                .map(|name| album.audio_key(&name))
+        })
+        .collect()
+}
 
-        let r2_key = format!("audio/{}", filename);
+/// Keys under the album's `audio/` prefix in `bucket` that aren't in `referenced`, sorted
+async fn orphaned_audio_keys(
+    bucket: &S3Bucket,
+    album: &Album,
+    referenced: &BTreeSet<String>,
+) -> Result<Vec<String>> {
+    let mut orphans: Vec<String> = bucket
+        .list(album.audio_key(""), None)
+        .await
+        .with_context(|| format!("Failed to list bucket {}", bucket.name()))?
+        .into_iter()
+        .flat_map(|page| page.contents.into_iter().map(|object| object.key))
+        .filter(|key| !referenced.contains(key))
+        .collect();
+    orphans.sort();
+    Ok(orphans)
+}
 
-        // Clone data needed for async task
-        let audio_file_clone = audio_file.clone();
-        let bucket_clone = bucket.clone();
-        let semaphore_clone = semaphore.clone();
+/// Delete `orphans` from `bucket`, after a confirmation unless `force`
+///
+/// # Returns
+///
+/// The number of objects deleted
+async fn prune_audio(bucket: &S3Bucket, orphans: &[String], force: bool) -> Result<usize> {
+    if orphans.is_empty() {
+        println!("   ✓ No removed tracks left in {}", bucket.name());
+        return Ok(0);
+    }
 
-        // Spawn upload task with retry logic and concurrency limiting
-        let task = tokio::spawn(async move {
-            // Acquire semaphore permit (limits concurrent uploads)
-            let _permit = semaphore_clone
-                .acquire()
-                .await
-                .expect("Semaphore should not be closed");
+    println!("   No longer in album.toml:");
+    for key in orphans {
+        println!("      - {}", key);
+    }
+    if !force {
+        print!(
+            "❓ Delete these {} object(s) from {}? (y/N): ",
+            orphans.len(),
+            bucket.name()
+        );
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
 
-            let content_type = match audio_file_clone.extension().and_then(|e| e.to_str()) {
-                Some("flac") => "audio/flac",
-                Some("mp3") => "audio/mpeg",
-                Some("wav") => "audio/wav",
-                Some("ogg") => "audio/ogg",
-                _ => "application/octet-stream",
-            };
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("   Left them in place");
+            return Ok(0);
+        }
+    }
 
-            // Read file into memory (for both small and large files)
-            let file_contents = tokio::fs::read(&audio_file_clone)
-                .await
-                .context("Failed to read file for upload")?;
+    for key in orphans {
+        bucket
+            .delete_object(key)
+            .await
+            .with_context(|| format!("Failed to delete object: {}", key))?;
+    }
+    println!("   ✓ Deleted {} object(s)", orphans.len());
+    Ok(orphans.len())
+}
 
-            // Retry logic: 5 attempts with exponential backoff
-            let mut last_error = None;
-            for attempt in 1..=5 {
-                let result = bucket_clone
-                    .put_object_with_content_type(&r2_key, &file_contents, content_type)
-                    .await
-                    .map(|_| ());
+/// Find or create the KV namespace the streaming worker keeps play counts in
+async fn ensure_stats_namespace(
+    client: &CloudflareClient,
+    project_name: &str,
+) -> Result<KvNamespace> {
+    let title = stats_namespace_title(project_name);
+    match client.get_kv_namespace(&title).await? {
+        Some(namespace) => Ok(namespace),
+        None => client
+            .create_kv_namespace(&title)
+            .await
+            .context("Failed to create KV namespace for play counts"),
+    }
+}
 
-                match result {
-                    Ok(_) => {
-                        return Ok::<String, anyhow::Error>(filename.clone());
+/// wrangler.toml vars and bindings for the streaming worker
+/// (crates/worker-template), from album.toml and the provisioned KV namespace
+///
+/// The worker is deployed separately, so `publish` prints these rather than
+/// applying them.
+fn stream_worker_settings(
+    album: &Album,
+    project_name: &str,
+    base_domain: Option<&str>,
+    stats: &KvNamespace,
+) -> String {
+    let mut settings = format!(
+        "[vars]\nALLOWED_ORIGINS = \"{}\"\n",
+        site_origins(album, project_name, base_domain)
+            .iter()
+            .filter(|origin| !origin.contains('*'))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let limit = album
+        .limits
+        .as_ref()
+        .and_then(|limits| limits.max_concurrent_streams);
+    if let Some(limit) = limit {
+        settings.push_str(&format!("MAX_CONCURRENT_STREAMS = \"{}\"\n", limit));
+    }
+    if let Some(prefix) = album.audio_key("").strip_suffix("/audio/") {
+        settings.push_str(&format!("AUDIO_KEY_PREFIX = \"{}\"\n", prefix));
+    }
+    settings.push_str(&format!(
+        "\n[[kv_namespaces]]\nbinding = \"STATS\"\nid = \"{}\"\n",
+        stats.id
+    ));
+    if limit.is_some() {
+        settings.push_str(
+            "\n[[durable_objects.bindings]]\nname = \"STREAM_LIMITER\"\nclass_name = \"StreamLimiter\"\n\n[[migrations]]\ntag = \"v1\"\nnew_classes = [\"StreamLimiter\"]\n",
+        );
+    }
+    settings
+}
+
+/// Everything `publish` would upload and create, for `--manifest-only`
+#[derive(Debug, Serialize)]
+struct PublishPlan {
+    project: String,
+    audio_bucket: String,
+    audio_base_url: String,
+    objects: Vec<PlannedObject>,
+    dns_records: Vec<PlannedDnsRecord>,
+}
+
+/// A single object `publish` would upload
+#[derive(Debug, Serialize)]
+struct PlannedObject {
+    /// "audio" for the audio bucket, "pages" for the site deployment
+    destination: &'static str,
+    key: String,
+    /// Local file, or `None` for generated content
+    source: Option<PathBuf>,
+    size: u64,
+    content_type: String,
+}
+
+/// A DNS record `publish` would create if missing
+#[derive(Debug, Serialize)]
+struct PlannedDnsRecord {
+    name: String,
+    #[serde(rename = "type")]
+    record_type: &'static str,
+    content: String,
+}
+
+/// Load album.toml the way every non-Cloudflare target (and the upload plan)
+/// publishes it: drafts dropped, oversized masters and colliding audio names
+/// rejected before anything is built or uploaded
+fn load_publishable_album(path: &Path, max_file_size_mb: u64) -> Result<Album> {
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nRun 'release-kit init {}' first",
+            path.display(),
+            path.display()
+        );
+    }
+
+    let mut album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    album.remove_drafts();
+    check_track_sizes(path, &album, max_file_size_mb)?;
+    check_audio_file_names(&album, false)?;
+    Ok(album)
+}
+
+/// Print the upload plan for `publish` as JSON, without touching Cloudflare
+///
+/// The site is built to a temporary directory so its files can be listed.
+/// Without a global config, the R2 account ID in URLs is a placeholder.
+pub async fn publish_manifest(path: PathBuf, max_file_size_mb: u64) -> Result<()> {
+    let album = load_publishable_album(&path, max_file_size_mb)?;
+
+    let config = load_config()?;
+    let plan = plan_publish(&path, &album, config.as_ref().map(|c| &c.cloudflare), false)?;
+    println!("{}", serde_json::to_string_pretty(&plan)?);
+
+    Ok(())
+}
+
+/// Work out what `publish` would upload, mirroring its storage and DNS choices
+fn plan_publish(
+    path: &Path,
+    album: &Album,
+    config: Option<&CloudflareConfig>,
+    include_drafts: bool,
+) -> Result<PublishPlan> {
+    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+    if project_name.is_empty() || project_name == "-" {
+        anyhow::bail!(
+            "Invalid album/artist names - cannot create project name.\nAlbum: '{}', Artist: '{}'",
+            album.metadata.title,
+            album.artist.name
+        );
+    }
+
+    let account_id = config.map_or("<account-id>", |c| c.account_id.as_str());
+    let base_domain = config.and_then(|c| c.base_domain.as_deref());
+    let mut dns_records = Vec::new();
+
+    let (audio_bucket, audio_base_url) = match &album.hosting.s3 {
+        Some(s3) => (s3.bucket.clone(), s3.public_base_url()),
+        None => {
+            let bucket_name = format!("{}-audio", project_name);
+            let cdn_url = match (signed_audio_base_url(album), base_domain) {
+                // The bucket stays private behind the signing worker
+                (Some(worker_url), _) => worker_url,
+                (None, Some(base_domain)) => {
+                    let cdn_domain = format!("{}.{}", bucket_name, base_domain);
+                    dns_records.push(PlannedDnsRecord {
+                        name: cdn_domain.clone(),
+                        record_type: DNS_RECORD_TYPE,
+                        content: format!("{}.r2.cloudflarestorage.com", account_id),
+                    });
+                    format!("https://{}", cdn_domain)
+                }
+                (None, None) => format!("https://pub-{}.r2.dev", account_id),
+            };
+            (bucket_name, cdn_url)
+        }
+    };
+
+    let mut objects = Vec::new();
+    for (track, filename) in album.tracks.iter().zip(album.audio_file_names()) {
+        let audio_file = path.join(&track.file);
+        let Ok(metadata) = fs::metadata(&audio_file) else {
+            continue;
+        };
+        objects.push(PlannedObject {
+            destination: "audio",
+            key: album.audio_key(&filename),
+            content_type: content_type_for(&audio_file),
+            source: Some(audio_file),
+            size: metadata.len(),
+        });
+    }
+    let manifest = serde_json::to_vec_pretty(&DeployManifest::from_album(path, album)?)?;
+    objects.push(PlannedObject {
+        destination: "audio",
+        key: manifest_key(album),
+        source: None,
+        size: manifest.len() as u64,
+        content_type: "application/json".to_string(),
+    });
+
+    let build = TempDir::new().context("Failed to create temporary directory")?;
+    build_static_site(
+        path,
+        build.path(),
+        &BuildOptions {
+            audio_base_url: Some(&audio_base_url),
+            include_drafts,
+            ..Default::default()
+        },
+    )?;
+    for file in collect_upload_files(build.path())? {
+        objects.push(PlannedObject {
+            destination: "pages",
+            key: file.relative_path,
+            source: None,
+            size: file.size,
+            content_type: file.content_type,
+        });
+    }
+
+    if let (Some(subdomain), Some(base_domain)) = (&album.hosting.cloudflare.subdomain, base_domain)
+    {
+        dns_records.push(PlannedDnsRecord {
+            name: format!("{}.{}", subdomain, base_domain),
+            record_type: DNS_RECORD_TYPE,
+            content: format!("{}.pages.dev", project_name),
+        });
+    }
+
+    Ok(PublishPlan {
+        project: project_name,
+        audio_bucket,
+        audio_base_url,
+        objects,
+        dns_records,
+    })
+}
+
+/// The Cloudflare calls `publish` would make, in order, for `--dry-run`
+///
+/// Worked out from the album, the global config and a build to a temporary
+/// directory; nothing here touches the network. Calls that depend on what
+/// already exists (a bucket, a DNS record) are listed as "if missing".
+fn dry_run_steps(
+    path: &Path,
+    album: &Album,
+    config: Option<&CloudflareConfig>,
+    options: &PublishOptions,
+    subdomain: Option<&str>,
+) -> Result<Vec<String>> {
+    let plan = plan_publish(path, album, config, options.include_drafts)?;
+    let environment = options.environment();
+    let account_id = config.map_or("<account-id>", |c| c.account_id.as_str());
+    let base_domain = config.and_then(|c| c.base_domain.as_deref());
+    let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+    let mut steps = vec![format!(
+        "Look up Pages project {} (create it if missing)",
+        plan.project
+    )];
+
+    let pages_files: Vec<u64> = if options.bundle_audio {
+        let build = TempDir::new().context("Failed to create temporary directory")?;
+        build_pages_site(
+            path,
+            build.path(),
+            None,
+            options.strip_metadata,
+            environment,
+            options.include_drafts,
+        )?;
+        collect_upload_files(build.path())?
+            .iter()
+            .map(|file| file.size)
+            .collect()
+    } else {
+        plan.objects
+            .iter()
+            .filter(|object| object.destination == "pages")
+            .map(|object| object.size)
+            .collect()
+    };
+
+    let mut prune_step = None;
+    if options.cover_only && !options.bundle_audio {
+        if album.hosting.s3.is_none() {
+            steps.push(format!(
+                "Look up R2 bucket {} (audio is not re-uploaded)",
+                plan.audio_bucket
+            ));
+        }
+    } else if !options.bundle_audio {
+        let storage = match &album.hosting.s3 {
+            Some(s3) => format!("S3 bucket {} at {}", s3.bucket, s3.endpoint),
+            None => {
+                steps.push(format!(
+                    "Create R2 bucket {} (if missing)",
+                    plan.audio_bucket
+                ));
+                match (signed_audio_base_url(album), base_domain) {
+                    (Some(worker_url), _) => steps.push(format!(
+                        "Keep R2 bucket {} private (no CORS or custom domain): tracks stream through {}",
+                        plan.audio_bucket, worker_url
+                    )),
+                    (None, base_domain) => {
+                        steps.push(format!(
+                            "Set R2 bucket CORS to allow {} (new buckets, or when cors_origins is set)",
+                            r2_cors_origins(album, &plan.project, base_domain).join(", ")
+                        ));
+                        if let Some(base_domain) = base_domain {
+                    let cdn_domain = format!("{}.{}", plan.audio_bucket, base_domain);
+                    steps.push(format!(
+                        "Connect custom domain {} to R2 bucket {}",
+                        cdn_domain, plan.audio_bucket
+                    ));
+                    steps.push(format!(
+                        "Add {} record {} → {}.r2.cloudflarestorage.com (if missing)",
+                        DNS_RECORD_TYPE, cdn_domain, account_id
+                    ));
+                }
                     }
-                    Err(e) => {
-                        last_error = Some(e);
-                        if attempt < 5 {
-                            // Exponential backoff: 1s, 2s, 3s, 4s
-                            tokio::time::sleep(Duration::from_secs(attempt)).await;
+                }
+                format!("R2 bucket {}", plan.audio_bucket)
+            }
+        };
+        let tracks: Vec<&PlannedObject> = plan
+            .objects
+            .iter()
+            .filter(|object| object.destination == "audio" && object.source.is_some())
+            .collect();
+        steps.push(format!(
+            "Upload {} audio file(s) ({:.1} MB) and {} to {}",
+            tracks.len(),
+            mb(tracks.iter().map(|object| object.size).sum()),
+            manifest_key(album),
+            storage
+        ));
+        if options.prune_removed && album.hosting.s3.is_none() {
+            prune_step = Some(format!(
+                "List {} under {} and delete objects album.toml no longer names (after a confirmation)",
+                storage,
+                album.audio_key("")
+            ));
+        }
+    }
+
+    if album.hosting.cloudflare.stream_worker_url.is_some() && !options.cover_only {
+        steps.push(format!(
+            "Create KV namespace {} for play counts (if missing)",
+            stats_namespace_title(&plan.project)
+        ));
+    }
+
+    steps.push(format!(
+        "Upload {} site file(s) ({:.1} MB) to Pages project {}{}",
+        pages_files.len(),
+        mb(pages_files.iter().sum()),
+        plan.project,
+        if environment == DeployEnvironment::Preview {
+            " as a preview deployment"
+        } else {
+            ""
+        }
+    ));
+
+    if let (Some(subdomain), Some(base_domain)) = (subdomain, base_domain) {
+        steps.push(format!(
+            "Add {} record {}.{} → {}.pages.dev (if missing)",
+            DNS_RECORD_TYPE, subdomain, base_domain, plan.project
+        ));
+    }
+    steps.extend(prune_step);
+
+    Ok(steps)
+}
+
+/// Default location of publish progress, relative to the album directory
+const PUBLISH_PROGRESS_FILE: &str = ".release-kit/publish-progress.toml";
+
+/// How far a publish got, so a re-run after a failure can skip finished steps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PublishProgress {
+    /// Pages project this progress belongs to
+    project: String,
+    /// Bucket the `uploaded` objects went to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bucket: Option<String>,
+    /// Uploaded audio object keys, with the sha256 of what was uploaded
+    #[serde(default)]
+    uploaded: BTreeMap<String, String>,
+    /// The site deployment, once it succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    site: Option<DeployedSite>,
+}
+
+/// A finished site deployment in `PublishProgress`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DeployedSite {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    /// `site_content_hash` of what was deployed
+    content_hash: String,
+    url: String,
+}
+
+/// A publish's progress file, rewritten after every completed step
+struct Checkpoint {
+    path: PathBuf,
+    progress: std::sync::Mutex<PublishProgress>,
+}
+
+impl Checkpoint {
+    /// Load `project`'s progress from the album directory, or start fresh
+    ///
+    /// Progress for another project (the album was renamed) or a file that
+    /// no longer parses is ignored, as is everything with `restart`.
+    fn open(album_dir: &Path, project: &str, restart: bool) -> Self {
+        let path = album_dir.join(PUBLISH_PROGRESS_FILE);
+        let progress = fs::read_to_string(&path)
+            .ok()
+            .filter(|_| !restart)
+            .and_then(|text| toml::from_str::<PublishProgress>(&text).ok())
+            .filter(|progress| progress.project == project)
+            .unwrap_or_else(|| PublishProgress {
+                project: project.to_string(),
+                ..Default::default()
+            });
+        Self {
+            path,
+            progress: std::sync::Mutex::new(progress),
+        }
+    }
+
+    fn progress(&self) -> PublishProgress {
+        self.progress.lock().unwrap().clone()
+    }
+
+    /// What an earlier run already did, or `None` when starting fresh
+    fn resume_summary(&self) -> Option<String> {
+        let progress = self.progress();
+        let mut done = Vec::new();
+        if !progress.uploaded.is_empty() {
+            done.push(format!(
+                "{} audio file(s) uploaded",
+                progress.uploaded.len()
+            ));
+        }
+        if progress.site.is_some() {
+            done.push("site deployed".to_string());
+        }
+        (!done.is_empty()).then(|| done.join(", "))
+    }
+
+    /// Apply `change` and save; a failed save only costs the ability to resume
+    fn update(&self, change: impl FnOnce(&mut PublishProgress)) {
+        let mut progress = self.progress.lock().unwrap();
+        change(&mut progress);
+        let saved = toml::to_string(&*progress)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&self.path, text)?;
+                Ok(())
+            });
+        if let Err(e) = saved {
+            eprintln!("   ⚠️  Failed to save publish progress: {:#}", e);
+        }
+    }
+
+    /// The publish completed: there is nothing left to resume
+    fn finish(&self) {
+        if self.path.exists()
+            && let Err(e) = fs::remove_file(&self.path)
+        {
+            eprintln!("   ⚠️  Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Location of the last successful publish's state, relative to the album directory
+const DEPLOY_STATE_FILE: &str = ".release-kit/deploy-state.toml";
+
+/// What the last successful publish deployed to
+///
+/// `status` and `teardown` read this rather than deriving names from
+/// album.toml, which stop matching once the album or artist is renamed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DeployState {
+    /// Pages project
+    project: String,
+    /// R2 bucket holding the audio (none when bundled or on `[hosting.s3]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bucket: Option<String>,
+    /// Public base URL the site links audio from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cdn_url: Option<String>,
+    /// When the publish finished (RFC 3339, UTC)
+    deployed_at: String,
+}
+
+impl DeployState {
+    /// The album directory's state, if it has been published (and the file parses)
+    fn load(album_dir: &Path) -> Option<Self> {
+        let text = fs::read_to_string(album_dir.join(DEPLOY_STATE_FILE)).ok()?;
+        toml::from_str(&text).ok()
+    }
+
+    fn save(&self, album_dir: &Path) -> Result<()> {
+        let path = album_dir.join(DEPLOY_STATE_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The deployment is gone: forget it
+    fn remove(album_dir: &Path) {
+        let path = album_dir.join(DEPLOY_STATE_FILE);
+        if path.exists()
+            && let Err(e) = fs::remove_file(&path)
+        {
+            eprintln!("   ⚠️  Failed to remove {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// One hash over every file in `build_dir`, by path and content
+///
+/// Equal hashes mean a rebuild produced the site that was already deployed.
+/// The feed's `<lastBuildDate>` is left out: it changes on every build.
+fn site_content_hash(build_dir: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for file in collect_upload_files(build_dir)? {
+        hasher.update(file.relative_path.as_bytes());
+        hasher.update(b"\0");
+        if file.relative_path == "feed.xml" {
+            let feed = fs::read_to_string(&file.path)
+                .with_context(|| format!("Failed to read file: {}", file.path.display()))?;
+            for line in feed.lines().filter(|l| !l.contains("<lastBuildDate>")) {
+                hasher.update(line.as_bytes());
+                hasher.update(b"\n");
+            }
+        } else {
+            hasher.update(file.hash.as_bytes());
+        }
+        hasher.update(b"\n");
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// A Cloudflare Pages deploy with every decision already made
+///
+/// `publish` handles prompts and storage setup; this does the uploads, build,
+/// and DNS, reporting progress through the `Deployer` trait.
+struct PagesDeployment<'a> {
+    client: &'a CloudflareClient,
+    path: &'a Path,
+    album: &'a Album,
+    project_name: &'a str,
+    project_exists: bool,
+    audio: PagesAudio,
+    concurrency: usize,
+    /// Custom domain as (subdomain, base domain)
+    custom_domain: Option<(String, String)>,
+    /// Upload audio with embedded tags removed
+    strip_metadata: bool,
+    /// Pages branch to deploy to (the production branch when `None`)
+    branch: Option<&'a str>,
+    /// Preview deploys are built with noindex
+    environment: DeployEnvironment,
+    /// Keep `draft = true` tracks in the site (`album` already has them or not)
+    include_drafts: bool,
+    /// Upload tracks the bucket already has from the last publish
+    reupload: bool,
+    /// Progress to resume from and record to, if any
+    checkpoint: Option<&'a Checkpoint>,
+}
+
+#[async_trait]
+impl Deployer for PagesDeployment<'_> {
+    async fn deploy_with_progress(&self, progress: ProgressFn<'_>) -> Result<DeploymentResult> {
+        let audio_base_url = match &self.audio {
+            PagesAudio::Bucket { bucket, base_url } => {
+                println!("📤 Uploading audio files...");
+                upload_audio(
+                    bucket,
+                    self.path,
+                    self.album,
+                    Some(self.concurrency),
+                    self.strip_metadata,
+                    self.reupload,
+                    self.checkpoint,
+                    progress,
+                )
+                .await?;
+                println!();
+                Some(base_url.as_str())
+            }
+            PagesAudio::Existing { base_url } => Some(base_url.as_str()),
+            PagesAudio::Bundled => None,
+        };
+
+        // Build static site to temp directory (audio only when bundled)
+        progress(DeployEvent::BuildingSite);
+        println!("📦 Building static site...");
+        let _temp_dir = TempDir::new().context("Failed to create temporary directory")?;
+        let build_dir = _temp_dir.path();
+        build_pages_site(
+            self.path,
+            build_dir,
+            audio_base_url,
+            self.strip_metadata,
+            self.environment,
+            self.include_drafts,
+        )?;
+        println!("   ✓ Built to: {}", build_dir.display());
+        println!();
+
+        // Create project if it doesn't exist
+        if !self.project_exists {
+            println!("📝 Creating Cloudflare Pages project...");
+            self.client.create_pages_project(self.project_name).await?;
+            println!("   ✓ Project created");
+            println!();
+        }
+
+        // Upload deployment, unless an interrupted publish already deployed this build
+        let content_hash = site_content_hash(build_dir)?;
+        let deployed = self
+            .checkpoint
+            .and_then(|checkpoint| checkpoint.progress().site)
+            .filter(|site| {
+                site.content_hash == content_hash && site.branch.as_deref() == self.branch
+            });
+        let deployment_url = match deployed {
+            Some(site) => {
+                println!("☁️  Site already deployed by the interrupted publish");
+                println!("   ✓ {}", site.url);
+                println!();
+                site.url
+            }
+            None => {
+                println!("☁️  Deploying to Cloudflare...");
+                let url = self
+                    .client
+                    .upload_deployment(
+                        self.project_name,
+                        build_dir,
+                        self.branch,
+                        self.concurrency,
+                        progress,
+                    )
+                    .await?;
+                if let Some(checkpoint) = self.checkpoint {
+                    checkpoint.update(|p| {
+                        p.site = Some(DeployedSite {
+                            branch: self.branch.map(str::to_string),
+                            content_hash,
+                            url: url.clone(),
+                        })
+                    });
+                }
+                println!("   ✓ Deployed successfully");
+                println!();
+                url
+            }
+        };
+
+        // Set up custom domain if configured
+        if let Some((subdomain, base_domain)) = &self.custom_domain {
+            progress(DeployEvent::CreatingDns);
+            println!("🌐 Setting up custom domain...");
+            let full_domain = format!("{}.{}", subdomain, base_domain);
+
+            // Get DNS zone
+            match self.client.get_dns_zone(base_domain).await? {
+                Some(zone) => {
+                    println!("   ✓ Found DNS zone for {}", base_domain);
+
+                    // Check if CNAME record already exists
+                    let target = format!("{}.pages.dev", self.project_name);
+                    if let Some(existing) =
+                        self.client.get_dns_record(&zone.id, &full_domain).await?
+                    {
+                        println!(
+                            "   ✓ DNS record already exists: {} → {}",
+                            full_domain, existing.content
+                        );
+                    } else {
+                        match self
+                            .client
+                            .create_dns_record(&zone.id, &full_domain, &target)
+                            .await
+                        {
+                            Ok(_) => {
+                                println!("   ✓ Created DNS record: {} → {}", full_domain, target);
+                            }
+                            Err(e) => {
+                                println!("   ⚠️  DNS record creation failed: {}", e);
+                                println!(
+                                    "   💡 You may need to create it manually in Cloudflare dashboard"
+                                );
+                            }
                         }
                     }
                 }
+                None => {
+                    println!("   ⚠️  Domain {} not found on Cloudflare", base_domain);
+                    println!("   💡 Add your domain to Cloudflare DNS first");
+                }
             }
+            println!();
+        }
 
-            Err(anyhow::anyhow!(
-                "{}: Failed after 5 attempts - {}",
-                filename,
-                last_error.unwrap()
-            ))
+        progress(DeployEvent::Done {
+            site_url: deployment_url.clone(),
         });
 
-        upload_tasks.push(task);
+        Ok(DeploymentResult {
+            feed_url: build_dir
+                .join("feed.xml")
+                .exists()
+                .then(|| format!("{}/feed.xml", deployment_url.trim_end_matches('/'))),
+            site_url: deployment_url,
+        })
     }
+}
 
-    // Wait for all uploads to complete
-    let mut successful_uploads = 0;
-    let mut failed_uploads = Vec::new();
+/// Build the site for a Pages deploy: audio from `audio_base_url`, or copied
+/// in (and size-checked against the Pages file limit) when `None`
+fn build_pages_site(
+    path: &Path,
+    build_dir: &Path,
+    audio_base_url: Option<&str>,
+    strip_metadata: bool,
+    environment: DeployEnvironment,
+    include_drafts: bool,
+) -> Result<()> {
+    build_static_site(
+        path,
+        build_dir,
+        &BuildOptions {
+            audio_base_url,
+            // Bucket uploads strip their own copies; bundled audio is stripped by the build
+            strip_metadata: strip_metadata && audio_base_url.is_none(),
+            environment,
+            include_drafts,
+            ..Default::default()
+        },
+    )?;
 
-    for task in upload_tasks {
-        match task.await {
-            Ok(Ok(filename)) => {
-                successful_uploads += 1;
-                println!("      ✓ {}", filename);
-            }
-            Ok(Err(e)) => {
-                // Show full error chain
-                failed_uploads.push(format!("{:#}", e));
-            }
-            Err(e) => {
-                failed_uploads.push(format!("Task panic: {}", e));
+    if audio_base_url.is_none() {
+        use walkdir::WalkDir;
+
+        let limit = PAGES_MAX_FILE_MB * 1024 * 1024;
+        for file in WalkDir::new(build_dir) {
+            let file = file?;
+            let size = file.metadata()?.len();
+            if file.file_type().is_file() && size > limit {
+                anyhow::bail!(
+                    "{} is {:.1} MB, over the {} MB Cloudflare Pages file limit",
+                    file.path()
+                        .strip_prefix(build_dir)
+                        .unwrap_or(file.path())
+                        .display(),
+                    size as f64 / (1024.0 * 1024.0),
+                    PAGES_MAX_FILE_MB
+                );
             }
         }
     }
+    Ok(())
+}
 
-    if !failed_uploads.is_empty() {
-        eprintln!("   ⚠️  Some uploads failed:");
-        for error in &failed_uploads {
-            eprintln!("      - {}", error);
+/// Check the album's R2 bucket CORS policy lets the site stream its audio
+///
+/// The player fetches audio cross-origin (the oscilloscope needs Web Audio
+/// access), so every origin the site is served from needs `GET` and `HEAD`.
+/// Those origins are `site.domain`, the project's pages.dev URL and the
+/// custom subdomain, if any.
+///
+/// # Returns
+///
+/// One warning per problem; empty when the policy is fine
+pub(crate) async fn cdn_cors_warnings(album: &Album) -> Result<Vec<String>> {
+    if album.hosting.s3.is_some() {
+        return Ok(vec![
+            "Audio is served from [hosting.s3]; check that bucket's CORS policy yourself"
+                .to_string(),
+        ]);
+    }
+
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    let client =
+        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+
+    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+    let bucket_name = format!("{}-audio", project_name);
+    if client.get_r2_bucket(&bucket_name).await?.is_none() {
+        return Ok(vec![format!(
+            "R2 bucket {} not found (publish the album first)",
+            bucket_name
+        )]);
+    }
+
+    let origins = site_origins(
+        album,
+        &project_name,
+        config.cloudflare.base_domain.as_deref(),
+    );
+
+    Ok(match client.get_r2_bucket_cors(&bucket_name).await? {
+        Some(policy) => cors_problems(&policy, &bucket_name, &origins),
+        None => vec![format!(
+            "R2 bucket {} has no CORS policy; the player's oscilloscope will not work",
+            bucket_name
+        )],
+    })
+}
+
+/// Origins `policy` fails to allow, or allows without `GET`/`HEAD`
+fn cors_problems(policy: &R2CorsPolicy, bucket_name: &str, origins: &[String]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for origin in origins {
+        let rules: Vec<&R2CorsRule> = policy
+            .rules
+            .iter()
+            .filter(|rule| {
+                rule.allowed
+                    .origins
+                    .iter()
+                    .any(|allowed| origin_allows(allowed, origin))
+            })
+            .collect();
+        if rules.is_empty() {
+            problems.push(format!(
+                "R2 bucket {} CORS does not allow origin {}",
+                bucket_name, origin
+            ));
+            continue;
         }
-        anyhow::bail!("{} upload(s) failed", failed_uploads.len());
+
+        let missing: Vec<&str> = ["GET", "HEAD"]
+            .into_iter()
+            .filter(|method| {
+                !rules.iter().any(|rule| {
+                    rule.allowed
+                        .methods
+                        .iter()
+                        .any(|m| m.eq_ignore_ascii_case(method))
+                })
+            })
+            .collect();
+        if !missing.is_empty() {
+            problems.push(format!(
+                "R2 bucket {} CORS does not allow {} from {}",
+                bucket_name,
+                missing.join("/"),
+                origin
+            ));
+        }
+    }
+    problems
+}
+
+/// Whether a CORS `allowed` origin covers `origin`; `allowed` may hold one
+/// `*` wildcard (`*` alone, or e.g. `https://*.example.pages.dev`)
+fn origin_allows(allowed: &str, origin: &str) -> bool {
+    let allowed = allowed.trim_end_matches('/').to_ascii_lowercase();
+    let origin = origin.to_ascii_lowercase();
+    match allowed.split_once('*') {
+        Some((prefix, suffix)) => {
+            origin.len() >= prefix.len() + suffix.len()
+                && origin.starts_with(prefix)
+                && origin.ends_with(suffix)
+        }
+        None => allowed == origin,
+    }
+}
+
+/// Public base URL of the album's R2 bucket, as `prepare_r2_audio` sets it up:
+/// its custom domain under `base_domain`, else the r2.dev URL
+///
+/// Albums with a `stream_worker_url` keep the bucket private; see
+/// [`signed_audio_base_url`].
+pub(crate) fn r2_public_base_url(config: &CloudflareConfig, project_name: &str) -> String {
+    match &config.base_domain {
+        Some(base_domain) => format!("https://{}-audio.{}", project_name, base_domain),
+        None => format!("https://pub-{}.r2.dev", config.account_id),
     }
+}
+
+/// The signing worker's URL when the album streams through one
+/// (`[hosting.cloudflare] stream_worker_url`), as the base its R2 audio is
+/// served from
+fn signed_audio_base_url(album: &Album) -> Option<String> {
+    album
+        .hosting
+        .cloudflare
+        .stream_worker_url
+        .as_deref()
+        .map(|url| url.trim_end_matches('/').to_string())
+}
+
+/// Create and expose the album's R2 bucket as needed
+///
+/// Albums streaming through a signing worker keep the bucket private: it
+/// gets no CORS policy or custom domain, and the worker's URL is returned.
+///
+/// Returns the bucket and the public base URL the audio is served from.
+async fn prepare_r2_audio(
+    client: &CloudflareClient,
+    config: &GlobalConfig,
+    album: &Album,
+    project_name: &str,
+) -> Result<(Box<S3Bucket>, String)> {
+    // R2 audio storage (always enabled)
+    // R2 bucket name: {project-name}-audio
+    //
+    // Memory Requirements:
+    // - Each audio file is loaded entirely into memory during upload
+    // - For large albums with high-quality audio (e.g., 10 FLAC files @ 200MB each),
+    //   peak memory usage can reach 600MB+ (3 concurrent uploads × 200MB per file)
+    // - Adjust concurrency (-c flag) if running on memory-constrained systems
+    // - Example: Use `-c 1` for single-file uploads to minimize memory usage
+    let bucket_name = format!("{}-audio", project_name);
+
+    println!("📦 Setting up R2 audio storage...");
+
+    // Check if R2 bucket exists
+    let bucket_exists = match client.get_r2_bucket(&bucket_name).await? {
+        Some(_) => {
+            println!("   ✓ R2 bucket exists: {}", bucket_name);
+            true
+        }
+        None => {
+            println!("   ℹ️  Creating R2 bucket: {}", bucket_name);
+            client.create_r2_bucket(&bucket_name).await?;
+            println!("   ✓ R2 bucket created");
+            false
+        }
+    };
 
-    println!("   ✓ Uploaded {} audio files", successful_uploads);
+    let bucket = r2_bucket(&config.cloudflare, &bucket_name)?;
+    let signed_base_url = signed_audio_base_url(album);
 
-    // Configure CORS if bucket was just created (optional - R2 buckets are public by default)
-    if !bucket_exists {
+    // Configure CORS if bucket was just created, or whenever the album sets its own origins
+    // (signed albums only reach the bucket through the worker's binding)
+    if signed_base_url.is_none()
+        && (!bucket_exists || !album.hosting.cloudflare.cors_origins.is_empty())
+    {
         println!("   🔧 Configuring R2 public access...");
-        match client.configure_r2_public_access(&bucket_name).await {
+        let origins = r2_cors_origins(
+            album,
+            project_name,
+            config.cloudflare.base_domain.as_deref(),
+        );
+        match client
+            .configure_r2_public_access(&bucket_name, &origins)
+            .await
+        {
             Ok(_) => {
                 println!("   ✓ Public access configured");
             }
             Err(e) => {
-                println!(
-                    "   ⚠️  CORS configuration failed (bucket is still publicly accessible): {}",
-                    e
-                );
+                println!("   ⚠️  CORS configuration failed: {}", e);
             }
         }
     }
@@ -1260,6 +3362,11 @@ pub async fn publish(path: PathBuf, force: bool, concurrency: Option<usize>) ->
         }
     }
 
+    if let Some(base_url) = signed_base_url {
+        println!("   🔒 Bucket stays private: full tracks stream through the signing worker");
+        return Ok((bucket, base_url));
+    }
+
     // Set up custom domain for R2 if base domain is configured
     let cdn_url = if let Some(base_domain) = &config.cloudflare.base_domain {
         let cdn_domain = format!("{}-audio.{}", project_name, base_domain);
@@ -1326,168 +3433,817 @@ pub async fn publish(path: PathBuf, force: bool, concurrency: Option<usize>) ->
         format!("https://pub-{}.r2.dev", config.cloudflare.account_id)
     };
 
-    println!("   ✓ Audio will be served from: {}", cdn_url);
-    println!();
+    Ok((bucket, cdn_url))
+}
 
-    // Build static site to temp directory (without audio - using R2)
-    println!("📦 Building static site...");
-    let _temp_dir = TempDir::new().context("Failed to create temporary directory")?;
-    let build_dir = _temp_dir.path();
-    build_static_site(&path, build_dir, false, Some(&cdn_url))?;
-    println!("   ✓ Built to: {}", build_dir.display());
-    println!();
+/// sha256 of a file's contents, as hex
+fn file_sha256(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
 
-    // Create project if it doesn't exist
-    if !project_exists {
-        println!("📝 Creating Cloudflare Pages project...");
-        client.create_pages_project(&project_name).await?;
-        println!("   ✓ Project created");
-        println!();
+    let bytes =
+        fs::read(path).with_context(|| format!("Failed to read file: {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// Size of the object at `key`, when HEAD finds one
+async fn remote_object_size(bucket: &S3Bucket, key: &str) -> Option<u64> {
+    let (head, _) = bucket.head_object(key).await.ok()?;
+    head.content_length
+        .and_then(|length| u64::try_from(length).ok())
+}
+
+/// Split upload outcomes into uploaded names and errors, both in track order
+///
+/// Outcomes arrive in completion order, tagged with their track position.
+fn upload_report(
+    mut outcomes: Vec<(usize, std::result::Result<String, String>)>,
+) -> (Vec<String>, Vec<String>) {
+    outcomes.sort_by_key(|(index, _)| *index);
+
+    let mut uploaded = Vec::new();
+    let mut failed = Vec::new();
+    for (_, outcome) in outcomes {
+        match outcome {
+            Ok(filename) => uploaded.push(filename),
+            Err(e) => failed.push(e),
+        }
     }
+    (uploaded, failed)
+}
 
-    // Upload deployment
-    println!("☁️  Deploying to Cloudflare...");
-    let deployment_url = client.upload_deployment(&project_name, build_dir).await?;
-    println!("   ✓ Deployed successfully");
-    println!();
+/// Copy every track, tags removed, into a staging directory laid out like the album
+///
+/// # Returns
+///
+/// The staging directory and how many of the copies had tags to remove
+fn stage_stripped_audio(path: &Path, album: &Album) -> Result<(TempDir, usize)> {
+    let staging = TempDir::new().context("Failed to create temporary directory")?;
+    let mut stripped = 0;
+    for track in &album.tracks {
+        let src = path.join(&track.file);
+        // Missing files are reported by the upload itself
+        if !src.exists() {
+            continue;
+        }
+        let dst = staging.path().join(&track.file);
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent).context("Failed to create staging directory")?;
+        }
+        if strip_audio_tags(&src, &dst)? {
+            stripped += 1;
+        }
+    }
+    Ok((staging, stripped))
+}
 
-    // Set up custom domain if configured
-    if let (Some(subdomain), Some(base_domain)) = (
-        &album.hosting.cloudflare.subdomain,
-        &config.cloudflare.base_domain,
-    ) {
-        println!("🌐 Setting up custom domain...");
-        let full_domain = format!("{}.{}", subdomain, base_domain);
+/// Upload every track to `bucket` (with retries), then the deploy manifest
+///
+/// With `strip_metadata`, tag-free copies are uploaded instead of the source
+/// files, and the manifest describes those copies. With a `checkpoint`, each
+/// finished upload is recorded, and tracks an interrupted publish already
+/// uploaded to this bucket (with the same content) are skipped. With a
+/// `[distribution] stream_format`, each track's stream copy is encoded and
+/// uploaded beside it.
+///
+/// Unless `reupload` is set, a track is also skipped when the bucket's deploy
+/// manifest lists it with the same content hash and a HEAD request finds the
+/// object still there at that size. Stream copies aren't in the manifest, so
+/// they are always uploaded.
+#[allow(clippy::too_many_arguments)]
+async fn upload_audio(
+    bucket: &S3Bucket,
+    path: &Path,
+    album: &Album,
+    concurrency: Option<usize>,
+    strip_metadata: bool,
+    reupload: bool,
+    checkpoint: Option<&Checkpoint>,
+    progress: ProgressFn<'_>,
+) -> Result<()> {
+    use futures::stream::{FuturesUnordered, StreamExt};
 
-        // Get DNS zone
-        match client.get_dns_zone(base_domain).await? {
-            Some(zone) => {
-                println!("   ✓ Found DNS zone for {}", base_domain);
-
-                // Check if CNAME record already exists
-                let target = format!("{}.pages.dev", project_name);
-                if let Some(existing) = client.get_dns_record(&zone.id, &full_domain).await? {
-                    println!(
-                        "   ✓ DNS record already exists: {} → {}",
-                        full_domain, existing.content
-                    );
-                } else {
-                    match client
-                        .create_dns_record(&zone.id, &full_domain, &target)
-                        .await
-                    {
-                        Ok(_) => {
-                            println!("   ✓ Created DNS record: {} → {}", full_domain, target);
-                        }
-                        Err(e) => {
-                            println!("   ⚠️  DNS record creation failed: {}", e);
-                            println!(
-                                "   💡 You may need to create it manually in Cloudflare dashboard"
-                            );
+    let audio_dir = path.join("audio");
+    if !audio_dir.exists() {
+        anyhow::bail!("Audio directory not found: {}", audio_dir.display());
+    }
+
+    let staged = if strip_metadata {
+        let (staging, stripped) = stage_stripped_audio(path, album)?;
+        println!("   ✓ Stripped metadata from {} audio files", stripped);
+        Some(staging)
+    } else {
+        None
+    };
+
+    // The player streams these; the masters stay the downloads
+    let mut stream_copies = Vec::new();
+    let stream_dir = match album.distribution.stream_copy_format() {
+        Some(format) => {
+            let stream_dir = TempDir::new().context("Failed to create temporary directory")?;
+            let copies =
+                encode_stream_copies(path, album, format, "ffmpeg", strip_metadata, |name| {
+                    stream_dir.path().join(name)
+                })?;
+            stream_copies = copies.into_iter().flatten().collect();
+            println!(
+                "   ✓ Encoded {} {} stream copies",
+                stream_copies.len(),
+                format.extension()
+            );
+            Some(stream_dir)
+        }
+        None => None,
+    };
+    let path = staged.as_ref().map_or(path, |staging| staging.path());
+
+    let mut uploads: Vec<(PathBuf, String)> = album
+        .tracks
+        .iter()
+        .zip(album.audio_file_names())
+        .map(|(track, filename)| (path.join(&track.file), filename))
+        .collect();
+    if let Some(stream_dir) = &stream_dir {
+        uploads.extend(
+            stream_copies
+                .into_iter()
+                .map(|name| (stream_dir.path().join(&name), name)),
+        );
+    }
+
+    // Uploads recorded for another bucket say nothing about this one
+    let bucket_name = bucket.name();
+    let already_uploaded = match checkpoint {
+        Some(checkpoint) if checkpoint.progress().bucket.as_deref() == Some(&bucket_name) => {
+            checkpoint.progress().uploaded
+        }
+        Some(checkpoint) => {
+            checkpoint.update(|p| {
+                p.bucket = Some(bucket_name.clone());
+                p.uploaded.clear();
+            });
+            BTreeMap::new()
+        }
+        None => BTreeMap::new(),
+    };
+    let mut skipped = Vec::new();
+
+    // What the last publish left in the bucket, by key
+    let previous: BTreeMap<String, ManifestTrack> = if reupload {
+        BTreeMap::new()
+    } else {
+        DeployManifest::fetch(bucket, album)
+            .await
+            .map(|manifest| {
+                manifest
+                    .tracks
+                    .into_iter()
+                    .map(|track| (track.key.clone(), track))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut unchanged = Vec::new();
+
+    // Create semaphore to limit concurrent uploads (default: 3)
+    let max_concurrent_uploads = concurrency.unwrap_or(3);
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(max_concurrent_uploads));
+    println!("   ℹ️  Max concurrent uploads: {}", max_concurrent_uploads);
+
+    // Collect upload tasks, with each one's key and content hash for the checkpoint
+    let mut upload_tasks = Vec::new();
+    let mut task_keys = Vec::new();
+
+    for (audio_file, filename) in uploads {
+        if !audio_file.exists() {
+            eprintln!(
+                "   ⚠️  Warning: Audio file not found: {}",
+                audio_file.display()
+            );
+            continue;
+        }
+
+        let r2_key = album.audio_key(&filename);
+
+        let previous_track = previous.get(&r2_key);
+        let sha256 = match (checkpoint, previous_track) {
+            (None, None) => None,
+            _ => Some(file_sha256(&audio_file)?),
+        };
+        if sha256.is_some() && already_uploaded.get(&r2_key) == sha256.as_ref() {
+            skipped.push(filename);
+            continue;
+        }
+        if let (Some(track), Some(sha256)) = (previous_track, &sha256)
+            && track.sha256 == *sha256
+            && remote_object_size(bucket, &r2_key).await == Some(track.size)
+        {
+            unchanged.push(filename);
+            continue;
+        }
+        task_keys.push((r2_key.clone(), sha256));
+
+        // Clone data needed for async task
+        let audio_file_clone = audio_file.clone();
+        let bucket_clone = bucket.clone();
+        let semaphore_clone = semaphore.clone();
+
+        // Spawn upload task with retry logic and concurrency limiting
+        let task = tokio::spawn(async move {
+            // Acquire semaphore permit (limits concurrent uploads)
+            let _permit = semaphore_clone
+                .acquire()
+                .await
+                .expect("Semaphore should not be closed");
+
+            let content_type = content_type_for(&audio_file_clone);
+
+            // Read file into memory (for both small and large files)
+            let file_contents = tokio::fs::read(&audio_file_clone)
+                .await
+                .context("Failed to read file for upload")?;
+
+            // Retry logic: 5 attempts with exponential backoff
+            let mut last_error = None;
+            for attempt in 1..=5 {
+                let result = bucket_clone
+                    .put_object_with_content_type(&r2_key, &file_contents, &content_type)
+                    .await
+                    .map(|_| ());
+
+                match result {
+                    Ok(_) => {
+                        return Ok::<String, anyhow::Error>(filename.clone());
+                    }
+                    Err(e) => {
+                        last_error = Some(e);
+                        if attempt < 5 {
+                            // Exponential backoff: 1s, 2s, 3s, 4s
+                            tokio::time::sleep(Duration::from_secs(attempt)).await;
                         }
                     }
                 }
             }
-            None => {
-                println!("   ⚠️  Domain {} not found on Cloudflare", base_domain);
-                println!("   💡 Add your domain to Cloudflare DNS first");
+
+            Err(anyhow::anyhow!(
+                "{}: Failed after 5 attempts - {}",
+                filename,
+                last_error.unwrap()
+            ))
+        });
+
+        upload_tasks.push(task);
+    }
+
+    // Wait for all uploads, reporting progress as each completes
+    let total = upload_tasks.len();
+    let mut successful_uploads = 0;
+    progress(DeployEvent::UploadingAudio { done: 0, total });
+
+    let mut pending: FuturesUnordered<_> = upload_tasks
+        .into_iter()
+        .enumerate()
+        .map(|(index, task)| async move { (index, task.await) })
+        .collect();
+    let mut outcomes = Vec::with_capacity(total);
+    while let Some((index, joined)) = pending.next().await {
+        let outcome = match joined {
+            Ok(Ok(filename)) => {
+                if let (Some(checkpoint), (key, Some(sha256))) = (checkpoint, &task_keys[index]) {
+                    checkpoint.update(|p| {
+                        p.uploaded.insert(key.clone(), sha256.clone());
+                    });
+                }
+                successful_uploads += 1;
+                progress(DeployEvent::UploadingAudio {
+                    done: successful_uploads,
+                    total,
+                });
+                Ok(filename)
             }
+            // Show full error chain
+            Ok(Err(e)) => Err(format!("{:#}", e)),
+            Err(e) => Err(format!("Task panic: {}", e)),
+        };
+        outcomes.push((index, outcome));
+    }
+
+    // Report in track order, whatever order the uploads finished in
+    let (uploaded, failed_uploads) = upload_report(outcomes);
+    for filename in &unchanged {
+        println!("      ↷ {} (unchanged since the last publish)", filename);
+    }
+    for filename in &skipped {
+        println!("      ↷ {} (uploaded by the interrupted publish)", filename);
+    }
+    for filename in &uploaded {
+        println!("      ✓ {}", filename);
+    }
+
+    if !failed_uploads.is_empty() {
+        eprintln!("   ⚠️  Some uploads failed:");
+        for error in &failed_uploads {
+            eprintln!("      - {}", error);
         }
-        println!();
+        anyhow::bail!("{} upload(s) failed", failed_uploads.len());
     }
 
-    println!("✅ Deployment complete!");
-    println!("   Live URL: {}", deployment_url);
-    if let (Some(subdomain), Some(base_domain)) = (
-        &album.hosting.cloudflare.subdomain,
-        &config.cloudflare.base_domain,
-    ) {
+    let mut not_uploaded = Vec::new();
+    if !unchanged.is_empty() {
+        not_uploaded.push(format!("{} unchanged", unchanged.len()));
+    }
+    if !skipped.is_empty() {
+        not_uploaded.push(format!("{} already uploaded", skipped.len()));
+    }
+    if not_uploaded.is_empty() {
+        println!("   ✓ Uploaded {} audio files", successful_uploads);
+    } else {
         println!(
-            "   Custom domain: https://{}.{} (DNS propagation may take a few minutes)",
-            subdomain, base_domain
+            "   ✓ Uploaded {} audio files ({})",
+            successful_uploads,
+            not_uploaded.join(", ")
         );
     }
 
+    // Record what was uploaded so `release-kit diff` can compare against it
+    let manifest = DeployManifest::from_album(path, album)?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    match bucket
+        .put_object_with_content_type(manifest_key(album), &manifest_json, "application/json")
+        .await
+    {
+        Ok(_) => println!("   ✓ Deploy manifest saved"),
+        Err(e) => println!("   ⚠️  Failed to save deploy manifest: {}", e),
+    }
+
     Ok(())
 }
 
-/// Show deployment status
-pub async fn status(path: Option<PathBuf>) -> Result<()> {
-    let path = path.unwrap_or_else(|| PathBuf::from("."));
+/// Publish album to Netlify
+///
+/// The site is built and shipped as a single zip deploy. Audio is included in
+/// the zip unless `[hosting.s3]` is configured, in which case it is uploaded
+/// there instead. Credentials come from ~/.release-kit/netlify.toml, separate
+/// from the Cloudflare config.
+///
+/// With `dry_run`, the site is built to a temporary directory and the
+/// uploads are listed; without a netlify.toml the site id is a placeholder.
+pub async fn publish_netlify(
+    path: PathBuf,
+    force: bool,
+    concurrency: Option<usize>,
+    max_file_size_mb: u64,
+    strip_metadata: bool,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("🚀 Publishing album to Netlify...\n");
+
+    let album = load_publishable_album(&path, max_file_size_mb)?;
+
+    let config = match load_netlify_config() {
+        Ok(config) => Some(config),
+        Err(_) if dry_run.is_enabled() => None,
+        Err(e) => return Err(e),
+    };
+    let site_id = config
+        .as_ref()
+        .map_or("<site-id>", |config| config.site_id.as_str());
 
-    println!("📊 Checking deployment status...\n");
+    println!("📋 Deployment Plan:");
+    println!("   Album: {}", album.metadata.title);
+    println!("   Artist: {}", album.artist.name);
+    println!("   Site: {}", site_id);
+    match &album.hosting.s3 {
+        Some(s3) => println!("   Target: Netlify (audio on {})", s3.endpoint),
+        None => println!("   Target: Netlify (zip deploy, audio included)"),
+    }
+    println!();
 
-    // Validate and load album config
-    let album_toml_path = path.join("album.toml");
-    if !album_toml_path.exists() {
-        anyhow::bail!(
-            "album.toml not found in {}\nNot an album directory?",
-            path.display()
-        );
+    if dry_run.is_enabled() {
+        let audio_base_url = album.hosting.s3.as_ref().map(|s3| s3.public_base_url());
+        let build = TempDir::new().context("Failed to create temporary directory")?;
+        build_static_site(
+            &path,
+            build.path(),
+            &BuildOptions {
+                audio_base_url: audio_base_url.as_deref(),
+                strip_metadata,
+                ..Default::default()
+            },
+        )?;
+        let mut steps = Vec::new();
+        if let Some(s3) = &album.hosting.s3 {
+            steps.push(format!(
+                "Upload {} audio file(s) and {} to S3 bucket {} at {}",
+                album.tracks.len(),
+                manifest_key(&album),
+                s3.bucket,
+                s3.endpoint
+            ));
+        }
+        steps.push(format!(
+            "Deploy {} to Netlify site {} as one zip",
+            site_files_summary(build.path())?,
+            site_id
+        ));
+        print_dry_run_steps(&steps);
+        return Ok(());
     }
+    let config = config.expect("loaded unless dry-running");
 
-    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
-    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+    if !force {
+        print!("❓ Deploy to Netlify? (y/N): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
 
-    // Validate project name is not empty or invalid
-    if project_name.is_empty() || project_name == "-" {
-        anyhow::bail!(
-            "Invalid album/artist names - cannot derive project name.\nAlbum: '{}', Artist: '{}'",
-            album.metadata.title,
-            album.artist.name
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("❌ Deployment cancelled");
+            return Ok(());
+        }
+        println!();
+    }
+
+    let audio_base_url = match &album.hosting.s3 {
+        Some(s3) => {
+            println!("📦 Uploading audio to S3-compatible storage...");
+            let bucket = custom_s3_bucket(s3)?;
+            upload_audio(
+                &bucket,
+                &path,
+                &album,
+                concurrency,
+                strip_metadata,
+                false,
+                None,
+                &|_| {},
+            )
+            .await?;
+            println!("   ✓ Audio will be served from: {}", s3.public_base_url());
+            println!();
+            Some(s3.public_base_url())
+        }
+        None => None,
+    };
+
+    println!("📦 Building static site...");
+    let temp_dir = TempDir::new().context("Failed to create temporary directory")?;
+    let report = build_static_site(
+        &path,
+        temp_dir.path(),
+        &BuildOptions {
+            audio_base_url: audio_base_url.as_deref(),
+            strip_metadata,
+            ..Default::default()
+        },
+    )?;
+    if strip_metadata && audio_base_url.is_none() {
+        println!(
+            "   ✓ Stripped metadata from {} audio files",
+            report.stripped_audio
         );
     }
+    println!("   ✓ Built to: {}", temp_dir.path().display());
+    println!();
 
-    println!("📋 Project Information:");
-    println!("   Album: {}", album.metadata.title);
-    println!("   Artist: {}", album.artist.name);
-    println!("   Project: {}", project_name);
+    println!("☁️  Deploying to Netlify...");
+    let result = NetlifyDeployer::new(config, temp_dir.path())
+        .deploy()
+        .await?;
+    println!("   ✓ Deployed successfully");
     println!();
 
-    // Load global config
-    let config = load_config()?
-        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    println!("✅ Deployment complete!");
+    println!("   Live URL: {}", result.site_url);
+    if let Some(feed_url) = &result.feed_url {
+        println!("   RSS feed: {}", feed_url);
+    }
 
-    // Query Cloudflare API
-    println!("☁️  Cloudflare Pages Status:");
-    let client =
-        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+    Ok(())
+}
 
-    match client.get_pages_project(&project_name).await? {
-        Some(project) => {
-            println!("   ✅ Status: Deployed");
-            println!("   Created: {}", project.created_on);
-            println!("   URL: https://{}.pages.dev", project_name);
+/// Publish album to IPFS through a pinning service (experimental)
+///
+/// Site and audio are added together as one directory; the root CID is
+/// printed along with a subdomain gateway URL. Credentials come from
+/// ~/.release-kit/ipfs.toml.
+///
+/// With `dry_run`, the site is built to a temporary directory and what
+/// would be pinned is listed; no ipfs.toml is needed.
+pub async fn publish_ipfs(
+    path: PathBuf,
+    force: bool,
+    max_file_size_mb: u64,
+    strip_metadata: bool,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("🚀 Publishing album to IPFS (experimental)...\n");
+
+    let album = load_publishable_album(&path, max_file_size_mb)?;
+    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
 
-            if let Some(domains) = &project.domains
-                && !domains.is_empty()
-            {
-                println!("   Custom Domains:");
-                for domain in domains {
-                    println!("     - https://{}", domain);
-                }
-            }
-        }
-        None => {
-            println!("   ❌ Status: Not deployed");
-            println!(
-                "   Run 'release-kit deploy publish {}' to deploy",
-                path.display()
-            );
-        }
-    }
+    println!("📋 Deployment Plan:");
+    println!("   Album: {}", album.metadata.title);
+    println!("   Artist: {}", album.artist.name);
+    println!("   Pin name: {}", project_name);
+    println!("   Target: IPFS (site and audio, pinned)");
+    println!();
+    println!("   ⚠️  Content added to IPFS is public and cannot be reliably deleted");
     println!();
 
-    println!("💰 Usage Information:");
-    println!("   Free Tier: 500 builds/month");
-    println!("   Builds this month: Check Cloudflare dashboard");
+    if dry_run.is_enabled() {
+        let build = TempDir::new().context("Failed to create temporary directory")?;
+        build_static_site(
+            &path,
+            build.path(),
+            &BuildOptions {
+                strip_metadata,
+                ..Default::default()
+            },
+        )?;
+        print_dry_run_steps(&[format!(
+            "Add {} to IPFS as one directory and pin it as {}",
+            site_files_summary(build.path())?,
+            project_name
+        )]);
+        return Ok(());
+    }
 
-    Ok(())
-}
+    let config = load_ipfs_config()?;
 
-/// Teardown deployment from Cloudflare Pages
-pub async fn teardown(path: PathBuf, force: bool) -> Result<()> {
-    println!("🗑️  Tearing down Cloudflare Pages deployment...\n");
+    if !force {
+        print!("❓ Publish to IPFS? (y/N): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("❌ Deployment cancelled");
+            return Ok(());
+        }
+        println!();
+    }
+
+    println!("📦 Building static site...");
+    let temp_dir = TempDir::new().context("Failed to create temporary directory")?;
+    let report = build_static_site(
+        &path,
+        temp_dir.path(),
+        &BuildOptions {
+            strip_metadata,
+            ..Default::default()
+        },
+    )?;
+    if strip_metadata {
+        println!(
+            "   ✓ Stripped metadata from {} audio files",
+            report.stripped_audio
+        );
+    }
+    println!("   ✓ Built to: {}", temp_dir.path().display());
+    println!();
+
+    println!("📌 Adding to IPFS and pinning...");
+    let pinned = IpfsDeployer::new(config, temp_dir.path(), &project_name)
+        .pin()
+        .await?;
+    println!("   ✓ Pinned");
+    println!();
+
+    println!("✅ Deployment complete!");
+    println!("   CID: {}", pinned.cid);
+    println!("   Gateway URL: {}", pinned.gateway_url);
+
+    Ok(())
+}
+
+/// Publish album to a local directory, for hosting outside Cloudflare
+///
+/// Builds the site (audio and feed.xml included) and copies it to `output`;
+/// no R2, Pages or DNS calls are made. Files already in `output` are
+/// overwritten, not removed. With `dry_run`, the build stays in a temporary
+/// directory and each file it would write to `output` is listed.
+pub async fn publish_static(
+    path: PathBuf,
+    output: PathBuf,
+    max_file_size_mb: u64,
+    strip_metadata: bool,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("🚀 Publishing album to a directory...\n");
+
+    let album = load_publishable_album(&path, max_file_size_mb)?;
+
+    println!("📋 Deployment Plan:");
+    println!("   Album: {}", album.metadata.title);
+    println!("   Artist: {}", album.artist.name);
+    println!("   Target: {} (site and audio)", output.display());
+    println!();
+
+    println!("📦 Building static site...");
+    let temp_dir = TempDir::new().context("Failed to create temporary directory")?;
+    let report = build_static_site(
+        &path,
+        temp_dir.path(),
+        &BuildOptions {
+            strip_metadata,
+            ..Default::default()
+        },
+    )?;
+    if strip_metadata {
+        println!(
+            "   ✓ Stripped metadata from {} audio files",
+            report.stripped_audio
+        );
+    }
+    println!("   ✓ Built to: {}", temp_dir.path().display());
+    println!();
+
+    if dry_run.is_enabled() {
+        let files = dry_run.report_staged(temp_dir.path(), &output)?;
+        println!(
+            "\n✅ Dry run complete - {} file(s) would be copied, nothing was",
+            files
+        );
+        return Ok(());
+    }
+
+    println!("📁 Copying site to {}...", output.display());
+    let result = StaticDeployer::new(temp_dir.path(), &output)
+        .deploy()
+        .await?;
+    println!("   ✓ Copied");
+    println!();
+
+    println!("✅ Deployment complete!");
+    println!("   Live URL: {}", result.site_url);
+    if let Some(feed_url) = &result.feed_url {
+        println!("   RSS feed: {}", feed_url);
+    }
+
+    Ok(())
+}
+
+/// Publish the whole site (HTML, JS, artwork, audio) to the `[hosting.s3]` bucket
+///
+/// For serving straight from a bucket or behind a CDN of your own, with no
+/// Cloudflare Pages involved. With `dry_run`, the site is built to a
+/// temporary directory and the upload is listed; no credentials are needed.
+pub async fn publish_s3(
+    path: PathBuf,
+    force: bool,
+    concurrency: Option<usize>,
+    max_file_size_mb: u64,
+    strip_metadata: bool,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("🚀 Publishing album to S3-compatible storage...\n");
+
+    let album = load_publishable_album(&path, max_file_size_mb)?;
+    let s3 =
+        album.hosting.s3.clone().context(
+            "--target s3 requires a [hosting.s3] section in album.toml (endpoint, bucket)",
+        )?;
+
+    println!("📋 Deployment Plan:");
+    println!("   Album: {}", album.metadata.title);
+    println!("   Artist: {}", album.artist.name);
+    println!("   Endpoint: {}", s3.endpoint);
+    println!("   Bucket: {}", s3.bucket);
+    println!("   Target: Static S3 site (audio included)");
+    println!();
+
+    if dry_run.is_enabled() {
+        let build = TempDir::new().context("Failed to create temporary directory")?;
+        build_static_site(
+            &path,
+            build.path(),
+            &BuildOptions {
+                strip_metadata,
+                ..Default::default()
+            },
+        )?;
+        print_dry_run_steps(&[format!(
+            "Upload {} to S3 bucket {} at {}",
+            site_files_summary(build.path())?,
+            s3.bucket,
+            s3.endpoint
+        )]);
+        return Ok(());
+    }
+
+    if !force {
+        print!("❓ Deploy to S3 bucket '{}'? (y/N): ", s3.bucket);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("❌ Deployment cancelled");
+            return Ok(());
+        }
+        println!();
+    }
+
+    println!("📦 Building static site...");
+    let temp_dir = TempDir::new().context("Failed to create temporary directory")?;
+    let report = build_static_site(
+        &path,
+        temp_dir.path(),
+        &BuildOptions {
+            strip_metadata,
+            ..Default::default()
+        },
+    )?;
+    if strip_metadata {
+        println!(
+            "   ✓ Stripped metadata from {} audio files",
+            report.stripped_audio
+        );
+    }
+    println!("   ✓ Built to: {}", temp_dir.path().display());
+    println!();
+
+    println!("☁️  Uploading site...");
+    let bucket = custom_s3_bucket(&s3)?;
+    let uploaded = upload_site(&bucket, temp_dir.path(), concurrency.unwrap_or(3)).await?;
+    println!("   ✓ Uploaded {} files", uploaded);
+    println!();
+
+    println!("✅ Deployment complete!");
+    println!("   Base URL: {}/", s3.public_base_url());
+    println!("   Entry point: {}/index.html", s3.public_base_url());
+
+    Ok(())
+}
+
+/// "N site file(s) (X MB)" for a built site
+fn site_files_summary(build_dir: &Path) -> Result<String> {
+    let files = collect_upload_files(build_dir)?;
+    Ok(format!(
+        "{} site file(s) ({:.1} MB)",
+        files.len(),
+        files.iter().map(|file| file.size).sum::<u64>() as f64 / (1024.0 * 1024.0)
+    ))
+}
+
+/// Print what a dry-run publish would do, in order
+fn print_dry_run_steps(steps: &[String]) {
+    println!("🧪 Dry run - publish would:");
+    for (i, step) in steps.iter().enumerate() {
+        println!("   {}. {}", i + 1, step);
+    }
+    println!("\n✅ Dry run complete - nothing was changed");
+}
+
+/// Cache-Control for a static-host object
+///
+/// Pages and feeds must pick up a re-publish promptly; everything else
+/// (audio, artwork, player script) changes rarely and is large enough to
+/// be worth caching for a day.
+fn cache_control_for(relative_path: &str) -> &'static str {
+    let extension = Path::new(relative_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("html" | "xml" | "json" | "txt") => "public, max-age=300, must-revalidate",
+        _ => "public, max-age=86400",
+    }
+}
+
+/// Upload every file in the build directory, keyed by its relative path
+async fn upload_site(bucket: &S3Bucket, build_dir: &Path, concurrency: usize) -> Result<usize> {
+    use futures::{StreamExt, TryStreamExt};
+
+    let files = collect_upload_files(build_dir)?;
+    let count = files.len();
+
+    futures::stream::iter(files)
+        .map(|file| async move {
+            let mut bucket = bucket.clone();
+            bucket.add_header("Cache-Control", cache_control_for(&file.relative_path));
+
+            let contents = tokio::fs::read(&file.path)
+                .await
+                .with_context(|| format!("Failed to read file: {}", file.path.display()))?;
+            bucket
+                .put_object_with_content_type(&file.relative_path, &contents, &file.content_type)
+                .await
+                .with_context(|| format!("Failed to upload {}", file.relative_path))?;
+            println!("      ✓ {}", file.relative_path);
+            Ok::<(), anyhow::Error>(())
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_collect::<Vec<()>>()
+        .await?;
+
+    Ok(count)
+}
+
+/// Show deployment status
+pub async fn status(path: Option<PathBuf>) -> Result<()> {
+    let path = path.unwrap_or_else(|| PathBuf::from("."));
+
+    println!("📊 Checking deployment status...\n");
 
     // Validate and load album config
     let album_toml_path = path.join("album.toml");
@@ -1496,243 +4252,2809 @@ pub async fn teardown(path: PathBuf, force: bool) -> Result<()> {
             "album.toml not found in {}\nNot an album directory?",
             path.display()
         );
-    }
+    }
+
+    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    let state = DeployState::load(&path);
+    let project_name = deployed_project_name(&album, state.as_ref())?;
+
+    println!("📋 Project Information:");
+    println!("   Album: {}", album.metadata.title);
+    println!("   Artist: {}", album.artist.name);
+    println!("   Project: {}", project_name);
+    if let Some(state) = &state {
+        println!("   Last published: {}", state.deployed_at);
+        if let Some(cdn_url) = &state.cdn_url {
+            println!("   Audio: {}", cdn_url);
+        }
+        let derived = derive_project_name(&album.artist.name, &album.metadata.title);
+        if derived != state.project {
+            println!(
+                "   ℹ️  album.toml now names the project {}; publish again to deploy there",
+                derived
+            );
+        }
+    }
+    println!();
+
+    // Load global config
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+
+    // Query Cloudflare API
+    println!("☁️  Cloudflare Pages Status:");
+    let client =
+        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+
+    match client.get_pages_project(&project_name).await? {
+        Some(project) => {
+            println!("   ✅ Status: Deployed");
+            println!("   Created: {}", project.created_on);
+            println!("   URL: https://{}.pages.dev", project_name);
+
+            if let Some(domains) = &project.domains
+                && !domains.is_empty()
+            {
+                println!("   Custom Domains:");
+                for domain in domains {
+                    println!("     - https://{}", domain);
+                }
+            }
+        }
+        None => {
+            println!("   ❌ Status: Not deployed");
+            println!(
+                "   Run 'release-kit deploy publish {}' to deploy",
+                path.display()
+            );
+        }
+    }
+    println!();
+
+    println!("💰 Usage Information:");
+    println!("   Free Tier: 500 builds/month");
+    println!("   Builds this month: Check Cloudflare dashboard");
+
+    Ok(())
+}
+
+/// Make an earlier production deployment of an album the live one again
+///
+/// Lists the project's recent production deployments with their timestamps.
+/// Without `deployment_id`, the one before the current is picked and a
+/// confirmation asked for; otherwise that deployment (by id or short id).
+/// The project is the deploy state's, as for `status`.
+pub async fn rollback(path: PathBuf, deployment_id: Option<String>, dry_run: DryRun) -> Result<()> {
+    println!("⏪ Rolling back Cloudflare Pages deployment...\n");
+
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nNot an album directory?",
+            path.display()
+        );
+    }
+
+    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    let project_name = deployed_project_name(&album, DeployState::load(&path).as_ref())?;
+
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    let client =
+        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+
+    let project = client
+        .get_pages_project(&project_name)
+        .await?
+        .with_context(|| format!("Pages project {} not found", project_name))?;
+    let deployments: Vec<ProjectDeployment> = client
+        .list_deployments(&project_name)
+        .await?
+        .into_iter()
+        .filter(|deployment| deployment.environment == "production")
+        .collect();
+    let live = live_deployment_index(
+        &deployments,
+        project
+            .canonical_deployment
+            .as_ref()
+            .map(|deployment| deployment.id.as_str()),
+    );
+
+    println!("📋 Recent production deployments of {}:", project_name);
+    for (i, deployment) in deployments.iter().take(ROLLBACK_LIST_LIMIT).enumerate() {
+        println!(
+            "   {} {}  {}{}",
+            if i == live { "*" } else { " " },
+            deployment.id,
+            deployment.created_on,
+            if i == live { "  (live)" } else { "" }
+        );
+    }
+    println!();
+
+    let target = rollback_target(&deployments, live, deployment_id.as_deref())?;
+
+    if !dry_run.allows(format_args!(
+        "roll {} back to deployment {}",
+        project_name, target.id
+    )) {
+        println!("✅ Dry run complete - nothing was changed");
+        return Ok(());
+    }
+
+    // Picking the previous deployment is a guess worth confirming
+    if deployment_id.is_none() {
+        print!(
+            "❓ Roll back to {} ({})? (y/N): ",
+            target.id, target.created_on
+        );
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("❌ Rollback cancelled");
+            println!("   Pass a deployment id to roll back to another one");
+            return Ok(());
+        }
+        println!();
+    }
+
+    let live = client
+        .rollback_deployment(&project_name, &target.id)
+        .await?;
+
+    println!("✅ Rollback complete!");
+    println!("   Live deployment: {} ({})", live.id, live.created_on);
+    println!("   URL: https://{}.pages.dev", project_name);
+
+    Ok(())
+}
+
+/// Production deployments `rollback` lists
+const ROLLBACK_LIST_LIMIT: usize = 10;
+
+/// Position of the live deployment among `deployments` (most recent first)
+///
+/// That's the project's `canonical_deployment`, which after a rollback is
+/// older than the newest; without one, the newest is assumed live.
+fn live_deployment_index(deployments: &[ProjectDeployment], canonical_id: Option<&str>) -> usize {
+    canonical_id
+        .and_then(|id| {
+            deployments
+                .iter()
+                .position(|deployment| deployment.id == id)
+        })
+        .unwrap_or(0)
+}
+
+/// The deployment to roll back to: `deployment_id` (an id or short id)
+/// among `deployments`, else the one before the live one
+///
+/// `deployments` are production deployments, most recent first; `live` is
+/// the position of the one being served.
+fn rollback_target<'a>(
+    deployments: &'a [ProjectDeployment],
+    live: usize,
+    deployment_id: Option<&str>,
+) -> Result<&'a ProjectDeployment> {
+    match deployment_id {
+        Some(id) => deployments
+            .iter()
+            .find(|deployment| deployment.id == id || deployment.short_id.as_deref() == Some(id))
+            .with_context(|| format!("No production deployment with id {}", id)),
+        None => deployments
+            .get(live + 1)
+            .context("No previous production deployment to roll back to"),
+    }
+}
+
+/// Teardown deployment from Cloudflare Pages
+///
+/// A dry run looks up what exists and stops before deleting any of it.
+pub async fn teardown(path: PathBuf, force: bool, dry_run: DryRun) -> Result<()> {
+    println!("🗑️  Tearing down Cloudflare Pages deployment...\n");
+
+    let target = teardown_target(&path)?;
+
+    println!("⚠️  WARNING: This will permanently delete:");
+    println!("   Project: {}", target.project_name);
+    println!("   URL: https://{}.pages.dev", target.project_name);
+    println!("   All deployments and history");
+    println!("   R2 Bucket: {} (if exists)", target.bucket_name);
+    println!("   All audio files in R2");
+    println!();
+
+    // Load global config
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+
+    // Check if project and/or R2 bucket exist
+    println!("🔍 Checking deployment status...");
+    let client =
+        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+
+    let existing = find_deployment(&client, &target).await?;
+
+    if existing.project {
+        println!("   ✓ Pages project found");
+    } else {
+        println!("   ℹ️  Pages project not found");
+    }
+
+    if existing.bucket {
+        println!("   ✓ R2 bucket found");
+    } else {
+        println!("   ℹ️  R2 bucket not found");
+    }
+
+    if !existing.project && !existing.bucket {
+        println!();
+        println!("ℹ️  Nothing to delete - deployment already cleaned up");
+        return Ok(());
+    }
+    println!();
+
+    if !dry_run.allows(format_args!("delete the above")) {
+        println!("✅ Dry run complete - nothing was deleted");
+        return Ok(());
+    }
+
+    // Confirmation prompt
+    if !force {
+        println!("⚠️  Type the project name to confirm deletion:");
+        print!("   > ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if input.trim() != target.project_name {
+            println!("❌ Project name doesn't match. Teardown cancelled.");
+            return Ok(());
+        }
+    }
+
+    delete_deployment(&client, &config, &target, existing).await?;
+    DeployState::remove(&path);
+    println!();
+
+    println!("✅ Teardown complete!");
+    println!("   Project {} has been deleted", target.project_name);
+
+    Ok(())
+}
+
+/// Phrase `teardown --all` requires before deleting anything
+const TEARDOWN_ALL_PHRASE: &str = "DELETE ALL";
+
+/// Delete the Cloudflare deployments of every album under a directory
+///
+/// Albums are the immediate subdirectories of `path` with an album.toml, as
+/// for `build-index`. Every project and bucket that exists is listed in one
+/// plan, then deleted after a single confirmation: typing `DELETE ALL`. A
+/// failing album does not stop the others. A dry run stops after the plan.
+pub async fn teardown_all(path: PathBuf, force: bool, dry_run: DryRun) -> Result<()> {
+    println!("🗑️  Tearing down Cloudflare Pages deployments...\n");
+
+    let targets = teardown_targets(&path)?;
+    if targets.is_empty() {
+        anyhow::bail!(
+            "No album directories (with album.toml) found in {}",
+            path.display()
+        );
+    }
+
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    let client =
+        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+
+    println!("🔍 Checking {} album(s)...", targets.len());
+    let mut plan = Vec::new();
+    for (album_dir, target) in targets {
+        let existing = find_deployment(&client, &target).await?;
+        if existing.project || existing.bucket {
+            plan.push((album_dir, target, existing));
+        }
+    }
+
+    if plan.is_empty() {
+        println!();
+        println!("ℹ️  Nothing to delete - deployments already cleaned up");
+        return Ok(());
+    }
+    println!();
+
+    println!("⚠️  WARNING: This will permanently delete:");
+    for (album_dir, target, existing) in &plan {
+        println!("   {}", album_dir.display());
+        if existing.project {
+            println!(
+                "      Project: {} (https://{}.pages.dev)",
+                target.project_name, target.project_name
+            );
+        }
+        if existing.bucket {
+            println!("      R2 Bucket: {}", target.bucket_name);
+        }
+    }
+    println!("   All deployments, history and audio files of the above");
+    println!();
+
+    if !dry_run.allows(format_args!("delete the above")) {
+        println!("✅ Dry run complete - nothing was deleted");
+        return Ok(());
+    }
+
+    if !force {
+        println!("⚠️  Type {} to confirm deletion:", TEARDOWN_ALL_PHRASE);
+        print!("   > ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !confirms_teardown_all(&input) {
+            println!("❌ Confirmation doesn't match. Teardown cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut failed = 0;
+    for (album_dir, target, existing) in &plan {
+        println!("🗑️  {}", target.project_name);
+        match delete_deployment(&client, &config, target, *existing).await {
+            Ok(()) => DeployState::remove(album_dir),
+            Err(e) => {
+                failed += 1;
+                eprintln!("   ✗ {}: {:#}", album_dir.display(), e);
+            }
+        }
+    }
+    println!();
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} teardown(s) failed", failed, plan.len());
+    }
+    println!("✅ Teardown complete!");
+    println!("   {} deployment(s) have been deleted", plan.len());
+
+    Ok(())
+}
+
+/// Whether `input` is the exact `teardown --all` confirmation phrase
+fn confirms_teardown_all(input: &str) -> bool {
+    input.trim_end_matches(['\r', '\n']) == TEARDOWN_ALL_PHRASE
+}
+
+/// Cloudflare resources belonging to one album
+#[derive(Debug, PartialEq)]
+struct TeardownTarget {
+    project_name: String,
+    bucket_name: String,
+}
+
+/// Which of a target's resources exist
+#[derive(Debug, Clone, Copy)]
+struct ExistingDeployment {
+    project: bool,
+    bucket: bool,
+}
+
+/// Resolve the Pages project and R2 bucket names for an album directory
+fn teardown_target(path: &Path) -> Result<TeardownTarget> {
+    // Validate and load album config
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nNot an album directory?",
+            path.display()
+        );
+    }
+
+    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    let state = DeployState::load(path);
+    let project_name = deployed_project_name(&album, state.as_ref())?;
+
+    Ok(TeardownTarget {
+        bucket_name: state
+            .and_then(|state| state.bucket)
+            .unwrap_or_else(|| format!("{}-audio", project_name)),
+        project_name,
+    })
+}
+
+/// The Pages project an album was published to: the deploy state's, else
+/// the one its names derive
+fn deployed_project_name(album: &Album, state: Option<&DeployState>) -> Result<String> {
+    if let Some(state) = state {
+        return Ok(state.project.clone());
+    }
+
+    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+
+    // Validate project name is not empty or invalid
+    if project_name.is_empty() || project_name == "-" {
+        anyhow::bail!(
+            "Invalid album/artist names - cannot derive project name.\nAlbum: '{}', Artist: '{}'",
+            album.metadata.title,
+            album.artist.name
+        );
+    }
+    Ok(project_name)
+}
+
+/// Teardown targets for every album directory under `path`, sorted
+fn teardown_targets(path: &Path) -> Result<Vec<(PathBuf, TeardownTarget)>> {
+    discover_albums(path)?
+        .into_iter()
+        .map(|album_dir| {
+            let target = teardown_target(&album_dir)
+                .with_context(|| format!("Failed to read {}", album_dir.display()))?;
+            Ok((album_dir, target))
+        })
+        .collect()
+}
+
+async fn find_deployment(
+    client: &CloudflareClient,
+    target: &TeardownTarget,
+) -> Result<ExistingDeployment> {
+    Ok(ExistingDeployment {
+        project: client
+            .get_pages_project(&target.project_name)
+            .await?
+            .is_some(),
+        bucket: client.get_r2_bucket(&target.bucket_name).await?.is_some(),
+    })
+}
+
+/// Delete whichever of a target's Pages project and R2 bucket exist
+async fn delete_deployment(
+    client: &CloudflareClient,
+    config: &GlobalConfig,
+    target: &TeardownTarget,
+    existing: ExistingDeployment,
+) -> Result<()> {
+    // Delete Pages project if it exists
+    if existing.project {
+        println!("🗑️  Deleting project from Cloudflare...");
+        client.delete_pages_project(&target.project_name).await?;
+        println!("   ✓ Deleted from Cloudflare Pages");
+    }
+
+    // Delete R2 bucket if it exists
+    if existing.bucket {
+        println!("   🗑️  Deleting R2 bucket: {}", target.bucket_name);
+
+        // First, empty the bucket
+        match client
+            .empty_r2_bucket(
+                &target.bucket_name,
+                &config.cloudflare.r2_access_key_id,
+                &config.cloudflare.r2_secret_access_key,
+            )
+            .await
+        {
+            Ok(_) => {
+                println!("   ✓ Emptied R2 bucket");
+            }
+            Err(e) => {
+                println!("   ⚠️  Failed to empty R2 bucket: {}", e);
+                println!("   💡 You may need to delete it manually from the Cloudflare dashboard");
+                return Ok(());
+            }
+        }
+
+        // Then delete the empty bucket
+        match client.delete_r2_bucket(&target.bucket_name).await {
+            Ok(_) => {
+                println!("   ✓ Deleted R2 bucket");
+            }
+            Err(e) => {
+                println!("   ⚠️  Failed to delete R2 bucket: {}", e);
+                println!("   💡 You may need to delete it manually from the Cloudflare dashboard");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::album_toml;
+
+    #[test]
+    fn test_derive_project_name_basic() {
+        assert_eq!(
+            derive_project_name("Artist Name", "My Album"),
+            "artist-name-my-album"
+        );
+    }
+
+    #[test]
+    fn test_derive_project_name_special_chars() {
+        assert_eq!(
+            derive_project_name("DJ K!ool", "Beats & Bass"),
+            "dj-kool-beats-bass"
+        );
+    }
+
+    #[test]
+    fn test_derive_project_name_unicode() {
+        assert_eq!(
+            derive_project_name("Café Tacvba", "Ré Album"),
+            "caf-tacvba-r-album"
+        );
+    }
+
+    #[test]
+    fn test_derive_project_name_multiple_spaces() {
+        assert_eq!(
+            derive_project_name("The   Cool  Band", "Super    Album"),
+            "the-cool-band-super-album"
+        );
+    }
+
+    #[test]
+    fn test_derive_project_name_hyphens() {
+        assert_eq!(derive_project_name("Jay-Z", "The-Album"), "jay-z-the-album");
+    }
+
+    #[test]
+    fn test_derive_project_name_numbers() {
+        assert_eq!(
+            derive_project_name("Blink 182", "Album 2023"),
+            "blink-182-album-2023"
+        );
+    }
+
+    #[test]
+    fn test_derive_project_name_all_special_chars() {
+        // Edge case: only special characters results in hyphen separator only
+        assert_eq!(derive_project_name("!!!", "???"), "-");
+    }
+
+    #[test]
+    fn test_derive_project_name_empty_strings() {
+        // Edge case: empty strings result in hyphen separator only
+        assert_eq!(derive_project_name("", ""), "-");
+    }
+
+    // Validation function tests
+    #[test]
+    fn test_validate_api_token_valid() {
+        assert!(validate_api_token("abcdefghijklmnopqrstuvwxyz0123456789ABCD").is_ok());
+        assert!(validate_api_token("abc123-def456_ghi789-jkl012_mno345-pqr678").is_ok());
+    }
+
+    #[test]
+    fn test_validate_api_token_too_short() {
+        assert!(validate_api_token("short").is_err());
+        assert!(validate_api_token("").is_err());
+    }
+
+    #[test]
+    fn test_validate_api_token_invalid_chars() {
+        assert!(validate_api_token("abcdefghijklmnopqrstuvwxyz0123456789@!").is_err());
+        assert!(validate_api_token("token with spaces and is long enough!!!").is_err());
+    }
+
+    #[test]
+    fn test_validate_account_id_valid() {
+        assert!(validate_account_id("a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6").is_ok());
+        assert!(validate_account_id("12345678901234567890123456789012").is_ok());
+    }
+
+    #[test]
+    fn test_validate_account_id_invalid() {
+        assert!(validate_account_id("short").is_err());
+        assert!(validate_account_id("").is_err());
+        assert!(validate_account_id("a1b2c3d4-e5f6-a7b8-c9d0-e1f2a3b4c5d6").is_err()); // hyphens
+    }
+
+    #[test]
+    fn test_validate_domain_valid() {
+        assert!(validate_domain("example.com").is_ok());
+        assert!(validate_domain("subdomain.example.com").is_ok());
+        assert!(validate_domain("my-domain.org").is_ok());
+    }
+
+    #[test]
+    fn test_validate_domain_invalid() {
+        assert!(validate_domain("").is_err());
+        assert!(validate_domain("no-tld").is_err());
+        assert!(validate_domain("invalid..com").is_err());
+        assert!(validate_domain(".example.com").is_err());
+        assert!(validate_domain("example.com.").is_err());
+    }
+
+    #[test]
+    fn test_validate_r2_access_key_valid() {
+        assert!(validate_r2_access_key("a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6").is_ok());
+        assert!(validate_r2_access_key("ABCDEFGHIJKLMNOPQRSTUVWXYZ012345").is_ok());
+    }
+
+    #[test]
+    fn test_validate_r2_access_key_invalid() {
+        assert!(validate_r2_access_key("short").is_err());
+        assert!(validate_r2_access_key("").is_err());
+        assert!(validate_r2_access_key("a1b2c3d4-e5f6-a7b8-c9d0-e1f2a3b4c5d6").is_err()); // hyphens
+    }
+
+    /// The shared album with a small and a "huge" track
+    fn size_test_album_toml() -> String {
+        album_toml(
+            r#"
+[[track]]
+file = "audio/01-small.flac"
+title = "Small"
+
+[[track]]
+file = "audio/02-huge.wav"
+title = "Huge"
+"#,
+        )
+    }
+
+    #[test]
+    fn test_audio_keys_stay_distinct_across_subdirectories() {
+        let dir = TempDir::new().unwrap();
+        for disc in ["disc1", "disc2"] {
+            fs::create_dir_all(dir.path().join("audio").join(disc)).unwrap();
+            fs::write(dir.path().join("audio").join(disc).join("intro.flac"), disc).unwrap();
+        }
+        let toml = size_test_album_toml()
+            .replace("audio/01-small.flac", "audio/disc1/intro.flac")
+            .replace("audio/02-huge.wav", "audio/disc2/intro.flac");
+        let keys = |toml: &str| {
+            let album = release_kit_core::config::parse_album_toml_str(toml).unwrap();
+            DeployManifest::from_album(dir.path(), &album)
+                .unwrap()
+                .tracks
+                .into_iter()
+                .map(|track| track.key)
+                .collect::<Vec<_>>()
+        };
+
+        // Flattened basenames are told apart by track number
+        assert_eq!(
+            keys(&toml),
+            vec!["audio/01-intro.flac", "audio/02-intro.flac"]
+        );
+
+        let nested = toml
+            .replace(
+                "accent_color = \"#ff6b35\"",
+                "accent_color = \"#ff6b35\"\npreserve_audio_paths = true",
+            )
+            .replace(
+                "[hosting.cloudflare]",
+                "[hosting.cloudflare]\nr2_prefix = \"v2\"",
+            );
+        assert_eq!(
+            keys(&nested),
+            vec!["v2/audio/disc1/intro.flac", "v2/audio/disc2/intro.flac"]
+        );
+
+        let album = release_kit_core::config::parse_album_toml_str(&nested).unwrap();
+        let stats = KvNamespace {
+            id: "kv-id".to_string(),
+            title: "stats".to_string(),
+        };
+        assert!(
+            stream_worker_settings(&album, "test-artist-test-album", None, &stats)
+                .contains("AUDIO_KEY_PREFIX = \"v2\"\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_aborts_on_oversized_track() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        // Sparse 2 MB file, over a 1 MB limit
+        fs::File::create(dir.path().join("audio/02-huge.wav"))
+            .unwrap()
+            .set_len(2 * 1024 * 1024)
+            .unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+
+        let options = PublishOptions {
+            force: true,
+            max_file_size_mb: 1,
+            ..Default::default()
+        };
+        let err = publish(dir.path().to_path_buf(), options)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("exceed the 1 MB upload limit"), "{}", err);
+        assert!(err.contains("02-huge.wav (2.0 MB)"), "{}", err);
+        assert!(!err.contains("01-small.flac"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_publish_prune_removed_leaves_s3_buckets_alone() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("album.toml"),
+            format!(
+                "{}\n[hosting.s3]\nendpoint = \"http://localhost:9000\"\nbucket = \"shared-audio\"\n",
+                size_test_album_toml()
+            ),
+        )
+        .unwrap();
+
+        let options = PublishOptions {
+            force: true,
+            prune_removed: true,
+            ..Default::default()
+        };
+        let err = publish(dir.path().to_path_buf(), options)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not the [hosting.s3] bucket"), "{}", err);
+        assert_eq!(CLIENTS_CREATED.with(|count| count.get()), 0);
+    }
+
+    #[tokio::test]
+    async fn test_publish_dry_run_makes_no_api_client() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+
+        let options = PublishOptions {
+            max_file_size_mb: 500,
+            dry_run: DryRun::new(true),
+            ..Default::default()
+        };
+        publish(dir.path().to_path_buf(), options).await.unwrap();
+
+        assert_eq!(CLIENTS_CREATED.with(|count| count.get()), 0);
+    }
+
+    #[test]
+    fn test_dry_run_steps_list_every_call() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album = parse_album_toml(dir.path().join("album.toml")).unwrap();
+        let config = CloudflareConfig {
+            api_token: "token".to_string(),
+            account_id: "acct".to_string(),
+            base_domain: Some("example.com".to_string()),
+            r2_access_key_id: "key".to_string(),
+            r2_secret_access_key: "secret".to_string(),
+        };
+
+        let steps = dry_run_steps(
+            dir.path(),
+            &album,
+            Some(&config),
+            &PublishOptions::default(),
+            Some("album"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            steps[0],
+            "Look up Pages project test-artist-test-album (create it if missing)"
+        );
+        assert_eq!(
+            steps[1],
+            "Create R2 bucket test-artist-test-album-audio (if missing)"
+        );
+        assert!(steps.contains(
+            &"Add CNAME record test-artist-test-album-audio.example.com → acct.r2.cloudflarestorage.com (if missing)"
+                .to_string()
+        ));
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("Upload 2 audio file(s)")
+                    && step.ends_with("to R2 bucket test-artist-test-album-audio"))
+        );
+        assert_eq!(
+            steps.last().unwrap(),
+            "Add CNAME record album.example.com → test-artist-test-album.pages.dev (if missing)"
+        );
+
+        // Pruning comes last, once the new site is live
+        let steps = dry_run_steps(
+            dir.path(),
+            &album,
+            Some(&config),
+            &PublishOptions {
+                prune_removed: true,
+                ..Default::default()
+            },
+            Some("album"),
+        )
+        .unwrap();
+        assert_eq!(
+            steps.last().unwrap(),
+            "List R2 bucket test-artist-test-album-audio under audio/ and delete objects album.toml no longer names (after a confirmation)"
+        );
+
+        // Bundled audio skips R2 entirely
+        let steps = dry_run_steps(
+            dir.path(),
+            &album,
+            None,
+            &PublishOptions {
+                bundle_audio: true,
+                branch: Some("staging".to_string()),
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(steps.len(), 2);
+        assert!(
+            steps[1].ends_with("to Pages project test-artist-test-album as a preview deployment")
+        );
+    }
+
+    #[test]
+    fn test_dry_run_steps_cover_only_skips_audio() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album = parse_album_toml(dir.path().join("album.toml")).unwrap();
+
+        let options = PublishOptions {
+            cover_only: true,
+            ..Default::default()
+        };
+        let steps = dry_run_steps(dir.path(), &album, None, &options, None).unwrap();
+        assert_eq!(
+            steps[1],
+            "Look up R2 bucket test-artist-test-album-audio (audio is not re-uploaded)"
+        );
+        assert!(!steps.iter().any(|step| step.contains("audio file(s)")));
+        assert!(steps[2].contains("site file(s)"), "{}", steps[2]);
+        assert_eq!(steps.len(), 3);
+    }
+
+    #[test]
+    fn test_build_pages_site_bundles_small_audio() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let out = TempDir::new().unwrap();
+
+        build_pages_site(
+            dir.path(),
+            out.path(),
+            None,
+            false,
+            DeployEnvironment::Production,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read(out.path().join("audio/01-small.flac")).unwrap(),
+            b"small"
+        );
+        let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"data-src="/audio/01-small.flac""#));
+    }
+
+    #[tokio::test]
+    async fn test_publish_local_audio_rejects_tracks_over_pages_limit() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        // Sparse 26 MB file: fine for R2, too big for Pages
+        fs::File::create(dir.path().join("audio/02-huge.wav"))
+            .unwrap()
+            .set_len(26 * 1024 * 1024)
+            .unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+
+        let options = PublishOptions {
+            force: true,
+            max_file_size_mb: 500,
+            bundle_audio: true,
+            ..Default::default()
+        };
+        let err = publish(dir.path().to_path_buf(), options)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err.contains("exceed the 25 MB Cloudflare Pages file limit"),
+            "{}",
+            err
+        );
+        assert!(err.contains("02-huge.wav (26.0 MB)"), "{}", err);
+        assert!(!err.contains("01-small.flac"), "{}", err);
+    }
+
+    #[test]
+    fn test_teardown_targets_enumerates_albums() {
+        let dir = TempDir::new().unwrap();
+        for (name, title) in [("b-album", "Second Album"), ("a-album", "First Album")] {
+            let album_dir = dir.path().join(name);
+            fs::create_dir(&album_dir).unwrap();
+            fs::write(
+                album_dir.join("album.toml"),
+                size_test_album_toml()
+                    .replace("title = \"Test Album\"", &format!("title = \"{}\"", title)),
+            )
+            .unwrap();
+        }
+        // Not an album: ignored
+        fs::create_dir(dir.path().join("scratch")).unwrap();
+
+        let targets = teardown_targets(dir.path()).unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                (
+                    dir.path().join("a-album"),
+                    TeardownTarget {
+                        project_name: "test-artist-first-album".to_string(),
+                        bucket_name: "test-artist-first-album-audio".to_string(),
+                    }
+                ),
+                (
+                    dir.path().join("b-album"),
+                    TeardownTarget {
+                        project_name: "test-artist-second-album".to_string(),
+                        bucket_name: "test-artist-second-album-audio".to_string(),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deploy_state_round_trip() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(DeployState::load(dir.path()), None);
+
+        let state = DeployState {
+            project: "test-artist-test-album".to_string(),
+            bucket: Some("test-artist-test-album-audio".to_string()),
+            cdn_url: Some("https://test-artist-test-album-audio.example.com".to_string()),
+            deployed_at: "2025-11-15T10:00:00Z".to_string(),
+        };
+        state.save(dir.path()).unwrap();
+        assert!(dir.path().join(".release-kit/deploy-state.toml").exists());
+        assert_eq!(DeployState::load(dir.path()), Some(state));
+
+        // Bundled audio: no bucket or CDN
+        let bundled = DeployState {
+            project: "test-artist-test-album".to_string(),
+            bucket: None,
+            cdn_url: None,
+            deployed_at: "2025-11-16T10:00:00Z".to_string(),
+        };
+        bundled.save(dir.path()).unwrap();
+        assert_eq!(DeployState::load(dir.path()), Some(bundled));
+
+        DeployState::remove(dir.path());
+        assert_eq!(DeployState::load(dir.path()), None);
+
+        // An unreadable file is as good as none
+        fs::write(dir.path().join(DEPLOY_STATE_FILE), "project = 1").unwrap();
+        assert_eq!(DeployState::load(dir.path()), None);
+    }
+
+    #[test]
+    fn test_teardown_target_uses_deploy_state_after_rename() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        DeployState {
+            project: "test-artist-test-album".to_string(),
+            bucket: Some("test-artist-test-album-audio".to_string()),
+            cdn_url: None,
+            deployed_at: "2025-11-15T10:00:00Z".to_string(),
+        }
+        .save(dir.path())
+        .unwrap();
+
+        // Retitled after publishing: the deployed project is still the target
+        fs::write(
+            dir.path().join("album.toml"),
+            size_test_album_toml().replace("title = \"Test Album\"", "title = \"Renamed Album\""),
+        )
+        .unwrap();
+        assert_eq!(
+            teardown_target(dir.path()).unwrap(),
+            TeardownTarget {
+                project_name: "test-artist-test-album".to_string(),
+                bucket_name: "test-artist-test-album-audio".to_string(),
+            }
+        );
+
+        // Without state, the names are derived from album.toml again
+        DeployState::remove(dir.path());
+        assert_eq!(
+            teardown_target(dir.path()).unwrap(),
+            TeardownTarget {
+                project_name: "test-artist-renamed-album".to_string(),
+                bucket_name: "test-artist-renamed-album-audio".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_teardown_all_requires_albums() {
+        let dir = TempDir::new().unwrap();
+        let err = teardown_all(dir.path().to_path_buf(), true, DryRun::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No album directories"), "{}", err);
+    }
+
+    #[test]
+    fn test_confirms_teardown_all_needs_exact_phrase() {
+        assert!(confirms_teardown_all("DELETE ALL\n"));
+        assert!(confirms_teardown_all("DELETE ALL\r\n"));
+        assert!(!confirms_teardown_all("delete all\n"));
+        assert!(!confirms_teardown_all("DELETE\n"));
+        assert!(!confirms_teardown_all(" DELETE ALL\n"));
+        assert!(!confirms_teardown_all("y\n"));
+        assert!(!confirms_teardown_all(""));
+    }
+
+    #[tokio::test]
+    async fn test_publish_static_copies_built_site() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let out = TempDir::new().unwrap();
+        let site = out.path().join("site");
+
+        publish_static(
+            dir.path().to_path_buf(),
+            site.clone(),
+            500,
+            false,
+            DryRun::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(site.join("index.html").exists());
+        assert!(site.join("player.js").exists());
+        assert!(site.join("feed.xml").exists());
+        assert_eq!(
+            fs::read(site.join("audio/01-small.flac")).unwrap(),
+            b"small"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_dry_run_uploads_and_copies_nothing() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        // Nothing listens here, so any upload would fail the publish
+        fs::write(
+            dir.path().join("album.toml"),
+            format!(
+                "{}\n[hosting.s3]\nendpoint = \"http://127.0.0.1:1\"\nbucket = \"album-audio\"\n",
+                size_test_album_toml()
+            ),
+        )
+        .unwrap();
+        let path = dir.path().to_path_buf();
+        let dry_run = DryRun::new(true);
+
+        let out = TempDir::new().unwrap();
+        let site = out.path().join("site");
+        publish_static(path.clone(), site.clone(), 500, false, dry_run)
+            .await
+            .unwrap();
+        assert!(!site.exists());
+
+        publish_s3(path.clone(), false, None, 500, false, dry_run)
+            .await
+            .unwrap();
+        publish_netlify(path.clone(), false, None, 500, false, dry_run)
+            .await
+            .unwrap();
+        publish_ipfs(path, false, 500, false, dry_run)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_plan_publish_lists_every_object() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        let plan = plan_publish(dir.path(), &album, None, false).unwrap();
+        let json: serde_json::Value = serde_json::to_value(&plan).unwrap();
+
+        assert_eq!(json["project"], "test-artist-test-album");
+        assert_eq!(json["audio_bucket"], "test-artist-test-album-audio");
+        assert_eq!(json["audio_base_url"], "https://pub-<account-id>.r2.dev");
+        assert!(json["dns_records"].as_array().unwrap().is_empty());
+
+        let objects = json["objects"].as_array().unwrap();
+        let tracks: Vec<_> = objects
+            .iter()
+            .filter(|o| o["destination"] == "audio" && o["source"].is_string())
+            .collect();
+        assert_eq!(tracks.len(), album.tracks.len());
+        assert_eq!(tracks[0]["key"], "audio/01-small.flac");
+        assert_eq!(tracks[0]["content_type"], "audio/flac");
+        assert_eq!(tracks[0]["size"], 5);
+        assert_eq!(tracks[1]["key"], "audio/02-huge.wav");
+        assert_eq!(tracks[1]["content_type"], "audio/wav");
+
+        assert!(objects.iter().any(|o| o["key"] == DEPLOY_MANIFEST_KEY));
+        let index = objects
+            .iter()
+            .find(|o| o["destination"] == "pages" && o["key"] == "index.html")
+            .unwrap();
+        assert_eq!(index["content_type"], "text/html");
+        // Audio is served from the bucket, not the Pages deployment
+        assert!(
+            !objects
+                .iter()
+                .any(|o| o["destination"] == "pages"
+                    && o["key"].as_str().unwrap().ends_with(".flac"))
+        );
+    }
+
+    #[test]
+    fn test_plan_publish_dns_records_with_base_domain() {
+        let dir = TempDir::new().unwrap();
+        let toml = size_test_album_toml().replace(
+            "[hosting.cloudflare]\n",
+            "[hosting.cloudflare]\nsubdomain = \"test-album\"\n",
+        );
+        fs::write(dir.path().join("album.toml"), &toml).unwrap();
+        let album = release_kit_core::config::parse_album_toml_str(&toml).unwrap();
+        let config = CloudflareConfig {
+            api_token: "token".to_string(),
+            account_id: "abc123".to_string(),
+            base_domain: Some("example.com".to_string()),
+            r2_access_key_id: "key".to_string(),
+            r2_secret_access_key: "secret".to_string(),
+        };
+
+        let plan = plan_publish(dir.path(), &album, Some(&config), false).unwrap();
+        assert_eq!(
+            plan.audio_base_url,
+            "https://test-artist-test-album-audio.example.com"
+        );
+        let records: Vec<_> = plan
+            .dns_records
+            .iter()
+            .map(|r| (r.name.as_str(), r.content.as_str()))
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                (
+                    "test-artist-test-album-audio.example.com",
+                    "abc123.r2.cloudflarestorage.com"
+                ),
+                ("test-album.example.com", "test-artist-test-album.pages.dev"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_track_sizes_within_limit() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        assert!(check_track_sizes(dir.path(), &album, 1).is_ok());
+    }
+
+    #[test]
+    fn test_batch_upload_files_many_file_output() {
+        let build = TempDir::new().unwrap();
+        fs::create_dir_all(build.path().join("waveforms")).unwrap();
+        for i in 0..250 {
+            fs::write(
+                build.path().join(format!("waveforms/{:03}.json", i)),
+                vec![b'x'; 100],
+            )
+            .unwrap();
+        }
+        fs::write(build.path().join("index.html"), "<html></html>").unwrap();
+
+        let files = collect_upload_files(build.path()).unwrap();
+        assert_eq!(files.len(), 251);
+
+        let refs: Vec<&UploadFile> = files.iter().collect();
+        // At most 100 files or 5000 bytes per batch
+        let batches = batch_upload_files(&refs, 5_000, 100);
+        assert!(batches.len() > 1);
+        for batch in &batches {
+            assert!(batch.len() <= 100);
+            assert!(batch.iter().map(|f| f.size).sum::<u64>() <= 5_000);
+        }
+
+        // Every file lands in exactly one batch, in order
+        let batched: Vec<&str> = batches
+            .iter()
+            .flatten()
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        let expected: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(batched, expected);
+    }
+
+    #[test]
+    fn test_batch_upload_files_oversized_file_alone() {
+        let file = |size| UploadFile {
+            relative_path: "f".to_string(),
+            path: PathBuf::from("f"),
+            hash: String::new(),
+            size,
+            content_type: String::new(),
+        };
+        let (a, big, b) = (file(10), file(500), file(10));
+        let batches = batch_upload_files(&[&a, &big, &b], 100, 10);
+        let sizes: Vec<Vec<u64>> = batches
+            .iter()
+            .map(|batch| batch.iter().map(|f| f.size).collect())
+            .collect();
+        assert_eq!(sizes, vec![vec![10], vec![500], vec![10]]);
+    }
+
+    #[test]
+    fn test_collect_upload_files_manifest_hashes() {
+        let build = TempDir::new().unwrap();
+        fs::create_dir_all(build.path().join("audio")).unwrap();
+        fs::write(build.path().join("index.html"), "same").unwrap();
+        fs::write(build.path().join("audio/track.txt"), "same").unwrap();
+
+        let files = collect_upload_files(build.path()).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["audio/track.txt", "index.html"]);
+
+        for f in &files {
+            assert_eq!(f.hash.len(), 32);
+            assert!(f.hash.chars().all(|c| c.is_ascii_hexdigit()));
+        }
+        // Same bytes, different extension -> different asset
+        assert_ne!(files[0].hash, files[1].hash);
+        assert_eq!(files[1].content_type, "text/html");
+    }
+
+    #[test]
+    fn test_pages_manifest_maps_paths_to_stable_hashes() {
+        use sha2::{Digest, Sha256};
+
+        let build = TempDir::new().unwrap();
+        fs::create_dir_all(build.path().join("artwork")).unwrap();
+        fs::write(build.path().join("index.html"), "<html></html>").unwrap();
+        fs::write(build.path().join("artwork/cover.png"), b"png").unwrap();
+
+        let files = collect_upload_files(build.path()).unwrap();
+        let manifest = pages_manifest(&files);
+        assert_eq!(
+            manifest.keys().collect::<Vec<_>>(),
+            vec!["/artwork/cover.png", "/index.html"]
+        );
+
+        // Content plus extension, not file length
+        let expected = format!("{:x}", Sha256::digest(b"<html></html>html"))[..32].to_string();
+        assert_eq!(manifest["/index.html"], expected);
+
+        // Rehashing the same tree gives the same manifest
+        let again = collect_upload_files(build.path()).unwrap();
+        assert_eq!(pages_manifest(&again), manifest);
+
+        // Changing a file changes only its hash
+        fs::write(build.path().join("artwork/cover.png"), b"new png").unwrap();
+        let changed = collect_upload_files(build.path()).unwrap();
+        let changed = pages_manifest(&changed);
+        assert_ne!(
+            changed["/artwork/cover.png"],
+            manifest["/artwork/cover.png"]
+        );
+        assert_eq!(changed["/index.html"], manifest["/index.html"]);
+    }
+
+    #[test]
+    fn test_describe_batch_names_first_files() {
+        let file = |name: &str| UploadFile {
+            relative_path: name.to_string(),
+            path: PathBuf::from(name),
+            hash: String::new(),
+            size: 0,
+            content_type: String::new(),
+        };
+        assert_eq!(
+            describe_batch(&[file("index.html"), file("player.js")]),
+            "index.html, player.js"
+        );
+        let batch: Vec<UploadFile> = ["a", "b", "c", "d", "e"].into_iter().map(file).collect();
+        assert_eq!(describe_batch(&batch), "a, b, c and 2 more");
+    }
+
+    #[test]
+    fn test_s3_bucket_for_custom_endpoint() {
+        let config = S3Config {
+            endpoint: "http://localhost:9000/".to_string(),
+            region: "eu-central-1".to_string(),
+            bucket: "album-audio".to_string(),
+            public_url: None,
+        };
+        let credentials =
+            S3Credentials::new(Some("minio"), Some("minio-secret"), None, None, None).unwrap();
+
+        let bucket = s3_bucket(&config.bucket, s3_region(&config), credentials).unwrap();
+
+        assert_eq!(bucket.name(), "album-audio");
+        assert_eq!(bucket.region().to_string(), "eu-central-1");
+        assert_eq!(bucket.region().endpoint(), "http://localhost:9000");
+        // Path-style: bucket in the path, not the hostname
+        assert_eq!(bucket.url(), "http://localhost:9000/album-audio");
+    }
+
+    #[test]
+    fn test_upload_report_is_in_track_order() {
+        // Completion order differs from track order
+        let outcomes = vec![
+            (2, Ok("03-c.flac".to_string())),
+            (0, Ok("01-a.flac".to_string())),
+            (3, Err("04-d.flac: Failed after 5 attempts".to_string())),
+            (1, Ok("02-b.flac".to_string())),
+            (4, Err("05-e.flac: Failed after 5 attempts".to_string())),
+        ];
+        let (uploaded, failed) = upload_report(outcomes);
+        assert_eq!(uploaded, vec!["01-a.flac", "02-b.flac", "03-c.flac"]);
+        assert_eq!(
+            failed,
+            vec![
+                "04-d.flac: Failed after 5 attempts",
+                "05-e.flac: Failed after 5 attempts"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cache_control_for() {
+        assert_eq!(
+            cache_control_for("index.html"),
+            "public, max-age=300, must-revalidate"
+        );
+        assert_eq!(
+            cache_control_for("feed.XML"),
+            "public, max-age=300, must-revalidate"
+        );
+        assert_eq!(
+            cache_control_for("audio/01-track.flac"),
+            "public, max-age=86400"
+        );
+        assert_eq!(cache_control_for("player.js"), "public, max-age=86400");
+    }
+
+    /// Full-site upload against a real MinIO server, e.g.
+    /// `docker run -p 9000:9000 minio/minio server /data` then
+    /// `RELEASE_KIT_MINIO_ENDPOINT=http://localhost:9000 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs a MinIO server at RELEASE_KIT_MINIO_ENDPOINT"]
+    async fn test_upload_site_to_minio() {
+        let endpoint = std::env::var("RELEASE_KIT_MINIO_ENDPOINT")
+            .expect("RELEASE_KIT_MINIO_ENDPOINT must be set");
+        let user = std::env::var("MINIO_ROOT_USER").unwrap_or_else(|_| "minioadmin".to_string());
+        let password =
+            std::env::var("MINIO_ROOT_PASSWORD").unwrap_or_else(|_| "minioadmin".to_string());
+
+        let config = S3Config {
+            endpoint,
+            region: "us-east-1".to_string(),
+            bucket: format!("release-kit-test-{}", std::process::id()),
+            public_url: None,
+        };
+        let credentials =
+            S3Credentials::new(Some(&user), Some(&password), None, None, None).unwrap();
+        S3Bucket::create_with_path_style(
+            &config.bucket,
+            s3_region(&config),
+            credentials.clone(),
+            s3::BucketConfiguration::default(),
+        )
+        .await
+        .unwrap();
+        let bucket = s3_bucket(&config.bucket, s3_region(&config), credentials).unwrap();
+
+        let album_dir = TempDir::new().unwrap();
+        fs::create_dir(album_dir.path().join("audio")).unwrap();
+        fs::write(album_dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(album_dir.path().join("audio/02-huge.wav"), b"wav").unwrap();
+        fs::write(album_dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let build = TempDir::new().unwrap();
+        build_static_site(album_dir.path(), build.path(), &BuildOptions::default()).unwrap();
+
+        let expected = collect_upload_files(build.path()).unwrap().len();
+        let uploaded = upload_site(&bucket, build.path(), 2).await.unwrap();
+        assert_eq!(uploaded, expected);
+
+        let mut keys: Vec<String> = bucket
+            .list(String::new(), None)
+            .await
+            .unwrap()
+            .into_iter()
+            .flat_map(|page| page.contents.into_iter().map(|o| o.key))
+            .collect();
+        keys.sort();
+        assert!(keys.contains(&"index.html".to_string()), "{:?}", keys);
+        assert!(keys.contains(&"player.js".to_string()), "{:?}", keys);
+        assert!(
+            keys.contains(&"audio/01-small.flac".to_string()),
+            "{:?}",
+            keys
+        );
+        assert!(
+            keys.contains(&"audio/02-huge.wav".to_string()),
+            "{:?}",
+            keys
+        );
+
+        let (head, _) = bucket.head_object("index.html").await.unwrap();
+        assert_eq!(head.content_type.as_deref(), Some("text/html"));
+        assert_eq!(
+            head.cache_control.as_deref(),
+            Some("public, max-age=300, must-revalidate")
+        );
+        let (head, _) = bucket.head_object("audio/01-small.flac").await.unwrap();
+        assert_eq!(head.content_type.as_deref(), Some("audio/flac"));
+        assert_eq!(head.cache_control.as_deref(), Some("public, max-age=86400"));
+
+        for key in &keys {
+            bucket.delete_object(key).await.unwrap();
+        }
+        bucket.delete().await.unwrap();
+    }
+
+    /// Serve keep-alive HTTP/1.1 on localhost, answering every request with
+    /// `respond(method, path, body)` as a JSON body (or 404 for `None`).
+    /// Returns the base URL and the `"METHOD path"` log.
+    async fn mock_http(
+        respond: fn(&str, &str, &[u8]) -> Option<String>,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        mock_http_replies(
+            move |method, path, body| match respond(method, path, body) {
+                Some(body) => MockReply::ok(body),
+                None => MockReply {
+                    status: "404 Not Found",
+                    headers: String::new(),
+                    body: String::new(),
+                },
+            },
+        )
+        .await
+    }
+
+    /// A `mock_http_replies` response
+    struct MockReply {
+        /// Status line after `HTTP/1.1`, e.g. `"429 Too Many Requests"`
+        status: &'static str,
+        /// Extra header lines, each ending in `\r\n`
+        headers: String,
+        body: String,
+    }
+
+    impl MockReply {
+        fn ok(body: String) -> Self {
+            Self {
+                status: "200 OK",
+                headers: String::new(),
+                body,
+            }
+        }
+    }
+
+    /// `mock_http` with full control of each reply's status and headers
+    async fn mock_http_replies(
+        respond: impl Fn(&str, &str, &[u8]) -> MockReply + Send + Sync + 'static,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let respond = std::sync::Arc::new(respond);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let server_log = log.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let log = server_log.clone();
+                let respond = respond.clone();
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 16 * 1024];
+                    loop {
+                        // Read one full request (head + Content-Length body)
+                        let (head_end, request_end) = loop {
+                            if let Some(head_end) = buffer.windows(4).position(|w| w == b"\r\n\r\n")
+                            {
+                                let head = String::from_utf8_lossy(&buffer[..head_end]);
+                                let length = head
+                                    .lines()
+                                    .find_map(|l| {
+                                        let (name, value) = l.split_once(':')?;
+                                        name.eq_ignore_ascii_case("content-length")
+                                            .then(|| value.trim().parse::<usize>().ok())?
+                                    })
+                                    .unwrap_or(0);
+                                if buffer.len() >= head_end + 4 + length {
+                                    break (head_end, head_end + 4 + length);
+                                }
+                            }
+                            match socket.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                            }
+                        };
+
+                        let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+                        let mut request_line = head.lines().next().unwrap().split(' ');
+                        let method = request_line.next().unwrap().to_string();
+                        let path = request_line.next().unwrap().to_string();
+                        let body = buffer[head_end + 4..request_end].to_vec();
+                        buffer.drain(..request_end);
+
+                        log.lock().unwrap().push(format!("{} {}", method, path));
+                        let reply = respond(&method, &path, &body);
+                        // HEAD replies carry the length but not the body
+                        let response = format!(
+                            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nETag: \"mock\"\r\n{}Content-Length: {}\r\n\r\n{}",
+                            reply.status,
+                            reply.headers,
+                            reply.body.len(),
+                            if method == "HEAD" { "" } else { &reply.body }
+                        );
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (base, log)
+    }
+
+    #[tokio::test]
+    async fn test_albums_sharing_a_bucket_keep_separate_manifests() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        let album = |prefix: &str, title: &str| {
+            release_kit_core::config::parse_album_toml_str(
+                &size_test_album_toml()
+                    .replace(
+                        "[hosting.cloudflare]\n",
+                        &format!("[hosting.cloudflare]\nr2_prefix = \"{}\"\n", prefix),
+                    )
+                    .replace("title = \"Test Album\"", &format!("title = \"{}\"", title)),
+            )
+            .unwrap()
+        };
+        let (first, second) = (album("v1", "First Press"), album("v2", "Remaster"));
+        assert_eq!(manifest_key(&first), "v1/release-kit/manifest.json");
+        assert!(is_manifest_key(&manifest_key(&first)));
+        assert!(is_manifest_key(DEPLOY_MANIFEST_KEY));
+        assert!(!is_manifest_key("audio/not-release-kit/manifest.json"));
+
+        // A bucket that keeps what is PUT into it
+        let objects = std::sync::Mutex::new(BTreeMap::<String, String>::new());
+        let (base, _log) = mock_http_replies(move |method, path, body| {
+            let mut objects = objects.lock().unwrap();
+            match method {
+                "PUT" => {
+                    objects.insert(path.to_string(), String::from_utf8_lossy(body).into_owned());
+                    MockReply::ok(String::new())
+                }
+                _ => match objects.get(path) {
+                    Some(object) => MockReply::ok(object.clone()),
+                    None => MockReply {
+                        status: "404 Not Found",
+                        headers: String::new(),
+                        body: String::new(),
+                    },
+                },
+            }
+        })
+        .await;
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: base,
+        };
+        let credentials =
+            S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+        let bucket = s3_bucket("album-audio", region, credentials).unwrap();
+
+        for album in [&first, &second] {
+            upload_audio(
+                &bucket,
+                dir.path(),
+                album,
+                None,
+                false,
+                false,
+                None,
+                &|_| {},
+            )
+            .await
+            .unwrap();
+        }
+
+        // The second publish left the first album's manifest alone
+        let manifest = DeployManifest::fetch(&bucket, &first).await.unwrap();
+        assert_eq!(manifest.title, "First Press");
+        assert_eq!(manifest.tracks[0].key, "v1/audio/01-small.flac");
+        let manifest = DeployManifest::fetch(&bucket, &second).await.unwrap();
+        assert_eq!(manifest.title, "Remaster");
+        assert_eq!(manifest.tracks[0].key, "v2/audio/01-small.flac");
+    }
+
+    #[tokio::test]
+    async fn test_upload_audio_skips_tracks_unchanged_since_last_publish() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"remastered").unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        // The last publish uploaded this 01-small.flac and an older 02-huge.wav
+        let mut previous = DeployManifest::from_album(dir.path(), &album).unwrap();
+        previous.tracks[1].sha256 = "0".repeat(64);
+        let manifest_json = serde_json::to_string(&previous).unwrap();
+        let manifest_path = format!("/album-audio/{}", DEPLOY_MANIFEST_KEY);
+
+        let (base, log) = mock_http_replies(move |method, path, _| match (method, path) {
+            ("GET", path) if path == manifest_path => MockReply::ok(manifest_json.clone()),
+            ("HEAD", "/album-audio/audio/01-small.flac") => MockReply::ok("small".to_string()),
+            ("HEAD", "/album-audio/audio/02-huge.wav") => MockReply::ok("old".to_string()),
+            ("PUT", _) => MockReply::ok(String::new()),
+            _ => MockReply {
+                status: "404 Not Found",
+                headers: String::new(),
+                body: String::new(),
+            },
+        })
+        .await;
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: base,
+        };
+        let credentials =
+            S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+        let bucket = s3_bucket("album-audio", region, credentials).unwrap();
+
+        upload_audio(
+            &bucket,
+            dir.path(),
+            &album,
+            None,
+            false,
+            false,
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        {
+            let log = log.lock().unwrap();
+            assert!(log.contains(&"HEAD /album-audio/audio/01-small.flac".to_string()));
+            assert!(!log.contains(&"PUT /album-audio/audio/01-small.flac".to_string()));
+            // A different hash uploads without asking the bucket
+            assert!(!log.contains(&"HEAD /album-audio/audio/02-huge.wav".to_string()));
+            assert!(log.contains(&"PUT /album-audio/audio/02-huge.wav".to_string()));
+            assert!(log.contains(&format!("PUT /album-audio/{}", DEPLOY_MANIFEST_KEY)));
+        }
+
+        // --reupload sends everything again
+        log.lock().unwrap().clear();
+        upload_audio(
+            &bucket,
+            dir.path(),
+            &album,
+            None,
+            false,
+            true,
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        let log = log.lock().unwrap();
+        assert!(log.contains(&"PUT /album-audio/audio/01-small.flac".to_string()));
+        assert!(log.contains(&"PUT /album-audio/audio/02-huge.wav".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_prune_removed_deletes_only_orphaned_audio() {
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+        let referenced = referenced_audio_keys(&album);
+        assert_eq!(
+            referenced.iter().collect::<Vec<_>>(),
+            vec!["audio/01-small.flac", "audio/02-huge.wav"]
+        );
+
+        let listing = [
+            "audio/01-small.flac",
+            "audio/02-huge.wav",
+            "audio/03-removed.flac",
+        ]
+        .iter()
+        .map(|key| {
+            format!(
+                "<Contents><Key>{}</Key><LastModified>2025-11-15T00:00:00.000Z</LastModified><ETag>\"mock\"</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+                key
+            )
+        })
+        .collect::<String>();
+        let listing = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><ListBucketResult><Name>album-audio</Name><Prefix>audio/</Prefix><KeyCount>3</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated>{}</ListBucketResult>",
+            listing
+        );
+        let (base, log) = mock_http_replies(move |method, path, _| match method {
+            "GET" if path.contains("list-type=2") => MockReply::ok(listing.clone()),
+            "DELETE" => MockReply {
+                status: "204 No Content",
+                headers: String::new(),
+                body: String::new(),
+            },
+            _ => MockReply {
+                status: "404 Not Found",
+                headers: String::new(),
+                body: String::new(),
+            },
+        })
+        .await;
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: base,
+        };
+        let credentials =
+            S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+        let bucket = s3_bucket("album-audio", region, credentials).unwrap();
+
+        let orphans = orphaned_audio_keys(&bucket, &album, &referenced)
+            .await
+            .unwrap();
+        assert_eq!(orphans, vec!["audio/03-removed.flac"]);
+        assert_eq!(prune_audio(&bucket, &orphans, true).await.unwrap(), 1);
+
+        let deletes: Vec<String> = log
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.starts_with("DELETE"))
+            .cloned()
+            .collect();
+        assert_eq!(deletes, vec!["DELETE /album-audio/audio/03-removed.flac"]);
+    }
+
+    #[test]
+    fn test_referenced_audio_keys_survive_draft_renumbering() {
+        let toml = album_toml(
+            "[[track]]\nfile = \"audio/x.flac\"\ntitle = \"Draft\"\ndraft = true\n\n[[track]]\nfile = \"audio/a/intro.flac\"\ntitle = \"Intro A\"\n\n[[track]]\nfile = \"audio/b/intro.flac\"\ntitle = \"Intro B\"\n",
+        );
+        let album = release_kit_core::config::parse_album_toml_str(&toml).unwrap();
+        let mut published = album.clone();
+        published.remove_drafts();
+        // Leaving the draft out renumbers the colliding intros
+        assert_eq!(
+            published.audio_file_names(),
+            vec!["01-intro.flac", "02-intro.flac"]
+        );
+
+        assert_eq!(
+            referenced_audio_keys(&album).iter().collect::<Vec<_>>(),
+            vec![
+                "audio/01-intro.flac",
+                "audio/02-intro.flac",
+                "audio/03-intro.flac",
+                "audio/x.flac",
+            ]
+        );
+    }
+
+    /// Cloudflare API and S3 responses for a full Pages deploy with a custom domain
+    fn mock_cloudflare(method: &str, path: &str, body: &[u8]) -> Option<String> {
+        let ok = |result: serde_json::Value| {
+            Some(serde_json::json!({ "success": true, "errors": [], "result": result }).to_string())
+        };
+
+        if method == "PUT" {
+            // S3 object upload
+            return Some(String::new());
+        }
+        if path.ends_with("/upload-token") {
+            return ok(serde_json::json!({ "jwt": "upload-jwt" }));
+        }
+        if path.ends_with("/pages/assets/check-missing") {
+            // Nothing uploaded before: every hash is missing
+            let request: serde_json::Value = serde_json::from_slice(body).unwrap();
+            return ok(request["hashes"].clone());
+        }
+        if path.ends_with("/pages/assets/upload") || path.ends_with("/pages/assets/upsert-hashes") {
+            return ok(serde_json::json!({}));
+        }
+        if path.ends_with("/deployments") {
+            return ok(serde_json::json!({ "url": "https://abc123.test-project.pages.dev" }));
+        }
+        if path.starts_with("/zones?name=") {
+            return ok(serde_json::json!([{ "id": "zone1", "name": "example.com" }]));
+        }
+        if path.starts_with("/zones/zone1/dns_records?name=") {
+            return ok(serde_json::json!([]));
+        }
+        if method == "POST" && path == "/zones/zone1/dns_records" {
+            let record: serde_json::Value = serde_json::from_slice(body).unwrap();
+            return ok(serde_json::json!({
+                "id": "record1",
+                "type": record["type"],
+                "name": record["name"],
+                "content": record["content"],
+                "proxied": true,
+            }));
+        }
+        None
+    }
+
+    #[tokio::test]
+    async fn test_clients_identify_with_user_agent() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Capture one raw request head
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !received.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                assert!(n > 0);
+                received.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(
+                    b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&received).to_lowercase()
+        });
+
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+        assert!(
+            client
+                .get_pages_project("test-project")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let head = server.await.unwrap();
+        let expected = format!("user-agent: {}\r\n", user_agent().to_lowercase());
+        assert!(head.contains(&expected), "{}", head);
+        assert!(
+            head.contains("authorization: bearer test-token"),
+            "{}",
+            head
+        );
+
+        // Storage requests carry it too
+        let credentials =
+            S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: base,
+        };
+        let bucket = s3_bucket("album-audio", region, credentials).unwrap();
+        assert_eq!(
+            bucket.extra_headers.get("user-agent").unwrap(),
+            user_agent().as_str()
+        );
+
+        assert_eq!(
+            user_agent_from(None),
+            format!("release-kit/{}", env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(
+            user_agent_from(Some(" label-sync/2.1 ".to_string())),
+            "label-sync/2.1"
+        );
+        assert_eq!(user_agent_from(Some(String::new())), user_agent_from(None));
+    }
+
+    /// A Pages project with three production deployments and a preview
+    fn mock_deployments(method: &str, path: &str, _body: &[u8]) -> Option<String> {
+        let ok = |result: serde_json::Value| {
+            Some(serde_json::json!({ "success": true, "errors": [], "result": result }).to_string())
+        };
+        let deployment = |id: &str, environment: &str, created_on: &str| {
+            serde_json::json!({
+                "id": id,
+                "short_id": &id[..4],
+                "environment": environment,
+                "created_on": created_on,
+                "url": format!("https://{}.test-project.pages.dev", &id[..4]),
+            })
+        };
+
+        let deployments = "/accounts/test-account/pages/projects/test-project/deployments";
+        match (method, path.strip_prefix(deployments)) {
+            ("GET", Some("")) => ok(serde_json::json!([
+                deployment("ccc3-third", "production", "2025-11-17T10:00:00Z"),
+                deployment("pre1-branch", "preview", "2025-11-16T12:00:00Z"),
+                deployment("bbb2-second", "production", "2025-11-16T10:00:00Z"),
+                deployment("aaa1-first", "production", "2025-11-15T10:00:00Z"),
+            ])),
+            ("POST", Some("/bbb2-second/rollback")) => ok(deployment(
+                "bbb2-second",
+                "production",
+                "2025-11-16T10:00:00Z",
+            )),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_and_rollback_deployments() {
+        let (base, log) = mock_http(mock_deployments).await;
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+
+        let deployments = client.list_deployments("test-project").await.unwrap();
+        assert_eq!(deployments.len(), 4);
+        assert_eq!(deployments[1].environment, "preview");
+        let production: Vec<ProjectDeployment> = deployments
+            .into_iter()
+            .filter(|deployment| deployment.environment == "production")
+            .collect();
+
+        // The one before the live deployment, or the one asked for
+        assert_eq!(live_deployment_index(&production, None), 0);
+        assert_eq!(live_deployment_index(&production, Some("ccc3-third")), 0);
+        assert_eq!(
+            rollback_target(&production, 0, None).unwrap().id,
+            "bbb2-second"
+        );
+        assert_eq!(
+            rollback_target(&production, 0, Some("aaa1-first"))
+                .unwrap()
+                .id,
+            "aaa1-first"
+        );
+        assert_eq!(
+            rollback_target(&production, 0, Some("aaa1"))
+                .unwrap()
+                .created_on,
+            "2025-11-15T10:00:00Z"
+        );
+        let err = rollback_target(&production, 0, Some("pre1-branch")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No production deployment with id pre1-branch")
+        );
+        let err = rollback_target(&production[..1], 0, None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No previous production deployment")
+        );
 
-    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
-    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+        // After an earlier rollback, the project serves an older deployment
+        let project: PagesProject = serde_json::from_value(serde_json::json!({
+            "name": "test-project",
+            "created_on": "2025-11-15T09:00:00Z",
+            "canonical_deployment": { "id": "bbb2-second", "environment": "production" },
+        }))
+        .unwrap();
+        let canonical = project.canonical_deployment.map(|deployment| deployment.id);
+        let live = live_deployment_index(&production, canonical.as_deref());
+        assert_eq!(live, 1);
+        assert_eq!(
+            rollback_target(&production, live, None).unwrap().id,
+            "aaa1-first"
+        );
+        let err = rollback_target(&production, 2, None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No previous production deployment")
+        );
 
-    // Validate project name is not empty or invalid
-    if project_name.is_empty() || project_name == "-" {
-        anyhow::bail!(
-            "Invalid album/artist names - cannot derive project name.\nAlbum: '{}', Artist: '{}'",
-            album.metadata.title,
-            album.artist.name
+        let live = client
+            .rollback_deployment("test-project", "bbb2-second")
+            .await
+            .unwrap();
+        assert_eq!(live.id, "bbb2-second");
+        assert!(log.lock().unwrap().contains(
+            &"POST /accounts/test-account/pages/projects/test-project/deployments/bbb2-second/rollback"
+                .to_string()
+        ));
+
+        // Unknown deployment: the API's 404 is an error
+        assert!(
+            client
+                .rollback_deployment("test-project", "zzz9-missing")
+                .await
+                .is_err()
         );
     }
 
-    let bucket_name = format!("{}-audio", project_name);
-
-    println!("⚠️  WARNING: This will permanently delete:");
-    println!("   Project: {}", project_name);
-    println!("   URL: https://{}.pages.dev", project_name);
-    println!("   All deployments and history");
-    println!("   R2 Bucket: {} (if exists)", bucket_name);
-    println!("   All audio files in R2");
-    println!();
-
-    // Load global config
-    let config = load_config()?
-        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    /// Replies `status` (with `Retry-After: 0`) to the first `failures`
+    /// requests, then a successful Pages project lookup
+    async fn mock_flaky(
+        failures: usize,
+        status: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        mock_http_replies(move |_, _, _| {
+            if count.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < failures {
+                return MockReply {
+                    status,
+                    headers: "Retry-After: 0\r\n".to_string(),
+                    body: r#"{"success":false,"errors":[{"code":10000,"message":"slow down"}],"result":null}"#
+                        .to_string(),
+                };
+            }
+            MockReply::ok(
+                serde_json::json!({
+                    "success": true,
+                    "errors": [],
+                    "result": { "name": "test-project", "created_on": "2025-01-01T00:00:00Z" },
+                })
+                .to_string(),
+            )
+        })
+        .await
+    }
 
-    // Check if project and/or R2 bucket exist
-    println!("🔍 Checking deployment status...");
-    let client =
-        CloudflareClient::new(&config.cloudflare.api_token, &config.cloudflare.account_id)?;
+    #[tokio::test]
+    async fn test_api_calls_retry_rate_limits_and_server_errors() {
+        for status in ["429 Too Many Requests", "503 Service Unavailable"] {
+            let (base, log) = mock_flaky(2, status).await;
+            let client = CloudflareClient::new("test-token", "test-account")
+                .unwrap()
+                .with_api_base(&base);
+
+            let project = client.get_pages_project("test-project").await.unwrap();
+            assert_eq!(project.unwrap().name, "test-project");
+            assert_eq!(log.lock().unwrap().len(), 3, "{}", status);
+        }
+    }
 
-    let project_exists = client.get_pages_project(&project_name).await?.is_some();
-    let bucket_exists = client.get_r2_bucket(&bucket_name).await?.is_some();
+    #[tokio::test]
+    async fn test_api_retries_stop_at_max_attempts() {
+        let (base, log) = mock_flaky(usize::MAX, "429 Too Many Requests").await;
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base)
+            .with_max_attempts(2);
 
-    if project_exists {
-        println!("   ✓ Pages project found");
-    } else {
-        println!("   ℹ️  Pages project not found");
+        let err = client
+            .create_pages_project("test-project")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("slow down"), "{}", err);
+        assert_eq!(log.lock().unwrap().len(), 2);
     }
 
-    if bucket_exists {
-        println!("   ✓ R2 bucket found");
-    } else {
-        println!("   ℹ️  R2 bucket not found");
+    #[test]
+    fn test_retry_backoff_grows_and_caps() {
+        assert_eq!(retry_backoff(1), Duration::from_secs(1));
+        assert_eq!(retry_backoff(2), Duration::from_secs(2));
+        assert_eq!(retry_backoff(3), Duration::from_secs(4));
+        assert_eq!(retry_backoff(20), Duration::from_secs(30));
+        assert!(is_retryable_status(reqwest::StatusCode::BAD_GATEWAY, true));
+        assert!(!is_retryable_status(
+            reqwest::StatusCode::BAD_GATEWAY,
+            false
+        ));
+        assert!(is_retryable_status(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            false
+        ));
+        assert!(!is_retryable_status(reqwest::StatusCode::NOT_FOUND, true));
     }
 
-    if !project_exists && !bucket_exists {
-        println!();
-        println!("ℹ️  Nothing to delete - deployment already cleaned up");
-        return Ok(());
+    #[tokio::test]
+    async fn test_api_creates_are_not_retried_on_server_errors() {
+        // The project may exist after a 503, so a second POST could fail or duplicate it
+        let (base, log) = mock_flaky(1, "503 Service Unavailable").await;
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+
+        let err = client
+            .create_pages_project("test-project")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("slow down"), "{}", err);
+        assert_eq!(log.lock().unwrap().len(), 1);
     }
-    println!();
 
-    // Confirmation prompt
-    if !force {
-        println!("⚠️  Type the project name to confirm deletion:");
-        print!("   > ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+    #[test]
+    fn test_api_max_attempts_from_env() {
+        assert_eq!(api_max_attempts_from(None).unwrap(), API_MAX_ATTEMPTS);
+        assert_eq!(
+            api_max_attempts_from(Some(" ".to_string())).unwrap(),
+            API_MAX_ATTEMPTS
+        );
+        assert_eq!(api_max_attempts_from(Some("8".to_string())).unwrap(), 8);
+        assert!(api_max_attempts_from(Some("0".to_string())).is_err());
+        assert!(api_max_attempts_from(Some("many".to_string())).is_err());
+    }
 
-        if input.trim() != project_name {
-            println!("❌ Project name doesn't match. Teardown cancelled.");
-            return Ok(());
+    /// KV namespace API with one unrelated namespace; creates echo the title
+    fn mock_kv(method: &str, path: &str, body: &[u8]) -> Option<String> {
+        let ok = |result: serde_json::Value| {
+            Some(serde_json::json!({ "success": true, "errors": [], "result": result }).to_string())
+        };
+        let namespaces = "/accounts/test-account/storage/kv/namespaces";
+        if method == "GET" && path.starts_with(namespaces) {
+            return ok(serde_json::json!([{ "id": "kv-other", "title": "other-stats" }]));
+        }
+        if method == "POST" && path == namespaces {
+            let request: serde_json::Value = serde_json::from_slice(body).unwrap();
+            return ok(serde_json::json!({ "id": "kv-new", "title": request["title"] }));
         }
+        None
     }
 
-    // Delete Pages project if it exists
-    if project_exists {
-        println!("🗑️  Deleting project from Cloudflare...");
-        client.delete_pages_project(&project_name).await?;
-        println!("   ✓ Deleted from Cloudflare Pages");
+    #[test]
+    fn test_stream_worker_settings_carry_limits() {
+        let mut album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+        let stats = KvNamespace {
+            id: "kv1".to_string(),
+            title: "test-artist-test-album-stats".to_string(),
+        };
+
+        let settings = stream_worker_settings(&album, "test-artist-test-album", None, &stats);
+        assert!(settings.contains(
+            "ALLOWED_ORIGINS = \"https://test.example.com,https://test-artist-test-album.pages.dev\"\n"
+        ));
+        assert!(settings.contains("binding = \"STATS\"\nid = \"kv1\"\n"));
+        assert!(!settings.contains("MAX_CONCURRENT_STREAMS"));
+        assert!(!settings.contains("STREAM_LIMITER"));
+
+        album.limits = Some(release_kit_core::types::Limits {
+            max_monthly_bandwidth_gb: 100,
+            max_concurrent_streams: Some(3),
+        });
+        let settings = stream_worker_settings(&album, "test-artist-test-album", None, &stats);
+        assert!(settings.contains("MAX_CONCURRENT_STREAMS = \"3\"\n"));
+        assert!(settings.contains("name = \"STREAM_LIMITER\"\nclass_name = \"StreamLimiter\""));
+        toml::from_str::<toml::Value>(&settings).unwrap();
     }
 
-    // Delete R2 bucket if it exists
-    if bucket_exists {
-        println!("   🗑️  Deleting R2 bucket: {}", bucket_name);
+    #[tokio::test]
+    async fn test_ensure_stats_namespace() {
+        let (base, log) = mock_http(mock_kv).await;
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
 
-        // First, empty the bucket
-        match client
-            .empty_r2_bucket(
-                &bucket_name,
-                &config.cloudflare.r2_access_key_id,
-                &config.cloudflare.r2_secret_access_key,
-            )
+        let namespace = ensure_stats_namespace(&client, "test-artist-test-album")
             .await
-        {
-            Ok(_) => {
-                println!("   ✓ Emptied R2 bucket");
-            }
-            Err(e) => {
-                println!("   ⚠️  Failed to empty R2 bucket: {}", e);
-                println!("   💡 You may need to delete it manually from the Cloudflare dashboard");
-                return Ok(());
-            }
-        }
+            .unwrap();
+        assert_eq!(namespace.id, "kv-new");
+        assert_eq!(namespace.title, "test-artist-test-album-stats");
 
-        // Then delete the empty bucket
-        match client.delete_r2_bucket(&bucket_name).await {
-            Ok(_) => {
-                println!("   ✓ Deleted R2 bucket");
-            }
-            Err(e) => {
-                println!("   ⚠️  Failed to delete R2 bucket: {}", e);
-                println!("   💡 You may need to delete it manually from the Cloudflare dashboard");
-            }
-        }
-    }
-    println!();
+        let existing = ensure_stats_namespace(&client, "other").await.unwrap();
+        assert_eq!(existing.id, "kv-other");
 
-    println!("✅ Teardown complete!");
-    println!("   Project {} has been deleted", project_name);
+        let log = log.lock().unwrap();
+        assert_eq!(
+            log.iter().filter(|l| l.starts_with("POST ")).count(),
+            1,
+            "{:?}",
+            log
+        );
+    }
 
-    Ok(())
-}
+    #[tokio::test]
+    async fn test_pages_deployment_reports_progress_events() {
+        let (base, log) = mock_http(mock_cloudflare).await;
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+        let credentials =
+            S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: base.clone(),
+        };
+        let deployment = PagesDeployment {
+            client: &client,
+            path: dir.path(),
+            album: &album,
+            project_name: "test-project",
+            project_exists: true,
+            audio: PagesAudio::Bucket {
+                bucket: s3_bucket("album-audio", region, credentials).unwrap(),
+                base_url: "https://cdn.example.com".to_string(),
+            },
+            concurrency: 2,
+            custom_domain: Some(("my-album".to_string(), "example.com".to_string())),
+            strip_metadata: false,
+            branch: None,
+            environment: DeployEnvironment::Production,
+            include_drafts: false,
+            reupload: false,
+            checkpoint: None,
+        };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let events = std::sync::Mutex::new(Vec::new());
+        let result = deployment
+            .deploy_with_progress(&|event| events.lock().unwrap().push(event))
+            .await
+            .unwrap();
 
-    #[test]
-    fn test_derive_project_name_basic() {
         assert_eq!(
-            derive_project_name("Artist Name", "My Album"),
-            "artist-name-my-album"
+            events.into_inner().unwrap(),
+            vec![
+                DeployEvent::UploadingAudio { done: 0, total: 2 },
+                DeployEvent::UploadingAudio { done: 1, total: 2 },
+                DeployEvent::UploadingAudio { done: 2, total: 2 },
+                DeployEvent::BuildingSite,
+                DeployEvent::UploadingSite { done: 0, total: 1 },
+                DeployEvent::UploadingSite { done: 1, total: 1 },
+                DeployEvent::CreatingDns,
+                DeployEvent::Done {
+                    site_url: "https://abc123.test-project.pages.dev".to_string()
+                },
+            ]
         );
+        assert_eq!(result.site_url, "https://abc123.test-project.pages.dev");
+        assert_eq!(
+            result.feed_url.as_deref(),
+            Some("https://abc123.test-project.pages.dev/feed.xml")
+        );
+
+        let log = log.lock().unwrap();
+        assert!(log.contains(&"PUT /album-audio/audio/01-small.flac".to_string()));
+        assert!(log.contains(&"PUT /album-audio/audio/02-huge.wav".to_string()));
+        assert!(log.contains(&format!("PUT /album-audio/{}", DEPLOY_MANIFEST_KEY)));
+        assert!(log.contains(&"POST /zones/zone1/dns_records".to_string()));
+        // Existing project: no create call
+        assert!(!log.contains(&"POST /accounts/test-account/pages/projects".to_string()));
     }
 
     #[test]
-    fn test_derive_project_name_special_chars() {
+    fn test_site_content_hash_ignores_feed_build_date() {
+        let build = |date: &str, title: &str| {
+            let dir = TempDir::new().unwrap();
+            fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+            fs::write(
+                dir.path().join("feed.xml"),
+                format!(
+                    "<rss>\n    <title>{}</title>\n    <lastBuildDate>{}</lastBuildDate>\n</rss>\n",
+                    title, date
+                ),
+            )
+            .unwrap();
+            site_content_hash(dir.path()).unwrap()
+        };
+
+        let hash = build("Sat, 15 Nov 2025 10:00:00 +0000", "Album");
+        assert_eq!(hash, build("Sat, 15 Nov 2025 10:00:01 +0000", "Album"));
+        assert_ne!(hash, build("Sat, 15 Nov 2025 10:00:00 +0000", "Renamed"));
+    }
+
+    /// `mock_cloudflare`, but every site deployment is rejected
+    fn mock_cloudflare_deploy_fails(method: &str, path: &str, body: &[u8]) -> Option<String> {
+        if path.ends_with("/deployments") {
+            return None;
+        }
+        mock_cloudflare(method, path, body)
+    }
+
+    #[tokio::test]
+    async fn test_resumed_pages_deployment_skips_completed_phases() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        async fn deploy(
+            album_dir: &Path,
+            album: &Album,
+            mock: fn(&str, &str, &[u8]) -> Option<String>,
+        ) -> (Result<DeploymentResult>, Vec<String>) {
+            let (base, log) = mock_http(mock).await;
+            let client = CloudflareClient::new("test-token", "test-account")
+                .unwrap()
+                .with_api_base(&base);
+            let credentials =
+                S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+            let region = S3Region::Custom {
+                region: "us-east-1".to_string(),
+                endpoint: base.clone(),
+            };
+            let checkpoint = Checkpoint::open(album_dir, "test-project", false);
+            let deployment = PagesDeployment {
+                client: &client,
+                path: album_dir,
+                album,
+                project_name: "test-project",
+                project_exists: true,
+                audio: PagesAudio::Bucket {
+                    bucket: s3_bucket("album-audio", region, credentials).unwrap(),
+                    base_url: "https://cdn.example.com".to_string(),
+                },
+                concurrency: 2,
+                custom_domain: None,
+                strip_metadata: false,
+                branch: None,
+                environment: DeployEnvironment::Production,
+                include_drafts: false,
+                reupload: false,
+                checkpoint: Some(&checkpoint),
+            };
+            let result = deployment.deploy().await;
+            let log = log.lock().unwrap().clone();
+            (result, log)
+        }
+        let audio_puts = |log: &[String]| {
+            log.iter()
+                .filter(|l| l.starts_with("PUT /album-audio/audio/"))
+                .count()
+        };
+        let site_deploys = |log: &[String]| {
+            log.iter()
+                .filter(|l| l.starts_with("POST ") && l.ends_with("/deployments"))
+                .count()
+        };
+
+        // The site deploy fails after both tracks were uploaded
+        let (result, log) = deploy(dir.path(), &album, mock_cloudflare_deploy_fails).await;
+        assert!(result.is_err());
+        assert_eq!(audio_puts(&log), 2, "{:?}", log);
+        let progress = Checkpoint::open(dir.path(), "test-project", false).progress();
+        assert_eq!(progress.bucket.as_deref(), Some("album-audio"));
         assert_eq!(
-            derive_project_name("DJ K!ool", "Beats & Bass"),
-            "dj-kool-beats-bass"
+            progress.uploaded.keys().collect::<Vec<_>>(),
+            vec!["audio/01-small.flac", "audio/02-huge.wav"]
         );
-    }
+        assert!(progress.site.is_none());
 
-    #[test]
-    fn test_derive_project_name_unicode() {
+        // Resuming skips the audio and deploys the site
+        let (result, log) = deploy(dir.path(), &album, mock_cloudflare).await;
         assert_eq!(
-            derive_project_name("Café Tacvba", "Ré Album"),
-            "caf-tacvba-r-album"
+            result.unwrap().site_url,
+            "https://abc123.test-project.pages.dev"
         );
-    }
+        assert_eq!(audio_puts(&log), 0, "{:?}", log);
+        assert_eq!(site_deploys(&log), 1, "{:?}", log);
 
-    #[test]
-    fn test_derive_project_name_multiple_spaces() {
+        // Resuming again skips the unchanged site too
+        let (result, log) = deploy(dir.path(), &album, mock_cloudflare).await;
         assert_eq!(
-            derive_project_name("The   Cool  Band", "Super    Album"),
-            "the-cool-band-super-album"
+            result.unwrap().site_url,
+            "https://abc123.test-project.pages.dev"
         );
-    }
+        assert_eq!(audio_puts(&log), 0, "{:?}", log);
+        assert_eq!(site_deploys(&log), 0, "{:?}", log);
 
-    #[test]
-    fn test_derive_project_name_hyphens() {
-        assert_eq!(derive_project_name("Jay-Z", "The-Album"), "jay-z-the-album");
-    }
+        // A changed track is uploaded again
+        fs::write(dir.path().join("audio/01-small.flac"), b"remastered").unwrap();
+        let (_, log) = deploy(dir.path(), &album, mock_cloudflare).await;
+        assert!(log.contains(&"PUT /album-audio/audio/01-small.flac".to_string()));
+        assert_eq!(audio_puts(&log), 1, "{:?}", log);
 
-    #[test]
-    fn test_derive_project_name_numbers() {
+        // Progress for another project, or with --restart, is ignored
         assert_eq!(
-            derive_project_name("Blink 182", "Album 2023"),
-            "blink-182-album-2023"
+            Checkpoint::open(dir.path(), "other-project", false).progress(),
+            PublishProgress {
+                project: "other-project".to_string(),
+                ..Default::default()
+            }
+        );
+        assert!(
+            Checkpoint::open(dir.path(), "test-project", true)
+                .resume_summary()
+                .is_none()
         );
     }
 
-    #[test]
-    fn test_derive_project_name_all_special_chars() {
-        // Edge case: only special characters results in hyphen separator only
-        assert_eq!(derive_project_name("!!!", "???"), "-");
+    /// `mock_cloudflare`, but the deployment is only accepted if it links the cover art
+    fn mock_cloudflare_cover(method: &str, path: &str, body: &[u8]) -> Option<String> {
+        if path.ends_with("/deployments")
+            && !String::from_utf8_lossy(body).contains("\"/artwork/cover.png\"")
+        {
+            return None;
+        }
+        mock_cloudflare(method, path, body)
     }
 
-    #[test]
-    fn test_derive_project_name_empty_strings() {
-        // Edge case: empty strings result in hyphen separator only
-        assert_eq!(derive_project_name("", ""), "-");
+    #[tokio::test]
+    async fn test_pages_cover_only_deployment_skips_audio() {
+        let (base, log) = mock_http(mock_cloudflare_cover).await;
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::create_dir(dir.path().join("artwork")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("artwork/cover.png"), b"new cover").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+        let deployment = PagesDeployment {
+            client: &client,
+            path: dir.path(),
+            album: &album,
+            project_name: "test-project",
+            project_exists: true,
+            audio: PagesAudio::Existing {
+                base_url: "https://cdn.example.com".to_string(),
+            },
+            concurrency: 2,
+            custom_domain: None,
+            strip_metadata: false,
+            branch: None,
+            environment: DeployEnvironment::Production,
+            include_drafts: false,
+            reupload: false,
+            checkpoint: None,
+        };
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let result = deployment
+            .deploy_with_progress(&|event| events.lock().unwrap().push(event))
+            .await
+            .unwrap();
+        assert_eq!(result.site_url, "https://abc123.test-project.pages.dev");
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.first(), Some(&DeployEvent::BuildingSite));
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, DeployEvent::UploadingAudio { .. }))
+        );
+
+        let log = log.lock().unwrap();
+        assert!(!log.iter().any(|l| l.starts_with("PUT ")), "{:?}", log);
+        assert!(log.contains(
+            &"POST /accounts/test-account/pages/projects/test-project/deployments".to_string()
+        ));
     }
 
-    // Validation function tests
-    #[test]
-    fn test_validate_api_token_valid() {
-        assert!(validate_api_token("abcdefghijklmnopqrstuvwxyz0123456789ABCD").is_ok());
-        assert!(validate_api_token("abc123-def456_ghi789-jkl012_mno345-pqr678").is_ok());
+    /// `mock_cloudflare`, but the deployment is only accepted as a noindex branch preview
+    fn mock_cloudflare_preview(method: &str, path: &str, body: &[u8]) -> Option<String> {
+        if path.ends_with("/deployments") {
+            let form = String::from_utf8_lossy(body);
+            let preview = form.contains("name=\"branch\"\r\n\r\nstaging\r\n")
+                && form.contains("name=\"_headers\"; filename=\"_headers\"")
+                && form.contains("X-Robots-Tag: noindex")
+                && !form.contains("\"/_headers\"");
+            if !preview {
+                return None;
+            }
+            return Some(
+                serde_json::json!({
+                    "success": true,
+                    "errors": [],
+                    "result": { "url": "https://staging.test-project.pages.dev" },
+                })
+                .to_string(),
+            );
+        }
+        mock_cloudflare(method, path, body)
     }
 
-    #[test]
-    fn test_validate_api_token_too_short() {
-        assert!(validate_api_token("short").is_err());
-        assert!(validate_api_token("").is_err());
+    #[tokio::test]
+    async fn test_pages_preview_deployment_is_noindex() {
+        let (base, log) = mock_http(mock_cloudflare_preview).await;
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-small.flac"), b"small").unwrap();
+        fs::write(dir.path().join("audio/02-huge.wav"), b"not huge").unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+        let credentials =
+            S3Credentials::new(Some("key"), Some("secret"), None, None, None).unwrap();
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: base.clone(),
+        };
+        let deployment = PagesDeployment {
+            client: &client,
+            path: dir.path(),
+            album: &album,
+            project_name: "test-project",
+            project_exists: true,
+            audio: PagesAudio::Bucket {
+                bucket: s3_bucket("album-audio", region, credentials).unwrap(),
+                base_url: "https://cdn.example.com".to_string(),
+            },
+            concurrency: 2,
+            custom_domain: None,
+            strip_metadata: false,
+            branch: Some("staging"),
+            environment: DeployEnvironment::Preview,
+            include_drafts: false,
+            reupload: false,
+            checkpoint: None,
+        };
+
+        let result = deployment.deploy().await.unwrap();
+        assert_eq!(result.site_url, "https://staging.test-project.pages.dev");
+        assert!(log.lock().unwrap().contains(
+            &"POST /accounts/test-account/pages/projects/test-project/deployments".to_string()
+        ));
     }
 
     #[test]
-    fn test_validate_api_token_invalid_chars() {
-        assert!(validate_api_token("abcdefghijklmnopqrstuvwxyz0123456789@!").is_err());
-        assert!(validate_api_token("token with spaces and is long enough!!!").is_err());
+    fn test_r2_cors_request_allows_site_origins() {
+        let mut album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+        album.hosting.cloudflare.subdomain = Some("my-album".to_string());
+
+        let body = r2_cors_request(&r2_cors_origins(
+            &album,
+            "test-artist-test-album",
+            Some("example.com"),
+        ));
+        assert_eq!(
+            body["rules"][0]["allowed"]["origins"],
+            serde_json::json!([
+                "https://test.example.com",
+                "https://test-artist-test-album.pages.dev",
+                "https://*.test-artist-test-album.pages.dev",
+                "https://my-album.example.com",
+            ])
+        );
+        assert_eq!(
+            body["rules"][0]["allowed"]["methods"],
+            serde_json::json!(["GET", "HEAD"])
+        );
+
+        // Extra origins are added; "*" replaces the whole list
+        album.hosting.cloudflare.cors_origins = vec!["https://embed.example.org/".to_string()];
+        let origins = r2_cors_origins(&album, "test-artist-test-album", None);
+        assert_eq!(origins.first().unwrap(), "https://test.example.com");
+        assert_eq!(origins.last().unwrap(), "https://embed.example.org");
+        assert!(!origins.contains(&"*".to_string()));
+
+        album.hosting.cloudflare.cors_origins.push("*".to_string());
+        assert_eq!(
+            r2_cors_origins(&album, "test-artist-test-album", None),
+            vec!["*"]
+        );
     }
 
-    #[test]
-    fn test_validate_account_id_valid() {
-        assert!(validate_account_id("a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6").is_ok());
-        assert!(validate_account_id("12345678901234567890123456789012").is_ok());
+    /// Answers every Cloudflare API call, with the album's R2 bucket already there
+    fn mock_cloudflare_existing_bucket(_method: &str, path: &str, _body: &[u8]) -> Option<String> {
+        let result = if path.ends_with("/r2/buckets/test-project-audio") {
+            serde_json::json!({ "name": "test-project-audio" })
+        } else if path.starts_with("/zones?") {
+            serde_json::json!([{ "id": "zone1", "name": "example.com" }])
+        } else {
+            serde_json::Value::Null
+        };
+        Some(serde_json::json!({ "success": true, "errors": [], "result": result }).to_string())
     }
 
-    #[test]
-    fn test_validate_account_id_invalid() {
-        assert!(validate_account_id("short").is_err());
-        assert!(validate_account_id("").is_err());
-        assert!(validate_account_id("a1b2c3d4-e5f6-a7b8-c9d0-e1f2a3b4c5d6").is_err()); // hyphens
+    #[tokio::test]
+    async fn test_signed_albums_keep_the_r2_bucket_private() {
+        let (base, log) = mock_http(mock_cloudflare_existing_bucket).await;
+        let client = CloudflareClient::new("test-token", "test-account")
+            .unwrap()
+            .with_api_base(&base);
+        let config = GlobalConfig {
+            cloudflare: CloudflareConfig {
+                api_token: "token".to_string(),
+                account_id: "abc123".to_string(),
+                base_domain: Some("example.com".to_string()),
+                r2_access_key_id: "key".to_string(),
+                r2_secret_access_key: "secret".to_string(),
+            },
+        };
+        let mut album =
+            release_kit_core::config::parse_album_toml_str(&size_test_album_toml()).unwrap();
+        album.hosting.cloudflare.cors_origins = vec!["https://example.com".to_string()];
+
+        // Unsigned: CORS and the custom domain expose the bucket
+        let (_, base_url) = prepare_r2_audio(&client, &config, &album, "test-project")
+            .await
+            .unwrap();
+        assert_eq!(base_url, "https://test-project-audio.example.com");
+        let exposed = |log: &[String]| {
+            log.iter()
+                .filter(|l| l.ends_with("/cors") || l.contains("/domains/") || l.contains("/zones"))
+                .count()
+        };
+        assert!(exposed(&log.lock().unwrap()) > 0);
+
+        // Signed: only the bucket lookups, and the audio is served by the worker
+        log.lock().unwrap().clear();
+        album.hosting.cloudflare.stream_worker_url =
+            Some("https://stream.example.com/".to_string());
+        let (_, base_url) = prepare_r2_audio(&client, &config, &album, "test-project")
+            .await
+            .unwrap();
+        assert_eq!(base_url, "https://stream.example.com");
+        let log = log.lock().unwrap().clone();
+        assert_eq!(exposed(&log), 0, "{:?}", log);
+        assert!(
+            log.iter()
+                .all(|l| l == "GET /accounts/test-account/r2/buckets/test-project-audio"),
+            "{:?}",
+            log
+        );
+
+        // The dry run plans no CDN domain either
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("album.toml"), size_test_album_toml()).unwrap();
+        let plan = plan_publish(dir.path(), &album, Some(&config.cloudflare), false).unwrap();
+        assert_eq!(plan.audio_base_url, "https://stream.example.com");
+        assert!(plan.dns_records.is_empty());
     }
 
     #[test]
-    fn test_validate_domain_valid() {
-        assert!(validate_domain("example.com").is_ok());
-        assert!(validate_domain("subdomain.example.com").is_ok());
-        assert!(validate_domain("my-domain.org").is_ok());
+    fn test_origin_allows_wildcards() {
+        assert!(origin_allows("*", "https://anything.example"));
+        assert!(origin_allows(
+            "https://*.album.pages.dev",
+            "https://abc123.album.pages.dev"
+        ));
+        assert!(!origin_allows(
+            "https://*.album.pages.dev",
+            "https://album.pages.dev"
+        ));
+        assert!(origin_allows(
+            "HTTPS://Site.Example/",
+            "https://site.example"
+        ));
+        assert!(!origin_allows(
+            "https://site.example",
+            "https://other.example"
+        ));
     }
 
     #[test]
-    fn test_validate_domain_invalid() {
-        assert!(validate_domain("").is_err());
-        assert!(validate_domain("no-tld").is_err());
-        assert!(validate_domain("invalid..com").is_err());
-        assert!(validate_domain(".example.com").is_err());
-        assert!(validate_domain("example.com.").is_err());
+    fn test_cors_problems_detects_missing_origin() {
+        let response: CloudflareResponse<R2CorsPolicy> = serde_json::from_str(
+            r#"{
+                "success": true,
+                "errors": [],
+                "result": {
+                    "rules": [
+                        {
+                            "allowed": {
+                                "methods": ["GET", "HEAD"],
+                                "origins": ["https://music.example.com/"],
+                                "headers": ["content-type"]
+                            },
+                            "maxAgeSeconds": 3600
+                        },
+                        {
+                            "allowed": {
+                                "methods": ["GET"],
+                                "origins": ["https://artist-album.pages.dev"]
+                            }
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+        let policy = response.result.unwrap();
+
+        let origins = [
+            "https://music.example.com".to_string(),
+            "https://artist-album.pages.dev".to_string(),
+            "https://album.example.org".to_string(),
+        ];
+        assert_eq!(
+            cors_problems(&policy, "artist-album-audio", &origins),
+            vec![
+                "R2 bucket artist-album-audio CORS does not allow HEAD from https://artist-album.pages.dev",
+                "R2 bucket artist-album-audio CORS does not allow origin https://album.example.org",
+            ]
+        );
+
+        // What configure_r2_public_access sets: everything allowed
+        let wildcard: R2CorsPolicy = serde_json::from_str(
+            r#"{"rules": [{"allowed": {"methods": ["GET", "HEAD"], "origins": ["*"]}}]}"#,
+        )
+        .unwrap();
+        assert!(cors_problems(&wildcard, "artist-album-audio", &origins).is_empty());
     }
 
+    /// Minimal FLAC stream header (`fLaC` marker + STREAMINFO block header)
+    const FLAC_MAGIC: &[u8] = b"fLaC\x00\x00\x00\x22\x10\x00\x10\x00";
+
     #[test]
-    fn test_validate_r2_access_key_valid() {
-        assert!(validate_r2_access_key("a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6").is_ok());
-        assert!(validate_r2_access_key("ABCDEFGHIJKLMNOPQRSTUVWXYZ012345").is_ok());
+    fn test_content_type_for_sniffs_extensionless_flac() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("master"), FLAC_MAGIC).unwrap();
+        fs::write(dir.path().join("track.flac"), b"not really flac").unwrap();
+        fs::write(dir.path().join("mystery.unknownext"), b"plain bytes").unwrap();
+
+        assert_eq!(content_type_for(&dir.path().join("master")), "audio/flac");
+        // Known extensions win without reading the file
+        assert_eq!(
+            content_type_for(&dir.path().join("track.flac")),
+            "audio/flac"
+        );
+        assert_eq!(
+            content_type_for(&dir.path().join("mystery.unknownext")),
+            "application/octet-stream"
+        );
     }
 
     #[test]
-    fn test_validate_r2_access_key_invalid() {
-        assert!(validate_r2_access_key("short").is_err());
-        assert!(validate_r2_access_key("").is_err());
-        assert!(validate_r2_access_key("a1b2c3d4-e5f6-a7b8-c9d0-e1f2a3b4c5d6").is_err()); // hyphens
+    fn test_collect_upload_files_sniffs_extensionless_audio() {
+        let build = TempDir::new().unwrap();
+        fs::write(build.path().join("index.html"), "<html></html>").unwrap();
+        fs::write(build.path().join("bonus"), FLAC_MAGIC).unwrap();
+
+        let files = collect_upload_files(build.path()).unwrap();
+        let bonus = files.iter().find(|f| f.relative_path == "bonus").unwrap();
+        assert_eq!(bonus.content_type, "audio/flac");
     }
 }
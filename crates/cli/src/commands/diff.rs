@@ -0,0 +1,286 @@
+use anyhow::{Context, Result};
+use release_kit_core::config::parse_album_toml;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::deploy::{DeployManifest, derive_project_name, load_config, r2_bucket};
+
+/// Difference between a local audio file and its deployed R2 object
+#[derive(Debug, PartialEq)]
+enum AudioChange {
+    /// Present locally, not yet uploaded
+    Added(String),
+    /// Uploaded, but no longer part of the album
+    Removed(String),
+    /// Uploaded, but the local file differs
+    Modified { key: String, reason: String },
+    /// Matches the deployed object
+    Unchanged(String),
+}
+
+/// Compare local album against deployed state.
+///
+/// Audio is compared against the R2 object listing (by size) and the deploy
+/// manifest saved by the last publish (by SHA-256). Album and track metadata
+/// are compared against the manifest.
+///
+/// # Arguments
+///
+/// * `path` - Path to album directory containing album.toml
+pub async fn run(path: PathBuf) -> Result<()> {
+    println!("🔍 Comparing local album with deployed state...\n");
+
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nRun 'release-kit init {}' first",
+            path.display(),
+            path.display()
+        );
+    }
+
+    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+    let project_name = derive_project_name(&album.artist.name, &album.metadata.title);
+    let bucket_name = format!("{}-audio", project_name);
+
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    let bucket = r2_bucket(&config.cloudflare, &bucket_name)?;
+
+    println!("   Project: {}", project_name);
+    println!("   Bucket: {}", bucket_name);
+    println!();
+
+    let local = DeployManifest::from_album(&path, &album)?;
+
+    // Deployed audio objects, keyed by R2 key
+    let mut remote = BTreeMap::new();
+    for page in bucket
+        .list(album.audio_key(""), None)
+        .await
+        .context("Failed to list R2 bucket")?
+    {
+        for obj in page.contents {
+            remote.insert(obj.key, obj.size);
+        }
+    }
+
+    let previous = DeployManifest::fetch(&bucket, &album).await;
+    if previous.is_none() {
+        println!("   ⚠️  No deploy manifest found - comparing audio by size only\n");
+    }
+
+    let audio_changes = diff_audio(&local, &remote, previous.as_ref());
+    let metadata_changes = previous
+        .as_ref()
+        .map(|p| diff_metadata(&local, p))
+        .unwrap_or_default();
+
+    println!("🎵 Audio:");
+    let mut changed = 0;
+    for change in &audio_changes {
+        match change {
+            AudioChange::Added(key) => {
+                changed += 1;
+                println!("  + {} (added)", key);
+            }
+            AudioChange::Removed(key) => {
+                changed += 1;
+                println!("  - {} (removed)", key);
+            }
+            AudioChange::Modified { key, reason } => {
+                changed += 1;
+                println!("  ~ {} (modified: {})", key, reason);
+            }
+            AudioChange::Unchanged(key) => {
+                println!("  ✓ {}", key);
+            }
+        }
+    }
+
+    if previous.is_some() {
+        println!();
+        println!("📋 Metadata:");
+        if metadata_changes.is_empty() {
+            println!("  ✓ No changes");
+        }
+        for change in &metadata_changes {
+            changed += 1;
+            println!("  ~ {}", change);
+        }
+    }
+
+    println!();
+    if changed > 0 {
+        println!("📝 {} change(s) since last deploy", changed);
+    } else {
+        println!("✅ Local album matches deployed state");
+    }
+
+    Ok(())
+}
+
+/// Diff local audio against deployed R2 objects and the previous manifest
+///
+/// Content hashes from the manifest take precedence; sizes from the R2
+/// listing are used when the manifest has no entry for a key.
+fn diff_audio(
+    local: &DeployManifest,
+    remote: &BTreeMap<String, u64>,
+    previous: Option<&DeployManifest>,
+) -> Vec<AudioChange> {
+    let mut changes = Vec::new();
+
+    for track in &local.tracks {
+        let Some(&remote_size) = remote.get(&track.key) else {
+            changes.push(AudioChange::Added(track.key.clone()));
+            continue;
+        };
+
+        let previous_hash = previous
+            .and_then(|p| p.tracks.iter().find(|t| t.key == track.key))
+            .map(|t| t.sha256.as_str());
+
+        let change = match previous_hash {
+            Some(hash) if hash != track.sha256 => AudioChange::Modified {
+                key: track.key.clone(),
+                reason: "content changed".to_string(),
+            },
+            None if remote_size != track.size => AudioChange::Modified {
+                key: track.key.clone(),
+                reason: format!("size {} → {} bytes", remote_size, track.size),
+            },
+            _ => AudioChange::Unchanged(track.key.clone()),
+        };
+        changes.push(change);
+    }
+
+    for key in remote.keys() {
+        if !local.tracks.iter().any(|t| &t.key == key) {
+            changes.push(AudioChange::Removed(key.clone()));
+        }
+    }
+
+    changes
+}
+
+/// Describe album and track metadata changes since the previous manifest
+fn diff_metadata(local: &DeployManifest, previous: &DeployManifest) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    let mut field = |name: &str, old: &str, new: &str| {
+        if old != new {
+            changes.push(format!("{}: \"{}\" → \"{}\"", name, old, new));
+        }
+    };
+    field("title", &previous.title, &local.title);
+    field("artist", &previous.artist, &local.artist);
+    field("release_date", &previous.release_date, &local.release_date);
+
+    for track in &local.tracks {
+        if let Some(old) = previous.tracks.iter().find(|t| t.key == track.key)
+            && old.title != track.title
+        {
+            changes.push(format!(
+                "track {}: \"{}\" → \"{}\"",
+                track.key, old.title, track.title
+            ));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::deploy::ManifestTrack;
+
+    fn manifest(tracks: &[(&str, &str, u64, &str)]) -> DeployManifest {
+        DeployManifest {
+            title: "Test Album".to_string(),
+            artist: "Test Artist".to_string(),
+            release_date: "2025-11-15".to_string(),
+            tracks: tracks
+                .iter()
+                .map(|(title, key, size, sha256)| ManifestTrack {
+                    title: title.to_string(),
+                    key: key.to_string(),
+                    size: *size,
+                    sha256: sha256.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_audio_changed_track_is_modified() {
+        let previous = manifest(&[
+            ("One", "audio/01-one.flac", 100, "aaa"),
+            ("Two", "audio/02-two.flac", 200, "bbb"),
+        ]);
+        // Track two re-mastered: same size, different content
+        let local = manifest(&[
+            ("One", "audio/01-one.flac", 100, "aaa"),
+            ("Two", "audio/02-two.flac", 200, "ccc"),
+        ]);
+        let remote = BTreeMap::from([
+            ("audio/01-one.flac".to_string(), 100),
+            ("audio/02-two.flac".to_string(), 200),
+        ]);
+
+        let changes = diff_audio(&local, &remote, Some(&previous));
+        assert_eq!(
+            changes,
+            vec![
+                AudioChange::Unchanged("audio/01-one.flac".to_string()),
+                AudioChange::Modified {
+                    key: "audio/02-two.flac".to_string(),
+                    reason: "content changed".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_audio_added_removed_and_size_fallback() {
+        let local = manifest(&[
+            ("One", "audio/01-one.flac", 150, "aaa"),
+            ("New", "audio/03-new.flac", 300, "ddd"),
+        ]);
+        let remote = BTreeMap::from([
+            ("audio/01-one.flac".to_string(), 100),
+            ("audio/02-old.flac".to_string(), 200),
+        ]);
+
+        let changes = diff_audio(&local, &remote, None);
+        assert_eq!(
+            changes,
+            vec![
+                AudioChange::Modified {
+                    key: "audio/01-one.flac".to_string(),
+                    reason: "size 100 → 150 bytes".to_string()
+                },
+                AudioChange::Added("audio/03-new.flac".to_string()),
+                AudioChange::Removed("audio/02-old.flac".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_metadata() {
+        let previous = manifest(&[("One", "audio/01-one.flac", 100, "aaa")]);
+        let mut local = manifest(&[("One (Remix)", "audio/01-one.flac", 100, "aaa")]);
+        local.title = "Test Album (Deluxe)".to_string();
+
+        let changes = diff_metadata(&local, &previous);
+        assert_eq!(
+            changes,
+            vec![
+                r#"title: "Test Album" → "Test Album (Deluxe)""#.to_string(),
+                r#"track audio/01-one.flac: "One" → "One (Remix)""#.to_string(),
+            ]
+        );
+        assert!(diff_metadata(&previous, &previous).is_empty());
+    }
+}
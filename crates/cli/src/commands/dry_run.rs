@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// The CLI-wide `--dry-run`: whether commands make changes or only describe them
+///
+/// Created once from the global flag and passed into every command that
+/// writes files or changes remote state. Local writes go through its
+/// `create_dir_all`/`write`/`copy`, which print the intended action instead
+/// of performing it; remote changes check `allows` (or `is_enabled`) first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DryRun {
+    enabled: bool,
+    quiet: bool,
+}
+
+impl DryRun {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            quiet: false,
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        self.enabled
+    }
+
+    /// The same, without printing intended actions (e.g. for `--format json`)
+    pub fn quiet(self) -> Self {
+        Self {
+            quiet: true,
+            ..self
+        }
+    }
+
+    /// Announce `action` when dry-running
+    ///
+    /// # Returns
+    ///
+    /// Whether to carry the action out
+    pub fn allows(self, action: impl Display) -> bool {
+        if self.enabled && !self.quiet {
+            println!("   [dry run] would {}", action);
+        }
+        !self.enabled
+    }
+
+    pub fn create_dir_all(self, path: &Path) -> io::Result<()> {
+        if path.is_dir() || !self.allows(format_args!("create {}/", path.display())) {
+            return Ok(());
+        }
+        fs::create_dir_all(path)
+    }
+
+    pub fn write(self, path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        let contents = contents.as_ref();
+        if !self.allows(format_args!(
+            "write {} ({} bytes)",
+            path.display(),
+            contents.len()
+        )) {
+            return Ok(());
+        }
+        fs::write(path, contents)
+    }
+
+    pub fn copy(self, from: &Path, to: &Path) -> io::Result<()> {
+        if !self.allows(format_args!("copy {} to {}", from.display(), to.display())) {
+            return Ok(());
+        }
+        fs::copy(from, to).map(|_| ())
+    }
+
+    /// Announce every file under `staged` as a write into `output`
+    ///
+    /// For commands that dry-run by building into a scratch directory first.
+    ///
+    /// # Returns
+    ///
+    /// The number of files
+    pub fn report_staged(self, staged: &Path, output: &Path) -> Result<usize> {
+        let mut files = 0;
+        for entry in WalkDir::new(staged).sort_by_file_name() {
+            let entry = entry.context("Failed to read staged output")?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(staged)?;
+            self.allows(format_args!(
+                "write {} ({} bytes)",
+                output.join(relative).display(),
+                entry.metadata()?.len()
+            ));
+            files += 1;
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("a/b");
+        let file = dir.path().join("file.txt");
+        let dry_run = DryRun::new(true);
+
+        dry_run.create_dir_all(&nested).unwrap();
+        dry_run.write(&file, "contents").unwrap();
+        dry_run.copy(&file, &dir.path().join("copy.txt")).unwrap();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let real = DryRun::default();
+        real.create_dir_all(&nested).unwrap();
+        real.write(&file, "contents").unwrap();
+        assert!(nested.is_dir());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "contents");
+    }
+}
@@ -0,0 +1,483 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use release_kit_core::rss::{
+    ITUNES_NAMESPACE, itunes_channel_tags, last_build_date, limit_items, rss_date, track_pub_dates,
+    xml_escape,
+};
+use release_kit_core::types::Album;
+use std::fs;
+use std::path::Path;
+
+use super::deploy::content_type_for;
+use super::template::{format_duration, normalize_base_path, site_url};
+
+/// A feed item for one track
+struct FeedItem {
+    title: String,
+    /// Track artist, when it isn't the album artist
+    author: Option<String>,
+    guid: String,
+    /// `None` for full tracks behind the signing worker, which only play on the site
+    enclosure: Option<Enclosure>,
+    duration: Option<String>,
+    explicit: bool,
+    pub_date: DateTime<Utc>,
+}
+
+/// A feed item's `<enclosure>`
+struct Enclosure {
+    url: String,
+    length: u64,
+    content_type: String,
+}
+
+/// Generate the RSS 2.0 podcast feed (`feed.xml`) for an album
+///
+/// One item per track, with an `<enclosure>` pointing at the same audio URL
+/// the player uses: the CDN when `audio_base_url` is given, otherwise the
+/// built site. Paid albums enclose preview clips, matching what the page
+/// streams. Full tracks behind a signing worker (`stream_worker_url`) get no
+/// enclosure, since feed readers can't fetch a signature. Item dates come from `track_pub_dates`, and `[rss] since` /
+/// `max_items` are applied to them.
+///
+/// # Arguments
+///
+/// * `album` - Album configuration
+/// * `source` - Album directory, for enclosure sizes and types
+/// * `output` - Build output directory, for preview clip sizes
+/// * `cover_art` - Optional cover art filename
+/// * `audio_base_url` - Optional CDN base URL for audio files
+/// * `base_path` - Path prefix when deployed under a subdirectory, or ""
+pub fn generate_feed(
+    album: &Album,
+    source: &Path,
+    output: &Path,
+    cover_art: Option<&str>,
+    audio_base_url: Option<&str>,
+    base_path: &str,
+) -> String {
+    let layout = album.site.layout;
+    let site_url = site_url(album, &normalize_base_path(base_path));
+    let cover_url =
+        cover_art.map(|cover| format!("{}/{}", site_url, layout.asset_path("artwork", cover)));
+
+    // Deployed full tracks stream through the signing worker, when configured
+    let stream_worker = audio_base_url
+        .and(album.hosting.cloudflare.stream_worker_url.as_deref())
+        .map(|url| url.trim_end_matches('/'));
+
+    let pub_dates = track_pub_dates(album.metadata.release_date, album.tracks.len());
+    let items: Vec<FeedItem> = album
+        .tracks
+        .iter()
+        .zip(album.audio_file_names())
+        .zip(pub_dates)
+        .map(|((track, filename), pub_date)| {
+            // Same choice of audio as the player in generate_html
+            let (url, file) = if track.preview_clip.is_some() && album.distribution.is_paid() {
+                let asset = layout.asset_path("audio", &track.preview_file_name());
+                (format!("{}/{}", site_url, asset), Some(output.join(asset)))
+            } else if let Some(worker) = stream_worker {
+                // Unplayable without a signature: kept as the guid only
+                (format!("{}/stream/{}", worker, filename), None)
+            } else if let Some(base_url) = audio_base_url {
+                (
+                    format!(
+                        "{}/{}",
+                        base_url.trim_end_matches('/'),
+                        album.audio_key(&filename)
+                    ),
+                    Some(source.join(&track.file)),
+                )
+            } else {
+                (
+                    format!("{}/{}", site_url, layout.asset_path("audio", &filename)),
+                    Some(source.join(&track.file)),
+                )
+            };
+
+            FeedItem {
+                title: track.title.clone(),
+                author: track.artist.clone(),
+                enclosure: file.map(|file| Enclosure {
+                    url: url.clone(),
+                    length: fs::metadata(&file).map(|m| m.len()).unwrap_or(0),
+                    content_type: content_type_for(&file),
+                }),
+                guid: url,
+                duration: track.duration.map(format_duration),
+                explicit: track.explicit,
+                pub_date,
+            }
+        })
+        .collect();
+
+    let since = album
+        .rss
+        .since
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc());
+    let items = limit_items(items, |item| item.pub_date, since, album.rss.max_items);
+
+    let items_xml: String = items
+        .iter()
+        .map(|item| {
+            let duration = item
+                .duration
+                .as_ref()
+                .map(|d| format!("\n      <itunes:duration>{}</itunes:duration>", d))
+                .unwrap_or_default();
+            let author = item
+                .author
+                .as_ref()
+                .map(|a| format!("\n      <itunes:author>{}</itunes:author>", xml_escape(a)))
+                .unwrap_or_default();
+            let enclosure = item
+                .enclosure
+                .as_ref()
+                .map(|e| {
+                    format!(
+                        "\n      <enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>",
+                        xml_escape(&e.url),
+                        e.length,
+                        xml_escape(&e.content_type)
+                    )
+                })
+                .unwrap_or_default();
+            format!(
+                r#"    <item>
+      <title>{}</title>{}
+      <link>{}</link>
+      <guid isPermaLink="false">{}</guid>
+      <pubDate>{}</pubDate>{}{}
+      <itunes:explicit>{}</itunes:explicit>
+    </item>
+"#,
+                xml_escape(&item.title),
+                author,
+                xml_escape(&site_url),
+                xml_escape(&item.guid),
+                rss_date(item.pub_date),
+                enclosure,
+                duration,
+                item.explicit
+            )
+        })
+        .collect();
+
+    let image_xml = cover_url
+        .as_ref()
+        .map(|url| {
+            format!(
+                "    <image>\n      <url>{}</url>\n      <title>{}</title>\n      <link>{}</link>\n    </image>\n",
+                xml_escape(url),
+                xml_escape(&album.metadata.title),
+                xml_escape(&site_url)
+            )
+        })
+        .unwrap_or_default();
+
+    let category_xml: String = album
+        .metadata
+        .genres()
+        .map(|genre| format!("\n    <category>{}</category>", xml_escape(genre)))
+        .collect();
+
+    let itunes_xml = album
+        .rss
+        .itunes
+        .as_ref()
+        .map(|itunes| itunes_channel_tags(itunes, cover_url.as_deref()))
+        .unwrap_or_default();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:itunes="{}">
+  <channel>
+    <title>{} - {}</title>
+    <link>{}</link>
+    <description>{}</description>
+    <language>{}</language>
+    <copyright>{}</copyright>{}
+    <managingEditor>{} ({})</managingEditor>
+    <lastBuildDate>{}</lastBuildDate>
+    <atom:link href="{}/feed.xml" rel="self" type="application/rss+xml"/>
+    <itunes:author>{}</itunes:author>
+    <itunes:explicit>{}</itunes:explicit>
+{}{}{}  </channel>
+</rss>
+"#,
+        ITUNES_NAMESPACE,
+        xml_escape(&album.metadata.title),
+        xml_escape(&album.metadata.artist),
+        xml_escape(&site_url),
+        xml_escape(&album.metadata.summary),
+        xml_escape(album.site.language()),
+        xml_escape(&album.copyright()),
+        category_xml,
+        xml_escape(&album.artist.rss_author_email),
+        xml_escape(&album.artist.name),
+        last_build_date(),
+        xml_escape(&site_url),
+        xml_escape(&album.artist.name),
+        album.metadata.explicit,
+        image_xml,
+        itunes_xml,
+        items_xml
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::album_toml;
+    use release_kit_core::config::parse_album_toml_str;
+    use tempfile::TempDir;
+
+    /// The shared album, with an `&` in the summary to check escaping
+    fn feed_album_toml() -> String {
+        album_toml(
+            r#"
+[[track]]
+file = "audio/01-first.flac"
+title = "First"
+duration = "3:05"
+
+[[track]]
+file = "audio/02-second.mp3"
+title = "Second"
+duration = "1:02:03"
+explicit = true
+"#,
+        )
+        .replace("A test album", "A test album & more")
+    }
+
+    fn source_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("audio/01-first.flac"), b"flac-bytes").unwrap();
+        fs::write(dir.path().join("audio/02-second.mp3"), b"mp3").unwrap();
+        dir
+    }
+
+    /// Attribute values of every `<enclosure>` in the feed
+    fn enclosures(doc: &roxmltree::Document) -> Vec<(String, String, String)> {
+        doc.descendants()
+            .filter(|n| n.has_tag_name("enclosure"))
+            .map(|n| {
+                (
+                    n.attribute("url").unwrap().to_string(),
+                    n.attribute("length").unwrap().to_string(),
+                    n.attribute("type").unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn child_text<'a>(node: roxmltree::Node<'a, 'a>, name: &str) -> &'a str {
+        node.children()
+            .find(|n| n.has_tag_name(name))
+            .and_then(|n| n.text())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_generate_feed_channel_and_cdn_enclosures() {
+        let src = source_dir();
+        let album = parse_album_toml_str(&feed_album_toml()).unwrap();
+
+        let xml = generate_feed(
+            &album,
+            src.path(),
+            src.path(),
+            Some("cover.jpg"),
+            Some("https://cdn.example.com/"),
+            "",
+        );
+        let doc = roxmltree::Document::parse(&xml).unwrap();
+
+        let channel = doc
+            .descendants()
+            .find(|n| n.has_tag_name("channel"))
+            .unwrap();
+        assert_eq!(child_text(channel, "title"), "Test Album - Test Artist");
+        assert_eq!(child_text(channel, "link"), "https://test.example.com");
+        assert_eq!(child_text(channel, "description"), "A test album & more");
+        assert_eq!(
+            child_text(channel, "managingEditor"),
+            "test@example.com (Test Artist)"
+        );
+        assert!(!child_text(channel, "lastBuildDate").is_empty());
+        let categories: Vec<_> = channel
+            .children()
+            .filter(|n| n.has_tag_name("category"))
+            .map(|n| n.text().unwrap_or_default())
+            .collect();
+        assert_eq!(categories, vec!["experimental"]);
+
+        assert_eq!(
+            enclosures(&doc),
+            vec![
+                (
+                    "https://cdn.example.com/audio/01-first.flac".to_string(),
+                    "10".to_string(),
+                    "audio/flac".to_string()
+                ),
+                (
+                    "https://cdn.example.com/audio/02-second.mp3".to_string(),
+                    "3".to_string(),
+                    "audio/mpeg".to_string()
+                ),
+            ]
+        );
+
+        let items: Vec<_> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("item"))
+            .collect();
+        assert_eq!(child_text(items[0], "title"), "First");
+        assert_eq!(child_text(items[0], "duration"), "3:05");
+        assert_eq!(
+            child_text(items[0], "pubDate"),
+            "Sat, 15 Nov 2025 00:00:00 GMT"
+        );
+        assert_eq!(child_text(items[0], "explicit"), "false");
+        assert_eq!(child_text(items[1], "duration"), "1:02:03");
+        assert_eq!(
+            child_text(items[1], "pubDate"),
+            "Sat, 15 Nov 2025 00:01:00 GMT"
+        );
+        assert_eq!(child_text(items[1], "explicit"), "true");
+        // No per-track artist: items fall back to the channel's author
+        assert!(!items[0].children().any(|n| n.has_tag_name("author")));
+
+        let image = channel
+            .children()
+            .find(|n| n.has_tag_name("image"))
+            .unwrap();
+        assert_eq!(
+            child_text(image, "url"),
+            "https://test.example.com/artwork/cover.jpg"
+        );
+    }
+
+    #[test]
+    fn test_generate_feed_local_audio_limits_and_itunes() {
+        let src = source_dir();
+        let toml = format!(
+            "{}max_items = 1\n\n[rss.itunes]\ncategory = \"Music\"\nowner_email = \"owner@example.com\"\n",
+            feed_album_toml()
+        );
+        let album = parse_album_toml_str(&toml).unwrap();
+
+        let xml = generate_feed(
+            &album,
+            src.path(),
+            src.path(),
+            Some("cover.jpg"),
+            None,
+            "/albums/test",
+        );
+        let doc = roxmltree::Document::parse(&xml).unwrap();
+
+        // Only the newest track survives the cap; audio served from the site
+        assert_eq!(
+            enclosures(&doc),
+            vec![(
+                "https://test.example.com/albums/test/audio/02-second.mp3".to_string(),
+                "3".to_string(),
+                "audio/mpeg".to_string()
+            )]
+        );
+
+        let channel = doc
+            .descendants()
+            .find(|n| n.has_tag_name("channel"))
+            .unwrap();
+        let itunes_image = channel
+            .children()
+            .find(|n| n.has_tag_name((ITUNES_NAMESPACE, "image")))
+            .unwrap();
+        assert_eq!(
+            itunes_image.attribute("href"),
+            Some("https://test.example.com/albums/test/artwork/cover.jpg")
+        );
+        assert!(
+            channel
+                .children()
+                .any(|n| n.has_tag_name((ITUNES_NAMESPACE, "category"))
+                    && n.attribute("text") == Some("Music"))
+        );
+    }
+
+    #[test]
+    fn test_generate_feed_track_artist_is_item_author() {
+        let src = source_dir();
+        let mut album = parse_album_toml_str(&feed_album_toml()).unwrap();
+        album.tracks[1].artist = Some("Guest & Friends".to_string());
+
+        let xml = generate_feed(&album, src.path(), src.path(), None, None, "");
+        let doc = roxmltree::Document::parse(&xml).unwrap();
+        let items: Vec<_> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("item"))
+            .collect();
+        assert!(
+            !items[0]
+                .children()
+                .any(|n| n.has_tag_name((ITUNES_NAMESPACE, "author")))
+        );
+        assert_eq!(child_text(items[1], "author"), "Guest & Friends");
+    }
+
+    #[test]
+    fn test_generate_feed_signed_full_tracks_have_no_enclosure() {
+        let src = source_dir();
+        let out = TempDir::new().unwrap();
+        fs::create_dir_all(out.path().join("audio")).unwrap();
+        fs::write(out.path().join("audio/first-preview.mp3"), b"clip").unwrap();
+        let toml = feed_album_toml()
+            .replace(
+                "download_enabled = false",
+                "download_enabled = true\ndownload_price = 5.0",
+            )
+            .replace(
+                "[hosting.cloudflare]\n",
+                "[hosting.cloudflare]\nstream_worker_url = \"https://stream.example.com/\"\n",
+            );
+        let mut album = parse_album_toml_str(&toml).unwrap();
+        album.tracks[0].preview_clip = Some(release_kit_core::types::PreviewClip {
+            start: std::time::Duration::from_secs(30),
+            end: std::time::Duration::from_secs(60),
+        });
+
+        let xml = generate_feed(
+            &album,
+            src.path(),
+            out.path(),
+            None,
+            Some("https://cdn.example.com"),
+            "",
+        );
+        let doc = roxmltree::Document::parse(&xml).unwrap();
+
+        // The paid preview clip is public; the signed full track is left out
+        assert_eq!(
+            enclosures(&doc),
+            vec![(
+                "https://test.example.com/audio/first-preview.mp3".to_string(),
+                "4".to_string(),
+                "audio/mpeg".to_string()
+            )]
+        );
+        assert!(!xml.contains("https://cdn.example.com"));
+        let items: Vec<_> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("item"))
+            .collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            child_text(items[1], "guid"),
+            "https://stream.example.com/stream/02-second.mp3"
+        );
+    }
+}
@@ -1,25 +1,29 @@
 use anyhow::{Context, Result};
 use chrono::Local;
+use lofty::file::TaggedFile;
 use lofty::prelude::*;
 use lofty::probe::Probe;
+use lofty::tag::ItemKey;
+use release_kit_core::artwork::detect_cover_art;
+use release_kit_core::config::is_valid_email;
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use toml;
 use walkdir::WalkDir;
 
+use super::dry_run::DryRun;
+
 const AUDIO_EXTENSIONS: &[&str] = &["flac", "wav", "mp3", "ogg"];
-const COVER_ART_NAMES: &[&str] = &[
-    "cover.jpg",
-    "cover.png",
-    "artwork.jpg",
-    "artwork.png",
-    "folder.jpg",
-    "folder.png",
-    "album.jpg",
-    "album.png",
-];
 const MAX_SCAN_DEPTH: usize = 2; // Maximum directory depth for audio file scanning
 
+/// Whether `path` ends in one of `AUDIO_EXTENSIONS`, in any case
+pub(crate) fn has_audio_extension(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        AUDIO_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str())
+    })
+}
+
 /// Escape a string for safe inclusion in TOML per TOML v1.0.0 spec
 ///
 /// Handles the required escape sequences for TOML basic strings:
@@ -47,73 +51,25 @@ fn toml_escape_string(s: &str) -> String {
         .replace('\t', "\\t")
 }
 
-/// Validate email format
-/// Checks for basic RFC 5322 compliance without full regex
-fn is_valid_email(email: &str) -> bool {
-    // Must have exactly one @ symbol
-    let at_count = email.matches('@').count();
-    if at_count != 1 {
-        return false;
-    }
-
-    let parts: Vec<&str> = email.split('@').collect();
-    let local = parts[0];
-    let domain = parts[1];
-
-    // Local part (before @) checks
-    if local.is_empty() || local.len() > 64 {
-        return false;
-    }
-
-    // Domain checks
-    if domain.is_empty() || domain.len() > 255 {
-        return false;
-    }
-
-    // Domain must have at least one dot
-    if !domain.contains('.') {
-        return false;
-    }
-
-    // Domain can't start/end with dot or hyphen
-    if domain.starts_with('.')
-        || domain.ends_with('.')
-        || domain.starts_with('-')
-        || domain.ends_with('-')
-    {
-        return false;
-    }
-
-    // No consecutive dots
-    if domain.contains("..") {
-        return false;
-    }
-
-    // Domain must have valid TLD (at least 2 chars after last dot)
-    if let Some(last_dot) = domain.rfind('.') {
-        let tld = &domain[last_dot + 1..];
-        if tld.len() < 2 {
-            return false;
-        }
-    }
-
-    true
-}
-
-#[derive(Debug)]
-struct DetectedTrack {
-    path: PathBuf,
-    title: String,
-    duration: Option<String>,
-    #[allow(dead_code)] // Will be used in future for format-specific handling
-    format: String,
+/// An audio file found by `init`
+#[derive(Debug, Serialize)]
+pub struct DetectedTrack {
+    pub path: PathBuf,
+    pub title: String,
+    /// M:SS, when the file could be probed
+    pub duration: Option<String>,
+    /// Uppercased extension (e.g. "FLAC")
+    pub format: String,
+    /// Genres from the file's tags, in tag order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub genres: Vec<String>,
 }
 
 /// Initialize a new album project directory with smart defaults.
 ///
 /// This command analyzes the given directory for audio files and cover art, then:
 /// - Scans for audio files (FLAC, WAV, MP3, OGG)
-/// - Extracts metadata (duration, format) from audio files
+/// - Extracts metadata (duration, format, genre tags) from audio files
 /// - Auto-generates track titles from filenames
 /// - Detects cover art using common naming conventions
 /// - Creates organized directory structure (audio/, artwork/, notes/)
@@ -123,6 +79,9 @@ struct DetectedTrack {
 /// # Arguments
 ///
 /// * `path` - Path to the directory to initialize (must exist)
+/// * `json` - Print only an [`InitReport`] as JSON instead of the progress report
+/// * `dry_run` - Report what would be created, copied and written, without
+///   touching the directory
 ///
 /// # Errors
 ///
@@ -136,7 +95,10 @@ struct DetectedTrack {
 /// ```no_run
 /// # use std::path::PathBuf;
 /// # async fn example() -> anyhow::Result<()> {
-/// release_kit::commands::init::run(PathBuf::from("my-album")).await?;
+/// use release_kit::commands::dry_run::DryRun;
+/// let dry_run = DryRun::default();
+/// release_kit::commands::init::run(PathBuf::from("my-album"), None, None, None, false, dry_run)
+///     .await?;
 /// # Ok(())
 /// # }
 /// ```
@@ -145,17 +107,107 @@ pub async fn run(
     artist: Option<String>,
     album: Option<String>,
     email: Option<String>,
+    json: bool,
+    dry_run: DryRun,
 ) -> Result<()> {
-    println!("Initializing album directory: {}", path.display());
+    if !json {
+        println!("Initializing album directory: {}", path.display());
+    }
 
     if !path.exists() {
         anyhow::bail!(
-            "Directory '{}' does not exist. Create it first: mkdir {}",
+            "Directory '{}' does not exist. Create it first: mkdir {}\nOr create and initialize it in one step: release-kit new {}",
+            path.display(),
             path.display(),
             path.display()
         );
     }
 
+    // JSON output stays parseable: the report alone describes the plan
+    let dry_run = if json { dry_run.quiet() } else { dry_run };
+    let report = scaffold(&path, artist, album, email, json, dry_run)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+    Ok(())
+}
+
+/// Create a new album directory (and any missing parents), then initialize it.
+///
+/// Same scaffolding as [`run`], for a directory that may not exist yet. An
+/// existing directory must be empty unless `force` is set; even with `force`,
+/// a directory that already has an album.toml is refused.
+///
+/// # Arguments
+///
+/// * `path` - Path of the album directory to create
+/// * `force` - Initialize an existing non-empty directory anyway
+/// * `dry_run` - Report what would be created and written, without doing it
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `path` exists but is not a directory
+/// - The directory is not empty and `force` is not set
+/// - album.toml already exists in the directory
+/// - File operations fail (permissions, disk space, etc.)
+pub async fn new_album(
+    path: PathBuf,
+    artist: Option<String>,
+    album: Option<String>,
+    email: Option<String>,
+    force: bool,
+    dry_run: DryRun,
+) -> Result<()> {
+    if path.exists() {
+        if !path.is_dir() {
+            anyhow::bail!("'{}' exists and is not a directory", path.display());
+        }
+        let is_empty = fs::read_dir(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .next()
+            .is_none();
+        if !is_empty && !force {
+            anyhow::bail!(
+                "Directory '{}' is not empty\nHint: Use 'release-kit init {}' to set up existing files, or pass --force",
+                path.display(),
+                path.display()
+            );
+        }
+    } else {
+        dry_run
+            .create_dir_all(&path)
+            .with_context(|| format!("Failed to create directory {}", path.display()))?;
+        if !dry_run.is_enabled() {
+            println!("✓ Created directory: {}", path.display());
+        }
+    }
+
+    println!("Initializing album directory: {}", path.display());
+    scaffold(&path, artist, album, email, false, dry_run)?;
+    Ok(())
+}
+
+/// What `init` detected and wrote (`init --format json`)
+#[derive(Debug, Serialize)]
+pub struct InitReport {
+    /// The generated album.toml
+    pub album_toml: PathBuf,
+    /// Cover art found in the directory, before it was copied into artwork/
+    pub cover_art: Option<PathBuf>,
+    pub tracks: Vec<DetectedTrack>,
+}
+
+/// Scaffold an existing directory: organize audio and artwork, write
+/// album.toml and notes, and print next steps (unless `quiet`)
+fn scaffold(
+    path: &Path,
+    artist: Option<String>,
+    album: Option<String>,
+    email: Option<String>,
+    quiet: bool,
+    dry_run: DryRun,
+) -> Result<InitReport> {
     let album_toml_path = path.join("album.toml");
     if album_toml_path.exists() {
         anyhow::bail!(
@@ -164,72 +216,103 @@ pub async fn run(
         );
     }
 
-    println!("\nAnalyzing directory...");
+    let step = |msg: String| {
+        if !quiet {
+            println!("{}", msg);
+        }
+    };
+
+    step("\nAnalyzing directory...".to_string());
 
     // Scan for audio files
-    let audio_files = scan_audio_files(&path)?;
+    let audio_files = scan_audio_files(path)?;
 
     if audio_files.is_empty() {
-        println!("⚠ No audio files found");
-        println!("Creating empty structure");
-        create_empty_structure(&path)?;
-        return Ok(());
+        step("⚠ No audio files found".to_string());
+        step("Creating empty structure".to_string());
+        create_empty_structure(path, dry_run)?;
+        if dry_run.is_enabled() && !quiet {
+            println!("\n✓ Dry run complete - nothing was written");
+        } else if !quiet {
+            println!("\n✓ Created empty structure");
+            println!("\nNext steps:");
+            println!("  1. Add audio files to audio/");
+            println!("  2. Add cover art to artwork/");
+            println!("  3. Edit album.toml");
+        }
+        return Ok(InitReport {
+            album_toml: album_toml_path,
+            cover_art: None,
+            tracks: Vec::new(),
+        });
     }
 
-    println!("✓ Found {} audio file(s)", audio_files.len());
+    step(format!("✓ Found {} audio file(s)", audio_files.len()));
 
     // Detect cover art
-    let cover_art = detect_cover_art(&path)?;
+    let cover_art = detect_cover_art(path);
     if let Some(ref cover) = cover_art {
-        println!("✓ Detected cover art: {}", cover.display());
+        step(format!("✓ Detected cover art: {}", cover.display()));
     }
 
     // Extract metadata from audio files
     let tracks = extract_track_metadata(&audio_files)?;
-    println!("✓ Extracted metadata from {} track(s)", tracks.len());
+    step(format!(
+        "✓ Extracted metadata from {} track(s)",
+        tracks.len()
+    ));
 
     // Create directory structure
-    create_directory_structure(&path)?;
+    create_directory_structure(path, dry_run)?;
 
     // Move/copy files to proper locations
-    organize_files(&path, &audio_files, &cover_art)?;
+    organize_files(path, &audio_files, &cover_art, dry_run)?;
 
     // Generate album.toml
     generate_album_toml(
-        &path,
+        path,
         &tracks,
         artist.as_deref(),
         album.as_deref(),
         email.as_deref(),
+        dry_run,
     )?;
 
     // Generate template notes
-    generate_notes_template(&path)?;
-
-    println!("\n✓ Initialization complete!");
-    println!("\nGenerated structure:");
-    println!("  {}/", path.display());
-    println!("  ├── album.toml           ← Edit this to set artist name, etc.");
-    println!("  ├── artwork/");
-    if cover_art.is_some() {
-        println!("  │   └── cover.jpg");
-    }
-    println!("  ├── audio/");
-    for track in &tracks {
-        println!(
-            "  │   └── {}",
-            track.path.file_name().unwrap().to_string_lossy()
-        );
-    }
-    println!("  └── notes/");
-    println!("      └── album.md         ← Add liner notes here");
+    generate_notes_template(path, dry_run)?;
+
+    if dry_run.is_enabled() && !quiet {
+        println!("\n✓ Dry run complete - nothing was written");
+    } else if !quiet {
+        println!("\n✓ Initialization complete!");
+        println!("\nGenerated structure:");
+        println!("  {}/", path.display());
+        println!("  ├── album.toml           ← Edit this to set artist name, etc.");
+        println!("  ├── artwork/");
+        if cover_art.is_some() {
+            println!("  │   └── cover.jpg");
+        }
+        println!("  ├── audio/");
+        for track in &tracks {
+            println!(
+                "  │   └── {}",
+                track.path.file_name().unwrap().to_string_lossy()
+            );
+        }
+        println!("  └── notes/");
+        println!("      └── album.md         ← Add liner notes here");
 
-    println!("\nNext steps:");
-    println!("  1. Edit album.toml (set artist name, release date, summary)");
-    println!("  2. Add liner notes to notes/album.md");
-    println!("  3. Preview: release-kit preview {}", path.display());
+        println!("\nNext steps:");
+        println!("  1. Edit album.toml (set artist name, release date, summary)");
+        println!("  2. Add liner notes to notes/album.md");
+        println!("  3. Preview: release-kit preview {}", path.display());
+    }
 
-    Ok(())
+    Ok(InitReport {
+        album_toml: album_toml_path,
+        cover_art,
+        tracks,
+    })
 }
 
 /// Scan directory for supported audio files.
@@ -237,6 +320,9 @@ pub async fn run(
 /// Recursively searches up to `MAX_SCAN_DEPTH` levels for files with
 /// supported audio extensions (FLAC, WAV, MP3, OGG).
 ///
+/// Audio files whose names aren't valid UTF-8 are an error: album.toml can't
+/// name them, and they'd be mangled on their way into URLs.
+///
 /// # Arguments
 ///
 /// * `dir` - Directory to scan
@@ -246,6 +332,7 @@ pub async fn run(
 /// Sorted vector of paths to audio files found
 fn scan_audio_files(dir: &Path) -> Result<Vec<PathBuf>> {
     let mut audio_files = Vec::new();
+    let mut non_utf8 = Vec::new();
 
     for entry in WalkDir::new(dir)
         .max_depth(MAX_SCAN_DEPTH)
@@ -256,56 +343,36 @@ fn scan_audio_files(dir: &Path) -> Result<Vec<PathBuf>> {
             continue;
         }
 
-        if let Some(ext) = entry.path().extension()
-            && AUDIO_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str())
-        {
+        if has_audio_extension(entry.path()) {
+            if entry.file_name().to_str().is_none() {
+                non_utf8.push(entry.path().display().to_string());
+                continue;
+            }
             audio_files.push(entry.path().to_path_buf());
         }
     }
 
+    if !non_utf8.is_empty() {
+        non_utf8.sort();
+        anyhow::bail!(
+            "Audio file names must be valid UTF-8 (they become URLs):\n  {}\nRename these files and run init again",
+            non_utf8.join("\n  ")
+        );
+    }
+
     // Sort by filename for consistent ordering
     audio_files.sort();
 
     Ok(audio_files)
 }
 
-fn detect_cover_art(dir: &Path) -> Result<Option<PathBuf>> {
-    // Try specific cover art names first
-    for name in COVER_ART_NAMES {
-        let path = dir.join(name);
-        if path.exists() {
-            return Ok(Some(path));
-        }
-    }
-
-    // Fallback: find first JPG or PNG
-    for entry in WalkDir::new(dir)
-        .max_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if !entry.file_type().is_file() {
-            continue;
-        }
-
-        if let Some(ext) = entry.path().extension() {
-            let ext_lower = ext.to_string_lossy().to_lowercase();
-            if ext_lower == "jpg" || ext_lower == "jpeg" || ext_lower == "png" {
-                return Ok(Some(entry.path().to_path_buf()));
-            }
-        }
-    }
-
-    Ok(None)
-}
-
 fn extract_track_metadata(audio_files: &[PathBuf]) -> Result<Vec<DetectedTrack>> {
     let mut tracks = Vec::new();
 
     for (idx, path) in audio_files.iter().enumerate() {
         let title = extract_track_title(path, idx + 1);
 
-        let (duration, format) = match Probe::open(path)
+        let (duration, format, genres) = match Probe::open(path)
             .context("Failed to open audio file")?
             .read()
         {
@@ -321,9 +388,9 @@ fn extract_track_metadata(audio_files: &[PathBuf]) -> Result<Vec<DetectedTrack>>
                     .map(|e| e.to_uppercase())
                     .unwrap_or_else(|| "Audio".to_string());
 
-                (Some(duration_str), format)
+                (Some(duration_str), format, tag_genres(&tagged_file))
             }
-            Err(_) => (None, "Audio".to_string()),
+            Err(_) => (None, "Audio".to_string(), Vec::new()),
         };
 
         tracks.push(DetectedTrack {
@@ -331,12 +398,42 @@ fn extract_track_metadata(audio_files: &[PathBuf]) -> Result<Vec<DetectedTrack>>
             title,
             duration,
             format,
+            genres,
         });
     }
 
     Ok(tracks)
 }
 
+/// Genre values from every tag in the file
+///
+/// A single value may hold several genres separated by `;` or NUL (as
+/// multi-valued ID3v2.4 frames do); each is returned on its own.
+fn tag_genres(tagged_file: &TaggedFile) -> Vec<String> {
+    tagged_file
+        .tags()
+        .iter()
+        .flat_map(|tag| tag.get_strings(&ItemKey::Genre))
+        .flat_map(|value| value.split([';', '\0']))
+        .map(str::trim)
+        .filter(|genre| !genre.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Distinct genres across the detected tracks, in order of first appearance
+///
+/// Genres differing only in case are one genre, spelled as first seen.
+fn detected_genres(tracks: &[DetectedTrack]) -> Vec<String> {
+    let mut genres: Vec<String> = Vec::new();
+    for genre in tracks.iter().flat_map(|track| &track.genres) {
+        if !genres.iter().any(|g| g.eq_ignore_ascii_case(genre)) {
+            genres.push(genre.clone());
+        }
+    }
+    genres
+}
+
 /// Extract a human-readable track title from a filename.
 ///
 /// Strips common track number prefixes (01-, 01_, track-01, etc.),
@@ -391,28 +488,26 @@ fn extract_track_title(path: &Path, track_number: usize) -> String {
     words.join(" ")
 }
 
-fn create_directory_structure(base: &Path) -> Result<()> {
-    fs::create_dir_all(base.join("artwork"))?;
-    fs::create_dir_all(base.join("audio"))?;
-    fs::create_dir_all(base.join("notes"))?;
+fn create_directory_structure(base: &Path, dry_run: DryRun) -> Result<()> {
+    dry_run.create_dir_all(&base.join("artwork"))?;
+    dry_run.create_dir_all(&base.join("audio"))?;
+    dry_run.create_dir_all(&base.join("notes"))?;
     Ok(())
 }
 
-fn create_empty_structure(base: &Path) -> Result<()> {
-    create_directory_structure(base)?;
-    generate_album_toml(base, &[], None, None, None)?;
-    generate_notes_template(base)?;
-
-    println!("\n✓ Created empty structure");
-    println!("\nNext steps:");
-    println!("  1. Add audio files to audio/");
-    println!("  2. Add cover art to artwork/");
-    println!("  3. Edit album.toml");
-
+fn create_empty_structure(base: &Path, dry_run: DryRun) -> Result<()> {
+    create_directory_structure(base, dry_run)?;
+    generate_album_toml(base, &[], None, None, None, dry_run)?;
+    generate_notes_template(base, dry_run)?;
     Ok(())
 }
 
-fn organize_files(base: &Path, audio_files: &[PathBuf], cover_art: &Option<PathBuf>) -> Result<()> {
+fn organize_files(
+    base: &Path,
+    audio_files: &[PathBuf],
+    cover_art: &Option<PathBuf>,
+    dry_run: DryRun,
+) -> Result<()> {
     // Move/copy audio files to audio/
     for audio_file in audio_files {
         let filename = audio_file.file_name().unwrap();
@@ -427,7 +522,9 @@ fn organize_files(base: &Path, audio_files: &[PathBuf], cover_art: &Option<PathB
             continue;
         }
 
-        fs::copy(audio_file, &dest).context("Failed to copy audio file")?;
+        dry_run
+            .copy(audio_file, &dest)
+            .context("Failed to copy audio file")?;
     }
 
     // Move/copy cover art to artwork/
@@ -452,7 +549,9 @@ fn organize_files(base: &Path, audio_files: &[PathBuf], cover_art: &Option<PathB
         };
 
         if should_copy {
-            fs::copy(cover_path, &dest).context("Failed to copy cover art")?;
+            dry_run
+                .copy(cover_path, &dest)
+                .context("Failed to copy cover art")?;
         }
     }
 
@@ -465,6 +564,7 @@ fn generate_album_toml(
     artist: Option<&str>,
     album: Option<&str>,
     email: Option<&str>,
+    dry_run: DryRun,
 ) -> Result<()> {
     let today = Local::now().format("%Y-%m-%d").to_string();
 
@@ -498,6 +598,20 @@ fn generate_album_toml(
         "  # TODO: Set email"
     };
 
+    let genres = detected_genres(tracks);
+    let (genre_list, genre_comment) = if genres.is_empty() {
+        ("\"experimental\"".to_string(), "  # TODO: Set genres")
+    } else {
+        (
+            genres
+                .iter()
+                .map(|genre| format!("\"{}\"", toml_escape_string(genre)))
+                .collect::<Vec<_>>()
+                .join(", "),
+            "  # From the audio tags",
+        )
+    };
+
     let mut toml = format!(
         "# Generated by release-kit init\n\
 # Edit this file to customize your album\n\
@@ -507,7 +621,7 @@ title = \"{album_title}\"{album_comment}\n\
 artist = \"{artist_name}\"{artist_comment}\n\
 release_date = \"{today}\"  # TODO: Set release date\n\
 summary = \"Description of this album\"  # TODO: Add summary\n\
-genre = [\"experimental\"]  # TODO: Set genres\n\
+genre = [{genre_list}]{genre_comment}\n\
 license = \"CC BY-NC-SA 4.0\"\n\
 liner_notes = \"notes/album.md\"\n\
 \n\
@@ -577,12 +691,12 @@ enabled = true
     toml::from_str::<toml::Value>(&toml)
         .context("Generated TOML is invalid - this is a bug in the template generator")?;
 
-    fs::write(base.join("album.toml"), toml)?;
+    dry_run.write(&base.join("album.toml"), toml)?;
 
     Ok(())
 }
 
-fn generate_notes_template(base: &Path) -> Result<()> {
+fn generate_notes_template(base: &Path, dry_run: DryRun) -> Result<()> {
     let template = r##"# Album Notes
 
 Write about your album here. This is markdown, so you can use:
@@ -606,7 +720,7 @@ List the gear you used...
 Thanks to...
 "##;
 
-    fs::write(base.join("notes").join("album.md"), template)?;
+    dry_run.write(&base.join("notes").join("album.md"), template)?;
 
     Ok(())
 }
@@ -614,6 +728,8 @@ Thanks to...
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::test_support::write_wav;
+    use release_kit_core::artwork::COVER_ART_NAMES;
     use std::fs;
     use tempfile::TempDir;
 
@@ -727,6 +843,33 @@ mod tests {
         assert!(result[0].ends_with("track.flac"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_audio_files_rejects_non_utf8_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = create_test_dir_with_audio(&["01-fine.flac"]);
+        // "02-café.flac" in Latin-1
+        fs::write(
+            dir.path().join(OsStr::from_bytes(b"02-caf\xe9.flac")),
+            b"audio",
+        )
+        .unwrap();
+        // Not audio, so not a track either way
+        fs::write(
+            dir.path().join(OsStr::from_bytes(b"notes-\xe9.txt")),
+            b"text",
+        )
+        .unwrap();
+
+        let err = scan_audio_files(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("must be valid UTF-8"), "{}", err);
+        assert!(err.contains("02-caf\u{fffd}.flac"), "{}", err);
+        assert!(!err.contains("01-fine.flac"), "{}", err);
+        assert!(!err.contains("notes-"), "{}", err);
+    }
+
     #[test]
     fn test_scan_audio_files_case_insensitive_extensions() {
         let dir =
@@ -782,7 +925,7 @@ mod tests {
         // Test each standard cover art name
         for name in COVER_ART_NAMES {
             let dir = create_test_dir_with_cover(name);
-            let result = detect_cover_art(dir.path()).unwrap();
+            let result = detect_cover_art(dir.path());
             assert!(result.is_some(), "Should detect cover art named '{}'", name);
             assert!(result.unwrap().ends_with(name));
         }
@@ -796,7 +939,7 @@ mod tests {
         fs::write(dir.path().join("folder.png"), b"image2").unwrap();
         fs::write(dir.path().join("random.jpg"), b"image3").unwrap();
 
-        let result = detect_cover_art(dir.path()).unwrap();
+        let result = detect_cover_art(dir.path());
         assert!(result.is_some());
         // Should prefer "cover.jpg" (first in COVER_ART_NAMES list)
         assert!(result.unwrap().ends_with("cover.jpg"));
@@ -808,7 +951,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         fs::write(dir.path().join("random-image.jpg"), b"image").unwrap();
 
-        let result = detect_cover_art(dir.path()).unwrap();
+        let result = detect_cover_art(dir.path());
         assert!(result.is_some(), "Should fall back to any JPG/PNG");
     }
 
@@ -819,7 +962,7 @@ mod tests {
         fs::write(dir.path().join("audio.flac"), b"audio").unwrap();
         fs::write(dir.path().join("readme.txt"), b"text").unwrap();
 
-        let result = detect_cover_art(dir.path()).unwrap();
+        let result = detect_cover_art(dir.path());
         assert!(
             result.is_none(),
             "Should return None when no cover art found"
@@ -831,14 +974,14 @@ mod tests {
         let dir = TempDir::new().unwrap();
         fs::write(dir.path().join("COVER.JPG"), b"image").unwrap();
 
-        let result = detect_cover_art(dir.path()).unwrap();
+        let result = detect_cover_art(dir.path());
         assert!(result.is_some(), "Should detect uppercase extensions");
     }
 
     #[test]
     fn test_create_directory_structure() {
         let dir = TempDir::new().unwrap();
-        create_directory_structure(dir.path()).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
 
         assert!(dir.path().join("artwork").is_dir());
         assert!(dir.path().join("audio").is_dir());
@@ -850,8 +993,8 @@ mod tests {
         let dir = TempDir::new().unwrap();
 
         // Create twice - should not error
-        create_directory_structure(dir.path()).unwrap();
-        create_directory_structure(dir.path()).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
 
         assert!(dir.path().join("artwork").is_dir());
         assert!(dir.path().join("audio").is_dir());
@@ -861,7 +1004,7 @@ mod tests {
     #[test]
     fn test_generate_album_toml_empty_tracks() {
         let dir = TempDir::new().unwrap();
-        generate_album_toml(dir.path(), &[], None, None, None).unwrap();
+        generate_album_toml(dir.path(), &[], None, None, None, DryRun::default()).unwrap();
 
         let toml_path = dir.path().join("album.toml");
         assert!(toml_path.exists(), "album.toml should be created");
@@ -882,16 +1025,18 @@ mod tests {
                 title: "First Track".to_string(),
                 duration: Some("5:23".to_string()),
                 format: "FLAC".to_string(),
+                genres: Vec::new(),
             },
             DetectedTrack {
                 path: PathBuf::from("02-second-track.flac"),
                 title: "Second Track".to_string(),
                 duration: Some("3:45".to_string()),
                 format: "FLAC".to_string(),
+                genres: Vec::new(),
             },
         ];
 
-        generate_album_toml(dir.path(), &tracks, None, None, None).unwrap();
+        generate_album_toml(dir.path(), &tracks, None, None, None, DryRun::default()).unwrap();
 
         let content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
         assert!(content.contains("[[track]]"));
@@ -903,10 +1048,57 @@ mod tests {
         assert!(content.contains("duration = \"3:45\""));
     }
 
+    #[test]
+    fn test_generate_album_toml_seeds_genres_from_tags() {
+        use lofty::config::WriteOptions;
+        use lofty::tag::{Tag, TagType};
+
+        let dir = TempDir::new().unwrap();
+        let files: Vec<PathBuf> = ["01-one.wav", "02-two.wav", "03-three.wav"]
+            .iter()
+            .map(|name| dir.path().join(name))
+            .collect();
+        for (file, genre) in files
+            .iter()
+            .zip([Some("Drone"), Some("drone;Ambient"), None])
+        {
+            write_wav(file, 1);
+            if let Some(genre) = genre {
+                let mut tag = Tag::new(TagType::Id3v2);
+                tag.set_genre(genre.to_string());
+                tag.save_to_path(file, WriteOptions::default()).unwrap();
+            }
+        }
+
+        let tracks = extract_track_metadata(&files).unwrap();
+        assert_eq!(tracks[0].genres, vec!["Drone"]);
+        assert_eq!(tracks[1].genres, vec!["drone", "Ambient"]);
+        assert!(tracks[2].genres.is_empty());
+
+        generate_album_toml(dir.path(), &tracks, None, None, None, DryRun::default()).unwrap();
+        let content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
+        assert!(content.contains("genre = [\"Drone\", \"Ambient\"]  # From the audio tags"));
+        let album = release_kit_core::config::parse_album_toml_str(&content).unwrap();
+        assert_eq!(album.metadata.genre, vec!["Drone", "Ambient"]);
+
+        // No tags anywhere: the placeholder
+        generate_album_toml(
+            dir.path(),
+            &tracks[2..],
+            None,
+            None,
+            None,
+            DryRun::default(),
+        )
+        .unwrap();
+        let content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
+        assert!(content.contains("genre = [\"experimental\"]  # TODO: Set genres"));
+    }
+
     #[test]
     fn test_generate_album_toml_includes_required_sections() {
         let dir = TempDir::new().unwrap();
-        generate_album_toml(dir.path(), &[], None, None, None).unwrap();
+        generate_album_toml(dir.path(), &[], None, None, None, DryRun::default()).unwrap();
 
         let content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
 
@@ -929,7 +1121,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         fs::create_dir(dir.path().join("notes")).unwrap();
 
-        generate_notes_template(dir.path()).unwrap();
+        generate_notes_template(dir.path(), DryRun::default()).unwrap();
 
         let notes_path = dir.path().join("notes").join("album.md");
         assert!(notes_path.exists(), "album.md should be created");
@@ -947,8 +1139,14 @@ mod tests {
         let audio_file = dir.path().join("track.flac");
         fs::write(&audio_file, b"audio data").unwrap();
 
-        create_directory_structure(dir.path()).unwrap();
-        organize_files(dir.path(), std::slice::from_ref(&audio_file), &None).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
+        organize_files(
+            dir.path(),
+            std::slice::from_ref(&audio_file),
+            &None,
+            DryRun::default(),
+        )
+        .unwrap();
 
         let dest = dir.path().join("audio").join("track.flac");
         assert!(dest.exists(), "Audio file should be copied to audio/");
@@ -963,8 +1161,8 @@ mod tests {
         let cover_file = dir.path().join("my-cover.jpg");
         fs::write(&cover_file, b"image data").unwrap();
 
-        create_directory_structure(dir.path()).unwrap();
-        organize_files(dir.path(), &[], &Some(cover_file)).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
+        organize_files(dir.path(), &[], &Some(cover_file), DryRun::default()).unwrap();
 
         let dest = dir.path().join("artwork").join("cover.jpg");
         assert!(
@@ -979,7 +1177,7 @@ mod tests {
     #[test]
     fn test_organize_files_skips_already_organized() {
         let dir = TempDir::new().unwrap();
-        create_directory_structure(dir.path()).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
 
         // Create file already in target location
         let audio_dir = dir.path().join("audio");
@@ -987,7 +1185,13 @@ mod tests {
         fs::write(&audio_file, b"original data").unwrap();
 
         // Try to organize - should skip
-        organize_files(dir.path(), std::slice::from_ref(&audio_file), &None).unwrap();
+        organize_files(
+            dir.path(),
+            std::slice::from_ref(&audio_file),
+            &None,
+            DryRun::default(),
+        )
+        .unwrap();
 
         // Content should remain unchanged
         let content = fs::read_to_string(&audio_file).unwrap();
@@ -1003,8 +1207,8 @@ mod tests {
         let png_cover = dir.path().join("cover.png");
         fs::write(&png_cover, b"png image").unwrap();
 
-        create_directory_structure(dir.path()).unwrap();
-        organize_files(dir.path(), &[], &Some(png_cover)).unwrap();
+        create_directory_structure(dir.path(), DryRun::default()).unwrap();
+        organize_files(dir.path(), &[], &Some(png_cover), DryRun::default()).unwrap();
 
         let dest = dir.path().join("artwork").join("cover.png");
         assert!(dest.exists(), "Should preserve .png extension");
@@ -1013,7 +1217,7 @@ mod tests {
     #[test]
     fn test_create_empty_structure() {
         let dir = TempDir::new().unwrap();
-        create_empty_structure(dir.path()).unwrap();
+        create_empty_structure(dir.path(), DryRun::default()).unwrap();
 
         // Check directories created
         assert!(dir.path().join("artwork").is_dir());
@@ -1025,6 +1229,152 @@ mod tests {
         assert!(dir.path().join("notes").join("album.md").exists());
     }
 
+    #[tokio::test]
+    async fn test_new_album_creates_missing_directories() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("albums").join("my-album");
+
+        new_album(path.clone(), None, None, None, false, DryRun::default())
+            .await
+            .unwrap();
+
+        assert!(path.join("album.toml").exists());
+        assert!(path.join("audio").is_dir());
+        assert!(path.join("notes").join("album.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_new_album_initializes_existing_empty_directory() {
+        let dir = TempDir::new().unwrap();
+
+        new_album(
+            dir.path().to_path_buf(),
+            None,
+            None,
+            None,
+            false,
+            DryRun::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(dir.path().join("album.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn test_new_album_refuses_non_empty_directory_without_force() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("01-track.flac"), b"audio").unwrap();
+
+        let err = new_album(
+            dir.path().to_path_buf(),
+            None,
+            None,
+            None,
+            false,
+            DryRun::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("is not empty"));
+        assert!(!dir.path().join("album.toml").exists());
+
+        new_album(
+            dir.path().to_path_buf(),
+            None,
+            None,
+            None,
+            true,
+            DryRun::default(),
+        )
+        .await
+        .unwrap();
+        assert!(dir.path().join("album.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn test_new_album_force_keeps_existing_album_toml() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("album.toml"), "# mine").unwrap();
+
+        let err = new_album(
+            dir.path().to_path_buf(),
+            None,
+            None,
+            None,
+            true,
+            DryRun::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("album.toml already exists"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("album.toml")).unwrap(),
+            "# mine"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_dry_run_creates_no_files() {
+        let dir = create_test_dir_with_audio(&["01-first.flac", "02-second.mp3"]);
+        fs::write(dir.path().join("cover.jpg"), b"fake image data").unwrap();
+        let before = fs::read_dir(dir.path()).unwrap().count();
+
+        run(
+            dir.path().to_path_buf(),
+            None,
+            None,
+            None,
+            false,
+            DryRun::new(true),
+        )
+        .await
+        .unwrap();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), before);
+        assert!(!dir.path().join("album.toml").exists());
+
+        // new creates not even the directory
+        let parent = TempDir::new().unwrap();
+        let path = parent.path().join("new-album");
+        new_album(path.clone(), None, None, None, false, DryRun::new(true))
+            .await
+            .unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_scaffold_json_report_lists_detected_files() {
+        let dir = create_test_dir_with_audio(&["01-first.flac", "02-second.mp3"]);
+        fs::write(dir.path().join("front.png"), b"fake image data").unwrap();
+
+        let report = scaffold(dir.path(), None, None, None, true, DryRun::default()).unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+
+        let tracks = json["tracks"].as_array().unwrap();
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0]["title"], "First");
+        // Unreadable audio has no duration or known format
+        assert!(tracks[1]["duration"].is_null());
+        assert_eq!(tracks[1]["format"], "Audio");
+        assert!(json["cover_art"].as_str().unwrap().ends_with("front.png"));
+        assert_eq!(
+            json["album_toml"],
+            dir.path().join("album.toml").to_string_lossy().as_ref()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_missing_directory_suggests_new() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("missing");
+
+        let err = run(path.clone(), None, None, None, false, DryRun::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("release-kit new"));
+        assert!(!path.exists());
+    }
+
     #[test]
     fn test_toml_escape_string() {
         // Test quote escaping
@@ -1046,37 +1396,6 @@ mod tests {
         assert_eq!(toml_escape_string("Normal String"), "Normal String");
     }
 
-    #[test]
-    fn test_is_valid_email() {
-        // Valid emails
-        assert!(is_valid_email("user@example.com"));
-        assert!(is_valid_email("test.user@domain.co.uk"));
-        assert!(is_valid_email("name+tag@example.org"));
-
-        // Invalid emails - missing @
-        assert!(!is_valid_email("user"));
-        assert!(!is_valid_email(""));
-
-        // Invalid emails - multiple @
-        assert!(!is_valid_email("user@@example.com"));
-        assert!(!is_valid_email("user@name@example.com"));
-
-        // Invalid emails - missing parts
-        assert!(!is_valid_email("@example.com"));
-        assert!(!is_valid_email("user@"));
-
-        // Invalid emails - invalid domain
-        assert!(!is_valid_email("user@domain")); // No TLD
-        assert!(!is_valid_email("user@.com")); // Domain starts with dot
-        assert!(!is_valid_email("user@domain.")); // Domain ends with dot
-        assert!(!is_valid_email("user@domain.c")); // TLD too short
-        assert!(!is_valid_email("user@domain..com")); // Consecutive dots
-
-        // Invalid emails - local part too long
-        let long_local = "a".repeat(65);
-        assert!(!is_valid_email(&format!("{}@example.com", long_local)));
-    }
-
     #[test]
     fn test_generate_album_toml_with_artist_and_album() {
         let dir = TempDir::new().unwrap();
@@ -1086,6 +1405,7 @@ mod tests {
             Some("Test Artist"),
             Some("Test Album"),
             None,
+            DryRun::default(),
         )
         .unwrap();
 
@@ -1112,6 +1432,7 @@ mod tests {
             Some(r#"Artist "The Quote""#),
             Some(r"Album\Backslash"),
             Some("test@example.com"),
+            DryRun::default(),
         )
         .unwrap();
 
@@ -1136,6 +1457,7 @@ mod tests {
             Some("Artist"),
             Some("Album"),
             Some("invalid-email"),
+            DryRun::default(),
         );
 
         // Should fail with invalid email
@@ -1155,6 +1477,7 @@ mod tests {
             Some(r#"Artist "Name""#),
             Some(r"Album\Title"),
             Some("test@example.com"),
+            DryRun::default(),
         );
 
         assert!(
@@ -1177,9 +1500,18 @@ mod tests {
             title: r#"Track "With" Quotes"#.to_string(),
             duration: Some("3:45".to_string()),
             format: "flac".to_string(),
+            genres: Vec::new(),
         }];
 
-        generate_album_toml(dir.path(), &tracks, Some("Artist"), Some("Album"), None).unwrap();
+        generate_album_toml(
+            dir.path(),
+            &tracks,
+            Some("Artist"),
+            Some("Album"),
+            None,
+            DryRun::default(),
+        )
+        .unwrap();
 
         // Verify TOML can be parsed
         let toml_content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
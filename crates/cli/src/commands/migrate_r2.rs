@@ -0,0 +1,256 @@
+use anyhow::{Context, Result};
+use futures::{StreamExt, TryStreamExt};
+use s3::Bucket as S3Bucket;
+use std::path::PathBuf;
+
+use super::deploy::{is_manifest_key, load_config, load_config_file, r2_bucket};
+use super::dry_run::DryRun;
+
+/// Objects copied at once
+const MIGRATE_CONCURRENCY: usize = 4;
+
+/// Copy R2 objects from one bucket to another, e.g. after renaming buckets
+/// or moving to another Cloudflare account.
+///
+/// Objects are streamed through this machine (R2 can't copy between
+/// accounts), keeping their keys, content types and cache headers; nothing
+/// is re-uploaded from the local masters and the source is left untouched.
+///
+/// # Arguments
+///
+/// * `from` - Source bucket, in the configured account
+/// * `to` - Destination bucket (must already exist)
+/// * `to_config` - Config file (like ~/.release-kit/config.toml) for the
+///   destination account, when it isn't the configured one
+/// * `prefix` - Only copy keys starting with this (e.g. "audio/")
+/// * `update_manifest` - Also copy the deploy manifest, last, once every
+///   object made it, so `diff` works against the destination
+/// * `dry_run` - List what would be copied; only the source is read
+pub async fn run(
+    from: String,
+    to: String,
+    to_config: Option<PathBuf>,
+    prefix: String,
+    update_manifest: bool,
+    dry_run: DryRun,
+) -> Result<()> {
+    println!("🚚 Migrating R2 objects...");
+    println!("   From: {}", from);
+    println!("   To: {}", to);
+    println!();
+
+    let config = load_config()?
+        .context("No Cloudflare configuration found.\nRun 'release-kit deploy configure' first")?;
+    let to_cloudflare = match &to_config {
+        Some(path) => load_config_file(path)?.cloudflare,
+        None => config.cloudflare.clone(),
+    };
+    let source = r2_bucket(&config.cloudflare, &from)?;
+    let dest = r2_bucket(&to_cloudflare, &to)?;
+
+    let report = migrate_objects(&source, &dest, &prefix, update_manifest, dry_run).await?;
+
+    println!();
+    if dry_run.is_enabled() {
+        println!(
+            "✅ Dry run complete - {} object(s) would be copied, nothing was",
+            report.copied.len()
+        );
+        return Ok(());
+    }
+    println!("✅ Copied {} object(s) to {}", report.copied.len(), to);
+    if report.manifest_copied {
+        println!("   ✓ Deploy manifest copied");
+    } else if report.manifest_skipped {
+        println!("   Deploy manifest left behind (use --update-manifest to copy it)");
+    }
+    println!(
+        "   The source bucket is unchanged; publish again to point the site at the new bucket"
+    );
+    Ok(())
+}
+
+/// What `migrate_objects` copied
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct MigrateReport {
+    /// Copied keys, sorted (the manifest is reported separately)
+    pub copied: Vec<String>,
+    pub manifest_copied: bool,
+    /// The source had a manifest, but it wasn't asked for
+    pub manifest_skipped: bool,
+}
+
+/// Copy every object under `prefix` from `source` to `dest`
+///
+/// Deploy manifests (one per `r2_prefix` sharing the bucket), when
+/// `update_manifest` asks for them, go last: a destination with a manifest
+/// then always holds everything it lists.
+pub(crate) async fn migrate_objects(
+    source: &S3Bucket,
+    dest: &S3Bucket,
+    prefix: &str,
+    update_manifest: bool,
+    dry_run: DryRun,
+) -> Result<MigrateReport> {
+    let mut keys: Vec<String> = source
+        .list(prefix.to_string(), None)
+        .await
+        .with_context(|| format!("Failed to list bucket {}", source.name()))?
+        .into_iter()
+        .flat_map(|page| page.contents.into_iter().map(|object| object.key))
+        .collect();
+    keys.sort();
+
+    let (manifests, keys): (Vec<String>, Vec<String>) =
+        keys.into_iter().partition(|key| is_manifest_key(key));
+    let has_manifest = !manifests.is_empty();
+
+    futures::stream::iter(&keys)
+        .map(|key| async move {
+            if !dry_run.allows(format_args!("copy {}", key)) {
+                return Ok(());
+            }
+            copy_object(source, dest, key).await?;
+            println!("      ✓ {}", key);
+            Ok::<(), anyhow::Error>(())
+        })
+        .buffer_unordered(MIGRATE_CONCURRENCY)
+        .try_collect::<Vec<()>>()
+        .await?;
+
+    let manifest_copied = has_manifest && update_manifest;
+    if manifest_copied {
+        for key in &manifests {
+            if dry_run.allows(format_args!("copy {}", key)) {
+                copy_object(source, dest, key).await?;
+            }
+        }
+    }
+
+    Ok(MigrateReport {
+        copied: keys,
+        manifest_copied,
+        manifest_skipped: has_manifest && !update_manifest,
+    })
+}
+
+/// Copy one object, keeping its content type and cache headers
+async fn copy_object(source: &S3Bucket, dest: &S3Bucket, key: &str) -> Result<()> {
+    let response = source
+        .get_object(key)
+        .await
+        .with_context(|| format!("Failed to download {}", key))?;
+    let headers = response.headers();
+    let content_type = headers
+        .get("content-type")
+        .map(String::as_str)
+        .unwrap_or("application/octet-stream");
+
+    let mut dest = dest.clone();
+    if let Some(cache_control) = headers.get("cache-control") {
+        dest.add_header("Cache-Control", cache_control);
+    }
+    dest.put_object_with_content_type(key, response.as_slice(), content_type)
+        .await
+        .with_context(|| format!("Failed to upload {} to {}", key, dest.name()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::deploy::{DEPLOY_MANIFEST_KEY, s3_bucket};
+    use super::*;
+    use s3::Region as S3Region;
+    use s3::creds::Credentials as S3Credentials;
+
+    /// Copy between two buckets on a real MinIO server, e.g.
+    /// `docker run -p 9000:9000 minio/minio server /data` then
+    /// `RELEASE_KIT_MINIO_ENDPOINT=http://localhost:9000 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs a MinIO server at RELEASE_KIT_MINIO_ENDPOINT"]
+    async fn test_migrate_objects_between_minio_buckets() {
+        let endpoint = std::env::var("RELEASE_KIT_MINIO_ENDPOINT")
+            .expect("RELEASE_KIT_MINIO_ENDPOINT must be set");
+        let user = std::env::var("MINIO_ROOT_USER").unwrap_or_else(|_| "minioadmin".to_string());
+        let password =
+            std::env::var("MINIO_ROOT_PASSWORD").unwrap_or_else(|_| "minioadmin".to_string());
+        let credentials =
+            S3Credentials::new(Some(&user), Some(&password), None, None, None).unwrap();
+        let region = S3Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint,
+        };
+
+        let mut buckets = Vec::new();
+        for role in ["from", "to"] {
+            let name = format!("release-kit-migrate-{}-{}", role, std::process::id());
+            S3Bucket::create_with_path_style(
+                &name,
+                region.clone(),
+                credentials.clone(),
+                s3::BucketConfiguration::default(),
+            )
+            .await
+            .unwrap();
+            buckets.push(s3_bucket(&name, region.clone(), credentials.clone()).unwrap());
+        }
+        let (source, dest) = (&buckets[0], &buckets[1]);
+
+        let mut cached = source.clone();
+        cached.add_header("Cache-Control", "public, max-age=86400");
+        cached
+            .put_object_with_content_type("audio/01-track.flac", b"flac", "audio/flac")
+            .await
+            .unwrap();
+        source
+            .put_object_with_content_type("audio/02-track.mp3", b"mp3", "audio/mpeg")
+            .await
+            .unwrap();
+        source
+            .put_object_with_content_type("index.html", b"<html></html>", "text/html")
+            .await
+            .unwrap();
+        source
+            .put_object_with_content_type(DEPLOY_MANIFEST_KEY, b"{}", "application/json")
+            .await
+            .unwrap();
+
+        // Audio only, manifest left behind
+        let report = migrate_objects(source, dest, "audio/", false, DryRun::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            report.copied,
+            vec!["audio/01-track.flac", "audio/02-track.mp3"]
+        );
+        assert!(!report.manifest_copied);
+
+        let object = dest.get_object("audio/01-track.flac").await.unwrap();
+        assert_eq!(object.as_slice(), b"flac");
+        let (head, _) = dest.head_object("audio/01-track.flac").await.unwrap();
+        assert_eq!(head.content_type.as_deref(), Some("audio/flac"));
+        assert_eq!(head.cache_control.as_deref(), Some("public, max-age=86400"));
+        let (head, _) = dest.head_object("audio/02-track.mp3").await.unwrap();
+        assert_eq!(head.content_type.as_deref(), Some("audio/mpeg"));
+        assert!(dest.head_object("index.html").await.is_err());
+        assert!(dest.head_object(DEPLOY_MANIFEST_KEY).await.is_err());
+
+        // Everything, manifest included
+        let report = migrate_objects(source, dest, "", true, DryRun::default())
+            .await
+            .unwrap();
+        assert_eq!(report.copied.len(), 3);
+        assert!(report.manifest_copied);
+        let (head, _) = dest.head_object(DEPLOY_MANIFEST_KEY).await.unwrap();
+        assert_eq!(head.content_type.as_deref(), Some("application/json"));
+
+        for bucket in &buckets {
+            for page in bucket.list(String::new(), None).await.unwrap() {
+                for object in page.contents {
+                    bucket.delete_object(&object.key).await.unwrap();
+                }
+            }
+            bucket.delete().await.unwrap();
+        }
+    }
+}
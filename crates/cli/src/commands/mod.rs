@@ -1,6 +1,16 @@
 pub mod build;
+pub mod build_index;
+pub mod bundle;
+pub mod check_links;
 pub mod deploy;
+pub mod diff;
+pub mod dry_run;
+pub mod feed;
 pub mod init;
+pub mod migrate_r2;
 pub mod preview;
+pub mod sync_durations;
 pub mod template;
+#[cfg(test)]
+mod test_support;
 pub mod validate;
@@ -1,18 +1,34 @@
 use anyhow::{Context, Result};
 use axum::{
     Router,
-    extract::State,
-    response::sse::{Event, KeepAlive, Sse},
+    extract::{OriginalUri, State},
+    handler::HandlerWithoutStateExt,
+    http::StatusCode,
+    response::{
+        Html,
+        sse::{Event, KeepAlive, Sse},
+    },
     routing::get,
 };
 use notify::{Event as NotifyEvent, EventKind, RecursiveMode, Watcher};
 use release_kit_core::config::parse_album_toml;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    io::{self, Write},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use tempfile::TempDir;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 use tower_http::services::ServeDir;
 
-use super::build::build_static_site;
+use super::build::{BuildOptions, build_static_site};
+use super::template::{html_escape, normalize_base_path};
+
+/// How long the watcher waits for a burst of events to settle before rebuilding
+///
+/// One editor save is often several events (write, rename, chmod).
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(300);
 
 #[derive(Clone)]
 struct AppState {
@@ -30,9 +46,28 @@ struct AppState {
 ///
 /// * `path` - Path to album directory containing album.toml
 /// * `port` - Port to serve on (default: 8080)
-pub async fn run(path: PathBuf, port: u16) -> Result<()> {
-    println!("🎵 Starting preview server...");
-    println!("   Album: {}", path.display());
+/// * `base_path` - Optional subdirectory to serve under (e.g., "/albums/foo"),
+///   matching a `build --base-path` deployment
+/// * `open` - Open the preview in the default browser once the server is listening
+/// * `print_url_only` - Print just the URL, once bound, and nothing else to
+///   stdout (for scripts capturing it); warnings and errors still go to stderr
+pub async fn run(
+    path: PathBuf,
+    port: u16,
+    base_path: Option<String>,
+    open: bool,
+    print_url_only: bool,
+) -> Result<()> {
+    let step = move |msg: String| {
+        if !print_url_only {
+            println!("{}", msg);
+        }
+    };
+
+    step("🎵 Starting preview server...".to_string());
+    step(format!("   Album: {}", path.display()));
+
+    let base_path = normalize_base_path(base_path.as_deref().unwrap_or(""));
 
     // Validate album directory exists
     if !path.exists() {
@@ -55,18 +90,25 @@ pub async fn run(path: PathBuf, port: u16) -> Result<()> {
 
     let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
 
-    println!("   ✓ Loaded: {}", album.metadata.title);
-    println!("   ✓ Artist: {}", album.metadata.artist);
-    println!("   ✓ Tracks: {}", album.tracks.len());
-    println!();
+    step(format!("   ✓ Loaded: {}", album.metadata.title));
+    step(format!("   ✓ Artist: {}", album.metadata.artist));
+    step(format!("   ✓ Tracks: {}\n", album.tracks.len()));
 
     // Create temporary build directory (auto-cleanup on drop)
     let _temp_dir = TempDir::new().context("Failed to create temporary directory")?;
     let build_dir = _temp_dir.path();
-    println!("📦 Building static site to temp directory...");
-    build_static_site(&path, build_dir, false, None)
-        .context("Failed to build static site for preview")?;
-    println!("   ✓ Built to: {}", build_dir.display());
+    step("📦 Building static site to temp directory...".to_string());
+    build_static_site(
+        &path,
+        build_dir,
+        &BuildOptions {
+            base_path: &base_path,
+            include_drafts: true,
+            ..Default::default()
+        },
+    )
+    .context("Failed to build static site for preview")?;
+    step(format!("   ✓ Built to: {}", build_dir.display()));
 
     // Create broadcast channel for reload events
     let (reload_tx, _) = broadcast::channel::<()>(100);
@@ -75,37 +117,48 @@ pub async fn run(path: PathBuf, port: u16) -> Result<()> {
         reload_tx: reload_tx.clone(),
     };
 
-    // Build router - serve built static files
-    let app = Router::new()
-        .route("/_reload", get(sse_handler))
-        .fallback_service(ServeDir::new(build_dir))
-        .with_state(state);
+    let app = build_router(build_dir, &base_path, state);
 
     // Start file watcher with rebuild on change
     let watcher_source = path.clone();
     let watcher_build = build_dir.to_path_buf();
+    let watcher_base = base_path.clone();
     let watcher_tx = reload_tx.clone();
     tokio::spawn(async move {
-        if let Err(e) = watch_and_rebuild(watcher_source, watcher_build, watcher_tx).await {
+        if let Err(e) = watch_and_rebuild(
+            watcher_source,
+            watcher_build,
+            watcher_base,
+            watcher_tx,
+            print_url_only,
+        )
+        .await
+        {
             eprintln!("File watcher error: {}", e);
         }
     });
 
     // Start server
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
-    println!("\n🚀 Preview ready at: http://localhost:{}", port);
-    println!("   Press Ctrl+C to stop\n");
-
     let listener = tokio::net::TcpListener::bind(addr)
         .await
         .context("Failed to bind to port")?;
 
+    // Bound already, so the URL is reachable by the time anyone reads it, and
+    // the browser's first request queues until the server accepts it rather
+    // than being refused
+    let url = preview_url(port, &base_path);
+    announce(&mut io::stdout(), &url, print_url_only)?;
+    if open {
+        open_browser(&url, print_url_only);
+    }
+
     // Set up graceful shutdown with Ctrl+C
-    let server = axum::serve(listener, app).with_graceful_shutdown(async {
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to listen for Ctrl+C");
-        println!("\n🛑 Shutting down preview server...");
+        step("\n🛑 Shutting down preview server...".to_string());
     });
 
     server.await.context("Server error")?;
@@ -114,13 +167,104 @@ pub async fn run(path: PathBuf, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// The URL the preview is served at
+fn preview_url(port: u16, base_path: &str) -> String {
+    format!("http://localhost:{}{}/", port, base_path)
+}
+
+/// Tell the user where the preview is, or with `url_only` print just the URL
+///
+/// The URL line is flushed straight away so a script reading it through a
+/// pipe isn't left waiting while the server runs.
+fn announce(out: &mut impl Write, url: &str, url_only: bool) -> io::Result<()> {
+    if url_only {
+        writeln!(out, "{}", url)?;
+    } else {
+        writeln!(out, "\n🚀 Preview ready at: {}", url)?;
+        writeln!(out, "   Press Ctrl+C to stop\n")?;
+    }
+    out.flush()
+}
+
+/// Open `url` in the default browser, or say why not (e.g. headless CI)
+///
+/// The browser is launched detached, so it doesn't wait on or receive the
+/// preview server's Ctrl+C. With `quiet`, success is silent and failure is
+/// reported on stderr.
+fn open_browser(url: &str, quiet: bool) {
+    match open::that_detached(url) {
+        Ok(()) if quiet => {}
+        Ok(()) => println!("   ✓ Opened in your browser\n"),
+        Err(e) if quiet => eprintln!("   ⚠ Could not open a browser ({})", e),
+        Err(e) => println!(
+            "   ⚠ Could not open a browser ({}); visit {} instead\n",
+            e, url
+        ),
+    }
+}
+
+/// Build the preview router serving the built site
+///
+/// With a non-empty `base_path` the site is nested under that prefix, so
+/// requests outside it 404 exactly as they would on a subdirectory deployment.
+/// Every 404 gets the `not_found` page rather than an empty body.
+fn build_router(build_dir: &Path, base_path: &str, state: AppState) -> Router {
+    let router = Router::new().route("/_reload", get(sse_handler));
+    let site = ServeDir::new(build_dir).not_found_service(not_found.into_service());
+
+    let router = if base_path.is_empty() {
+        router.fallback_service(site)
+    } else {
+        router.nest_service(base_path, site).fallback(not_found)
+    };
+
+    router.with_state(state)
+}
+
+/// 404 page naming the missing path
+///
+/// Build only warns about audio or artwork it can't find, so during authoring
+/// a 404 is usually a typo'd path in album.toml.
+async fn not_found(OriginalUri(uri): OriginalUri) -> (StatusCode, Html<String>) {
+    let path = html_escape(uri.path());
+    (
+        StatusCode::NOT_FOUND,
+        Html(format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>404 Not Found - release-kit preview</title>
+    <style>
+        body {{ font-family: system-ui, sans-serif; max-width: 40rem; margin: 4rem auto; padding: 0 1rem; line-height: 1.5; }}
+        code {{ background: #f0f0f0; padding: 0.1rem 0.3rem; border-radius: 3px; }}
+    </style>
+</head>
+<body>
+    <h1>404 Not Found</h1>
+    <p>Nothing was built at <code>{}</code>.</p>
+    <p>If a track, cover or image in <code>album.toml</code> points here, check that
+    the file exists at that path in the album directory: build skips missing files
+    with a warning instead of failing.</p>
+</body>
+</html>
+"#,
+            path
+        )),
+    )
+}
+
 /// Watch for file changes, rebuild, and trigger reload
+///
+/// With `quiet`, only failed rebuilds are reported (on stderr).
 async fn watch_and_rebuild(
     source_path: PathBuf,
     build_path: PathBuf,
+    base_path: String,
     reload_tx: broadcast::Sender<()>,
+    quiet: bool,
 ) -> Result<()> {
-    let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+    let (tx, mut rx) = mpsc::channel(100);
 
     let mut watcher =
         notify::recommended_watcher(move |res: Result<NotifyEvent, notify::Error>| {
@@ -132,32 +276,62 @@ async fn watch_and_rebuild(
     // Watch album directory recursively
     watcher.watch(&source_path, RecursiveMode::Recursive)?;
 
-    while let Some(event) = rx.recv().await {
-        match event.kind {
-            EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_) => {
-                // Filter out temporary files and hidden files
-                if event.paths.iter().any(|p| {
-                    let filename = p.file_name().unwrap_or_default().to_string_lossy();
-                    !filename.starts_with('.') && !filename.ends_with('~')
-                }) {
-                    println!("   📝 File changed, rebuilding...");
-
-                    // Rebuild the static site
-                    if let Err(e) = build_static_site(&source_path, &build_path, false, None) {
-                        eprintln!("   ❌ Build failed: {}", e);
-                    } else {
-                        println!("   ✓ Rebuilt, reloading browser...");
-                        let _ = reload_tx.send(());
-                    }
-                }
+    while next_change(&mut rx, DEBOUNCE_WINDOW).await {
+        if !quiet {
+            println!("   📝 File changed, rebuilding...");
+        }
+
+        // Rebuild the static site
+        if let Err(e) = build_static_site(
+            &source_path,
+            &build_path,
+            &BuildOptions {
+                base_path: &base_path,
+                include_drafts: true,
+                ..Default::default()
+            },
+        ) {
+            eprintln!("   ❌ Build failed: {}", e);
+        } else {
+            if !quiet {
+                println!("   ✓ Rebuilt, reloading browser...");
             }
-            _ => {}
+            let _ = reload_tx.send(());
         }
     }
 
     Ok(())
 }
 
+/// Wait for a change worth rebuilding for, then let the rest of its burst pass
+///
+/// Returns once `window` goes by without another event, so a burst becomes a
+/// single rebuild, or `false` once the watcher has gone away.
+async fn next_change(rx: &mut mpsc::Receiver<NotifyEvent>, window: Duration) -> bool {
+    loop {
+        match rx.recv().await {
+            Some(event) if is_relevant(&event) => break,
+            Some(_) => continue,
+            None => return false,
+        }
+    }
+
+    // Any event, relevant or not, means the burst is still going
+    while let Ok(Some(_)) = tokio::time::timeout(window, rx.recv()).await {}
+    true
+}
+
+/// Whether an event touches something other than temporary and hidden files
+fn is_relevant(event: &NotifyEvent) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|p| {
+        let filename = p.file_name().unwrap_or_default().to_string_lossy();
+        !filename.starts_with('.') && !filename.ends_with('~')
+    })
+}
+
 /// SSE endpoint for hot reload
 async fn sse_handler(
     State(state): State<AppState>,
@@ -174,3 +348,176 @@ async fn sse_handler(
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn test_state() -> AppState {
+        let (reload_tx, _) = broadcast::channel::<()>(1);
+        AppState { reload_tx }
+    }
+
+    async fn get_body(app: Router, uri: &str) -> (StatusCode, String) {
+        let response = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    async fn get_status(app: Router, uri: &str) -> StatusCode {
+        app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[test]
+    fn test_preview_url() {
+        assert_eq!(preview_url(8080, ""), "http://localhost:8080/");
+        assert_eq!(
+            preview_url(3000, "/albums/foo"),
+            "http://localhost:3000/albums/foo/"
+        );
+    }
+
+    #[test]
+    fn test_announce_url_only_prints_one_url_line() {
+        let url = preview_url(8080, "/albums/foo");
+
+        let mut out = Vec::new();
+        announce(&mut out, &url, true).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().collect::<Vec<_>>(), vec![url.as_str()]);
+        assert_eq!(out, "http://localhost:8080/albums/foo/\n");
+
+        let mut banner = Vec::new();
+        announce(&mut banner, &url, false).unwrap();
+        let banner = String::from_utf8(banner).unwrap();
+        assert!(banner.contains("Preview ready at: http://localhost:8080/albums/foo/"));
+        assert!(banner.lines().count() > 1);
+    }
+
+    fn modify_event(path: &str) -> NotifyEvent {
+        NotifyEvent::new(EventKind::Modify(notify::event::ModifyKind::Any))
+            .add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_is_relevant_skips_hidden_and_backup_files() {
+        assert!(is_relevant(&modify_event("/album/album.toml")));
+        assert!(!is_relevant(&modify_event("/album/.album.toml.swp")));
+        assert!(!is_relevant(&modify_event("/album/album.toml~")));
+        assert!(!is_relevant(
+            &NotifyEvent::new(EventKind::Access(notify::event::AccessKind::Any))
+                .add_path(PathBuf::from("/album/album.toml"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_next_change_coalesces_a_burst_into_one_rebuild() {
+        let (tx, mut rx) = mpsc::channel(100);
+        tokio::spawn(async move {
+            // One save: write, chmod, rename, plus an editor swap file
+            for path in [
+                "/album/album.toml",
+                "/album/.album.toml.swp",
+                "/album/album.toml",
+                "/album/album.toml",
+            ] {
+                tx.send(modify_event(path)).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        });
+
+        let mut rebuilds = 0;
+        while next_change(&mut rx, Duration::from_millis(50)).await {
+            rebuilds += 1;
+        }
+        assert_eq!(rebuilds, 1);
+    }
+
+    #[tokio::test]
+    async fn test_next_change_separates_bursts() {
+        let (tx, mut rx) = mpsc::channel(100);
+        tokio::spawn(async move {
+            for _ in 0..2 {
+                tx.send(modify_event("/album/album.toml")).await.unwrap();
+                tx.send(modify_event("/album/album.toml")).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+        });
+
+        let mut rebuilds = 0;
+        while next_change(&mut rx, Duration::from_millis(50)).await {
+            rebuilds += 1;
+        }
+        assert_eq!(rebuilds, 2);
+    }
+
+    #[tokio::test]
+    async fn test_router_serves_under_base_path() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+
+        let app = build_router(dir.path(), "/albums/foo", test_state());
+
+        assert_eq!(get_status(app.clone(), "/").await, StatusCode::NOT_FOUND);
+        assert_eq!(
+            get_status(app.clone(), "/index.html").await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get_status(app.clone(), "/albums/foo/").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            get_status(app, "/albums/foo/index.html").await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_router_missing_file_gets_404_page() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+
+        let (status, body) = get_body(
+            build_router(dir.path(), "", test_state()),
+            "/audio/missing.flac",
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("<h1>404 Not Found</h1>"));
+        assert!(body.contains("<code>/audio/missing.flac</code>"));
+        assert!(body.contains("album.toml"));
+
+        // Under a base path, the full requested path is shown, and paths
+        // outside the base path get the page too
+        let app = build_router(dir.path(), "/albums/foo", test_state());
+        let (status, body) = get_body(app.clone(), "/albums/foo/audio/missing.flac").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("<code>/albums/foo/audio/missing.flac</code>"));
+        let (status, body) = get_body(app, "/audio/missing.flac").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("<h1>404 Not Found</h1>"));
+    }
+
+    #[tokio::test]
+    async fn test_router_serves_at_root_without_base_path() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+
+        let app = build_router(dir.path(), "", test_state());
+
+        assert_eq!(get_status(app, "/").await, StatusCode::OK);
+    }
+}
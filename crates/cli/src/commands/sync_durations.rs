@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use release_kit_core::config::parse_album_toml;
+use release_kit_core::types::format_duration;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml_edit::{DocumentMut, value};
+
+use super::dry_run::DryRun;
+
+/// What happened to a single track's `duration` field
+#[derive(Debug, PartialEq)]
+enum DurationChange {
+    /// Duration was missing and has been added
+    Added(String),
+    /// Duration was present but wrong and has been corrected
+    Corrected { from: String, to: String },
+    /// Duration already matches the audio file
+    Unchanged,
+    /// Audio file could not be probed (missing or unreadable)
+    Skipped(String),
+}
+
+/// Probe every track and write its duration back into album.toml.
+///
+/// Adds `duration` where missing and corrects it where it disagrees with the
+/// audio file. The file is edited in place with `toml_edit`, so comments,
+/// ordering, and formatting are preserved.
+///
+/// # Arguments
+///
+/// * `path` - Path to album directory containing album.toml
+/// * `dry_run` - Report the changes without rewriting album.toml
+pub async fn run(path: PathBuf, dry_run: DryRun) -> Result<()> {
+    println!("⏱️  Syncing track durations in: {}\n", path.display());
+
+    let changes = sync_durations(&path, dry_run)?;
+
+    let album = parse_album_toml(path.join("album.toml")).context("Failed to parse album.toml")?;
+    let mut updated = 0;
+    for (i, (track, change)) in album.tracks.iter().zip(&changes).enumerate() {
+        let track_num = i + 1;
+        match change {
+            DurationChange::Added(d) => {
+                updated += 1;
+                println!("  + Track {} ({}): added {}", track_num, track.title, d);
+            }
+            DurationChange::Corrected { from, to } => {
+                updated += 1;
+                println!(
+                    "  ~ Track {} ({}): {} → {}",
+                    track_num, track.title, from, to
+                );
+            }
+            DurationChange::Unchanged => {
+                println!("  ✓ Track {} ({}): up to date", track_num, track.title);
+            }
+            DurationChange::Skipped(reason) => {
+                println!(
+                    "  ⚠ Track {} ({}): skipped - {}",
+                    track_num, track.title, reason
+                );
+            }
+        }
+    }
+
+    println!();
+    if updated > 0 && dry_run.is_enabled() {
+        println!(
+            "✅ Dry run complete - {} duration(s) would be updated in album.toml",
+            updated
+        );
+    } else if updated > 0 {
+        println!("✅ Updated {} duration(s) in album.toml", updated);
+    } else {
+        println!("✅ All durations already up to date");
+    }
+
+    Ok(())
+}
+
+/// Probe tracks and rewrite album.toml, returning one change per track
+fn sync_durations(path: &Path, dry_run: DryRun) -> Result<Vec<DurationChange>> {
+    let album_toml_path = path.join("album.toml");
+    if !album_toml_path.exists() {
+        anyhow::bail!(
+            "album.toml not found in {}\nRun 'release-kit init {}' first",
+            path.display(),
+            path.display()
+        );
+    }
+
+    // Parse with the real parser first so paths are validated before probing
+    let album = parse_album_toml(&album_toml_path).context("Failed to parse album.toml")?;
+
+    let content = fs::read_to_string(&album_toml_path).context("Failed to read album.toml")?;
+    let mut doc: DocumentMut = content.parse().context("Failed to parse album.toml")?;
+
+    let mut changes = Vec::with_capacity(album.tracks.len());
+    let mut dirty = false;
+
+    if let Some(tables) = doc
+        .get_mut("track")
+        .and_then(|t| t.as_array_of_tables_mut())
+    {
+        for (track, table) in album.tracks.iter().zip(tables.iter_mut()) {
+            let actual = match probe_duration(&path.join(&track.file)) {
+                Ok(d) => d,
+                Err(e) => {
+                    changes.push(DurationChange::Skipped(format!("{:#}", e)));
+                    continue;
+                }
+            };
+            let actual_str = format_duration(Some(actual));
+
+            let change = match track.duration {
+                None => DurationChange::Added(actual_str.clone()),
+                Some(configured) if configured.as_secs() != actual.as_secs() => {
+                    DurationChange::Corrected {
+                        from: format_duration(Some(configured)),
+                        to: actual_str.clone(),
+                    }
+                }
+                Some(_) => DurationChange::Unchanged,
+            };
+
+            if change != DurationChange::Unchanged {
+                // Keep any trailing comment (e.g. "# Auto-detected") on the value
+                match table.get_mut("duration").and_then(|i| i.as_value_mut()) {
+                    Some(existing) => {
+                        let decor = existing.decor().clone();
+                        *existing = actual_str.into();
+                        *existing.decor_mut() = decor;
+                    }
+                    None => {
+                        table.insert("duration", value(actual_str));
+                    }
+                }
+                dirty = true;
+            }
+
+            changes.push(change);
+        }
+    }
+
+    if dirty {
+        dry_run
+            .write(&album_toml_path, doc.to_string())
+            .context("Failed to write album.toml")?;
+    }
+
+    Ok(changes)
+}
+
+/// Read the playback duration of an audio file
+pub fn probe_duration(audio_path: &Path) -> Result<Duration> {
+    if !audio_path.exists() {
+        anyhow::bail!("audio file not found: {}", audio_path.display());
+    }
+
+    let tagged_file = Probe::open(audio_path)
+        .context("cannot open audio file")?
+        .read()
+        .context("not a valid audio file")?;
+
+    Ok(tagged_file.properties().duration())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{album_toml, write_wav};
+    use tempfile::TempDir;
+
+    /// A commented album.toml, to check the rewrite keeps comments
+    fn commented_album_toml() -> String {
+        format!(
+            "# My album{}",
+            album_toml(
+                r#"
+[[track]]
+file = "audio/01-missing-duration.wav"
+title = "Missing Duration"
+
+[[track]]
+file = "audio/02-wrong-duration.wav"
+title = "Wrong Duration"
+duration = "9:59"  # Auto-detected
+
+[[track]]
+file = "audio/03-correct-duration.wav"
+title = "Correct Duration"
+duration = "0:02"
+"#
+            )
+        )
+    }
+
+    #[test]
+    fn test_sync_durations_adds_and_corrects() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("audio")).unwrap();
+        write_wav(&dir.path().join("audio/01-missing-duration.wav"), 3);
+        write_wav(&dir.path().join("audio/02-wrong-duration.wav"), 5);
+        write_wav(&dir.path().join("audio/03-correct-duration.wav"), 2);
+        fs::write(dir.path().join("album.toml"), commented_album_toml()).unwrap();
+
+        let changes = sync_durations(dir.path(), DryRun::default()).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                DurationChange::Added("0:03".to_string()),
+                DurationChange::Corrected {
+                    from: "9:59".to_string(),
+                    to: "0:05".to_string()
+                },
+                DurationChange::Unchanged,
+            ]
+        );
+
+        let album = parse_album_toml(dir.path().join("album.toml")).unwrap();
+        assert_eq!(album.tracks[0].duration, Some(Duration::from_secs(3)));
+        assert_eq!(album.tracks[1].duration, Some(Duration::from_secs(5)));
+        assert_eq!(album.tracks[2].duration, Some(Duration::from_secs(2)));
+
+        // Comments and layout survive the rewrite
+        let content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
+        assert!(content.starts_with("# My album\n"));
+        assert!(content.contains(r#"duration = "0:05"  # Auto-detected"#));
+    }
+
+    #[test]
+    fn test_sync_durations_skips_missing_audio() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("album.toml"), commented_album_toml()).unwrap();
+
+        let changes = sync_durations(dir.path(), DryRun::default()).unwrap();
+        assert!(
+            changes
+                .iter()
+                .all(|c| matches!(c, DurationChange::Skipped(_)))
+        );
+
+        // Nothing probed, so the file is untouched
+        let content = fs::read_to_string(dir.path().join("album.toml")).unwrap();
+        assert_eq!(content, commented_album_toml());
+    }
+}
@@ -1,10 +1,12 @@
-use release_kit_core::types::Album;
+use release_kit_core::types::{
+    Album, LossyFormat, SiteBackground, SiteConfig, SiteLayout, Theme, Track,
+};
 use std::path::Path;
 
 /// HTML-escape a string to prevent XSS attacks
 ///
 /// Escapes: & < > " '
-fn html_escape(s: &str) -> String {
+pub(crate) fn html_escape(s: &str) -> String {
     s.chars()
         .map(|c| match c {
             '&' => "&amp;".to_string(),
@@ -17,49 +19,834 @@ fn html_escape(s: &str) -> String {
         .collect()
 }
 
-/// Detect cover art in artwork directory
-pub fn detect_cover_art(artwork_dir: &Path) -> Option<String> {
-    const COVER_ART_NAMES: &[&str] = &[
-        "cover.jpg",
-        "cover.png",
-        "cover.jpeg",
-        "artwork.jpg",
-        "artwork.png",
-        "folder.jpg",
-        "folder.png",
-        "album.jpg",
-        "album.png",
-    ];
+/// Cover art as the built site serves it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverImage {
+    /// Filename of the original cover in the artwork directory
+    pub file: String,
+    /// WebP version, served via `<picture>` with `file` as fallback
+    pub webp: Option<String>,
+    /// Intrinsic width and height, when the image could be read
+    pub dimensions: Option<(u32, u32)>,
+    /// Downscaled copies as (filename, width), smallest first, for `srcset`
+    pub variants: Vec<(String, u32)>,
+}
 
-    // Try standard names first
-    for name in COVER_ART_NAMES {
-        let path = artwork_dir.join(name);
-        if path.exists() {
-            return Some(name.to_string());
+impl CoverImage {
+    /// A cover known only by its filename
+    pub fn new(file: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            ..Default::default()
         }
     }
+}
 
-    // Fallback: find first image file
-    if let Ok(entries) = std::fs::read_dir(artwork_dir) {
-        for entry in entries.flatten() {
-            if let Some(ext) = entry.path().extension() {
-                let ext_lower = ext.to_string_lossy().to_lowercase();
-                if (ext_lower == "jpg" || ext_lower == "jpeg" || ext_lower == "png")
-                    && let Some(filename) = entry.file_name().to_str()
-                {
-                    return Some(filename.to_string());
+/// Build the `<img>` for a cover, wrapped in `<picture>` when a WebP version exists
+///
+/// `sizes` is the displayed width the browser picks a `srcset` candidate
+/// for; the srcset is only emitted when downscaled variants exist.
+fn cover_image_html(
+    cover: &CoverImage,
+    base_path: &str,
+    layout: SiteLayout,
+    class: &str,
+    sizes: &str,
+) -> String {
+    let url = |file: &str| {
+        format!(
+            "{}/{}",
+            base_path,
+            html_escape(&layout.asset_path("artwork", file))
+        )
+    };
+
+    let mut attrs = String::new();
+    if let Some((width, height)) = cover.dimensions {
+        if !cover.variants.is_empty() {
+            let candidates: Vec<String> = cover
+                .variants
+                .iter()
+                .map(|(file, w)| format!("{} {}w", url(file), w))
+                .chain(std::iter::once(format!("{} {}w", url(&cover.file), width)))
+                .collect();
+            attrs.push_str(&format!(
+                r#" srcset="{}" sizes="{}""#,
+                candidates.join(", "),
+                sizes
+            ));
+        }
+        attrs.push_str(&format!(r#" width="{}" height="{}""#, width, height));
+    }
+
+    let img = format!(
+        r#"<img src="{}"{} alt="Album cover" class="{}">"#,
+        url(&cover.file),
+        attrs,
+        class
+    );
+
+    match &cover.webp {
+        Some(webp) => format!(
+            r#"<picture><source srcset="{}/{}" type="image/webp">{}</picture>"#,
+            base_path,
+            html_escape(&layout.asset_path("artwork", webp)),
+            img
+        ),
+        None => img,
+    }
+}
+
+/// Liner notes markdown, read from the files album.toml names
+#[derive(Debug, Clone, Default)]
+pub struct LinerNotes {
+    /// `[album] liner_notes`
+    pub album: Option<String>,
+    /// Each track's `liner_notes`, in track order
+    pub tracks: Vec<Option<String>>,
+}
+
+impl LinerNotes {
+    /// Read the album and track notes, relative to the album directory
+    ///
+    /// Files that cannot be read are left out, with a warning for each.
+    pub fn read(dir: &Path, album: &Album) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut read = |notes: Option<&std::path::PathBuf>| {
+            let notes = notes?;
+            match std::fs::read_to_string(dir.join(notes)) {
+                Ok(markdown) => Some(markdown),
+                Err(e) => {
+                    warnings.push(format!(
+                        "Could not read liner notes {}: {}",
+                        notes.display(),
+                        e
+                    ));
+                    None
                 }
             }
+        };
+        let album_notes = read(album.metadata.liner_notes.as_ref());
+        let tracks = album
+            .tracks
+            .iter()
+            .map(|track| read(track.liner_notes.as_ref()))
+            .collect();
+        (
+            Self {
+                album: album_notes,
+                tracks,
+            },
+            warnings,
+        )
+    }
+
+    /// Notes for the track at `index`, if it has any
+    fn track(&self, index: usize) -> Option<&str> {
+        self.tracks.get(index).and_then(|notes| notes.as_deref())
+    }
+}
+
+/// Render user-written markdown (liner notes) to HTML that is safe to inline
+///
+/// Raw HTML in the source is dropped rather than passed through, so
+/// `<script>` blocks and `on*=` handlers never reach the page (along with
+/// the text between inline `<script>`/`<style>` tags), and links or images
+/// with a `javascript:`-style URL lose their destination.
+pub(crate) fn render_markdown(markdown: &str) -> String {
+    use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+
+    fn safe(url: CowStr<'_>) -> CowStr<'_> {
+        if is_safe_url(&url) {
+            url
+        } else {
+            CowStr::from("")
         }
     }
+    let events = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES,
+    )
+    .scan(false, |in_raw_text, event| {
+        let keep = match &event {
+            Event::InlineHtml(tag) => {
+                let tag = tag.to_ascii_lowercase();
+                if tag.starts_with("<script") || tag.starts_with("<style") {
+                    *in_raw_text = true;
+                } else if tag.starts_with("</script") || tag.starts_with("</style") {
+                    *in_raw_text = false;
+                }
+                false
+            }
+            Event::Html(_) => false,
+            _ => !*in_raw_text,
+        };
+        Some(keep.then_some(event))
+    })
+    .flatten()
+    .map(|event| match event {
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
 
-    None
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
 }
 
-/// Format duration for display
+/// Whether a markdown link target is relative or uses http(s)/mailto
+fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside a scheme
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    match url.split_once(':') {
+        Some((scheme, _))
+            if scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)) =>
+        {
+            ["http", "https", "mailto"].contains(&scheme.to_ascii_lowercase().as_str())
+        }
+        _ => true,
+    }
+}
+
+/// Format duration for display (M:SS, or H:MM:SS from an hour up)
 pub fn format_duration(duration: std::time::Duration) -> String {
-    let secs = duration.as_secs();
-    format!("{}:{:02}", secs / 60, secs % 60)
+    release_kit_core::types::format_duration(Some(duration))
+}
+
+/// Normalize a base path for URL prefixing
+///
+/// Returns `""` for root deployments, otherwise a path with a leading slash
+/// and no trailing slash (e.g. `albums/foo/` -> `/albums/foo`), so it can be
+/// prepended directly to absolute asset paths.
+pub fn normalize_base_path(base_path: &str) -> String {
+    let trimmed = base_path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+/// Absolute URL of the deployed site root, e.g. `https://album.example.com/albums/foo`
+///
+/// Built from `site.domain`; `base_path` must already be normalized.
+pub(crate) fn site_url(album: &Album, base_path: &str) -> String {
+    format!(
+        "https://{}{}",
+        album.site.domain.trim_end_matches('/'),
+        base_path
+    )
+}
+
+/// Open Graph and Twitter Card tags, so shared links unfurl with a preview
+///
+/// Link previews need absolute URLs, so the page and image URLs are built
+/// from `site.domain` and left out when no domain is set.
+fn social_meta_html(album: &Album, cover: Option<&CoverImage>, base_path: &str) -> String {
+    let title = html_escape(&album.metadata.title);
+    let description = html_escape(&album.metadata.summary);
+    let mut tags = vec![
+        ("property", "og:type", "music.album".to_string()),
+        ("property", "og:title", title.clone()),
+        ("property", "og:description", description.clone()),
+    ];
+
+    let has_domain = !album.site.domain.trim().is_empty();
+    let image_url = cover.filter(|_| has_domain).map(|cover| {
+        html_escape(&format!(
+            "{}/{}",
+            site_url(album, base_path),
+            album.site.layout.asset_path("artwork", &cover.file)
+        ))
+    });
+    if has_domain {
+        tags.push((
+            "property",
+            "og:url",
+            html_escape(&format!("{}/", site_url(album, base_path))),
+        ));
+    }
+    if let Some(image_url) = &image_url {
+        tags.push(("property", "og:image", image_url.clone()));
+        if let Some((width, height)) = cover.and_then(|c| c.dimensions) {
+            tags.push(("property", "og:image:width", width.to_string()));
+            tags.push(("property", "og:image:height", height.to_string()));
+        }
+    }
+
+    let card = if image_url.is_some() {
+        "summary_large_image"
+    } else {
+        "summary"
+    };
+    tags.push(("name", "twitter:card", card.to_string()));
+    tags.push(("name", "twitter:title", title));
+    tags.push(("name", "twitter:description", description));
+    if let Some(image_url) = image_url {
+        tags.push(("name", "twitter:image", image_url));
+    }
+    if let Some(url) = &album.artist.url {
+        tags.push(("property", "music:musician", html_escape(url)));
+    }
+    // Open Graph has no genre property; keywords carries `[album] genre`
+    let genres: Vec<&str> = album.metadata.genres().collect();
+    if !genres.is_empty() {
+        tags.push(("name", "keywords", html_escape(&genres.join(", "))));
+    }
+
+    tags.iter()
+        .map(|(attr, key, content)| {
+            format!("\n    <meta {}=\"{}\" content=\"{}\">", attr, key, content)
+        })
+        .collect()
+}
+
+/// Render the "Downloads" section: the album zip plus one row of format links
+/// per track, or "" when downloads are disabled
+///
+/// Each configured format links to the track's original audio when its
+/// extension matches, or to the lossy variant `build --transcode` made
+/// (`stream_file` is set), and is left out otherwise. Price settings only
+/// change the text shown; nothing is charged.
+fn downloads_html(album: &Album, audio_base_url: Option<&str>, base_path: &str) -> String {
+    let distribution = &album.distribution;
+    if !distribution.download_enabled {
+        return String::new();
+    }
+    let layout = album.site.layout;
+
+    let price_text = match (
+        distribution.download_price.filter(|p| *p > 0.0),
+        distribution.pay_what_you_want,
+    ) {
+        (Some(price), true) => format!("Name your price (suggested ${:.2})", price),
+        (None, true) => "Name your price".to_string(),
+        (Some(price), false) => format!("${:.2}", price),
+        (None, false) => "Free download".to_string(),
+    };
+
+    let track_rows: String = album
+        .tracks
+        .iter()
+        .zip(album.audio_file_names())
+        .map(|(track, filename)| {
+            let source_ext = Path::new(&filename)
+                .extension()
+                .map(|e| e.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_default();
+            let links: String = distribution
+                .download_formats
+                .iter()
+                .filter_map(|format| {
+                    let codec = format.split('-').next().unwrap_or_default();
+                    let file = if codec.eq_ignore_ascii_case(&source_ext) {
+                        filename.clone()
+                    } else if let (Some(lossy), Some(_)) =
+                        (LossyFormat::parse(format), &track.stream_file)
+                    {
+                        Path::new(&filename)
+                            .with_extension(lossy.extension())
+                            .to_string_lossy()
+                            .replace('\\', "/")
+                    } else {
+                        return None;
+                    };
+                    // Transcodes only exist in the local build; originals follow the CDN
+                    let url = match audio_base_url {
+                        Some(base_url) if file == filename => {
+                            format!("{}/{}", base_url, html_escape(&album.audio_key(&file)))
+                        }
+                        _ => format!(
+                            "{}/{}",
+                            base_path,
+                            html_escape(&layout.asset_path("audio", &file))
+                        ),
+                    };
+                    Some(format!(
+                        r#" <a href="{}" download>{}</a>"#,
+                        url,
+                        html_escape(&format.to_uppercase())
+                    ))
+                })
+                .collect();
+            format!(
+                r#"
+                <li class="download-track"><span class="download-track-title">{}</span>{}</li>"#,
+                html_escape(&track.title),
+                links
+            )
+        })
+        .collect();
+
+    format!(
+        r#"
+        <div class="downloads">
+            <h2>Downloads</h2>
+            <div class="download-price">{}</div>
+            <a class="download-album" href="{}/{}" download>Full album (.zip)</a>
+            <ul class="download-tracks">{}
+            </ul>
+        </div>
+"#,
+        html_escape(&price_text),
+        base_path,
+        html_escape(&album.download_zip_path()),
+        track_rows
+    )
+}
+
+/// Render the `[artwork] gallery` section, or "" without images
+///
+/// Each thumbnail links to a full-size overlay shown with CSS `:target`, so the
+/// lightbox needs no script; clicking the overlay closes it. Images are copied
+/// into the site's `gallery` directory by the build.
+fn gallery_html(album: &Album, base_path: &str) -> String {
+    let names = album.artwork.gallery_file_names();
+    if names.is_empty() {
+        return String::new();
+    }
+    let layout = album.site.layout;
+
+    let mut thumbnails = String::new();
+    let mut overlays = String::new();
+    for (i, name) in names.iter().enumerate() {
+        let src = format!(
+            "{}/{}",
+            base_path,
+            html_escape(&layout.asset_path("gallery", name))
+        );
+        let alt = html_escape(&format!(
+            "{} - image {} of {}",
+            album.metadata.title,
+            i + 1,
+            names.len()
+        ));
+        thumbnails.push_str(&format!(
+            r##"
+                <li class="gallery-item"><a href="#gallery-{n}"><img src="{src}" alt="{alt}" loading="lazy"></a></li>"##,
+            n = i + 1,
+        ));
+        overlays.push_str(&format!(
+            r##"
+            <a class="lightbox" id="gallery-{n}" href="#gallery"><img src="{src}" alt="{alt}" loading="lazy"></a>"##,
+            n = i + 1,
+        ));
+    }
+
+    format!(
+        r#"
+        <div class="gallery" id="gallery">
+            <h2>Gallery</h2>
+            <ul class="gallery-grid">{}
+            </ul>{}
+        </div>
+"#,
+        thumbnails, overlays
+    )
+}
+
+/// A track's `chapters` as a list of seek buttons below its row, or "" without chapters
+///
+/// `data-track` ties the list to its row's `data-index`; `data-duration`
+/// (when known) lets the player place the chapters on the progress bar.
+fn chapters_html(index: usize, track: &Track) -> String {
+    if track.chapters.is_empty() {
+        return String::new();
+    }
+    let items: String = track
+        .chapters
+        .iter()
+        .map(|chapter| {
+            format!(
+                r#"
+                    <li><button type="button" class="chapter" data-start="{}"><span class="chapter-time">{}</span> {}</button></li>"#,
+                chapter.start.as_secs(),
+                format_duration(chapter.start),
+                html_escape(&chapter.name)
+            )
+        })
+        .collect();
+    let duration_attr = track
+        .duration
+        .map(|duration| format!(r#" data-duration="{}""#, duration.as_secs()))
+        .unwrap_or_default();
+    format!(
+        r#"
+                <ol class="track-chapters" data-track="{}"{} aria-label="Chapters of {}">{}
+                </ol>"#,
+        index,
+        duration_attr,
+        html_escape(&track.title),
+        items
+    )
+}
+
+/// `[album] genre` as a row of tag pills for the album header, or "" without genres
+fn genres_html(album: &Album) -> String {
+    let pills: String = album
+        .metadata
+        .genres()
+        .map(|genre| format!(r#"<li class="genre">{}</li>"#, html_escape(genre)))
+        .collect();
+    if pills.is_empty() {
+        return String::new();
+    }
+    format!(
+        r#"
+                <ul class="genres" aria-label="Genres">{}</ul>"#,
+        pills
+    )
+}
+
+/// Render the `[merch]` grid shown below the tracklist, or "" without items
+///
+/// Each item links to its store page; images were copied into the site's
+/// `merch` directory by the build.
+fn merch_html(album: &Album, base_path: &str) -> String {
+    if album.merch.items.is_empty() {
+        return String::new();
+    }
+    let layout = album.site.layout;
+
+    let items: String = album
+        .merch
+        .items
+        .iter()
+        .map(|item| {
+            let image = item
+                .image_file_name()
+                .map(|file| {
+                    format!(
+                        r#"<img src="{}/{}" alt="{}" loading="lazy">"#,
+                        base_path,
+                        html_escape(&layout.asset_path("merch", &file)),
+                        html_escape(&item.name)
+                    )
+                })
+                .unwrap_or_default();
+            let price = item
+                .price
+                .as_ref()
+                .map(|price| format!(r#"<span class="merch-price">{}</span>"#, html_escape(price)))
+                .unwrap_or_default();
+            format!(
+                r#"
+                <li class="merch-item"><a href="{}">{}<span class="merch-name">{}</span>{}</a></li>"#,
+                html_escape(&item.url),
+                image,
+                html_escape(&item.name),
+                price
+            )
+        })
+        .collect();
+
+    format!(
+        r#"
+        <div class="merch">
+            <h2>Merch</h2>
+            <ul class="merch-grid">{}
+            </ul>
+        </div>
+"#,
+        items
+    )
+}
+
+/// Map a `[site.verification]` provider key to its `<meta name>`
+///
+/// Known providers get their documented tag names; any other key is used
+/// verbatim so new providers work without a release-kit update.
+fn verification_meta_name(provider: &str) -> &str {
+    match provider {
+        "google" => "google-site-verification",
+        "bing" => "msvalidate.01",
+        "yandex" => "yandex-verification",
+        "pinterest" => "p:domain_verify",
+        "facebook" => "facebook-domain-verification",
+        other => other,
+    }
+}
+
+/// Parse a `#rgb`, `#rrggbb` or `#rrggbbaa` color; anything else (named colors, CSS
+/// fragments like `red; }`) is refused so it never reaches the stylesheet
+fn parse_hex_color(color: &str) -> Option<[u8; 3]> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        3 => {
+            let mut rgb = [0; 3];
+            for (i, c) in hex.chars().enumerate() {
+                rgb[i] = channel(&c.to_string())? * 17;
+            }
+            Some(rgb)
+        }
+        // #rrggbbaa: the alpha is dropped, the page sets its own opacities
+        6 | 8 => Some([
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ]),
+        _ => None,
+    }
+}
+
+/// Problems with `[site] theme` / `accent_color` that make the page fall back
+/// to the default theme's colors
+pub(crate) fn theme_warnings(site: &SiteConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if Theme::from_name(&site.theme).is_none() {
+        warnings.push(format!(
+            "Unknown theme '{}' - using default (themes: default, light, minimal, high-contrast)",
+            site.theme
+        ));
+    }
+    if !site.accent_color.trim().is_empty() && parse_hex_color(&site.accent_color).is_none() {
+        warnings.push(format!(
+            "accent_color '{}' is not a #rgb, #rrggbb or #rrggbbaa color - using the theme's",
+            site.accent_color
+        ));
+    }
+    warnings
+}
+
+/// `:root` color variables for `[site] theme`, with `accent_color` (when a
+/// valid hex color) replacing the theme's primary
+fn theme_css(site: &SiteConfig) -> String {
+    let theme = Theme::from_name(&site.theme).unwrap_or_default();
+    // primary, base-100, base-200, base-300, base-content, secondary, neutral
+    let (primary, base_100, base_200, base_300, content, secondary, neutral) = match theme {
+        Theme::Default => (
+            [0x00, 0xff, 0x88],
+            "#1a1a1f",
+            "#222228",
+            "#2a2a30",
+            "#e0e0e0",
+            "#4a4a5e",
+            "#2a2a3e",
+        ),
+        Theme::Light => (
+            [0x00, 0x8f, 0x5a],
+            "#f4f4f0",
+            "#ffffff",
+            "#e6e6e0",
+            "#1e1e24",
+            "#8a8a9e",
+            "#d8d8de",
+        ),
+        Theme::Minimal => (
+            [0xf0, 0xf0, 0xf0],
+            "#111111",
+            "#161616",
+            "#1f1f1f",
+            "#c8c8c8",
+            "#555555",
+            "#222222",
+        ),
+        Theme::HighContrast => (
+            [0xff, 0xee, 0x00],
+            "#000000",
+            "#000000",
+            "#111111",
+            "#ffffff",
+            "#ffffff",
+            "#000000",
+        ),
+    };
+    let primary = parse_hex_color(&site.accent_color).unwrap_or(primary);
+    let shade = |factor: u16| {
+        let [r, g, b] = primary.map(|c| (c as u16 * factor / 100) as u8);
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    };
+    let [r, g, b] = primary;
+
+    format!(
+        r#":root {{
+            --primary: #{:02x}{:02x}{:02x};
+            --primary-focus: {};
+            --primary-deep: {};
+            --primary-rgb: {}, {}, {};
+            --base-100: {};
+            --base-200: {};
+            --base-300: {};
+            --base-content: {};
+            --secondary: {};
+            --neutral: {};
+        }}"#,
+        r,
+        g,
+        b,
+        shade(80),
+        shade(67),
+        r,
+        g,
+        b,
+        base_100,
+        base_200,
+        base_300,
+        content,
+        secondary,
+        neutral
+    )
+}
+
+/// Footer "Credits" section for `[credits]`, or empty when there are none
+fn credits_html(album: &Album) -> String {
+    if album.credits.is_empty() {
+        return String::new();
+    }
+    let entries: String = album
+        .credits
+        .iter()
+        .map(|(role, names)| {
+            let names: Vec<String> = names.iter().map(|n| html_escape(n)).collect();
+            format!(
+                "<dt>{}</dt><dd>{}</dd>",
+                html_escape(&credit_role_label(role)),
+                names.join(", ")
+            )
+        })
+        .collect();
+    format!(
+        r#"<section class="footer-credits"><h3>Credits</h3><dl>{}</dl></section>
+            "#,
+        entries
+    )
+}
+
+/// Display label for a `[credits]` role key: `art_by` -> "Art by"
+fn credit_role_label(role: &str) -> String {
+    let role = role.trim().replace(['_', '-'], " ");
+    let mut chars = role.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => role,
+    }
+}
+
+/// `body` background declarations for `[site] background`
+fn background_css(background: SiteBackground) -> &'static str {
+    match background {
+        SiteBackground::Solid => "background-color: var(--base-100);",
+        SiteBackground::Gradient => {
+            "background: linear-gradient(160deg, var(--base-200) 0%, var(--base-100) 60%) fixed;"
+        }
+        SiteBackground::Pattern => {
+            r#"background-color: var(--base-100);
+            background-image:
+                repeating-linear-gradient(
+                    45deg,
+                    transparent,
+                    transparent 35px,
+                    rgba(255, 255, 255, 0.01) 35px,
+                    rgba(255, 255, 255, 0.01) 70px
+                );"#
+        }
+    }
+}
+
+/// Glow rules for headings and the progress bar, or nothing with `[site] glow = false`
+fn glow_css(glow: bool) -> &'static str {
+    if !glow {
+        return "";
+    }
+    r#"
+        h1 {
+            text-shadow: 0 0 20px var(--primary);
+        }
+
+        .tracks h2 {
+            text-shadow: 0 0 10px var(--primary);
+        }
+
+        .progress-fill {
+            box-shadow: 0 0 10px var(--primary);
+        }
+"#
+}
+
+/// Player keyboard shortcuts as (keys, action), listed in the help overlay
+///
+/// Keys are HTML; keep in sync with the keydown handler in `generate_player_js`.
+/// `{seconds}` is replaced with `[site] seek_seconds`.
+const KEYBOARD_SHORTCUTS: &[(&str, &str)] = &[
+    ("<kbd>Space</kbd>", "Play / pause"),
+    ("<kbd>←</kbd> <kbd>→</kbd>", "Previous / next track"),
+    (
+        "<kbd>J</kbd> <kbd>L</kbd> or <kbd>,</kbd> <kbd>.</kbd>",
+        "Seek back / forward {seconds}s",
+    ),
+    ("<kbd>↑</kbd> <kbd>↓</kbd>", "Volume up / down"),
+    ("Click the progress bar", "Seek"),
+    ("<kbd>?</kbd>", "Show / hide shortcuts"),
+    ("<kbd>Esc</kbd>", "Close this help"),
+];
+
+/// The "?" player button and the shortcut help dialog it opens
+///
+/// Empty strings when `[site] keyboard_help = false`; the player script
+/// skips the help wiring when the dialog is absent.
+fn keyboard_help_html(enabled: bool, seek_seconds: u32) -> (&'static str, String) {
+    if !enabled {
+        return ("", String::new());
+    }
+
+    let button = r#"
+                        <button class="player-btn help" id="help-btn" aria-label="Keyboard shortcuts" aria-haspopup="dialog" aria-controls="shortcut-help">?</button>"#;
+
+    let rows: String = KEYBOARD_SHORTCUTS
+        .iter()
+        .map(|(keys, action)| {
+            format!(
+                "\n                <dt>{}</dt><dd>{}</dd>",
+                keys,
+                action.replace("{seconds}", &seek_seconds.to_string())
+            )
+        })
+        .collect();
+    let overlay = format!(
+        r#"
+    <div class="shortcut-help" id="shortcut-help" role="dialog" aria-modal="true" aria-labelledby="shortcut-help-title" hidden>
+        <div class="shortcut-help-panel">
+            <h2 id="shortcut-help-title">Keyboard shortcuts</h2>
+            <dl class="shortcut-list">{}
+            </dl>
+            <button class="shortcut-help-close" id="shortcut-help-close">Close</button>
+        </div>
+    </div>
+"#,
+        rows
+    );
+
+    (button, overlay)
 }
 
 /// Generate the complete HTML for the album player page
@@ -70,80 +857,254 @@ pub fn format_duration(duration: std::time::Duration) -> String {
 /// # Arguments
 ///
 /// * `album` - Album configuration
-/// * `cover_art` - Optional cover art filename
+/// * `cover` - Optional cover art, with its WebP version, dimensions and downscaled variants
+/// * `notes` - Optional liner notes markdown, rendered into the page
 /// * `is_preview` - Whether this is for preview mode (adds SSE reload)
 /// * `audio_base_url` - Optional CDN base URL for audio files (e.g., "https://cdn.example.com")
+/// * `base_path` - Path prefix when deployed under a subdirectory (e.g., "/albums/foo"), or ""
 pub fn generate_html(
     album: &Album,
-    cover_art: Option<&str>,
+    cover: Option<&CoverImage>,
+    notes: Option<&LinerNotes>,
     is_preview: bool,
     audio_base_url: Option<&str>,
+    base_path: &str,
 ) -> String {
+    let base_path = normalize_base_path(base_path);
+    // Deployed full tracks stream through the signing worker, when configured
+    let stream_worker = audio_base_url
+        .and(album.hosting.cloudflare.stream_worker_url.as_deref())
+        .map(|url| html_escape(url.trim_end_matches('/')));
+
     // Generate track list HTML with data attributes for player
-    let tracks_html: String = album
+    let default_track = album.default_track_index();
+    let track_rows: Vec<String> = album
         .tracks
         .iter()
+        .zip(album.audio_file_names())
+        .zip(album.track_numbers())
         .enumerate()
-        .map(|(i, track)| {
+        .map(|(i, ((track, filename), number))| {
             let duration = track
                 .duration
                 .map(format_duration)
                 .unwrap_or_else(|| String::from("--:--"));
 
-            let filename = track
-                .file
-                .file_name()
-                .and_then(|f| f.to_str())
-                .unwrap_or("");
-
             // HTML-escape all user-provided strings to prevent XSS
-            let escaped_filename = html_escape(filename);
             let escaped_title = html_escape(&track.title);
+            // The player gets the [distribution] stream_format copy when there is one
+            let streamed = track.stream_copy.as_deref().unwrap_or(&filename);
+            let escaped_streamed = html_escape(streamed);
+
+            // Construct audio URL: use CDN if provided, otherwise the local build
+            // Paid downloads stream the free preview clip (always built locally)
+            let layout = album.site.layout;
+            let streams_preview = track.preview_clip.is_some() && album.distribution.is_paid();
+            let audio_url = if streams_preview {
+                format!(
+                    "{}/{}",
+                    base_path,
+                    html_escape(&layout.asset_path("audio", &track.preview_file_name()))
+                )
+            } else if let Some(worker) = &stream_worker {
+                // Unplayable without a signature; the player fetches one from data-sign
+                format!("{}/stream/{}", worker, escaped_streamed)
+            } else if let Some(base_url) = audio_base_url {
+                // CDN keys are `audio/<name>` (under any r2_prefix), whatever the site layout
+                format!("{}/{}", base_url, html_escape(&album.audio_key(streamed)))
+            } else {
+                // Stream the stream copy, else the lossy transcode, when the build made one
+                let streamed = track
+                    .stream_copy
+                    .as_deref()
+                    .or(track.stream_file.as_deref())
+                    .unwrap_or(&filename);
+                format!(
+                    "{}/{}",
+                    base_path,
+                    html_escape(&layout.asset_path("audio", streamed))
+                )
+            };
+
+            let explicit_badge = if track.explicit {
+                r#" <span class="explicit-badge" title="Explicit" aria-label="Explicit">E</span>"#
+            } else {
+                ""
+            };
+
+            // Tempo and key, for DJs and producers
+            let details: Vec<String> = track
+                .bpm
+                .map(|bpm| format!("{} BPM", bpm))
+                .into_iter()
+                .chain(track.key.as_deref().map(html_escape))
+                .collect();
+            let details_html = if details.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    r#" <span class="track-meta">{}</span>"#,
+                    details.join(" · ")
+                )
+            };
+
+            // Compilations and features name the track's own artist
+            let (artist_attr, artist_html) = match &track.artist {
+                Some(artist) => {
+                    let artist = html_escape(artist);
+                    (
+                        format!(r#" data-artist="{}""#, artist),
+                        format!(r#"<span class="track-artist">{}</span>"#, artist),
+                    )
+                }
+                None => (String::new(), String::new()),
+            };
 
-            // Construct audio URL: use CDN if provided, otherwise local /audio/
-            let audio_url = if let Some(base_url) = audio_base_url {
-                format!("{}/audio/{}", base_url, escaped_filename)
+            let credits: Vec<String> = track
+                .credits()
+                .into_iter()
+                .map(|(role, names)| {
+                    let names: Vec<String> = names.iter().map(|n| html_escape(n)).collect();
+                    format!("{} {}", role, names.join(", "))
+                })
+                .collect();
+            let credits_html = if credits.is_empty() {
+                String::new()
             } else {
-                format!("/audio/{}", escaped_filename)
+                format!(
+                    r#"<span class="track-credits">{}</span>"#,
+                    credits.join(" · ")
+                )
             };
 
+            let sign_attr = match &stream_worker {
+                Some(worker) if !streams_preview => {
+                    format!(r#" data-sign="{}/sign/{}""#, worker, escaped_streamed)
+                }
+                _ => String::new(),
+            };
+
+            // Outside the row too; the player seeks when a chapter is clicked
+            let chapters_html = chapters_html(i, track);
+
+            // Outside the row, so opening the panel doesn't start playback
+            let notes_html = notes
+                .and_then(|notes| notes.track(i))
+                .filter(|markdown| !markdown.trim().is_empty())
+                .map(|markdown| {
+                    format!(
+                        r#"
+                <details class="track-notes"><summary>Notes</summary><div class="notes-body">{}</div></details>"#,
+                        render_markdown(markdown)
+                    )
+                })
+                .unwrap_or_default();
+
+            let selected_class = if default_track == Some(i) {
+                " selected"
+            } else {
+                ""
+            };
+
+            // One digit per bar, e.g. data-peaks="0358...", for the progress bar
+            let peaks_attr = track
+                .peaks
+                .as_ref()
+                .filter(|_| album.site.peak_bars)
+                .map(|peaks| {
+                    let digits: String = peaks.iter().map(|level| level.min(&9).to_string()).collect();
+                    format!(r#" data-peaks="{}""#, digits)
+                })
+                .unwrap_or_default();
+
             format!(
-                r#"<div class="track" data-index="{}" data-src="{}" data-title="{}">
+                r#"<div class="track{}" data-index="{}" data-src="{}"{}{} data-title="{}"{} role="button" tabindex="0">
                     <span class="track-number">{:02}</span>
-                    <span class="track-title">{}</span>
+                    <span class="track-title">{}{}{}{}{}</span>
                     <span class="track-duration">{}</span>
-                </div>"#,
+                </div>{}{}"#,
+                selected_class,
                 i,
                 audio_url,
+                sign_attr,
+                peaks_attr,
                 escaped_title,
-                i + 1,
+                artist_attr,
+                number,
                 escaped_title,
-                duration
+                explicit_badge,
+                details_html,
+                artist_html,
+                credits_html,
+                duration,
+                chapters_html,
+                notes_html
             )
         })
         .collect();
 
-    // Generate cover art HTML if it exists (with HTML escaping)
-    let cover_art_html = if let Some(cover_filename) = cover_art {
-        let escaped_cover = html_escape(cover_filename);
-        format!(
-            r#"<img src="/artwork/{}" alt="Album cover" class="cover-art">"#,
-            escaped_cover
-        )
+    // [site] track_sort reorders the rows only; each keeps its data-index and
+    // data-src, so a row still plays its own track
+    let display_order = album.track_display_order();
+
+    // Multi-disc albums get a heading before each disc's tracks; the rows stay
+    // siblings, so the player still walks one flat list across discs
+    let tracks_html = if album.is_multi_disc() {
+        let mut html = String::new();
+        let mut current_disc = None;
+        for &i in &display_order {
+            let disc = album.tracks[i].disc_number();
+            if current_disc != Some(disc) {
+                html.push_str(&format!(r#"<h3 class="disc-heading">Disc {}</h3>"#, disc));
+                current_disc = Some(disc);
+            }
+            html.push_str(&track_rows[i]);
+        }
+        html
     } else {
-        String::new()
+        display_order
+            .iter()
+            .map(|&i| track_rows[i].as_str())
+            .collect()
     };
 
+    let genres_html = genres_html(album);
+    let downloads_html = downloads_html(album, audio_base_url, &base_path);
+    let gallery_html = gallery_html(album, &base_path);
+    let merch_html = merch_html(album, &base_path);
+    let liner_notes_html = notes
+        .and_then(|notes| notes.album.as_deref())
+        .filter(|markdown| !markdown.trim().is_empty())
+        .map(|markdown| {
+            format!(
+                r#"
+        <details class="liner-notes">
+            <summary>Liner Notes</summary>
+            <div class="notes-body">{}</div>
+        </details>
+"#,
+                render_markdown(markdown)
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate cover art HTML if it exists (with HTML escaping)
+    let cover_art_html = cover
+        .map(|cover| cover_image_html(cover, &base_path, album.site.layout, "cover-art", "300px"))
+        .unwrap_or_default();
+
     // Generate player album art HTML (smaller version, with HTML escaping)
-    let player_art_html = if let Some(cover_filename) = cover_art {
-        let escaped_cover = html_escape(cover_filename);
-        format!(
-            r#"<img src="/artwork/{}" alt="Album cover" class="player-album-art">"#,
-            escaped_cover
-        )
-    } else {
-        String::new()
-    };
+    let player_art_html = cover
+        .map(|cover| {
+            cover_image_html(
+                cover,
+                &base_path,
+                album.site.layout,
+                "player-album-art",
+                "96px",
+            )
+        })
+        .unwrap_or_default();
 
     // Preview badge only shown in preview mode
     let preview_badge = if is_preview {
@@ -170,13 +1131,100 @@ pub fn generate_html(
         ""
     };
 
-    // Footer text differs between preview and build
-    let footer_text = if is_preview {
+    // Attribution text differs between preview and build
+    let attribution = if is_preview {
         "Generated by release-kit • Press Ctrl+C to stop preview"
     } else {
         "Generated by release-kit"
     };
 
+    // Footer: copyright, optional custom links, then the release-kit attribution
+    let footer_links_html = if album.site.footer.links.is_empty() {
+        String::new()
+    } else {
+        let links: Vec<String> = album
+            .site
+            .footer
+            .links
+            .iter()
+            .map(|link| {
+                format!(
+                    r#"<a href="{}">{}</a>"#,
+                    html_escape(&link.url),
+                    html_escape(&link.label)
+                )
+            })
+            .collect();
+        format!(r#"<nav class="footer-links">{}</nav>"#, links.join(" • "))
+    };
+    let footer_html = format!(
+        r#"{}<div class="footer-copyright">{}</div>
+            {}
+            <div class="footer-attribution">{}</div>"#,
+        credits_html(album),
+        html_escape(&album.copyright()),
+        footer_links_html,
+        attribution
+    );
+
+    // Site ownership verification tags (Search Console, Bing Webmaster, etc.)
+    let verification_meta: String = album
+        .site
+        .verification
+        .iter()
+        .map(|(provider, token)| {
+            format!(
+                "\n    <meta name=\"{}\" content=\"{}\">",
+                html_escape(verification_meta_name(provider)),
+                html_escape(token)
+            )
+        })
+        .collect();
+
+    let social_meta = social_meta_html(album, cover, &base_path);
+
+    let robots_meta = if album.site.allow_indexing {
+        ""
+    } else {
+        "\n    <meta name=\"robots\" content=\"noindex\">"
+    };
+
+    let theme_css = theme_css(&album.site);
+    let background_css = background_css(album.site.background);
+    let glow_css = glow_css(album.site.glow);
+    let preload = album.distribution.preload.as_str();
+    // [distribution] default_track is named in the player before anything plays
+    let player_track_title = default_track
+        .map(|i| html_escape(&album.tracks[i].title))
+        .unwrap_or_else(|| "Select a track".to_string());
+    let player_artist = default_track
+        .and_then(|i| album.tracks[i].artist.as_deref())
+        .map(html_escape)
+        .unwrap_or_else(|| html_escape(&album.metadata.artist));
+    // The player marks the current track's chapters here
+    let chapter_markers_html = if album.tracks.iter().any(|t| !t.chapters.is_empty()) {
+        "\n                <div class=\"chapter-markers\" id=\"chapter-markers\"></div>"
+    } else {
+        ""
+    };
+    // [site] peak_bars: the player draws the current track's data-peaks here
+    let peaks_html = if album.site.peak_bars {
+        "\n                <div class=\"progress-peaks\" id=\"progress-peaks\" aria-hidden=\"true\"></div>"
+    } else {
+        ""
+    };
+    // Gapless playback buffers the next track in a second, spare element
+    let (gapless_attr, spare_audio_html) = if album.distribution.gapless {
+        (
+            r#" data-gapless="true""#,
+            "\n    <audio id=\"audio-next\" preload=\"auto\"></audio>",
+        )
+    } else {
+        ("", "")
+    };
+    let (help_button_html, help_overlay_html) =
+        keyboard_help_html(album.site.keyboard_help, album.site.seek_seconds);
+
     // HTML-escape all album metadata to prevent XSS
     let escaped_title = html_escape(&album.metadata.title);
     let escaped_artist = html_escape(&album.metadata.artist);
@@ -184,23 +1232,14 @@ pub fn generate_html(
 
     format!(
         r#"<!DOCTYPE html>
-<html lang="en">
+<html lang="{}" dir="{}">
 <head>
     <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">{robots_meta}{}{social_meta}
     <title>{} - {}</title>
     <style>
-        /* Theme - Metallic Analog Lab */
-        :root {{
-            --primary: #00ff88;
-            --primary-focus: #00cc66;
-            --base-100: #1a1a1f;
-            --base-200: #222228;
-            --base-300: #2a2a30;
-            --base-content: #e0e0e0;
-            --secondary: #4a4a5e;
-            --neutral: #2a2a3e;
-        }}
+        /* Theme: [site] theme and accent_color */
+        {theme_css}
 
         * {{ margin: 0; padding: 0; box-sizing: border-box; }}
 
@@ -208,15 +1247,7 @@ pub fn generate_html(
             font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
             line-height: 1.6;
             color: var(--base-content);
-            background-color: var(--base-100);
-            background-image:
-                repeating-linear-gradient(
-                    45deg,
-                    transparent,
-                    transparent 35px,
-                    rgba(255, 255, 255, 0.01) 35px,
-                    rgba(255, 255, 255, 0.01) 70px
-                );
+            {background_css}
             padding: 2rem;
             padding-bottom: 200px; /* Space for player */
         }}
@@ -234,77 +1265,290 @@ pub fn generate_html(
         }}
 
         .preview-badge {{
-            background: linear-gradient(135deg, var(--primary-focus) 0%, #00aa55 100%);
+            background: linear-gradient(135deg, var(--primary-focus) 0%, var(--primary-deep) 100%);
             color: #000000;
             padding: 0.5rem 1rem;
             border-radius: 4px;
-            display: inline-block;
-            margin-bottom: 1.5rem;
-            font-weight: bold;
-            text-shadow: 0 1px 0 rgba(255, 255, 255, 0.2);
-            box-shadow: 0 2px 8px rgba(0, 255, 136, 0.3);
+            display: inline-block;
+            margin-bottom: 1.5rem;
+            font-weight: bold;
+            text-shadow: 0 1px 0 rgba(255, 255, 255, 0.2);
+            box-shadow: 0 2px 8px rgba(var(--primary-rgb), 0.3);
+        }}
+
+        .album-header {{
+            margin-bottom: 2rem;
+            padding-bottom: 2rem;
+            border-bottom: 2px solid rgba(255, 255, 255, 0.1);
+            display: flex;
+            gap: 2rem;
+            align-items: flex-start;
+        }}
+
+        .album-info {{
+            flex: 1;
+        }}
+
+        /* Let <picture> wrappers lay out as their <img> */
+        picture {{
+            display: contents;
+        }}
+
+        .cover-art {{
+            width: 300px;
+            height: 300px;
+            object-fit: cover;
+            border-radius: 4px;
+            box-shadow: 0 8px 24px rgba(0, 0, 0, 0.4);
+            border: 1px solid rgba(255, 255, 255, 0.1);
+        }}
+
+        h1 {{
+            font-size: 2.5rem;
+            margin-bottom: 0.5rem;
+            color: var(--primary);
+        }}
+
+        .artist {{
+            font-size: 1.4rem;
+            color: var(--base-content);
+            margin-bottom: 0.5rem;
+            opacity: 0.9;
+        }}
+
+        .release-date {{
+            color: var(--base-content);
+            opacity: 0.6;
+            font-size: 0.9rem;
+        }}
+
+        .genres {{
+            list-style: none;
+            display: flex;
+            flex-wrap: wrap;
+            gap: 0.4rem;
+            margin: 0.75rem 0 0;
+            padding: 0;
+        }}
+
+        .genre {{
+            font-size: 0.75rem;
+            padding: 0.2rem 0.6rem;
+            border-radius: 999px;
+            border: 1px solid rgba(var(--primary-rgb), 0.5);
+            background: rgba(var(--primary-rgb), 0.1);
+            color: var(--primary);
+            text-transform: lowercase;
+            letter-spacing: 0.05em;
+        }}
+
+        .summary {{
+            margin: 1rem 0;
+            padding: 1rem;
+            background: rgba(0, 0, 0, 0.3);
+            border-left: 3px solid var(--primary);
+            border-radius: 4px;
+            line-height: 1.8;
+        }}
+
+        .tracks {{
+            margin-top: 2rem;
+        }}
+
+        .tracks h2 {{
+            font-size: 1.3rem;
+            margin-bottom: 1rem;
+            color: var(--primary);
+        }}
+
+        .disc-heading {{
+            font-size: 1rem;
+            margin: 1.5rem 0 0.5rem;
+            color: var(--base-content);
+            opacity: 0.7;
+            text-transform: uppercase;
+            letter-spacing: 0.1em;
+        }}
+
+        .disc-heading:first-child {{
+            margin-top: 0;
+        }}
+
+        .liner-notes {{
+            margin-top: 2rem;
+        }}
+
+        .liner-notes summary {{
+            font-size: 1.3rem;
+            font-weight: bold;
+            color: var(--primary);
+            cursor: pointer;
+        }}
+
+        .track-notes {{
+            padding: 0 1rem 0.75rem 5rem;
+            font-size: 0.9rem;
+        }}
+
+        .track-notes summary {{
+            opacity: 0.7;
+            cursor: pointer;
+        }}
+
+        .notes-body {{
+            margin-top: 0.75rem;
+            line-height: 1.6;
+            opacity: 0.9;
+        }}
+
+        .notes-body p,
+        .notes-body ul,
+        .notes-body ol,
+        .notes-body blockquote {{
+            margin-bottom: 0.75rem;
+        }}
+
+        .notes-body ul,
+        .notes-body ol {{
+            padding-left: 1.5rem;
+        }}
+
+        .notes-body a {{
+            color: var(--primary);
+        }}
+
+        .gallery {{
+            margin-top: 2rem;
+        }}
+
+        .gallery h2 {{
+            font-size: 1.3rem;
+            margin-bottom: 1rem;
+            color: var(--primary);
+        }}
+
+        .gallery-grid {{
+            list-style: none;
+            display: grid;
+            grid-template-columns: repeat(auto-fill, minmax(120px, 1fr));
+            gap: 0.75rem;
+        }}
+
+        .gallery-item img {{
+            width: 100%;
+            aspect-ratio: 1;
+            object-fit: cover;
+            border-radius: 4px;
         }}
 
-        .album-header {{
-            margin-bottom: 2rem;
-            padding-bottom: 2rem;
-            border-bottom: 2px solid rgba(255, 255, 255, 0.1);
+        .lightbox {{
+            display: none;
+            position: fixed;
+            inset: 0;
+            align-items: center;
+            justify-content: center;
+            background: rgba(0, 0, 0, 0.9);
+            cursor: zoom-out;
+            z-index: 2000;
+        }}
+
+        .lightbox:target {{
             display: flex;
-            gap: 2rem;
-            align-items: flex-start;
         }}
 
-        .album-info {{
-            flex: 1;
+        .lightbox img {{
+            max-width: 95vw;
+            max-height: 90vh;
+            object-fit: contain;
         }}
 
-        .cover-art {{
-            width: 300px;
-            height: 300px;
-            object-fit: cover;
-            border-radius: 4px;
-            box-shadow: 0 8px 24px rgba(0, 0, 0, 0.4);
-            border: 1px solid rgba(255, 255, 255, 0.1);
+        .merch {{
+            margin-top: 2rem;
         }}
 
-        h1 {{
-            font-size: 2.5rem;
-            margin-bottom: 0.5rem;
+        .merch h2 {{
+            font-size: 1.3rem;
+            margin-bottom: 1rem;
             color: var(--primary);
-            text-shadow: 0 0 20px var(--primary);
         }}
 
-        .artist {{
-            font-size: 1.4rem;
-            color: var(--base-content);
-            margin-bottom: 0.5rem;
-            opacity: 0.9;
+        .merch-grid {{
+            list-style: none;
+            display: grid;
+            grid-template-columns: repeat(auto-fill, minmax(160px, 1fr));
+            gap: 1rem;
         }}
 
-        .release-date {{
-            color: var(--base-content);
-            opacity: 0.6;
-            font-size: 0.9rem;
+        .merch-item a {{
+            display: flex;
+            flex-direction: column;
+            gap: 0.25rem;
+            color: inherit;
+            text-decoration: none;
         }}
 
-        .summary {{
-            margin: 1rem 0;
-            padding: 1rem;
-            background: rgba(0, 0, 0, 0.3);
-            border-left: 3px solid var(--primary);
+        .merch-item img {{
+            width: 100%;
+            aspect-ratio: 1;
+            object-fit: cover;
             border-radius: 4px;
-            line-height: 1.8;
+            margin-bottom: 0.25rem;
         }}
 
-        .tracks {{
+        .merch-item a:hover .merch-name {{
+            color: var(--primary);
+        }}
+
+        .merch-price {{
+            opacity: 0.7;
+            font-size: 0.85rem;
+        }}
+
+        .downloads {{
             margin-top: 2rem;
         }}
 
-        .tracks h2 {{
+        .downloads h2 {{
             font-size: 1.3rem;
             margin-bottom: 1rem;
             color: var(--primary);
-            text-shadow: 0 0 10px var(--primary);
+        }}
+
+        .download-price {{
+            opacity: 0.8;
+            margin-bottom: 1rem;
+        }}
+
+        .download-album {{
+            display: inline-block;
+            margin-bottom: 1rem;
+            padding: 0.5rem 1rem;
+            border: 1px solid var(--primary);
+            border-radius: 4px;
+            color: var(--primary);
+            text-decoration: none;
+        }}
+
+        .download-tracks {{
+            list-style: none;
+        }}
+
+        .download-track {{
+            display: flex;
+            gap: 1rem;
+            align-items: center;
+            padding: 0.5rem 0;
+            border-bottom: 1px solid rgba(255, 255, 255, 0.05);
+        }}
+
+        .download-track-title {{
+            flex: 1;
+        }}
+
+        .download-track a {{
+            color: var(--primary);
+            font-size: 0.85rem;
+            text-decoration: none;
         }}
 
         .track {{
@@ -320,12 +1564,12 @@ pub fn generate_html(
         }}
 
         .track:hover {{
-            background-color: rgba(0, 255, 136, 0.05);
+            background-color: rgba(var(--primary-rgb), 0.05);
             transform: translateX(4px);
         }}
 
         .track.playing {{
-            background: linear-gradient(90deg, rgba(0, 255, 136, 0.1) 0%, transparent 100%);
+            background: linear-gradient(90deg, rgba(var(--primary-rgb), 0.1) 0%, transparent 100%);
             border-left: 3px solid var(--primary);
         }}
 
@@ -336,7 +1580,8 @@ pub fn generate_html(
             font-size: 0.9rem;
         }}
 
-        .track.playing .track-number {{
+        .track.playing .track-number,
+        .track.selected .track-number {{
             color: var(--primary);
             opacity: 1;
         }}
@@ -350,6 +1595,44 @@ pub fn generate_html(
             color: var(--primary);
         }}
 
+        .explicit-badge {{
+            display: inline-block;
+            margin-left: 0.4rem;
+            padding: 0 0.3rem;
+            font-size: 0.65rem;
+            font-weight: 700;
+            line-height: 1.3;
+            vertical-align: middle;
+            border-radius: 2px;
+            color: var(--base-100);
+            background: var(--base-content);
+            opacity: 0.6;
+        }}
+
+        .track-meta {{
+            margin-left: 0.5rem;
+            font-size: 0.8rem;
+            font-weight: 400;
+            color: var(--base-content);
+            opacity: 0.5;
+        }}
+
+        .track-artist {{
+            display: block;
+            font-size: 0.8rem;
+            font-weight: 400;
+            color: var(--base-content);
+            opacity: 0.7;
+        }}
+
+        .track-credits {{
+            display: block;
+            font-size: 0.75rem;
+            font-weight: 400;
+            color: var(--base-content);
+            opacity: 0.6;
+        }}
+
         .track-duration {{
             color: var(--base-content);
             opacity: 0.5;
@@ -364,7 +1647,7 @@ pub fn generate_html(
             left: 0;
             right: 0;
             background: linear-gradient(135deg, var(--base-200) 0%, var(--base-100) 100%);
-            border-top: 2px solid rgba(0, 255, 136, 0.2);
+            border-top: 2px solid rgba(var(--primary-rgb), 0.2);
             padding: 1rem;
             box-shadow:
                 0 -4px 20px rgba(0, 0, 0, 0.5),
@@ -449,7 +1732,7 @@ pub fn generate_html(
 
         .player-btn:hover {{
             box-shadow:
-                0 4px 8px rgba(0, 255, 136, 0.2),
+                0 4px 8px rgba(var(--primary-rgb), 0.2),
                 0 2px 4px rgba(0, 0, 0, 0.4);
             transform: translateY(-2px);
         }}
@@ -462,13 +1745,29 @@ pub fn generate_html(
         .player-btn.play {{
             width: 50px;
             height: 50px;
-            background: linear-gradient(135deg, var(--primary-focus) 0%, #00aa55 100%);
+            background: linear-gradient(135deg, var(--primary-focus) 0%, var(--primary-deep) 100%);
             color: #000000;
         }}
 
         .player-progress {{
             grid-column: 1 / -1;
             margin-top: 0.5rem;
+            position: relative;
+        }}
+
+        .progress-tooltip {{
+            position: absolute;
+            bottom: calc(100% + 6px);
+            transform: translateX(-50%);
+            padding: 0.15rem 0.4rem;
+            background: var(--base-300);
+            color: var(--base-content);
+            border: 1px solid var(--secondary);
+            border-radius: 3px;
+            font-size: 0.75rem;
+            font-variant-numeric: tabular-nums;
+            white-space: nowrap;
+            pointer-events: none;
         }}
 
         .progress-bar {{
@@ -487,7 +1786,72 @@ pub fn generate_html(
             background: linear-gradient(90deg, var(--primary) 0%, var(--primary-focus) 100%);
             width: 0%;
             transition: width 0.1s linear;
-            box-shadow: 0 0 10px var(--primary);
+        }}
+
+        .chapter-markers {{
+            position: absolute;
+            inset: 0;
+            pointer-events: none;
+        }}
+
+        .chapter-marker {{
+            position: absolute;
+            top: 0;
+            bottom: 0;
+            width: 3px;
+            margin-left: -1px;
+            background: var(--secondary);
+            cursor: pointer;
+            pointer-events: auto;
+        }}
+
+        .track-chapters {{
+            list-style: none;
+            padding: 0 1rem 0.75rem 5rem;
+            font-size: 0.9rem;
+        }}
+
+        .chapter {{
+            background: none;
+            border: none;
+            color: inherit;
+            font: inherit;
+            cursor: pointer;
+            padding: 0.15rem 0;
+            opacity: 0.8;
+        }}
+
+        .chapter:hover,
+        .chapter:focus-visible {{
+            color: var(--primary);
+            opacity: 1;
+        }}
+
+        .chapter-time {{
+            font-family: monospace;
+            opacity: 0.7;
+            margin-right: 0.5rem;
+        }}
+
+        .progress-bar.has-peaks {{
+            height: 20px;
+        }}
+
+        .progress-peaks {{
+            position: absolute;
+            inset: 2px 0;
+            display: flex;
+            align-items: center;
+            gap: 1px;
+            pointer-events: none;
+        }}
+
+        .progress-peaks i {{
+            flex: 1;
+            min-height: 2px;
+            background: var(--base-content);
+            opacity: 0.35;
+            border-radius: 1px;
         }}
 
         .oscilloscope {{
@@ -498,7 +1862,7 @@ pub fn generate_html(
             border-radius: 4px;
             border: 1px solid rgba(255, 255, 255, 0.1);
             box-shadow:
-                inset 0 0 20px rgba(0, 255, 136, 0.1),
+                inset 0 0 20px rgba(var(--primary-rgb), 0.1),
                 0 2px 4px rgba(0, 0, 0, 0.3);
         }}
 
@@ -512,10 +1876,165 @@ pub fn generate_html(
             text-align: center;
         }}
 
+        .footer-links {{
+            margin-top: 0.5rem;
+        }}
+
+        .footer-credits {{
+            margin-bottom: 1rem;
+        }}
+
+        .footer-credits h3 {{
+            font-size: 0.9rem;
+            text-transform: uppercase;
+            letter-spacing: 0.1em;
+            margin-bottom: 0.5rem;
+        }}
+
+        .footer-credits dl {{
+            display: grid;
+            grid-template-columns: max-content auto;
+            gap: 0.25rem 1rem;
+            justify-content: center;
+            margin: 0;
+        }}
+
+        .footer-credits dt {{
+            text-align: right;
+        }}
+
+        .footer-credits dd {{
+            margin: 0;
+            text-align: left;
+        }}
+
+        .footer-links a {{
+            color: var(--primary);
+            text-decoration: none;
+        }}
+
+        .footer-links a:hover {{
+            text-decoration: underline;
+        }}
+
+        .footer-attribution {{
+            margin-top: 0.5rem;
+            font-size: 0.75rem;
+            opacity: 0.7;
+        }}
+
         audio {{
             display: none;
         }}
 
+        /* Keyboard shortcut help */
+        .player-btn.help {{
+            font-size: 1.1rem;
+            font-weight: 700;
+        }}
+
+        .shortcut-help {{
+            position: fixed;
+            inset: 0;
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            background: rgba(0, 0, 0, 0.7);
+            z-index: 2000;
+        }}
+
+        .shortcut-help[hidden] {{
+            display: none;
+        }}
+
+        .shortcut-help-panel {{
+            background: linear-gradient(135deg, var(--base-200) 0%, var(--base-100) 100%);
+            border: 1px solid rgba(var(--primary-rgb), 0.2);
+            border-radius: 8px;
+            padding: 1.5rem 2rem;
+            min-width: 280px;
+            box-shadow: 0 8px 24px rgba(0, 0, 0, 0.5);
+        }}
+
+        .shortcut-help-panel h2 {{
+            font-size: 1.2rem;
+            margin-bottom: 1rem;
+            color: var(--primary);
+        }}
+
+        .shortcut-list {{
+            display: grid;
+            grid-template-columns: auto 1fr;
+            gap: 0.5rem 1.5rem;
+            margin-bottom: 1.5rem;
+        }}
+
+        .shortcut-list dt {{
+            white-space: nowrap;
+        }}
+
+        .shortcut-list dd {{
+            opacity: 0.8;
+        }}
+
+        kbd {{
+            display: inline-block;
+            min-width: 1.6rem;
+            padding: 0.1rem 0.4rem;
+            font-family: inherit;
+            font-size: 0.85rem;
+            text-align: center;
+            border: 1px solid rgba(255, 255, 255, 0.2);
+            border-radius: 4px;
+            background: rgba(0, 0, 0, 0.3);
+        }}
+
+        .shortcut-help-close {{
+            background: linear-gradient(135deg, var(--secondary) 0%, var(--neutral) 100%);
+            border: 1px solid rgba(255, 255, 255, 0.1);
+            color: var(--base-content);
+            padding: 0.4rem 1.2rem;
+            border-radius: 4px;
+            cursor: pointer;
+        }}
+
+        .shortcut-help-close:focus-visible,
+        .player-btn:focus-visible,
+        .track:focus-visible,
+        .progress-bar:focus-visible {{
+            outline: 2px solid var(--primary);
+            outline-offset: 2px;
+        }}
+
+        /* Right-to-left languages: grid and flex layouts follow dir, mirror the rest */
+        [dir="rtl"] .summary,
+        [dir="rtl"] .track.playing {{
+            border-left: none;
+            border-right: 3px solid var(--primary);
+        }}
+
+        [dir="rtl"] .track:hover {{
+            transform: translateX(-4px);
+        }}
+
+        [dir="rtl"] .track.playing {{
+            background: linear-gradient(270deg, rgba(var(--primary-rgb), 0.1) 0%, transparent 100%);
+        }}
+
+        [dir="rtl"] .track-duration {{
+            text-align: left;
+        }}
+
+        [dir="rtl"] .track-meta {{
+            margin-left: 0;
+            margin-right: 0.5rem;
+        }}
+
+        [dir="rtl"] .explicit-badge {{
+            margin-left: 0;
+            margin-right: 0.4rem;
+        }}
+
         @media (max-width: 768px) {{
             .album-header {{
                 flex-direction: column;
@@ -550,7 +2069,7 @@ pub fn generate_html(
                 font-size: 0.75rem;
             }}
         }}
-    </style>
+{glow_css}    </style>
 </head>
 <body>
     <div class="container">
@@ -561,7 +2080,7 @@ pub fn generate_html(
             <div class="album-info">
                 <h1>{}</h1>
                 <div class="artist">by {}</div>
-                <div class="release-date">Release: {}</div>
+                <div class="release-date">Release: {}</div>{genres_html}
                 <div class="summary">{}</div>
             </div>
         </div>
@@ -572,6 +2091,7 @@ pub fn generate_html(
                 {}
             </div>
         </div>
+{liner_notes_html}{gallery_html}{merch_html}{downloads_html}
 
         <div class="footer">
             {}
@@ -579,53 +2099,58 @@ pub fn generate_html(
     </div>
 
     <!-- Fixed Player -->
-    <div class="player">
+    <div class="player" data-seek-seconds="{}"{gapless_attr}>
         <div class="player-content">
             {}
             <div class="player-right">
                 <canvas id="oscilloscope" class="oscilloscope" width="1200" height="140"></canvas>
                 <div class="player-info-controls">
                     <div class="player-info">
-                        <div class="player-track" id="player-track">Select a track</div>
-                        <div class="player-artist" id="player-artist">{}</div>
+                        <div class="player-track" id="player-track">{player_track_title}</div>
+                        <div class="player-artist" id="player-artist" data-album-artist="{escaped_artist}">{player_artist}</div>
                     </div>
                     <div class="player-controls">
-                        <button class="player-btn" id="prev-btn">
-                            <svg width="20" height="20" fill="currentColor" viewBox="0 0 20 20">
+                        <button class="player-btn" id="prev-btn" aria-label="Previous track">
+                            <svg width="20" height="20" fill="currentColor" viewBox="0 0 20 20" aria-hidden="true">
                                 <path d="M14 4v12M12 6l-6 6 6 6V6z"/>
                             </svg>
                         </button>
-                        <button class="player-btn play" id="play-btn">
-                            <svg id="play-icon" width="24" height="24" fill="currentColor">
+                        <button class="player-btn play" id="play-btn" aria-label="Play">
+                            <svg id="play-icon" width="24" height="24" fill="currentColor" aria-hidden="true">
                                 <path d="M8 5v14l11-7z"/>
                             </svg>
-                            <svg id="pause-icon" width="24" height="24" fill="currentColor" style="display:none">
+                            <svg id="pause-icon" width="24" height="24" fill="currentColor" style="display:none" aria-hidden="true">
                                 <path d="M6 4h4v16H6V4zm8 0h4v16h-4V4z"/>
                             </svg>
                         </button>
-                        <button class="player-btn" id="next-btn">
-                            <svg width="20" height="20" fill="currentColor">
+                        <button class="player-btn" id="next-btn" aria-label="Next track">
+                            <svg width="20" height="20" fill="currentColor" aria-hidden="true">
                                 <path d="M18 4v12M16 6l-6 6 6 6V6z" transform="scale(-1, 1) translate(-24, 0)"/>
                             </svg>
-                        </button>
+                        </button>{help_button_html}
                     </div>
                 </div>
             </div>
         </div>
 
         <div class="player-progress">
-            <div class="progress-bar" id="progress-bar">
-                <div class="progress-fill" id="progress-fill"></div>
+            <div class="progress-bar" id="progress-bar" role="slider" tabindex="0" aria-label="Seek" aria-valuemin="0" aria-valuemax="0" aria-valuenow="0" aria-valuetext="0:00">
+                <div class="progress-fill" id="progress-fill"></div>{peaks_html}{chapter_markers_html}
             </div>
+            <div class="progress-tooltip" id="progress-tooltip" hidden></div>
         </div>
     </div>
 
-    <audio id="audio" preload="metadata"></audio>
+{help_overlay_html}
+    <audio id="audio" preload="{preload}"></audio>{spare_audio_html}
 
     {}
-    <script src="{}"></script>
+    <script src="{}{}"></script>
 </body>
 </html>"#,
+        html_escape(album.site.language()),
+        if album.site.is_rtl() { "rtl" } else { "ltr" },
+        verification_meta,
         escaped_title,
         escaped_artist,
         preview_badge,
@@ -635,10 +2160,11 @@ pub fn generate_html(
         album.metadata.release_date,
         escaped_summary,
         tracks_html,
-        footer_text,
+        footer_html,
+        album.site.seek_seconds,
         player_art_html,
-        escaped_artist,
         reload_script,
+        base_path,
         if is_preview {
             "/_player.js"
         } else {
@@ -660,6 +2186,11 @@ class AnalogOscilloscope {
         this.dataArray = new Uint8Array(analyser.frequencyBinCount);
         this.animationId = null;
         this.isRunning = false;
+
+        // Trace in the page's accent color (the theme's --primary-rgb)
+        const rgb = getComputedStyle(document.documentElement)
+            .getPropertyValue('--primary-rgb').trim() || '0, 255, 136';
+        this.color = { primary: `rgb(${rgb})`, faint: `rgba(${rgb}, 0.1)` };
     }
 
     start() {
@@ -690,9 +2221,9 @@ class AnalogOscilloscope {
         this.drawGrid();
 
         this.ctx.lineWidth = 2;
-        this.ctx.strokeStyle = '#00ff88';
+        this.ctx.strokeStyle = this.color.primary;
         this.ctx.shadowBlur = 15;
-        this.ctx.shadowColor = '#00ff88';
+        this.ctx.shadowColor = this.color.primary;
         this.ctx.beginPath();
 
         const sliceWidth = this.canvas.width / this.dataArray.length;
@@ -716,7 +2247,7 @@ class AnalogOscilloscope {
     }
 
     drawGrid() {
-        this.ctx.strokeStyle = 'rgba(0, 255, 136, 0.1)';
+        this.ctx.strokeStyle = this.color.faint;
         this.ctx.lineWidth = 1;
 
         const numHLines = 4;
@@ -744,6 +2275,8 @@ class AudioPlayer {
         this.audio = document.getElementById('audio');
         this.tracks = Array.from(document.querySelectorAll('.track'));
         this.currentTrackIndex = -1;
+        // [distribution] default_track: where play starts, or -1 for the first track
+        this.defaultTrackIndex = this.tracks.findIndex(t => t.classList.contains('selected'));
 
         this.playBtn = document.getElementById('play-btn');
         this.prevBtn = document.getElementById('prev-btn');
@@ -752,12 +2285,36 @@ class AudioPlayer {
         this.pauseIcon = document.getElementById('pause-icon');
         this.progressBar = document.getElementById('progress-bar');
         this.progressFill = document.getElementById('progress-fill');
+        // [site] peak_bars (absent when off)
+        this.progressPeaks = document.getElementById('progress-peaks');
+        // Absent when no track has chapters
+        this.chapterMarkers = document.getElementById('chapter-markers');
+        this.progressTooltip = document.getElementById('progress-tooltip');
         this.playerTrackEl = document.getElementById('player-track');
         this.playerArtistEl = document.getElementById('player-artist');
 
+        // Shortcut help (absent when [site] keyboard_help = false)
+        this.helpBtn = document.getElementById('help-btn');
+        this.helpOverlay = document.getElementById('shortcut-help');
+        this.helpCloseBtn = document.getElementById('shortcut-help-close');
+        this.helpReturnFocus = null;
+
+        // [site] seek_seconds, for the J/L and ,/. keys
+        const player = document.querySelector('.player');
+        this.seekSeconds = parseFloat(player && player.dataset.seekSeconds) || 10;
+
+        // [distribution] gapless: the spare element buffers the next track,
+        // then the two swap roles when the current one ends
+        this.spare = player && player.dataset.gapless === 'true'
+            ? document.getElementById('audio-next')
+            : null;
+        this.preloadIndex = -1;
+        this.spareIndex = -1;
+
         this.audioContext = null;
         this.analyser = null;
-        this.source = null;
+        // One MediaElementSource per element: each can only ever get one
+        this.sources = new Map();
         this.oscilloscope = null;
 
         this.initializeAudio();
@@ -765,11 +2322,70 @@ class AudioPlayer {
         this.initializeOscilloscope();
     }
 
+    // Listen on both elements, but only the one currently playing counts
+    onAudio(type, handler) {
+        [this.audio, this.spare].filter(Boolean).forEach(el => {
+            el.addEventListener(type, () => {
+                if (el === this.audio) handler();
+            });
+        });
+    }
+
     initializeAudio() {
-        this.audio.addEventListener('timeupdate', () => this.updateProgress());
-        this.audio.addEventListener('ended', () => this.next());
-        this.audio.addEventListener('play', () => this.updatePlayButton(true));
-        this.audio.addEventListener('pause', () => this.updatePlayButton(false));
+        this.onAudio('timeupdate', () => {
+            this.updateProgress();
+            this.preloadNext();
+        });
+        this.onAudio('ended', () => this.advance());
+        this.onAudio('play', () => this.updatePlayButton(true));
+        this.onAudio('pause', () => this.updatePlayButton(false));
+    }
+
+    // Route an element through the analyser, once per element
+    connectSource(el) {
+        if (!this.audioContext || this.sources.has(el)) return;
+        const source = this.audioContext.createMediaElementSource(el);
+        source.connect(this.analyser);
+        this.sources.set(el, source);
+    }
+
+    // Gapless: start buffering the next track in the spare element near the end
+    preloadNext() {
+        const nextIndex = this.currentTrackIndex + 1;
+        if (!this.spare || nextIndex >= this.tracks.length || this.preloadIndex === nextIndex) return;
+        if (!this.audio.duration || this.audio.duration - this.audio.currentTime > 15) return;
+
+        this.preloadIndex = nextIndex;
+        this.resolveSrc(this.tracks[nextIndex]).then(src => {
+            // The listener may have jumped elsewhere meanwhile
+            if (this.preloadIndex !== nextIndex) return;
+            this.spare.src = src;
+            this.spare.load();
+            this.spareIndex = nextIndex;
+        }).catch(err => console.error('Preloading the next track failed:', err));
+    }
+
+    // The current track ended: swap in the buffered next track, or load it
+    advance() {
+        if (!this.spare || this.spareIndex !== this.currentTrackIndex + 1) {
+            this.next();
+            return;
+        }
+
+        const finished = this.audio;
+        this.audio = this.spare;
+        this.spare = finished;
+        this.audio.volume = finished.volume;
+        this.preloadIndex = this.spareIndex = -1;
+        this.connectSource(this.audio);
+
+        const index = this.currentTrackIndex + 1;
+        this.showTrack(index);
+        this.audio.play().catch(err => {
+            console.error('Playback failed:', err);
+            this.showError(`Failed to play "${this.tracks[index].dataset.title}": ${err.message}`);
+            this.tracks[index].classList.remove('playing');
+        });
     }
 
     initializeOscilloscope() {
@@ -789,24 +2405,20 @@ class AudioPlayer {
             this.analyser = this.audioContext.createAnalyser();
             this.analyser.fftSize = 2048;
 
-            // Only create source if it doesn't exist (can only call once per audio element)
-            if (!this.source) {
-                this.source = this.audioContext.createMediaElementSource(this.audio);
-                this.source.connect(this.analyser);
-            }
+            this.connectSource(this.audio);
             this.analyser.connect(this.audioContext.destination);
 
             this.oscilloscope = new AnalogOscilloscope(canvas, this.analyser);
         };
 
-        this.audio.addEventListener('play', () => {
+        this.onAudio('play', () => {
             setupAudioContext();
             if (this.oscilloscope) {
                 this.oscilloscope.start();
             }
         });
 
-        this.audio.addEventListener('pause', () => {
+        this.onAudio('pause', () => {
             if (this.oscilloscope) {
                 this.oscilloscope.stop();
             }
@@ -816,6 +2428,22 @@ class AudioPlayer {
     attachEventListeners() {
         this.tracks.forEach((track, index) => {
             track.addEventListener('click', () => this.playTrack(index));
+            // Tracks are role="button": Enter and Space activate them like one
+            track.addEventListener('keydown', (e) => {
+                if (e.key === 'Enter' || e.key === ' ') {
+                    e.preventDefault();
+                    e.stopPropagation();
+                    this.playTrack(index);
+                }
+            });
+            const chapters = this.chapterList(index);
+            if (chapters) {
+                chapters.querySelectorAll('.chapter').forEach(button => {
+                    button.addEventListener('click', () => {
+                        this.seekToChapter(index, parseFloat(button.dataset.start));
+                    });
+                });
+            }
         });
 
         this.playBtn.addEventListener('click', () => this.togglePlay());
@@ -823,11 +2451,42 @@ class AudioPlayer {
         this.nextBtn.addEventListener('click', () => this.next());
 
         this.progressBar.addEventListener('click', (e) => this.seek(e));
+        // The progress bar is a slider: arrows seek while it has focus
+        this.progressBar.addEventListener('keydown', (e) => this.seekWithKeys(e));
+        this.progressBar.addEventListener('mousemove', (e) => this.showHoverTime(e));
+        this.progressBar.addEventListener('mouseleave', () => {
+            this.progressTooltip.hidden = true;
+        });
+
+        if (this.helpOverlay) {
+            this.helpBtn.addEventListener('click', () => this.openHelp());
+            this.helpCloseBtn.addEventListener('click', () => this.closeHelp());
+            // Clicking the backdrop (outside the panel) closes
+            this.helpOverlay.addEventListener('click', (e) => {
+                if (e.target === this.helpOverlay) this.closeHelp();
+            });
+        }
 
         document.addEventListener('keydown', (e) => {
             if (e.target.tagName === 'INPUT' || e.target.tagName === 'TEXTAREA') return;
+            // A focused button handles its own Enter / Space
+            if (e.target.tagName === 'BUTTON' && (e.key === 'Enter' || e.code === 'Space')) return;
+
+            // While the help is open, keys only close it or move focus within it
+            if (this.isHelpOpen()) {
+                if (e.key === 'Escape' || e.key === '?') {
+                    e.preventDefault();
+                    this.closeHelp();
+                } else if (e.key === 'Tab') {
+                    this.trapHelpFocus(e);
+                }
+                return;
+            }
 
-            if (e.code === 'Space') {
+            if (e.key === '?' && this.helpOverlay) {
+                e.preventDefault();
+                this.openHelp();
+            } else if (e.code === 'Space') {
                 e.preventDefault();
                 this.togglePlay();
             } else if (e.code === 'ArrowLeft') {
@@ -836,49 +2495,205 @@ class AudioPlayer {
             } else if (e.code === 'ArrowRight') {
                 e.preventDefault();
                 this.next();
+            } else if ((e.key === 'j' || e.key === ',') && !e.ctrlKey && !e.metaKey && !e.altKey) {
+                e.preventDefault();
+                this.seekBy(-this.seekSeconds);
+            } else if ((e.key === 'l' || e.key === '.') && !e.ctrlKey && !e.metaKey && !e.altKey) {
+                e.preventDefault();
+                this.seekBy(this.seekSeconds);
+            } else if (e.code === 'ArrowUp') {
+                e.preventDefault();
+                this.changeVolume(0.1);
+            } else if (e.code === 'ArrowDown') {
+                e.preventDefault();
+                this.changeVolume(-0.1);
             }
         });
     }
 
-    playTrack(index) {
-        if (index < 0 || index >= this.tracks.length) return;
+    seekWithKeys(e) {
+        let handled = true;
+        if (e.key === 'ArrowLeft' || e.key === 'ArrowDown') {
+            this.seekBy(-this.seekSeconds);
+        } else if (e.key === 'ArrowRight' || e.key === 'ArrowUp') {
+            this.seekBy(this.seekSeconds);
+        } else if (e.key === 'Home') {
+            this.seekBy(-Infinity);
+        } else if (e.key === 'End') {
+            this.seekBy(Infinity);
+        } else {
+            handled = false;
+        }
+        if (handled) {
+            e.preventDefault();
+            e.stopPropagation();
+        }
+    }
 
-        const track = this.tracks[index];
-        const src = track.dataset.src;
-        const title = track.dataset.title;
+    // Jump within the current track, clamped to [0, duration]
+    seekBy(seconds) {
+        if (!this.audio.duration) return;
+        this.audio.currentTime = Math.min(
+            this.audio.duration,
+            Math.max(0, this.audio.currentTime + seconds)
+        );
+    }
 
+    changeVolume(delta) {
+        this.audio.volume = Math.min(1, Math.max(0, this.audio.volume + delta));
+    }
+
+    isHelpOpen() {
+        return this.helpOverlay && !this.helpOverlay.hidden;
+    }
+
+    openHelp() {
+        if (!this.helpOverlay) return;
+        this.helpReturnFocus = document.activeElement;
+        this.helpOverlay.hidden = false;
+        this.helpCloseBtn.focus();
+    }
+
+    closeHelp() {
+        if (!this.isHelpOpen()) return;
+        this.helpOverlay.hidden = true;
+        if (this.helpReturnFocus) {
+            this.helpReturnFocus.focus();
+            this.helpReturnFocus = null;
+        }
+    }
+
+    // Keep Tab / Shift+Tab cycling inside the dialog
+    trapHelpFocus(e) {
+        const focusable = Array.from(
+            this.helpOverlay.querySelectorAll('button, [href], [tabindex]:not([tabindex="-1"])')
+        );
+        if (focusable.length === 0) return;
+
+        const first = focusable[0];
+        const last = focusable[focusable.length - 1];
+        if (e.shiftKey && document.activeElement === first) {
+            e.preventDefault();
+            last.focus();
+        } else if (!e.shiftKey && document.activeElement === last) {
+            e.preventDefault();
+            first.focus();
+        }
+    }
+
+    // Mark a track as the current one in the list and the player
+    showTrack(index) {
+        const track = this.tracks[index];
         this.currentTrackIndex = index;
 
-        this.tracks.forEach(t => t.classList.remove('playing'));
+        this.tracks.forEach(t => {
+            t.classList.remove('playing', 'selected');
+            t.removeAttribute('aria-current');
+        });
         track.classList.add('playing');
+        track.setAttribute('aria-current', 'true');
+
+        this.playerTrackEl.textContent = track.dataset.title;
+        this.playerArtistEl.textContent = track.dataset.artist || this.playerArtistEl.dataset.albumArtist;
+        this.showPeaks(track.dataset.peaks || '');
+        this.showChapters(index);
+    }
 
-        this.playerTrackEl.textContent = title;
+    // The track's chapter list, rendered below its row
+    chapterList(index) {
+        return document.querySelector(`.track-chapters[data-track="${this.tracks[index].dataset.index}"]`);
+    }
 
-        this.audio.src = src;
-        this.audio.play().catch(err => {
+    // Mark the track's chapters on the progress bar (needs its duration)
+    showChapters(index) {
+        if (!this.chapterMarkers) return;
+        const list = this.chapterList(index);
+        const duration = list ? parseFloat(list.dataset.duration) : NaN;
+        const buttons = list && duration > 0 ? Array.from(list.querySelectorAll('.chapter')) : [];
+        this.chapterMarkers.replaceChildren(...buttons.map(button => {
+            const start = parseFloat(button.dataset.start);
+            const marker = document.createElement('span');
+            marker.className = 'chapter-marker';
+            marker.style.left = `${Math.min(100, (start / duration) * 100)}%`;
+            marker.title = button.textContent;
+            marker.addEventListener('click', (e) => {
+                // Exactly the chapter start, not wherever the click landed
+                e.stopPropagation();
+                this.seekToChapter(index, start);
+            });
+            return marker;
+        }));
+    }
+
+    // Jump to `start` seconds into a track, starting it first if needed
+    seekToChapter(index, start) {
+        if (index === this.currentTrackIndex && this.audio.src) {
+            this.audio.currentTime = start;
+            if (this.audio.paused) this.audio.play();
+            return;
+        }
+        this.audio.addEventListener('loadedmetadata', () => {
+            this.audio.currentTime = start;
+        }, { once: true });
+        this.playTrack(index);
+    }
+
+    // Static bars behind the progress fill, one per data-peaks digit
+    showPeaks(peaks) {
+        if (!this.progressPeaks) return;
+        this.progressPeaks.replaceChildren(...Array.from(peaks, level => {
+            const bar = document.createElement('i');
+            bar.style.height = `${(Number(level) + 1) * 10}%`;
+            return bar;
+        }));
+        this.progressBar.classList.toggle('has-peaks', peaks.length > 0);
+    }
+
+    playTrack(index) {
+        if (index < 0 || index >= this.tracks.length) return;
+
+        const track = this.tracks[index];
+        const title = track.dataset.title;
+
+        this.showTrack(index);
+        // A picked track invalidates whatever was buffered after the old one
+        this.preloadIndex = this.spareIndex = -1;
+
+        this.resolveSrc(track).then(src => {
+            // Another track may have been picked while the URL was signed
+            if (this.currentTrackIndex !== index) return;
+            this.audio.src = src;
+            return this.audio.play();
+        }).catch(err => {
             console.error('Playback failed:', err);
             this.showError(`Failed to play "${title}": ${err.message}`);
             track.classList.remove('playing');
         });
     }
 
+    // Signed-stream tracks get a fresh short-lived URL from the worker first
+    resolveSrc(track) {
+        if (!track.dataset.sign) return Promise.resolve(track.dataset.src);
+        return fetch(track.dataset.sign).then(res => {
+            if (!res.ok) throw new Error(`stream link refused (${res.status})`);
+            return res.json();
+        }).then(body => body.url);
+    }
+
     showError(message) {
         // Display error to user
         this.playerTrackEl.textContent = '⚠️ ' + message;
         this.playerTrackEl.style.color = '#ff006e';
         setTimeout(() => {
             this.playerTrackEl.style.color = '';
-            if (this.currentTrackIndex >= 0) {
-                this.playerTrackEl.textContent = this.tracks[this.currentTrackIndex]?.dataset.title || 'Select a track';
-            } else {
-                this.playerTrackEl.textContent = 'Select a track';
-            }
+            const index = this.currentTrackIndex >= 0 ? this.currentTrackIndex : this.defaultTrackIndex;
+            this.playerTrackEl.textContent = this.tracks[index]?.dataset.title || 'Select a track';
         }, 3000);
     }
 
     togglePlay() {
         if (this.currentTrackIndex === -1 && this.tracks.length > 0) {
-            this.playTrack(0);
+            this.playTrack(Math.max(0, this.defaultTrackIndex));
         } else if (this.audio.paused) {
             this.audio.play();
         } else {
@@ -904,10 +2719,41 @@ class AudioPlayer {
         this.audio.currentTime = percent * this.audio.duration;
     }
 
+    // Tooltip over the progress bar with the time a click there would seek to
+    showHoverTime(e) {
+        if (!this.audio.duration || !isFinite(this.audio.duration)) {
+            this.progressTooltip.hidden = true;
+            return;
+        }
+        const rect = this.progressBar.getBoundingClientRect();
+        const percent = Math.min(1, Math.max(0, (e.clientX - rect.left) / rect.width));
+        this.progressTooltip.textContent = this.formatTime(percent * this.audio.duration);
+        this.progressTooltip.style.left = `${percent * 100}%`;
+        this.progressTooltip.hidden = false;
+    }
+
+    // M:SS, or H:MM:SS from an hour up (as in the tracklist)
+    formatTime(seconds) {
+        const total = Math.floor(seconds);
+        const hours = Math.floor(total / 3600);
+        const mins = Math.floor((total % 3600) / 60);
+        const secs = String(total % 60).padStart(2, '0');
+        return hours > 0
+            ? `${hours}:${String(mins).padStart(2, '0')}:${secs}`
+            : `${mins}:${secs}`;
+    }
+
     updateProgress() {
         if (!this.audio.duration) return;
         const percent = (this.audio.currentTime / this.audio.duration) * 100;
         this.progressFill.style.width = `${percent}%`;
+
+        if (isFinite(this.audio.duration)) {
+            this.progressBar.setAttribute('aria-valuemax', Math.floor(this.audio.duration));
+            this.progressBar.setAttribute('aria-valuenow', Math.floor(this.audio.currentTime));
+            this.progressBar.setAttribute('aria-valuetext',
+                `${this.formatTime(this.audio.currentTime)} of ${this.formatTime(this.audio.duration)}`);
+        }
     }
 
     updatePlayButton(isPlaying) {
@@ -918,6 +2764,7 @@ class AudioPlayer {
             this.playIcon.style.display = 'block';
             this.pauseIcon.style.display = 'none';
         }
+        this.playBtn.setAttribute('aria-label', isPlaying ? 'Pause' : 'Play');
     }
 }
 
@@ -934,6 +2781,21 @@ if (document.readyState === 'loading') {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::test_support::album_toml;
+    use release_kit_core::config::parse_album_toml_str;
+
+    /// Parse a minimal album, with `site_extra` inserted after the `[site]` keys
+    fn test_album(site_extra: &str) -> Album {
+        let toml = album_toml(&format!(
+            r#"{site_extra}
+
+[[track]]
+file = "audio/01-test.flac"
+title = "Test Track"
+"#
+        ));
+        parse_album_toml_str(&toml).unwrap()
+    }
 
     #[test]
     fn test_html_escape_basic_characters() {
@@ -1001,7 +2863,964 @@ mod tests {
         );
         assert_eq!(
             format_duration(std::time::Duration::from_secs(3661)),
-            "61:01"
+            "1:01:01"
+        );
+        assert_eq!(
+            format_duration(std::time::Duration::from_secs(4710)),
+            "1:18:30"
+        );
+    }
+
+    #[test]
+    fn test_generate_html_emits_verification_meta() {
+        let album = test_album(
+            r#"
+[site.verification]
+google = "abc123"
+"custom-verify" = "x\"y"
+"#,
+        );
+        let html = generate_html(&album, None, None, false, None, "");
+
+        assert!(html.contains(r#"<meta name="google-site-verification" content="abc123">"#));
+        // Unknown providers are emitted verbatim, with values escaped
+        assert!(html.contains(r#"<meta name="custom-verify" content="x&quot;y">"#));
+    }
+
+    #[test]
+    fn test_generate_html_track_chapters() {
+        use release_kit_core::types::Chapter;
+        use std::time::Duration;
+
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"<ol class="track-chapters""#));
+        assert!(!html.contains(r#"id="chapter-markers""#));
+
+        album.tracks[0].duration = Some(Duration::from_secs(3720));
+        album.tracks[0].chapters = vec![
+            Chapter {
+                name: "Intro".to_string(),
+                start: Duration::ZERO,
+            },
+            Chapter {
+                name: "Drop & <Build>".to_string(),
+                start: Duration::from_secs(750),
+            },
+            Chapter {
+                name: "Outro".to_string(),
+                start: Duration::from_secs(3605),
+            },
+        ];
+        let html = generate_html(&album, None, None, false, None, "");
+
+        // Listed after the row, tied to it by index, with seek offsets in seconds
+        let row = html.find(r#"data-index="0""#).unwrap();
+        let list = html
+            .find(r#"<ol class="track-chapters" data-track="0" data-duration="3720""#)
+            .unwrap();
+        assert!(row < list);
+        assert!(html.contains(
+            r#"<button type="button" class="chapter" data-start="0"><span class="chapter-time">0:00</span> Intro</button>"#
+        ));
+        assert!(html.contains(
+            r#"<button type="button" class="chapter" data-start="750"><span class="chapter-time">12:30</span> Drop &amp; &lt;Build&gt;</button>"#
+        ));
+        assert!(html.contains(r#"data-start="3605"><span class="chapter-time">1:00:05</span>"#));
+        assert!(html.contains(r#"<div class="chapter-markers" id="chapter-markers"></div>"#));
+
+        let js = generate_player_js();
+        assert!(js.contains("showChapters(index) {"));
+        assert!(js.contains("(start / duration) * 100"));
+        assert!(js.contains("this.audio.currentTime = start;"));
+    }
+
+    #[test]
+    fn test_generate_html_peak_bars() {
+        let mut album = test_album("");
+        album.tracks[0].peaks = Some(vec![0, 3, 9, 5]);
+
+        // Off by default, even with peaks measured
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains("data-peaks"));
+        assert!(!html.contains(r#"id="progress-peaks""#));
+
+        album.site.peak_bars = true;
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"data-src="/audio/01-test.flac" data-peaks="0395""#));
+        assert!(html.contains(
+            r#"<div class="progress-peaks" id="progress-peaks" aria-hidden="true"></div>"#
+        ));
+        assert!(generate_player_js().contains("showPeaks(peaks) {"));
+    }
+
+    #[test]
+    fn test_generate_html_genre_pills() {
+        let mut album = test_album("");
+        album.metadata.genre = vec![
+            "experimental".to_string(),
+            "drone".to_string(),
+            " ".to_string(),
+            "<noise>".to_string(),
+        ];
+
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<li class="genre">experimental</li>"#));
+        assert!(html.contains(r#"<li class="genre">drone</li>"#));
+        assert!(html.contains(r#"<li class="genre">&lt;noise&gt;</li>"#));
+        assert_eq!(html.matches(r#"<li class="genre">"#).count(), 3);
+        assert!(
+            html.contains(r#"<meta name="keywords" content="experimental, drone, &lt;noise&gt;">"#)
+        );
+
+        // No genres, no empty container
+        album.metadata.genre.clear();
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"class="genres""#));
+        assert!(!html.contains(r#"name="keywords""#));
+    }
+
+    #[test]
+    fn test_generate_html_social_meta() {
+        let cover = CoverImage {
+            dimensions: Some((1400, 1400)),
+            ..CoverImage::new("cover.jpg")
+        };
+        let html = generate_html(
+            &test_album(""),
+            Some(&cover),
+            None,
+            false,
+            None,
+            "/albums/foo",
+        );
+
+        assert!(html.contains(r#"<meta property="og:type" content="music.album">"#));
+        assert!(html.contains(r#"<meta property="og:title" content="Test Album">"#));
+        assert!(html.contains(r#"<meta property="og:description" content="A test album">"#));
+        assert!(html.contains(
+            r#"<meta property="og:url" content="https://test.example.com/albums/foo/">"#
+        ));
+        assert!(html.contains(r#"<meta property="og:image" content="https://test.example.com/albums/foo/artwork/cover.jpg">"#));
+        assert!(html.contains(r#"<meta property="og:image:width" content="1400">"#));
+        assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+        assert!(html.contains(r#"<meta name="twitter:title" content="Test Album">"#));
+        assert!(html.contains(r#"<meta name="twitter:image" content="https://test.example.com/albums/foo/artwork/cover.jpg">"#));
+
+        // Without a cover there is no image, and a plain summary card
+        let mut album = test_album("");
+        album.metadata.title = "Rock & \"Roll\"".to_string();
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(
+            html.contains(r#"<meta property="og:title" content="Rock &amp; &quot;Roll&quot;">"#)
+        );
+        assert!(html.contains(r#"<meta name="twitter:card" content="summary">"#));
+        assert!(!html.contains("og:image"));
+    }
+
+    #[test]
+    fn test_generate_html_without_verification() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(!html.contains("verification"));
+    }
+
+    #[test]
+    fn test_generate_html_footer_default_copyright() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(html.contains(r#"<div class="footer-copyright">© 2025 Test Artist</div>"#));
+        assert!(html.contains(r#"<div class="footer-attribution">Generated by release-kit</div>"#));
+        assert!(!html.contains(r#"<nav class="footer-links">"#));
+    }
+
+    #[test]
+    fn test_generate_html_footer_credits() {
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains("footer-credits\">"));
+
+        album.credits.insert(
+            "art_by".to_string(),
+            vec!["Ana".to_string(), "Bo".to_string()],
+        );
+        album
+            .credits
+            .insert("engineer".to_string(), vec!["Jo <3".to_string()]);
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(
+            r#"<section class="footer-credits"><h3>Credits</h3><dl><dt>Art by</dt><dd>Ana, Bo</dd><dt>Engineer</dt><dd>Jo &lt;3</dd></dl></section>"#
+        ));
+        // Credits come before the copyright line
+        assert!(
+            html.find("footer-credits\">").unwrap() < html.find("footer-copyright\">").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generate_html_footer_custom_copyright_and_links() {
+        let album = test_album(
+            r#"
+[site.footer]
+copyright = "© 2025 Label & Co"
+
+[[site.footer.links]]
+label = "Bandcamp"
+url = "https://artist.bandcamp.com"
+
+[[site.footer.links]]
+label = "Contact"
+url = "mailto:me@example.com"
+"#,
+        );
+        let html = generate_html(&album, None, None, false, None, "");
+
+        assert!(html.contains(r#"<div class="footer-copyright">© 2025 Label &amp; Co</div>"#));
+        assert!(html.contains(r#"<a href="https://artist.bandcamp.com">Bandcamp</a>"#));
+        assert!(html.contains(r#"<a href="mailto:me@example.com">Contact</a>"#));
+        assert!(html.contains("footer-attribution"));
+    }
+
+    #[test]
+    fn test_generate_html_merch_grid() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(!html.contains(r#"<div class="merch">"#));
+
+        let album = test_album(
+            r#"
+[[merch.items]]
+name = "Vinyl <LP> & \"Sleeve\""
+url = "https://shop.example.com/lp?size=12&color=black"
+image = "merch/vinyl.jpg"
+price = "$25"
+
+[[merch.items]]
+name = "Tote"
+url = "https://shop.example.com/tote"
+"#,
+        );
+        let html = generate_html(&album, None, None, false, None, "/albums/foo");
+
+        assert!(html.contains(r#"<div class="merch">"#));
+        assert!(html.contains(r#"<li class="merch-item"><a href="https://shop.example.com/lp?size=12&amp;color=black"><img src="/albums/foo/merch/vinyl.jpg" alt="Vinyl &lt;LP&gt; &amp; &quot;Sleeve&quot;" loading="lazy"><span class="merch-name">Vinyl &lt;LP&gt; &amp; &quot;Sleeve&quot;</span><span class="merch-price">$25</span></a></li>"#));
+        assert!(html.contains(r#"<li class="merch-item"><a href="https://shop.example.com/tote"><span class="merch-name">Tote</span></a></li>"#));
+        // Below the tracklist
+        assert!(html.find(r#"<div id="track-list">"#) < html.find(r#"<div class="merch">"#));
+    }
+
+    #[test]
+    fn test_generate_html_gallery() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(!html.contains(r#"<div class="gallery""#));
+
+        let mut album = test_album(
+            r#"
+[artwork]
+gallery = ["artwork/1.jpg", "photos/studio.png"]
+"#,
+        );
+        album.metadata.title = "Live <at> \"The Hall\" & More".to_string();
+        let html = generate_html(&album, None, None, false, None, "/albums/foo");
+
+        assert!(html.contains(r#"<div class="gallery" id="gallery">"#));
+        assert!(html.contains(r##"<li class="gallery-item"><a href="#gallery-1"><img src="/albums/foo/gallery/1.jpg" alt="Live &lt;at&gt; &quot;The Hall&quot; &amp; More - image 1 of 2" loading="lazy"></a></li>"##));
+        assert!(html.contains(r##"<li class="gallery-item"><a href="#gallery-2"><img src="/albums/foo/gallery/studio.png" alt="Live &lt;at&gt; &quot;The Hall&quot; &amp; More - image 2 of 2" loading="lazy"></a></li>"##));
+        assert!(html.contains(r##"<a class="lightbox" id="gallery-2" href="#gallery"><img src="/albums/foo/gallery/studio.png""##));
+        assert!(!html.contains("<at>"));
+        // Separate from the cover, below the tracklist
+        assert!(html.find(r#"<div id="track-list">"#) < html.find(r#"<div class="gallery""#));
+    }
+
+    #[test]
+    fn test_render_markdown_strips_scripts_and_handlers() {
+        let html = render_markdown(
+            "Recorded **live** in one take.\n\n<script>alert(1)</script>\n\n<img src=x onerror=\"alert(2)\"> and <b onclick=\"alert(3)\">inline</b>\n\n[site](https://example.com) [bad](javascript:alert(4)) [sneaky](JaVaScRiPt:alert(5))",
+        );
+
+        assert!(html.contains("<strong>live</strong>"));
+        assert!(html.contains(r#"<a href="https://example.com">site</a>"#));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.to_lowercase().contains("javascript"));
+    }
+
+    #[test]
+    fn test_is_safe_url() {
+        assert!(is_safe_url("https://example.com"));
+        assert!(is_safe_url("mailto:me@example.com"));
+        assert!(is_safe_url("notes/track.md"));
+        assert!(is_safe_url("#credits"));
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url(" JavaScript:alert(1)"));
+        assert!(!is_safe_url("data:text/html,<script>"));
+    }
+
+    #[test]
+    fn test_generate_html_liner_notes() {
+        let mut album = test_album("");
+        let mut second = album.tracks[0].clone();
+        second.title = "Second".to_string();
+        album.tracks.push(second);
+
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"<details class="liner-notes">"#));
+        assert!(!html.contains(r#"<details class="track-notes">"#));
+
+        let notes = LinerNotes {
+            album: Some("Made in **winter**.<script>alert(1)</script>".to_string()),
+            tracks: vec![Some("The *first* one.".to_string()), None],
+        };
+        let html = generate_html(&album, None, Some(&notes), false, None, "");
+
+        assert!(html.contains(r#"<details class="liner-notes">"#));
+        assert!(html.contains("<summary>Liner Notes</summary>"));
+        assert!(html.contains("<strong>winter</strong>"));
+        assert!(!html.contains("alert(1)"));
+        // Only the first track has a notes panel, right after its row
+        assert_eq!(html.matches(r#"<details class="track-notes">"#).count(), 1);
+        let panel = html.find(r#"<details class="track-notes">"#).unwrap();
+        assert!(html.find(r#"data-index="0""#).unwrap() < panel);
+        assert!(panel < html.find(r#"data-index="1""#).unwrap());
+        assert!(html.contains("<em>first</em>"));
+    }
+
+    #[test]
+    fn test_normalize_base_path() {
+        assert_eq!(normalize_base_path(""), "");
+        assert_eq!(normalize_base_path("/"), "");
+        assert_eq!(normalize_base_path("/albums/foo"), "/albums/foo");
+        assert_eq!(normalize_base_path("albums/foo/"), "/albums/foo");
+    }
+
+    #[test]
+    fn test_generate_html_prefixes_base_path() {
+        let html = generate_html(
+            &test_album(""),
+            Some(&CoverImage::new("cover.jpg")),
+            None,
+            false,
+            None,
+            "/albums/foo/",
+        );
+        assert!(html.contains(r#"data-src="/albums/foo/audio/01-test.flac""#));
+        assert!(html.contains(r#"src="/albums/foo/artwork/cover.jpg""#));
+        assert!(html.contains(r#"<script src="/albums/foo/player.js"></script>"#));
+    }
+
+    #[test]
+    fn test_generate_html_cover_dimensions_and_srcset() {
+        let cover = CoverImage {
+            file: "cover.jpg".to_string(),
+            dimensions: Some((1200, 1200)),
+            variants: vec![
+                ("cover-300.jpg".to_string(), 300),
+                ("cover-600.jpg".to_string(), 600),
+            ],
+            ..Default::default()
+        };
+        let html = generate_html(&test_album(""), Some(&cover), None, false, None, "");
+        assert!(html.contains(r#"<img src="/artwork/cover.jpg" srcset="/artwork/cover-300.jpg 300w, /artwork/cover-600.jpg 600w, /artwork/cover.jpg 1200w" sizes="300px" width="1200" height="1200" alt="Album cover" class="cover-art">"#));
+        assert!(html.contains(
+            r#"sizes="96px" width="1200" height="1200" alt="Album cover" class="player-album-art">"#
+        ));
+
+        // Small covers get dimensions but no srcset
+        let cover = CoverImage {
+            dimensions: Some((250, 250)),
+            ..CoverImage::new("cover.jpg")
+        };
+        let html = generate_html(&test_album(""), Some(&cover), None, false, None, "");
+        assert!(html.contains(r#"<img src="/artwork/cover.jpg" width="250" height="250" alt="Album cover" class="cover-art">"#));
+        assert!(!html.contains("srcset=\"/artwork/cover.jpg"));
+    }
+
+    #[test]
+    fn test_generate_html_explicit_badge() {
+        let mut album = test_album("");
+        let mut clean = album.tracks[0].clone();
+        clean.title = "Clean Track".to_string();
+        clean.file = "audio/02-clean.flac".into();
+        album.tracks[0].explicit = true;
+        album.tracks.push(clean);
+
+        let html = generate_html(&album, None, None, false, None, "");
+
+        assert!(html.contains(
+            r#"<span class="track-title">Test Track <span class="explicit-badge" title="Explicit" aria-label="Explicit">E</span></span>"#
+        ));
+        assert!(html.contains(r#"<span class="track-title">Clean Track</span>"#));
+    }
+
+    #[test]
+    fn test_generate_html_default_language_ltr() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(html.contains(r#"<html lang="en" dir="ltr">"#));
+    }
+
+    #[test]
+    fn test_generate_html_rtl_language() {
+        let album = test_album(r#"language = "ar-EG""#);
+        let html = generate_html(&album, None, None, false, None, "");
+
+        assert!(html.contains(r#"<html lang="ar-EG" dir="rtl">"#));
+        // Mirrored styles are present for the RTL layout
+        assert!(html.contains(r#"[dir="rtl"] .track.playing {"#));
+        assert!(html.contains("border-right: 3px solid var(--primary);"));
+        assert!(html.contains("transform: translateX(-4px);"));
+    }
+
+    #[test]
+    fn test_generate_html_preview_clip_for_paid_downloads() {
+        let mut album = test_album("");
+        album.tracks[0].preview_clip = Some(release_kit_core::types::PreviewClip {
+            start: std::time::Duration::from_secs(30),
+            end: std::time::Duration::from_secs(60),
+        });
+
+        // Free downloads: full track
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"data-src="/audio/01-test.flac""#));
+
+        // Paid downloads: preview clip, even when audio is on a CDN
+        album.distribution.download_enabled = true;
+        album.distribution.download_price = Some(5.0);
+        let html = generate_html(
+            &album,
+            None,
+            None,
+            false,
+            Some("https://cdn.example.com"),
+            "",
+        );
+        assert!(html.contains(r#"data-src="/audio/test-track-preview.mp3""#));
+        assert!(!html.contains(r#"data-src="https://cdn.example.com/audio/01-test.flac""#));
+    }
+
+    #[test]
+    fn test_generate_html_signed_streams_through_worker() {
+        let mut album = test_album("");
+        album.hosting.cloudflare.stream_worker_url =
+            Some("https://stream.example.com/".to_string());
+
+        let html = generate_html(
+            &album,
+            None,
+            None,
+            false,
+            Some("https://cdn.example.com"),
+            "",
+        );
+        assert!(html.contains(
+            r#"data-src="https://stream.example.com/stream/01-test.flac" data-sign="https://stream.example.com/sign/01-test.flac""#
+        ));
+        assert!(!html.contains("https://cdn.example.com/audio/01-test.flac"));
+        assert!(generate_player_js().contains("fetch(track.dataset.sign)"));
+
+        // Local builds and previews play the bundled audio directly
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"data-src="/audio/01-test.flac" data-title"#));
+        assert!(!html.contains("data-sign="));
+    }
+
+    #[test]
+    fn test_generate_html_accent_color_sets_primary() {
+        // test_album uses accent_color = "#ff6b35"
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(html.contains("--primary: #ff6b35;"));
+        assert!(html.contains("--primary-rgb: 255, 107, 53;"));
+        assert!(html.contains("--base-100: #1a1a1f;"));
+        assert!(!html.contains("#00ff88"));
+
+        // Short form is expanded
+        let mut album = test_album("");
+        album.site.accent_color = "#0af".to_string();
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains("--primary: #00aaff;"));
+
+        // The oscilloscope follows the same variable
+        assert!(generate_player_js().contains("getPropertyValue('--primary-rgb')"));
+    }
+
+    #[test]
+    fn test_generate_html_rejects_css_injection_in_accent_color() {
+        let mut album = test_album("");
+        album.site.accent_color = "red; } body { background: url(evil.png)".to_string();
+
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains("red;"));
+        assert!(!html.contains("evil.png"));
+        // Falls back to the theme's own primary
+        assert!(html.contains("--primary: #00ff88;"));
+        assert_eq!(theme_warnings(&album.site).len(), 1);
+
+        album.site.accent_color = "#12345g".to_string();
+        assert!(parse_hex_color(&album.site.accent_color).is_none());
+        assert_eq!(parse_hex_color("#ff6b35ff"), Some([0xff, 0x6b, 0x35]));
+        assert!(parse_hex_color("#ff6b35f").is_none());
+        assert_eq!(parse_hex_color(" #FF6B35 "), Some([0xff, 0x6b, 0x35]));
+    }
+
+    #[test]
+    fn test_generate_html_progress_hover_tooltip() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(
+            html.contains(r#"<div class="progress-tooltip" id="progress-tooltip" hidden></div>"#)
+        );
+
+        let js = generate_player_js();
+        assert!(js.contains(
+            "this.progressBar.addEventListener('mousemove', (e) => this.showHoverTime(e));"
+        ));
+        assert!(js.contains("showHoverTime(e) {"));
+        assert!(js.contains("getElementById('progress-tooltip')"));
+    }
+
+    #[test]
+    fn test_generate_html_default_track_preselected() {
+        use release_kit_core::types::DefaultTrack;
+
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(!html.contains(r#"class="track selected""#));
+        assert!(
+            html.contains(r#"<div class="player-track" id="player-track">Select a track</div>"#)
+        );
+
+        let mut album = test_album("");
+        album.tracks.push(album.tracks[0].clone());
+        album.tracks[1].title = "The <Single>".to_string();
+        album.distribution.default_track = Some(DefaultTrack::Slug("the-single".to_string()));
+        let html = generate_html(&album, None, None, false, None, "");
+
+        assert_eq!(html.matches(r#"class="track selected""#).count(), 1);
+        assert!(html.contains(r#"<div class="track selected" data-index="1""#));
+        assert!(html.contains(r#"<div class="track" data-index="0""#));
+        assert!(
+            html.contains(
+                r#"<div class="player-track" id="player-track">The &lt;Single&gt;</div>"#
+            )
+        );
+        // Selected, not autoplayed: play starts there
+        assert!(
+            generate_player_js().contains("this.playTrack(Math.max(0, this.defaultTrackIndex));")
+        );
+        assert!(!html.contains("autoplay"));
+    }
+
+    #[test]
+    fn test_generate_html_accessible_player_controls() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+
+        assert!(html.contains(r#"data-title="Test Track" role="button" tabindex="0">"#));
+        assert!(
+            html.contains(
+                r#"<button class="player-btn" id="prev-btn" aria-label="Previous track">"#
+            )
+        );
+        assert!(
+            html.contains(r#"<button class="player-btn play" id="play-btn" aria-label="Play">"#)
+        );
+        assert!(
+            html.contains(r#"<button class="player-btn" id="next-btn" aria-label="Next track">"#)
+        );
+        assert!(html.contains(r#"<div class="progress-bar" id="progress-bar" role="slider" tabindex="0" aria-label="Seek" aria-valuemin="0" aria-valuemax="0" aria-valuenow="0" aria-valuetext="0:00">"#));
+        // Icons are decoration: the buttons' labels name them
+        assert!(!html.contains(r#"fill="currentColor">"#));
+
+        let js = generate_player_js();
+        assert!(js.contains("track.addEventListener('keydown', (e) => {"));
+        assert!(js.contains("if (e.key === 'Enter' || e.key === ' ') {"));
+        assert!(js.contains(
+            "this.progressBar.addEventListener('keydown', (e) => this.seekWithKeys(e));"
+        ));
+        assert!(js.contains("this.progressBar.setAttribute('aria-valuenow'"));
+        assert!(
+            js.contains("this.playBtn.setAttribute('aria-label', isPlaying ? 'Pause' : 'Play');")
+        );
+    }
+
+    #[test]
+    fn test_generate_html_gapless_player() {
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains("data-gapless"));
+        assert!(!html.contains(r#"id="audio-next""#));
+
+        album.distribution.gapless = true;
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(
+            html.contains(r#"<div class="player" data-seek-seconds="10" data-gapless="true">"#)
+        );
+        assert!(html.contains(r#"<audio id="audio-next" preload="auto"></audio>"#));
+        assert!(generate_player_js().contains("dataset.gapless === 'true'"));
+    }
+
+    #[test]
+    fn test_generate_html_audio_preload() {
+        use release_kit_core::types::AudioPreload;
+
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<audio id="audio" preload="metadata"></audio>"#));
+
+        album.distribution.preload = AudioPreload::None;
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<audio id="audio" preload="none"></audio>"#));
+
+        album.distribution.preload = AudioPreload::Auto;
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<audio id="audio" preload="auto"></audio>"#));
+    }
+
+    #[test]
+    fn test_generate_html_theme_palettes() {
+        let mut album = test_album("");
+        album.site.accent_color = String::new();
+        album.site.theme = "light".to_string();
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains("--base-100: #f4f4f0;"));
+        assert!(html.contains("--primary: #008f5a;"));
+        assert!(theme_warnings(&album.site).is_empty());
+
+        album.site.theme = "High-Contrast".to_string();
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains("--base-100: #000000;"));
+        assert!(html.contains("--primary: #ffee00;"));
+
+        // Unknown names fall back to the default palette, with a warning
+        album.site.theme = "vaporwave".to_string();
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains("--base-100: #1a1a1f;"));
+        assert_eq!(
+            theme_warnings(&album.site),
+            vec![
+                "Unknown theme 'vaporwave' - using default (themes: default, light, minimal, high-contrast)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_html_downloads_only_when_enabled() {
+        let mut album = test_album("");
+        album.distribution.download_formats = vec!["flac".to_string(), "mp3-320".to_string()];
+
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"<div class="downloads">"#));
+        assert!(!html.contains(".zip"));
+
+        album.distribution.download_enabled = true;
+        let html = generate_html(
+            &album,
+            None,
+            None,
+            false,
+            Some("https://cdn.example.com"),
+            "",
+        );
+        assert!(html.contains(r#"<div class="downloads">"#));
+        assert!(html.contains(r#"<div class="download-price">Free download</div>"#));
+        assert!(html.contains(
+            r#"<a class="download-album" href="/download/test-album.zip" download>Full album (.zip)</a>"#
+        ));
+        // Not transcoded: only the original's format is offered
+        assert!(html.contains(
+            r#"<span class="download-track-title">Test Track</span> <a href="https://cdn.example.com/audio/01-test.flac" download>FLAC</a></li>"#
+        ));
+
+        // Transcoded: every format, lossy variants from the local build
+        album.tracks[0].stream_file = Some("01-test.mp3".to_string());
+        album.distribution.download_price = Some(7.0);
+        album.distribution.pay_what_you_want = true;
+        let html = generate_html(&album, None, None, false, None, "/albums/test");
+        assert!(html.contains(
+            r#"<a href="/albums/test/audio/01-test.flac" download>FLAC</a> <a href="/albums/test/audio/01-test.mp3" download>MP3-320</a>"#
+        ));
+        assert!(html.contains("Name your price (suggested $7.00)"));
+        assert!(html.contains(r#"href="/albums/test/download/test-album.zip""#));
+    }
+
+    #[test]
+    fn test_generate_html_streams_stream_copy() {
+        let mut album = test_album("");
+        album.distribution.download_enabled = true;
+        album.tracks[0].stream_copy = Some("01-test.stream.opus".to_string());
+
+        // Local build: the player gets the copy, downloads the master
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"data-src="/audio/01-test.stream.opus""#));
+        assert!(html.contains(r#"<a href="/audio/01-test.flac" download>FLAC</a>"#));
+        assert!(!html.contains(r#"href="/audio/01-test.stream.opus""#));
+
+        // CDN: the copy was uploaded beside the master
+        let html = generate_html(
+            &album,
+            None,
+            None,
+            false,
+            Some("https://cdn.example.com"),
+            "",
+        );
+        assert!(html.contains(r#"data-src="https://cdn.example.com/audio/01-test.stream.opus""#));
+        assert!(
+            html.contains(
+                r#"<a href="https://cdn.example.com/audio/01-test.flac" download>FLAC</a>"#
+            )
+        );
+
+        // Under an r2_prefix, URLs follow the prefixed keys
+        album.hosting.cloudflare.r2_prefix = Some("v2".to_string());
+        let html = generate_html(
+            &album,
+            None,
+            None,
+            false,
+            Some("https://cdn.example.com"),
+            "",
+        );
+        assert!(
+            html.contains(r#"data-src="https://cdn.example.com/v2/audio/01-test.stream.opus""#)
+        );
+        assert!(html.contains(r#"href="https://cdn.example.com/v2/audio/01-test.flac""#));
+    }
+
+    #[test]
+    fn test_generate_html_track_sort_title() {
+        let mut album = test_album("");
+        for (title, file) in [("charlie", "03-c.flac"), ("Alpha", "02-a.flac")] {
+            let mut track = album.tracks[0].clone();
+            track.title = title.to_string();
+            track.file = format!("audio/{}", file).into();
+            album.tracks.push(track);
+        }
+        album.tracks[0].title = "Bravo".to_string();
+        let row =
+            |html: &str, index: usize| html.find(&format!(r#"data-index="{}""#, index)).unwrap();
+
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(row(&html, 0) < row(&html, 1) && row(&html, 1) < row(&html, 2));
+
+        // Rows reorder alphabetically; each keeps its index, audio and number
+        album.site.track_sort = release_kit_core::types::TrackSort::Title;
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(row(&html, 2) < row(&html, 0) && row(&html, 0) < row(&html, 1));
+        assert!(html.contains(r#"data-index="2" data-src="/audio/02-a.flac""#));
+        assert!(html.contains(r#"data-index="0" data-src="/audio/01-test.flac""#));
+        let alpha = &html[row(&html, 2)..];
+        assert!(
+            alpha
+                .split(r#"class="track-number">"#)
+                .nth(1)
+                .unwrap()
+                .starts_with("03<")
+        );
+    }
+
+    #[test]
+    fn test_generate_html_explicit_track_number() {
+        let mut album = test_album("");
+        let mut next = album.tracks[0].clone();
+        next.title = "Next Track".to_string();
+        next.file = "audio/02-next.flac".into();
+        album.tracks.push(next);
+
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<span class="track-number">01</span>"#));
+        assert!(html.contains(r#"<span class="track-number">02</span>"#));
+
+        // A continued series starting at 7; the player index stays positional
+        album.tracks[0].track_number = Some(7);
+        album.tracks[1].track_number = Some(8);
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<span class="track-number">07</span>"#));
+        assert!(html.contains(r#"<span class="track-number">08</span>"#));
+        assert!(!html.contains(r#"<span class="track-number">01</span>"#));
+        assert!(html.contains(r#"data-index="0""#));
+    }
+
+    #[test]
+    fn test_generate_html_bpm_and_key() {
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"<span class="track-meta">"#));
+
+        album.tracks[0].bpm = Some(128.0);
+        album.tracks[0].key = Some("F#m".to_string());
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(
+            r#"<span class="track-title">Test Track <span class="track-meta">128 BPM · F#m</span></span>"#
+        ));
+
+        album.tracks[0].bpm = Some(92.5);
+        album.tracks[0].key = None;
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"<span class="track-meta">92.5 BPM</span>"#));
+    }
+
+    #[test]
+    fn test_generate_html_track_credits() {
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"<span class="track-credits">"#));
+
+        album.tracks[0].producer = vec!["Ada".to_string(), "Lin & Co".to_string()];
+        album.tracks[0].mastered_by = vec!["Sam".to_string()];
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(
+            r#"<span class="track-credits">Produced by Ada, Lin &amp; Co · Mastered by Sam</span></span>"#
+        ));
+    }
+
+    #[test]
+    fn test_generate_html_track_artists() {
+        let mut album = test_album("");
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains(r#"<span class="track-artist">"#));
+        assert!(html.contains(
+            r#"<div class="player-artist" id="player-artist" data-album-artist="Test Artist">Test Artist</div>"#
+        ));
+
+        album.tracks[0].artist = Some("Ada & Lin".to_string());
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(
+            html.contains(r#"data-title="Test Track" data-artist="Ada &amp; Lin" role="button""#)
+        );
+        assert!(html.contains(r#"<span class="track-artist">Ada &amp; Lin</span></span>"#));
+        // The player names the album artist until something plays...
+        assert!(html.contains(r#"data-album-artist="Test Artist">Test Artist</div>"#));
+
+        // ...or the default track's own artist
+        album.distribution.default_track = Some(release_kit_core::types::DefaultTrack::Index(0));
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(html.contains(r#"data-album-artist="Test Artist">Ada &amp; Lin</div>"#));
+    }
+
+    #[test]
+    fn test_generate_html_default_background_and_glow() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(html.contains("repeating-linear-gradient("));
+        assert!(html.contains("text-shadow: 0 0 20px var(--primary);"));
+        assert!(html.contains("box-shadow: 0 0 10px var(--primary);"));
+    }
+
+    #[test]
+    fn test_generate_html_flat_look() {
+        let html = generate_html(
+            &test_album("background = \"solid\"\nglow = false"),
+            None,
+            None,
+            false,
+            None,
+            "",
+        );
+        assert!(!html.contains("repeating-linear-gradient("));
+        assert!(!html.contains("0 0 20px var(--primary)"));
+        assert!(!html.contains("0 0 10px var(--primary)"));
+        assert!(html.contains("background-color: var(--base-100);\n            padding: 2rem;"));
+
+        let html = generate_html(
+            &test_album("background = \"gradient\""),
+            None,
+            None,
+            false,
+            None,
+            "",
+        );
+        assert!(!html.contains("repeating-linear-gradient("));
+        assert!(
+            html.contains("linear-gradient(160deg, var(--base-200) 0%, var(--base-100) 60%) fixed")
+        );
+        assert!(html.contains("text-shadow: 0 0 20px var(--primary);"));
+    }
+
+    #[test]
+    fn test_generate_html_disc_headings_only_for_multi_disc() {
+        let mut album = test_album("");
+        let mut second = album.tracks[0].clone();
+        second.title = "Second Disc Opener".to_string();
+        second.file = "audio/02-test.flac".into();
+        album.tracks.push(second);
+
+        // Everything on disc 1: flat list
+        album.tracks[1].disc = Some(1);
+        let html = generate_html(&album, None, None, false, None, "");
+        assert!(!html.contains("disc-heading\">"));
+        assert!(html.contains(r#"<span class="track-number">02</span>"#));
+
+        album.tracks[1].disc = Some(2);
+        let html = generate_html(&album, None, None, false, None, "");
+        let disc1 = html
+            .find(r#"<h3 class="disc-heading">Disc 1</h3>"#)
+            .unwrap();
+        let first = html.find(r#"data-title="Test Track""#).unwrap();
+        let disc2 = html
+            .find(r#"<h3 class="disc-heading">Disc 2</h3>"#)
+            .unwrap();
+        let second = html.find(r#"data-title="Second Disc Opener""#).unwrap();
+        assert!(disc1 < first && first < disc2 && disc2 < second);
+        // Numbering restarts, but player indices run across discs
+        assert!(!html.contains(r#"<span class="track-number">02</span>"#));
+        assert!(html.contains(r#"data-index="1""#));
+    }
+
+    #[test]
+    fn test_generate_html_keyboard_help_overlay() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(html.contains(r#"<button class="player-btn help" id="help-btn" aria-label="Keyboard shortcuts" aria-haspopup="dialog" aria-controls="shortcut-help">?</button>"#));
+        assert!(html.contains(r#"<div class="shortcut-help" id="shortcut-help" role="dialog" aria-modal="true" aria-labelledby="shortcut-help-title" hidden>"#));
+        assert!(html.contains("<dt><kbd>Space</kbd></dt><dd>Play / pause</dd>"));
+        assert!(html.contains("<dt><kbd>↑</kbd> <kbd>↓</kbd></dt><dd>Volume up / down</dd>"));
+        assert!(html.contains(r#"id="shortcut-help-close""#));
+
+        let js = generate_player_js();
+        assert!(js.contains("if (e.key === '?' && this.helpOverlay) {"));
+        assert!(js.contains("if (e.key === 'Escape' || e.key === '?') {"));
+        assert!(js.contains("this.trapHelpFocus(e);"));
+        assert!(js.contains("this.changeVolume(0.1);"));
+    }
+
+    #[test]
+    fn test_generate_html_seek_keys() {
+        let html = generate_html(&test_album(""), None, None, false, None, "");
+        assert!(html.contains(r#"<div class="player" data-seek-seconds="10">"#));
+        assert!(html.contains("<dd>Seek back / forward 10s</dd>"));
+
+        let html = generate_html(
+            &test_album("seek_seconds = 30"),
+            None,
+            None,
+            false,
+            None,
+            "",
+        );
+        assert!(html.contains(r#"data-seek-seconds="30""#));
+        assert!(html.contains("<dd>Seek back / forward 30s</dd>"));
+
+        let js = generate_player_js();
+        assert!(js.contains(
+            "this.seekSeconds = parseFloat(player && player.dataset.seekSeconds) || 10;"
+        ));
+        assert!(js.contains("(e.key === 'j' || e.key === ',')"));
+        assert!(js.contains("this.seekBy(-this.seekSeconds);"));
+        assert!(js.contains("(e.key === 'l' || e.key === '.')"));
+        assert!(js.contains("this.seekBy(this.seekSeconds);"));
+        // Clamped to [0, duration]
+        assert!(js.contains(
+            "Math.min(\n            this.audio.duration,\n            Math.max(0, this.audio.currentTime + seconds)\n        )"
+        ));
+        // Track navigation keeps the arrow keys
+        assert!(js.contains("} else if (e.code === 'ArrowLeft') {"));
+    }
+
+    #[test]
+    fn test_generate_html_keyboard_help_disabled() {
+        let html = generate_html(
+            &test_album("keyboard_help = false"),
+            None,
+            None,
+            false,
+            None,
+            "",
         );
+        assert!(!html.contains(r#"id="help-btn""#));
+        assert!(!html.contains(r#"id="shortcut-help""#));
     }
 }
@@ -0,0 +1,64 @@
+//! Fixtures shared by the command tests
+
+use std::fs;
+use std::path::Path;
+
+/// A minimal valid album.toml with `tracks` between the `[site]` keys and
+/// `[distribution]`, so it can carry `[[track]]` entries and, ahead of them,
+/// extra `[site]` keys
+pub fn album_toml(tracks: &str) -> String {
+    format!(
+        r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+{tracks}
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+    )
+}
+
+/// Write a silent 8kHz mono 8-bit PCM WAV of the given length
+pub fn write_wav(path: &Path, seconds: u32) {
+    let sample_rate: u32 = 8000;
+    let data_len = sample_rate * seconds;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes()); // byte rate
+    wav.extend_from_slice(&1u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&8u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 128);
+    fs::write(path, wav).unwrap();
+}
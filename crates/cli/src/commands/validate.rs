@@ -1,13 +1,27 @@
 use anyhow::{Context, Result};
+use lofty::file::FileType;
 use lofty::prelude::*;
 use lofty::probe::Probe;
-use release_kit_core::config::parse_album_toml;
+use release_kit_core::artwork::{detect_cover_art, is_standard_cover_name};
+use release_kit_core::config::{album_toml_schema, parse_album_toml};
+use release_kit_core::types::Album;
+use release_kit_validator::{AlbumSummary, ValidationReport};
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::deploy::cdn_cors_warnings;
+use super::init::has_audio_extension;
 
 /// Validation result tracker
 struct ValidationResults {
     errors: Vec<String>,
     warnings: Vec<String>,
+    /// Things checked that need no action
+    info: Vec<String>,
+    /// Warnings fail validation too (`validate --strict`)
+    strict: bool,
+    /// Suppress progress output (`validate --format json`)
+    quiet: bool,
 }
 
 impl ValidationResults {
@@ -15,6 +29,9 @@ impl ValidationResults {
         Self {
             errors: Vec::new(),
             warnings: Vec::new(),
+            info: Vec::new(),
+            strict: false,
+            quiet: false,
         }
     }
 
@@ -26,8 +43,50 @@ impl ValidationResults {
         self.warnings.push(msg.into());
     }
 
+    fn note(&mut self, msg: impl Into<String>) {
+        self.info.push(msg.into());
+    }
+
+    /// Print a progress line, unless quiet
+    fn step(&self, msg: impl AsRef<str>) {
+        if !self.quiet {
+            println!("{}", msg.as_ref());
+        }
+    }
+
     fn is_valid(&self) -> bool {
-        self.errors.is_empty()
+        self.errors.is_empty() && (!self.strict || self.warnings.is_empty())
+    }
+
+    /// Errors plus any warnings promoted by strict mode
+    fn failure_count(&self) -> usize {
+        if self.strict {
+            self.errors.len() + self.warnings.len()
+        } else {
+            self.errors.len()
+        }
+    }
+
+    /// Convert to the validator crate's report shape; strict mode reports
+    /// warnings as errors
+    fn into_report(self, album: &Album) -> ValidationReport {
+        let (mut errors, warnings) = (self.errors, self.warnings);
+        let warnings = if self.strict {
+            errors.extend(warnings);
+            Vec::new()
+        } else {
+            warnings
+        };
+        ValidationReport {
+            errors,
+            warnings,
+            info: self.info,
+            album: Some(AlbumSummary {
+                title: album.metadata.title.clone(),
+                artist: album.metadata.artist.clone(),
+                tracks: album.tracks.len(),
+            }),
+        }
     }
 }
 
@@ -42,11 +101,60 @@ impl ValidationResults {
 /// - Liner notes exist if referenced
 /// - Audio file formats are supported
 ///
+/// With `schema`, album.toml is only checked against the JSON Schema
+/// (see `release-kit schema`). With `check_cdn_cors`, the deployed R2
+/// bucket's CORS policy is also fetched and checked against the site's
+/// origins (warnings only). With `strict`, every warning (placeholder
+/// metadata, missing cover art, ...) fails validation like an error.
+/// With `json`, the only output is a [`ValidationReport`] on stdout.
+///
 /// Returns Ok if validation passes, Err with detailed report if not.
-pub async fn run(path: PathBuf) -> Result<()> {
-    println!("🔍 Validating album at: {}\n", path.display());
+pub async fn run(
+    path: PathBuf,
+    schema: bool,
+    check_cdn_cors: bool,
+    strict: bool,
+    json: bool,
+) -> Result<()> {
+    if schema {
+        println!("🔍 Validating album at: {}\n", path.display());
+        return validate_schema(&path);
+    }
 
     let mut results = ValidationResults::new();
+    results.strict = strict;
+    results.quiet = json;
+    let album = check_album(&path, check_cdn_cors, &mut results).await?;
+
+    let failures = results.failure_count();
+    let valid = results.is_valid();
+    if json {
+        let report = results.into_report(&album);
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_results(&results);
+    }
+
+    if !valid {
+        anyhow::bail!("Validation failed with {} error(s)", failures);
+    }
+
+    if !json {
+        println!("\n✅ Validation passed! Album is ready for deployment.");
+    }
+    Ok(())
+}
+
+/// Run every check on the album at `path`, collecting into `results`
+///
+/// Errors only for albums that can't be checked at all (no album.toml, or
+/// one that doesn't parse).
+async fn check_album(
+    path: &Path,
+    check_cdn_cors: bool,
+    results: &mut ValidationResults,
+) -> Result<Album> {
+    results.step(format!("🔍 Validating album at: {}\n", path.display()));
 
     // Check directory exists
     if !path.exists() {
@@ -65,42 +173,98 @@ pub async fn run(path: PathBuf) -> Result<()> {
 
     let album = parse_album_toml(&config_path).context("Failed to parse album.toml")?;
 
-    println!("✓ Configuration loaded");
-    println!(
+    results.step("✓ Configuration loaded");
+    results.step(format!(
         "  Album: {} by {}",
         album.metadata.title, album.metadata.artist
-    );
-    println!("  Tracks: {}", album.tracks.len());
-    println!();
+    ));
+    results.step(format!("  Tracks: {}\n", album.tracks.len()));
 
     // Validate metadata completeness
-    validate_metadata(&album, &mut results);
+    validate_metadata(&album, results);
 
     // Validate directory structure
-    validate_directories(&path, &mut results);
+    validate_directories(path, results);
 
     // Validate audio files
-    validate_audio_files(&path, &album, &mut results);
+    validate_audio_files(path, &album, results);
 
     // Validate cover art (warning only)
-    validate_cover_art(&path, &mut results);
+    validate_cover_art(path, results);
 
     // Validate liner notes
-    validate_liner_notes(&path, &album, &mut results);
+    validate_liner_notes(path, &album, results);
+
+    // Validate the CDN lets the site fetch its audio (needs the Cloudflare API)
+    if check_cdn_cors {
+        results.step("🌐 Checking CDN CORS policy...");
+        let warnings = cdn_cors_warnings(&album)
+            .await
+            .context("Failed to check CDN CORS policy")?;
+        if warnings.is_empty() {
+            results.step("  ✓ CORS allows the site's origins");
+            results.note("CORS allows the site's origins");
+        }
+        for warning in warnings {
+            results.warn(warning);
+        }
+    }
 
-    // Print results
-    print_results(&results);
+    Ok(album)
+}
 
-    if !results.is_valid() {
-        anyhow::bail!("Validation failed with {} error(s)", results.errors.len());
+/// Print the album.toml JSON Schema to stdout
+pub fn print_schema() -> Result<()> {
+    let schema = serde_json::to_string_pretty(&album_toml_schema())?;
+    println!("{}", schema);
+    Ok(())
+}
+
+fn validate_schema(path: &Path) -> Result<()> {
+    println!("📐 Validating album.toml against JSON Schema...");
+
+    let config_path = path.join("album.toml");
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+    let errors = schema_errors(&content)?;
+    if !errors.is_empty() {
+        println!();
+        println!("❌ Schema errors ({}):", errors.len());
+        for error in &errors {
+            println!("  • {}", error);
+        }
+        println!();
+        anyhow::bail!("Schema validation failed with {} error(s)", errors.len());
     }
 
-    println!("\n✅ Validation passed! Album is ready for deployment.");
+    println!("\n✅ album.toml matches the schema.");
     Ok(())
 }
 
+/// Check TOML content against the schema, returning one message per violation
+fn schema_errors(content: &str) -> Result<Vec<String>> {
+    let value: toml::Value = toml::from_str(content).context("Failed to parse album.toml")?;
+    let instance = serde_json::to_value(value)?;
+    let schema = serde_json::to_value(album_toml_schema())?;
+    let validator = jsonschema::validator_for(&schema)
+        .map_err(|e| anyhow::anyhow!("Invalid album.toml schema: {}", e))?;
+
+    Ok(validator
+        .iter_errors(&instance)
+        .map(|e| {
+            let location = e.instance_path.to_string();
+            if location.is_empty() {
+                e.to_string()
+            } else {
+                format!("{}: {}", location, e)
+            }
+        })
+        .collect())
+}
+
 fn validate_metadata(album: &release_kit_core::types::Album, results: &mut ValidationResults) {
-    println!("📋 Validating metadata...");
+    results.step("📋 Validating metadata...");
 
     // Check for TODO placeholders
     if album.metadata.title.contains("TODO") || album.metadata.title == "My Album" {
@@ -133,11 +297,11 @@ fn validate_metadata(album: &release_kit_core::types::Album, results: &mut Valid
         results.error("No tracks defined in album.toml");
     }
 
-    println!("  ✓ Metadata structure valid");
+    results.step("  ✓ Metadata structure valid");
 }
 
 fn validate_directories(path: &Path, results: &mut ValidationResults) {
-    println!("📁 Validating directory structure...");
+    results.step("📁 Validating directory structure...");
 
     let required_dirs = ["audio", "artwork", "notes"];
     for dir in required_dirs {
@@ -149,7 +313,7 @@ fn validate_directories(path: &Path, results: &mut ValidationResults) {
         }
     }
 
-    println!("  ✓ Directory structure valid");
+    results.step("  ✓ Directory structure valid");
 }
 
 fn validate_audio_files(
@@ -157,7 +321,25 @@ fn validate_audio_files(
     album: &release_kit_core::types::Album,
     results: &mut ValidationResults,
 ) {
-    println!("🎵 Validating audio files...");
+    results.step("🎵 Validating audio files...");
+
+    // album.toml can't name these, and they'd be mangled on their way into URLs
+    for entry in WalkDir::new(base_path.join("audio"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && has_audio_extension(e.path())
+                && e.file_name().to_str().is_none()
+        })
+    {
+        let relative = entry.path().strip_prefix(base_path).unwrap_or(entry.path());
+        results.error(format!(
+            "{} has a file name that isn't valid UTF-8 - rename it",
+            relative.display()
+        ));
+    }
 
     for (i, track) in album.tracks.iter().enumerate() {
         let track_num = i + 1;
@@ -173,6 +355,18 @@ fn validate_audio_files(
             continue;
         }
 
+        // Extension must match the actual format, or uploads get the wrong content-type
+        if let Some((extension, actual)) = format_mismatch(&audio_path) {
+            results.error(format!(
+                "Track {} ({}) has a .{} extension but contains {} audio",
+                track_num,
+                track.file.display(),
+                extension,
+                actual
+            ));
+            continue;
+        }
+
         // Check file is readable and valid audio
         match Probe::open(&audio_path) {
             Ok(probe) => match probe.read() {
@@ -226,47 +420,62 @@ fn validate_audio_files(
         }
     }
 
-    println!("  ✓ Audio files validated ({} tracks)", album.tracks.len());
+    results.step(format!(
+        "  ✓ Audio files validated ({} tracks)",
+        album.tracks.len()
+    ));
+}
+
+/// Compare an audio file's extension with the format sniffed from its contents
+///
+/// # Returns
+///
+/// The extension and the detected format name when they disagree, or `None`
+/// if they match or either can't be determined
+fn format_mismatch(audio_path: &Path) -> Option<(String, String)> {
+    let by_extension = FileType::from_path(audio_path)?;
+    let by_content = Probe::open(audio_path)
+        .ok()?
+        .guess_file_type()
+        .ok()?
+        .file_type()?;
+
+    if by_content == by_extension {
+        return None;
+    }
+    let extension = audio_path.extension()?.to_string_lossy().to_string();
+    Some((extension, format!("{:?}", by_content).to_uppercase()))
 }
 
 fn validate_cover_art(base_path: &Path, results: &mut ValidationResults) {
-    println!("🎨 Validating artwork...");
+    results.step("🎨 Validating artwork...");
 
     let artwork_dir = base_path.join("artwork");
-    let cover_names = [
-        "cover.jpg",
-        "cover.png",
-        "cover.jpeg",
-        "artwork.jpg",
-        "artwork.png",
-    ];
-
-    let has_cover = cover_names
-        .iter()
-        .any(|name| artwork_dir.join(name).exists());
-
-    if !has_cover {
-        // Check if any image exists
-        if let Ok(entries) = std::fs::read_dir(&artwork_dir) {
-            let has_any_image = entries.flatten().any(|entry| {
-                if let Some(ext) = entry.path().extension() {
-                    let ext_lower = ext.to_string_lossy().to_lowercase();
-                    ext_lower == "jpg" || ext_lower == "jpeg" || ext_lower == "png"
-                } else {
-                    false
-                }
-            });
+    if !artwork_dir.is_dir() {
+        results.warn("Cannot read artwork directory");
+        return;
+    }
 
-            if !has_any_image {
-                results.warn("No cover art found in artwork/ - add cover.jpg or cover.png");
+    // Same detection as init and build, so whatever is reported here is what ships
+    match detect_cover_art(&artwork_dir) {
+        None => {
+            results.warn("No cover art found in artwork/ - add cover.jpg or cover.png");
+        }
+        Some(cover) => {
+            let filename = cover
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if is_standard_cover_name(&filename) {
+                results.step(format!("  ✓ Cover art found ({})", filename));
+                results.note(format!("Cover art found ({})", filename));
             } else {
-                results.warn("Cover art found but not using standard name (cover.jpg/cover.png)");
+                results.warn(format!(
+                    "Cover art found but not using standard name (cover.jpg/cover.png): {}",
+                    filename
+                ));
             }
-        } else {
-            results.warn("Cannot read artwork directory");
         }
-    } else {
-        println!("  ✓ Cover art found");
     }
 }
 
@@ -275,7 +484,7 @@ fn validate_liner_notes(
     album: &release_kit_core::types::Album,
     results: &mut ValidationResults,
 ) {
-    println!("📝 Validating liner notes...");
+    results.step("📝 Validating liner notes...");
 
     let mut notes_checked = 0;
 
@@ -309,9 +518,14 @@ fn validate_liner_notes(
     }
 
     if notes_checked > 0 {
-        println!("  ✓ Liner notes validated ({} files)", notes_checked);
+        results.step(format!(
+            "  ✓ Liner notes validated ({} files)",
+            notes_checked
+        ));
+        results.note(format!("Liner notes validated ({} files)", notes_checked));
     } else {
-        println!("  ⚠ No liner notes configured (optional)");
+        results.step("  ⚠ No liner notes configured (optional)");
+        results.note("No liner notes configured (optional)");
     }
 }
 
@@ -319,7 +533,14 @@ fn print_results(results: &ValidationResults) {
     println!();
 
     if !results.warnings.is_empty() {
-        println!("⚠️  Warnings ({}):", results.warnings.len());
+        if results.strict {
+            println!(
+                "❌ Warnings promoted to errors by --strict ({}):",
+                results.warnings.len()
+            );
+        } else {
+            println!("⚠️  Warnings ({}):", results.warnings.len());
+        }
         for warning in &results.warnings {
             println!("  • {}", warning);
         }
@@ -334,3 +555,238 @@ fn print_results(results: &ValidationResults) {
         println!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::build::{BuildOptions, build_static_site};
+    use crate::commands::test_support::{album_toml, write_wav};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_strict_fails_on_init_template() {
+        let dir = TempDir::new().unwrap();
+        write_wav(&dir.path().join("01-track.wav"), 1);
+        crate::commands::init::run(
+            dir.path().to_path_buf(),
+            None,
+            None,
+            None,
+            false,
+            crate::commands::dry_run::DryRun::default(),
+        )
+        .await
+        .unwrap();
+
+        // The un-edited template only has placeholder warnings
+        run(dir.path().to_path_buf(), false, false, false, false)
+            .await
+            .unwrap();
+
+        let err = run(dir.path().to_path_buf(), false, false, true, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Validation failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_json_report_lists_errors() {
+        let dir = TempDir::new().unwrap();
+        for sub in ["audio", "artwork", "notes"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        fs::write(
+            dir.path().join("album.toml"),
+            album_toml("[[track]]\nfile = \"audio/01-missing.flac\"\ntitle = \"Missing\"\n"),
+        )
+        .unwrap();
+
+        let mut results = ValidationResults::new();
+        results.quiet = true;
+        let album = check_album(dir.path(), false, &mut results).await.unwrap();
+        assert!(!results.is_valid());
+
+        let json = serde_json::to_string(&results.into_report(&album)).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            report["errors"],
+            serde_json::json!(["Track 1 audio file not found: audio/01-missing.flac"])
+        );
+        let warnings = report["warnings"].as_array().unwrap();
+        assert!(warnings.contains(&serde_json::json!(
+            "No cover art found in artwork/ - add cover.jpg or cover.png"
+        )));
+        assert_eq!(report["album"]["title"], "Test Album");
+        assert_eq!(report["album"]["tracks"], 1);
+
+        // The command itself still fails
+        assert!(
+            run(dir.path().to_path_buf(), false, false, false, true)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_strict_promotes_warnings() {
+        let mut results = ValidationResults::new();
+        results.warn("Site domain is a placeholder - update before deployment");
+        assert!(results.is_valid());
+        assert_eq!(results.failure_count(), 0);
+
+        results.strict = true;
+        assert!(!results.is_valid());
+        assert_eq!(results.failure_count(), 1);
+
+        results.error("No tracks defined in album.toml");
+        assert_eq!(results.failure_count(), 2);
+    }
+
+    #[test]
+    fn test_cover_art_detected_consistently_across_init_validate_build() {
+        for name in ["cover.jpeg", "COVER.JPG", "folder.png", "Album.PNG"] {
+            let src = TempDir::new().unwrap();
+            let out = TempDir::new().unwrap();
+
+            // init: detects cover art in the album root
+            fs::write(src.path().join(name), b"image").unwrap();
+            let detected = detect_cover_art(src.path());
+            assert_eq!(detected, Some(src.path().join(name)), "init: {}", name);
+
+            // validate: same file in artwork/ is accepted without warnings
+            fs::create_dir(src.path().join("artwork")).unwrap();
+            fs::rename(src.path().join(name), src.path().join("artwork").join(name)).unwrap();
+            let mut results = ValidationResults::new();
+            validate_cover_art(src.path(), &mut results);
+            assert!(
+                results.warnings.is_empty(),
+                "validate: {} -> {:?}",
+                name,
+                results.warnings
+            );
+
+            // build: the page references that same file
+            fs::write(src.path().join("album.toml"), album_toml("")).unwrap();
+            build_static_site(src.path(), out.path(), &BuildOptions::default()).unwrap();
+            let html = fs::read_to_string(out.path().join("index.html")).unwrap();
+            assert!(
+                html.contains(&format!("/artwork/{}", name)),
+                "build: {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_cover_art_warns_on_non_standard_name() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("artwork")).unwrap();
+        fs::write(dir.path().join("artwork/front.jpg"), b"image").unwrap();
+
+        let mut results = ValidationResults::new();
+        validate_cover_art(dir.path(), &mut results);
+        assert_eq!(results.warnings.len(), 1);
+        assert!(results.warnings[0].contains("front.jpg"));
+    }
+
+    #[test]
+    fn test_schema_accepts_good_config() {
+        let good = album_toml(
+            "[[track]]\nfile = \"audio/01.flac\"\ntitle = \"One\"\nduration = \"3:00\"\n",
+        );
+        let errors = schema_errors(&good).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_schema_rejects_bad_config() {
+        let bad = album_toml("")
+            .replace("streaming_enabled = true", "streaming_enabled = \"yes\"")
+            .replace("title = \"Test Album\"\n", "");
+        let errors = schema_errors(&bad).unwrap();
+
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("/distribution/streaming_enabled")),
+            "{:?}",
+            errors
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("\"title\" is a required property")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_audio_rejects_non_utf8_file_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("audio")).unwrap();
+        write_wav(&dir.path().join("audio/01-track.wav"), 1);
+        write_wav(
+            &dir.path()
+                .join("audio")
+                .join(OsStr::from_bytes(b"02-caf\xe9.wav")),
+            1,
+        );
+        // Not audio, so it can stay
+        fs::write(
+            dir.path()
+                .join("audio")
+                .join(OsStr::from_bytes(b"notes-\xe9.txt")),
+            b"text",
+        )
+        .unwrap();
+        let album = release_kit_core::config::parse_album_toml_str(&album_toml(
+            "[[track]]\nfile = \"audio/01-track.wav\"\ntitle = \"One\"\n",
+        ))
+        .unwrap();
+
+        let mut results = ValidationResults::new();
+        validate_audio_files(dir.path(), &album, &mut results);
+        assert_eq!(
+            results.errors,
+            vec!["audio/02-caf\u{fffd}.wav has a file name that isn't valid UTF-8 - rename it"]
+        );
+    }
+
+    #[test]
+    fn test_validate_audio_rejects_flac_renamed_to_mp3() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("audio")).unwrap();
+        // FLAC stream marker and STREAMINFO header, saved under an .mp3 name
+        fs::write(
+            dir.path().join("audio/01-track.mp3"),
+            b"fLaC\x00\x00\x00\x22\x10\x00\x10\x00",
+        )
+        .unwrap();
+        let toml = album_toml("[[track]]\nfile = \"audio/01-track.mp3\"\ntitle = \"Renamed\"\n");
+        let album = release_kit_core::config::parse_album_toml_str(&toml).unwrap();
+
+        let mut results = ValidationResults::new();
+        validate_audio_files(dir.path(), &album, &mut results);
+        assert_eq!(
+            results.errors,
+            vec!["Track 1 (audio/01-track.mp3) has a .mp3 extension but contains FLAC audio"]
+        );
+
+        // Same bytes under the right name: no mismatch
+        fs::rename(
+            dir.path().join("audio/01-track.mp3"),
+            dir.path().join("audio/01-track.flac"),
+        )
+        .unwrap();
+        assert_eq!(
+            format_mismatch(&dir.path().join("audio/01-track.flac")),
+            None
+        );
+    }
+}
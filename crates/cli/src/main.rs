@@ -11,6 +11,10 @@ use std::path::PathBuf;
 struct Cli {
     #[command(subcommand)]
     command: Command,
+
+    /// Print what would be written, uploaded or deleted, and change nothing
+    #[arg(long, global = true)]
+    dry_run: bool,
 }
 
 #[derive(Parser)]
@@ -31,12 +35,63 @@ enum Command {
         /// Artist email for RSS feed
         #[arg(short, long)]
         email: Option<String>,
+
+        /// Output format; json prints only what was detected and written
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Create a new album directory and initialize it
+    New {
+        /// Path of the album directory to create
+        path: PathBuf,
+
+        /// Artist name (pre-populates artist field)
+        #[arg(short, long)]
+        artist: Option<String>,
+
+        /// Album title (pre-populates album title)
+        #[arg(short = 't', long)]
+        album: Option<String>,
+
+        /// Artist email for RSS feed
+        #[arg(short, long)]
+        email: Option<String>,
+
+        /// Initialize the directory even if it is not empty
+        #[arg(long)]
+        force: bool,
     },
 
     /// Validate album configuration
     Validate {
         /// Path to album directory
         path: PathBuf,
+
+        /// Only check album.toml against the JSON Schema
+        #[arg(long)]
+        schema: bool,
+
+        /// Also check the deployed R2 bucket's CORS policy allows the site
+        #[arg(long, conflicts_with = "schema")]
+        check_cdn_cors: bool,
+
+        /// Treat warnings (placeholders, missing cover art) as errors
+        #[arg(long, conflicts_with = "schema")]
+        strict: bool,
+
+        /// Output format; json prints only the report, for CI
+        #[arg(long, value_enum, default_value = "text", conflicts_with = "schema")]
+        format: OutputFormat,
+    },
+
+    /// Print the JSON Schema for album.toml
+    Schema,
+
+    /// Probe audio files and write track durations into album.toml
+    SyncDurations {
+        /// Path to album directory
+        path: PathBuf,
     },
 
     /// Preview site locally with hot reload
@@ -47,6 +102,18 @@ enum Command {
         /// Port to serve on
         #[arg(short, long, default_value = "8080")]
         port: u16,
+
+        /// Serve under a subdirectory (e.g., /albums/foo) to match a base-path deployment
+        #[arg(long, visible_alias = "album-subpath")]
+        base_path: Option<String>,
+
+        /// Open the preview in the default browser once the server is listening
+        #[arg(long)]
+        open: bool,
+
+        /// Print only the URL, once the server is listening (for scripts)
+        #[arg(long)]
+        print_url_only: bool,
     },
 
     /// Build site without deploying
@@ -55,8 +122,99 @@ enum Command {
         path: PathBuf,
 
         /// Output directory for generated site
+        #[arg(short, long, required_unless_present = "check")]
+        output: Option<PathBuf>,
+
+        /// Path prefix when deploying under a subdirectory (e.g., /albums/foo)
+        #[arg(long)]
+        base_path: Option<String>,
+
+        /// Also emit a WebP cover and serve it via <picture> with the original as fallback
+        #[arg(long)]
+        modern_images: bool,
+
+        /// Verify the build would succeed without writing any output
+        #[arg(long)]
+        check: bool,
+
+        /// Remove embedded tags (comments, artwork, etc.) from the copied audio
+        #[arg(long)]
+        strip_metadata: bool,
+
+        /// Encode FLAC/WAV tracks to the MP3/OGG download_formats with ffmpeg
+        #[arg(long)]
+        transcode: bool,
+
+        /// Keep tracks marked `draft = true` (otherwise only `preview` shows them)
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// Leave releases before this date (YYYY-MM-DD) out of feed.xml; overrides `[rss] since`
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+    },
+
+    /// Build every album in a directory into its own subdirectory
+    BuildIndex {
+        /// Directory containing one subdirectory per album
+        path: PathBuf,
+
+        /// Output directory for the generated sites
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Maximum number of albums to build at once
+        #[arg(long, default_value_t = 4)]
+        parallel_builds: usize,
+    },
+
+    /// Bundle the album into a single self-contained HTML file
+    Bundle {
+        /// Path to album directory
+        path: PathBuf,
+
+        /// Output HTML file
         #[arg(short, long)]
         output: PathBuf,
+
+        /// Embed audio files up to this many megabytes; larger ones are referenced
+        #[arg(long, default_value_t = 2)]
+        max_inline_audio_mb: u64,
+    },
+
+    /// Compare local album against the deployed audio and last deploy manifest
+    Diff {
+        /// Path to album directory
+        path: PathBuf,
+    },
+
+    /// Check the deployed site, feed and audio URLs all resolve
+    CheckLinks {
+        /// Path to album directory
+        path: PathBuf,
+    },
+
+    /// Copy R2 objects between buckets or accounts, without re-uploading
+    MigrateR2 {
+        /// Source bucket
+        #[arg(long)]
+        from: String,
+
+        /// Destination bucket (must already exist)
+        #[arg(long)]
+        to: String,
+
+        /// Config file for the destination account (default: the configured one)
+        #[arg(long)]
+        to_config: Option<PathBuf>,
+
+        /// Only copy keys starting with this (e.g. audio/)
+        #[arg(long, default_value = "")]
+        prefix: String,
+
+        /// Also copy the deploy manifest, after every object made it
+        #[arg(long)]
+        update_manifest: bool,
     },
 
     /// Deploy site to hosting platform
@@ -85,11 +243,15 @@ enum DeployCommand {
     /// Create token at: https://dash.cloudflare.com/profile/api-tokens
     Configure,
 
-    /// Publish album to Cloudflare Pages
+    /// Publish album to Cloudflare Pages (or another --target)
     Publish {
         /// Path to album directory
         path: PathBuf,
 
+        /// Hosting target (netlify reads ~/.release-kit/netlify.toml, s3 uses [hosting.s3], static writes to --output)
+        #[arg(long, value_enum, default_value = "cloudflare")]
+        target: DeployTarget,
+
         /// Skip confirmation prompts
         #[arg(long)]
         force: bool,
@@ -97,6 +259,56 @@ enum DeployCommand {
         /// Max concurrent uploads (default: 3)
         #[arg(long, short = 'c')]
         concurrency: Option<usize>,
+
+        /// Refuse to upload any track larger than this many megabytes
+        #[arg(long, default_value_t = 500)]
+        max_file_size_mb: u64,
+
+        /// Print the upload plan as JSON and exit without deploying
+        #[arg(long)]
+        manifest_only: bool,
+
+        /// Remove embedded tags (comments, artwork, etc.) from the uploaded audio
+        #[arg(long, conflicts_with = "manifest_only")]
+        strip_metadata: bool,
+
+        /// Destination directory (required with --target static)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+
+        /// Deploy to this Pages branch; anything but main is a noindexed preview
+        #[arg(long, conflicts_with = "manifest_only")]
+        branch: Option<String>,
+
+        /// Where Cloudflare deploys serve audio from; local bundles it into
+        /// Pages (no R2, every track under 25 MB)
+        #[arg(
+            long,
+            value_enum,
+            default_value = "cdn",
+            conflicts_with = "manifest_only"
+        )]
+        audio_mode: AudioMode,
+
+        /// Redeploy just the site (e.g. new cover art); audio already uploaded is left alone
+        #[arg(long, conflicts_with = "manifest_only")]
+        cover_only: bool,
+
+        /// Start over instead of resuming an interrupted publish
+        #[arg(long)]
+        restart: bool,
+
+        /// Deploy tracks marked `draft = true` too
+        #[arg(long, conflicts_with = "manifest_only")]
+        include_drafts: bool,
+
+        /// Upload every track, even ones unchanged since the last publish
+        #[arg(long, conflicts_with_all = ["manifest_only", "cover_only"])]
+        reupload: bool,
+
+        /// Once deployed, delete R2 audio for tracks no longer in album.toml
+        #[arg(long, conflicts_with_all = ["manifest_only", "cover_only"])]
+        prune_removed: bool,
     },
 
     /// Show deployment status and info
@@ -105,47 +317,271 @@ enum DeployCommand {
         path: Option<PathBuf>,
     },
 
+    /// Roll back to an earlier Cloudflare Pages deployment (lists recent ones)
+    Rollback {
+        /// Path to album directory
+        path: PathBuf,
+
+        /// Deployment to make live again (default: the one before the current)
+        deployment_id: Option<String>,
+    },
+
     /// Delete deployment from Cloudflare Pages
     Teardown {
-        /// Path to album directory
+        /// Path to album directory (with --all, a directory of album directories)
         path: PathBuf,
 
         /// Skip confirmation prompt (dangerous!)
         #[arg(long)]
         force: bool,
+
+        /// Tear down every album under the path, after typing DELETE ALL
+        #[arg(long)]
+        all: bool,
     },
 }
 
 #[derive(Debug, Clone, ValueEnum)]
 enum DeployTarget {
     Cloudflare,
-    // Future: Netlify, Static
+    Netlify,
+    /// Whole site, audio included, to the [hosting.s3] bucket
+    S3,
+    /// Experimental: site and audio pinned to IPFS (reads ~/.release-kit/ipfs.toml)
+    Ipfs,
+    /// Whole site, audio included, copied to --output (no Cloudflare calls)
+    Static,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AudioMode {
+    /// R2 (or [hosting.s3]), outside the Pages deployment
+    Cdn,
+    /// Inside the Pages deployment, for small releases
+    Local,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable progress and results
+    Text,
+    /// A single JSON document on stdout
+    Json,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
+    let dry_run = commands::dry_run::DryRun::new(cli.dry_run);
+
     match cli.command {
         Command::Init {
             path,
             artist,
             album,
             email,
-        } => commands::init::run(path, artist, album, email).await,
-        Command::Validate { path } => commands::validate::run(path).await,
-        Command::Preview { path, port } => commands::preview::run(path, port).await,
-        Command::Build { path, output } => commands::build::run(path, output).await,
+            format,
+        } => {
+            let json = matches!(format, OutputFormat::Json);
+            commands::init::run(path, artist, album, email, json, dry_run).await
+        }
+        Command::New {
+            path,
+            artist,
+            album,
+            email,
+            force,
+        } => commands::init::new_album(path, artist, album, email, force, dry_run).await,
+        Command::Validate {
+            path,
+            schema,
+            check_cdn_cors,
+            strict,
+            format,
+        } => {
+            let json = matches!(format, OutputFormat::Json);
+            commands::validate::run(path, schema, check_cdn_cors, strict, json).await
+        }
+        Command::Schema => commands::validate::print_schema(),
+        Command::SyncDurations { path } => commands::sync_durations::run(path, dry_run).await,
+        Command::Preview {
+            path,
+            port,
+            base_path,
+            open,
+            print_url_only,
+        } => commands::preview::run(path, port, base_path, open, print_url_only).await,
+        Command::Build {
+            path,
+            output,
+            base_path,
+            modern_images,
+            check,
+            strip_metadata,
+            transcode,
+            include_drafts,
+            since,
+        } => {
+            commands::build::run(
+                path,
+                output,
+                base_path,
+                modern_images,
+                check,
+                strip_metadata,
+                transcode,
+                include_drafts,
+                since,
+                dry_run,
+            )
+            .await
+        }
+        Command::BuildIndex {
+            path,
+            output,
+            parallel_builds,
+        } => commands::build_index::run(path, output, parallel_builds, dry_run).await,
+        Command::Bundle {
+            path,
+            output,
+            max_inline_audio_mb,
+        } => commands::bundle::run(path, output, max_inline_audio_mb, dry_run).await,
+        Command::Diff { path } => commands::diff::run(path).await,
+        Command::CheckLinks { path } => commands::check_links::run(path).await,
+        Command::MigrateR2 {
+            from,
+            to,
+            to_config,
+            prefix,
+            update_manifest,
+        } => commands::migrate_r2::run(from, to, to_config, prefix, update_manifest, dry_run).await,
         Command::Deploy { command } => match command {
-            DeployCommand::Configure => commands::deploy::configure().await,
+            DeployCommand::Configure => commands::deploy::configure(dry_run).await,
             DeployCommand::Publish {
                 path,
+                target,
                 force,
                 concurrency,
-            } => commands::deploy::publish(path, force, concurrency).await,
+                max_file_size_mb,
+                manifest_only,
+                strip_metadata,
+                output,
+                branch,
+                audio_mode,
+                cover_only,
+                restart,
+                include_drafts,
+                reupload,
+                prune_removed,
+            } => match target {
+                // --manifest-only changes nothing, so --dry-run has nothing to hold back
+                DeployTarget::Cloudflare if manifest_only => {
+                    commands::deploy::publish_manifest(path, max_file_size_mb).await
+                }
+                _ if manifest_only => {
+                    anyhow::bail!("--manifest-only is only supported with --target cloudflare")
+                }
+                _ if cover_only && !matches!(target, DeployTarget::Cloudflare) => {
+                    anyhow::bail!("--cover-only is only supported with --target cloudflare")
+                }
+                _ if include_drafts && !matches!(target, DeployTarget::Cloudflare) => {
+                    anyhow::bail!("--include-drafts is only supported with --target cloudflare")
+                }
+                _ if reupload && !matches!(target, DeployTarget::Cloudflare) => {
+                    anyhow::bail!("--reupload is only supported with --target cloudflare")
+                }
+                _ if prune_removed && !matches!(target, DeployTarget::Cloudflare) => {
+                    anyhow::bail!("--prune-removed is only supported with --target cloudflare")
+                }
+                DeployTarget::Static => {
+                    let output = output.ok_or_else(|| {
+                        anyhow::anyhow!("--output is required with --target static")
+                    })?;
+                    commands::deploy::publish_static(
+                        path,
+                        output,
+                        max_file_size_mb,
+                        strip_metadata,
+                        dry_run,
+                    )
+                    .await
+                }
+                _ if output.is_some() => {
+                    anyhow::bail!("--output is only supported with --target static")
+                }
+                _ if branch.is_some() && !matches!(target, DeployTarget::Cloudflare) => {
+                    anyhow::bail!("--branch is only supported with --target cloudflare")
+                }
+                _ if audio_mode == AudioMode::Local
+                    && !matches!(target, DeployTarget::Cloudflare) =>
+                {
+                    anyhow::bail!("--audio-mode local is only supported with --target cloudflare")
+                }
+                DeployTarget::Cloudflare => {
+                    commands::deploy::publish(
+                        path,
+                        commands::deploy::PublishOptions {
+                            force,
+                            concurrency,
+                            max_file_size_mb,
+                            strip_metadata,
+                            branch,
+                            bundle_audio: audio_mode == AudioMode::Local,
+                            dry_run,
+                            cover_only,
+                            restart,
+                            include_drafts,
+                            reupload,
+                            prune_removed,
+                        },
+                    )
+                    .await
+                }
+                DeployTarget::Ipfs => {
+                    commands::deploy::publish_ipfs(
+                        path,
+                        force,
+                        max_file_size_mb,
+                        strip_metadata,
+                        dry_run,
+                    )
+                    .await
+                }
+                DeployTarget::S3 => {
+                    commands::deploy::publish_s3(
+                        path,
+                        force,
+                        concurrency,
+                        max_file_size_mb,
+                        strip_metadata,
+                        dry_run,
+                    )
+                    .await
+                }
+                DeployTarget::Netlify => {
+                    commands::deploy::publish_netlify(
+                        path,
+                        force,
+                        concurrency,
+                        max_file_size_mb,
+                        strip_metadata,
+                        dry_run,
+                    )
+                    .await
+                }
+            },
             DeployCommand::Status { path } => commands::deploy::status(path).await,
-            DeployCommand::Teardown { path, force } => {
-                commands::deploy::teardown(path, force).await
+            DeployCommand::Rollback {
+                path,
+                deployment_id,
+            } => commands::deploy::rollback(path, deployment_id, dry_run).await,
+            DeployCommand::Teardown { path, force, all } => {
+                if all {
+                    commands::deploy::teardown_all(path, force, dry_run).await
+                } else {
+                    commands::deploy::teardown(path, force, dry_run).await
+                }
             }
         },
         Command::Completions { shell } => {
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Standard cover art filenames, in priority order
+///
+/// Matching is case-insensitive, so `COVER.JPG` and `Cover.Jpeg` are
+/// treated the same as `cover.jpg` / `cover.jpeg`.
+pub const COVER_ART_NAMES: &[&str] = &[
+    "cover.jpg",
+    "cover.jpeg",
+    "cover.png",
+    "artwork.jpg",
+    "artwork.jpeg",
+    "artwork.png",
+    "folder.jpg",
+    "folder.jpeg",
+    "folder.png",
+    "album.jpg",
+    "album.jpeg",
+    "album.png",
+];
+
+/// Image extensions accepted as cover art (lowercase)
+pub const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png"];
+
+/// Check whether a path has a supported image extension (case-insensitive)
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Check whether a filename is one of the standard cover art names
+pub fn is_standard_cover_name(filename: &str) -> bool {
+    COVER_ART_NAMES.contains(&filename.to_lowercase().as_str())
+}
+
+/// Detect cover art in a directory
+///
+/// This is the single cover detection used by `init`, `build`, and
+/// `validate`, so a cover found at one stage is found at every stage.
+///
+/// Standard names in `COVER_ART_NAMES` win, in priority order; otherwise the
+/// alphabetically first image file is used. Only the top level of `dir` is
+/// searched.
+///
+/// # Returns
+///
+/// Full path to the detected cover (`dir` joined with the actual filename,
+/// preserving its on-disk case), or `None` if no image was found
+pub fn detect_cover_art(dir: &Path) -> Option<PathBuf> {
+    let mut images: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_image_file(path))
+        .collect();
+
+    // Sort for deterministic fallback selection (read_dir order is unspecified)
+    images.sort();
+
+    let lowercase_name = |path: &PathBuf| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+
+    // Try standard names first
+    for name in COVER_ART_NAMES {
+        if let Some(path) = images.iter().find(|p| lowercase_name(p) == *name) {
+            return Some(path.clone());
+        }
+    }
+
+    // Fallback: first image file
+    images.into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn dir_with(files: &[&str]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for file in files {
+            fs::write(dir.path().join(file), b"image").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_detect_cover_art_standard_names() {
+        for name in COVER_ART_NAMES {
+            let dir = dir_with(&[name]);
+            let detected = detect_cover_art(dir.path());
+            assert_eq!(
+                detected,
+                Some(dir.path().join(name)),
+                "Should detect cover art named '{}'",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_cover_art_uppercase_and_jpeg() {
+        for name in ["COVER.JPG", "Cover.Jpeg", "ALBUM.PNG"] {
+            let dir = dir_with(&[name, "zzz.jpg"]);
+            // Standard name wins over the fallback, with on-disk case preserved
+            assert_eq!(detect_cover_art(dir.path()), Some(dir.path().join(name)));
+        }
+    }
+
+    #[test]
+    fn test_detect_cover_art_priority_and_fallback() {
+        let dir = dir_with(&["folder.png", "cover.jpg", "random.jpg"]);
+        assert_eq!(
+            detect_cover_art(dir.path()),
+            Some(dir.path().join("cover.jpg"))
+        );
+
+        let dir = dir_with(&["b-image.PNG", "a-image.jpg", "notes.txt"]);
+        assert_eq!(
+            detect_cover_art(dir.path()),
+            Some(dir.path().join("a-image.jpg"))
+        );
+    }
+
+    #[test]
+    fn test_detect_cover_art_none() {
+        let dir = dir_with(&["audio.flac", "readme.txt"]);
+        assert_eq!(detect_cover_art(dir.path()), None);
+
+        assert_eq!(detect_cover_art(&dir.path().join("missing")), None);
+    }
+
+    #[test]
+    fn test_is_standard_cover_name() {
+        assert!(is_standard_cover_name("cover.jpg"));
+        assert!(is_standard_cover_name("COVER.JPEG"));
+        assert!(!is_standard_cover_name("random.jpg"));
+    }
+}
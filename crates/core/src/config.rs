@@ -1,12 +1,16 @@
+use crate::artwork::is_image_file;
 use crate::error::{Error, Result};
+use crate::rss::is_itunes_category;
 use crate::types::*;
+use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Raw TOML configuration structure
 /// This matches the album.toml file structure exactly
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 #[allow(dead_code)] // MVP: Some fields not yet used in Album struct
 struct RawConfig {
     album: RawAlbumMetadata,
@@ -19,9 +23,23 @@ struct RawConfig {
     #[serde(default)]
     limits: Option<Limits>,
     rss: RssConfig,
+    #[serde(default)]
+    credits: BTreeMap<String, RawCreditNames>,
+    #[serde(default)]
+    merch: Merch,
+    #[serde(default)]
+    artwork: Artwork,
+}
+
+/// Names for a `[credits]` role: one (`engineer = "Jo"`) or a list
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum RawCreditNames {
+    One(String),
+    Many(Vec<String>),
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 struct RawAlbumMetadata {
     title: String,
     artist: String,
@@ -31,9 +49,11 @@ struct RawAlbumMetadata {
     catalog_number: Option<String>,
     license: String,
     liner_notes: Option<String>, // Convert to PathBuf
+    #[serde(default)]
+    explicit: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 struct RawArtist {
     name: String,
     url: Option<String>,
@@ -41,28 +61,75 @@ struct RawArtist {
     rss_author_email: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 struct RawTrack {
     file: String, // Convert to PathBuf
     title: String,
-    duration: Option<String>,    // Parse as Duration (format: "MM:SS")
-    liner_notes: Option<String>, // Convert to PathBuf
+    artist: Option<String>,       // Defaults to album.artist
+    duration: Option<String>,     // Parse as Duration ("MM:SS" or "H:MM:SS")
+    liner_notes: Option<String>,  // Convert to PathBuf
+    explicit: Option<bool>,       // Defaults to album.explicit
+    preview_clip: Option<String>, // Parse as PreviewClip (format: "M:SS-M:SS")
+    track_number: Option<u32>,    // Overrides the position in the track list
+    bpm: Option<f64>,
+    key: Option<String>, // Musical key, e.g. "Am" or "F#"
+    disc: Option<u32>,   // Defaults to disc 1
+    #[serde(default)]
+    draft: bool, // Preview-only until --include-drafts
+    #[serde(default)]
+    producer: Vec<String>,
+    #[serde(default)]
+    mastered_by: Vec<String>,
+    #[serde(default)]
+    written_by: Vec<String>,
+    #[serde(default)]
+    chapters: Vec<RawChapter>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
+struct RawChapter {
+    name: String,
+    start: String, // Parse as Duration ("MM:SS" or "H:MM:SS")
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
 struct RawHostingConfig {
     cloudflare: CloudflareConfig,
+    s3: Option<S3Config>,
+}
+
+/// JSON Schema for album.toml, generated from the config types
+///
+/// Editors can use this for autocompletion, and external tools for validation
+/// (TOML converted to JSON).
+pub fn album_toml_schema() -> schemars::Schema {
+    schemars::schema_for!(RawConfig)
 }
 
 /// Parse album.toml from a file path
+///
+/// TOML syntax errors are reported with the file path, line, and column, plus
+/// the offending line and a caret under the problem.
 pub fn parse_album_toml<P: AsRef<Path>>(path: P) -> Result<Album> {
-    let content = fs::read_to_string(path)?;
-    parse_album_toml_str(&content)
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).map_err(|e| {
+        Error::IoError(std::io::Error::new(
+            e.kind(),
+            format!("Cannot read {}: {}", path.display(), e),
+        ))
+    })?;
+    parse_album_toml_source(&content, &path.display().to_string())
 }
 
 /// Parse album.toml from a string (useful for testing)
 pub fn parse_album_toml_str(content: &str) -> Result<Album> {
-    let raw: RawConfig = toml::from_str(content)?;
+    parse_album_toml_source(content, "album.toml")
+}
+
+/// Parse album.toml content, using `source` to label TOML syntax errors
+fn parse_album_toml_source(content: &str, source: &str) -> Result<Album> {
+    let raw: RawConfig = toml::from_str(content)
+        .map_err(|e| Error::ConfigParse(describe_toml_error(source, content, &e)))?;
 
     // Parse release date
     let release_date = chrono::NaiveDate::parse_from_str(&raw.album.release_date, "%Y-%m-%d")
@@ -84,6 +151,7 @@ pub fn parse_album_toml_str(content: &str) -> Result<Album> {
         catalog_number: raw.album.catalog_number,
         license: raw.album.license,
         liner_notes,
+        explicit: raw.album.explicit,
     };
 
     // Convert artist
@@ -112,6 +180,63 @@ pub fn parse_album_toml_str(content: &str) -> Result<Album> {
                 None
             };
 
+            let preview_clip = if let Some(clip_str) = t.preview_clip {
+                Some(parse_preview_clip(&clip_str, duration)?)
+            } else {
+                None
+            };
+            let chapters = parse_chapters(&t.title, t.chapters, duration)?;
+
+            if t.track_number == Some(0) {
+                return Err(Error::ConfigParse(format!(
+                    "Track '{}': track_number must be positive",
+                    t.title
+                )));
+            }
+            if t.disc == Some(0) {
+                return Err(Error::ConfigParse(format!(
+                    "Track '{}': disc must be positive",
+                    t.title
+                )));
+            }
+
+            if let Some(bpm) = t.bpm
+                && !(bpm.is_finite() && bpm > 0.0)
+            {
+                return Err(Error::ConfigParse(format!(
+                    "Track '{}': bpm must be a positive number",
+                    t.title
+                )));
+            }
+            if let Some(key) = &t.key
+                && !is_valid_musical_key(key)
+            {
+                return Err(Error::ConfigParse(format!(
+                    "Track '{}': invalid key '{}', expected a note A-G with optional #/b and 'm' for minor (e.g. \"Am\", \"F#\")",
+                    t.title, key
+                )));
+            }
+
+            if t.artist.as_deref().is_some_and(|artist| artist.trim().is_empty()) {
+                return Err(Error::ConfigParse(format!(
+                    "Track '{}': artist cannot be empty (leave it out to use the album artist)",
+                    t.title
+                )));
+            }
+
+            for (field, names) in [
+                ("producer", &t.producer),
+                ("mastered_by", &t.mastered_by),
+                ("written_by", &t.written_by),
+            ] {
+                if names.iter().any(|name| name.trim().is_empty()) {
+                    return Err(Error::ConfigParse(format!(
+                        "Track '{}': {} names cannot be empty",
+                        t.title, field
+                    )));
+                }
+            }
+
             let file = validate_path(&t.file, "track.file")?;
             let liner_notes = if let Some(notes_path) = t.liner_notes {
                 Some(validate_path(&notes_path, "track.liner_notes")?)
@@ -122,13 +247,50 @@ pub fn parse_album_toml_str(content: &str) -> Result<Album> {
             Ok(Track {
                 file,
                 title: t.title,
+                artist: t.artist,
                 duration,
                 liner_notes,
+                explicit: t.explicit.unwrap_or(metadata.explicit),
+                preview_clip,
+                chapters,
+                track_number: t.track_number,
+                bpm: t.bpm,
+                key: t.key,
+                disc: t.disc,
+                draft: t.draft,
+                stream_file: None,
+                stream_copy: None,
+                peaks: None,
+                producer: t.producer,
+                mastered_by: t.mastered_by,
+                written_by: t.written_by,
             })
         })
         .collect();
 
-    Ok(Album {
+    if raw.site.seek_seconds == 0 {
+        return Err(Error::ConfigParse(
+            "site.seek_seconds must be at least 1".to_string(),
+        ));
+    }
+    if raw.rss.max_items == Some(0) {
+        return Err(Error::ConfigParse(
+            "rss.max_items must be at least 1".to_string(),
+        ));
+    }
+    if let Some(itunes) = &raw.rss.itunes {
+        validate_itunes(itunes)?;
+    }
+    validate_hex_color(&raw.site.accent_color, "site.accent_color")?;
+    validate_footer(&raw.site.footer)?;
+    let credits = parse_credits(raw.credits)?;
+    validate_merch(&raw.merch)?;
+    if let Some(prefix) = &raw.hosting.cloudflare.r2_prefix {
+        validate_r2_prefix(prefix)?;
+    }
+    validate_gallery(&raw.artwork)?;
+
+    let album = Album {
         metadata,
         artist,
         site: raw.site,
@@ -136,9 +298,78 @@ pub fn parse_album_toml_str(content: &str) -> Result<Album> {
         distribution: raw.distribution,
         hosting: HostingConfig {
             cloudflare: raw.hosting.cloudflare,
+            s3: raw.hosting.s3,
         },
+        limits: raw.limits,
         rss: raw.rss,
-    })
+        credits,
+        merch: raw.merch,
+        artwork: raw.artwork,
+    };
+
+    if let Some(default_track) = &album.distribution.default_track
+        && album.default_track_index().is_none()
+    {
+        return Err(Error::ConfigParse(match default_track {
+            DefaultTrack::Index(index) => format!(
+                "distribution.default_track: no track at index {} (0 is the first of {} tracks)",
+                index,
+                album.tracks.len()
+            ),
+            DefaultTrack::Slug(slug) => format!(
+                "distribution.default_track: no track with slug '{}' (track slugs: {})",
+                slug,
+                album
+                    .tracks
+                    .iter()
+                    .map(|t| t.slug())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }));
+    }
+
+    if let Some(format) = &album.distribution.stream_format
+        && LossyFormat::parse(format).is_none()
+    {
+        return Err(Error::ConfigParse(format!(
+            "distribution.stream_format: unknown format '{}', expected opus, mp3 or ogg with an optional bitrate (e.g. \"opus-128\", \"mp3-96\")",
+            format
+        )));
+    }
+
+    // Discs are listed in order, so the page can group tracks under headings
+    for pair in album.tracks.windows(2) {
+        if pair[1].disc_number() < pair[0].disc_number() {
+            return Err(Error::ConfigParse(format!(
+                "Track '{}' (disc {}) is listed after disc {}; list tracks in disc order",
+                pair[1].title,
+                pair[1].disc_number(),
+                pair[0].disc_number()
+            )));
+        }
+    }
+
+    // Track numbers are unique within a disc
+    let numbers = album.track_numbers();
+    for (i, number) in numbers.iter().enumerate() {
+        let disc = album.tracks[i].disc_number();
+        let duplicate =
+            (0..i).any(|j| numbers[j] == *number && album.tracks[j].disc_number() == disc);
+        if duplicate {
+            let location = if album.is_multi_disc() {
+                format!(" on disc {}", disc)
+            } else {
+                String::new()
+            };
+            return Err(Error::ConfigParse(format!(
+                "Duplicate track number {}{} (track '{}')",
+                number, location, album.tracks[i].title
+            )));
+        }
+    }
+
+    Ok(album)
 }
 
 /// Validate and convert a path string to PathBuf.
@@ -205,24 +436,282 @@ fn validate_path(path_str: &str, field_name: &str) -> Result<PathBuf> {
     Ok(path.to_path_buf())
 }
 
-/// Parse duration string in format "MM:SS" or "M:SS"
-fn parse_duration(s: &str) -> Result<std::time::Duration> {
-    let parts: Vec<&str> = s.split(':').collect();
-    if parts.len() != 2 {
+/// Convert `[credits]` to role -> names, rejecting blank roles and names
+fn parse_credits(raw: BTreeMap<String, RawCreditNames>) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut credits = BTreeMap::new();
+    for (role, names) in raw {
+        if role.trim().is_empty() {
+            return Err(Error::ConfigParse(
+                "credits: role names cannot be empty".to_string(),
+            ));
+        }
+        let names = match names {
+            RawCreditNames::One(name) => vec![name],
+            RawCreditNames::Many(names) => names,
+        };
+        if names.is_empty() || names.iter().any(|name| name.trim().is_empty()) {
+            return Err(Error::ConfigParse(format!(
+                "credits.{}: names cannot be empty",
+                role
+            )));
+        }
+        credits.insert(role, names);
+    }
+    Ok(credits)
+}
+
+/// Validate `[[merch.items]]`: a name, an http(s) store link and a safe image
+/// path, with no two images sharing a filename
+fn validate_merch(merch: &Merch) -> Result<()> {
+    // Images share the site's one merch/ directory, like the gallery's
+    let mut seen = std::collections::HashSet::new();
+    for item in &merch.items {
+        if item.name.trim().is_empty() {
+            return Err(Error::ConfigParse(
+                "merch: item names cannot be empty".to_string(),
+            ));
+        }
+        let host = item
+            .url
+            .strip_prefix("https://")
+            .or_else(|| item.url.strip_prefix("http://"));
+        if host.is_none_or(|host| host.trim().is_empty()) {
+            return Err(Error::ConfigParse(format!(
+                "merch item '{}': url '{}' must be an http:// or https:// link",
+                item.name, item.url
+            )));
+        }
+        if let Some(image) = &item.image {
+            validate_path(&image.to_string_lossy(), "merch.items.image")?;
+        }
+        if let Some(name) = item.image_file_name()
+            && !seen.insert(name.to_lowercase())
+        {
+            return Err(Error::ConfigParse(format!(
+                "merch.items: more than one image is named '{}'",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate `[[site.footer.links]]`: each goes to an http(s) page or a mailto: address
+fn validate_footer(footer: &FooterConfig) -> Result<()> {
+    for link in &footer.links {
+        let target = link
+            .url
+            .strip_prefix("https://")
+            .or_else(|| link.url.strip_prefix("http://"))
+            .or_else(|| link.url.strip_prefix("mailto:"));
+        if target.is_none_or(|target| target.trim().is_empty()) {
+            return Err(Error::ConfigParse(format!(
+                "site.footer.links '{}': url '{}' must be an http://, https:// or mailto: link",
+                link.label, link.url
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate `[artwork] gallery`: safe JPEG/PNG paths with distinct filenames
+///
+/// Gallery images share one directory in the built site, so two with the
+/// same filename would overwrite each other.
+fn validate_gallery(artwork: &Artwork) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for (image, name) in artwork.gallery.iter().zip(artwork.gallery_file_names()) {
+        validate_path(&image.to_string_lossy(), "artwork.gallery")?;
+        if !is_image_file(image) {
+            return Err(Error::ConfigParse(format!(
+                "artwork.gallery: '{}' is not a JPEG or PNG image",
+                image.display()
+            )));
+        }
+        if !seen.insert(name.to_lowercase()) {
+            return Err(Error::ConfigParse(format!(
+                "artwork.gallery: more than one image is named '{}'",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate `[hosting.cloudflare] r2_prefix`: `/`-separated segments of
+/// letters, digits, `.`, `_` and `-` (surrounding slashes are ignored)
+fn validate_r2_prefix(prefix: &str) -> Result<()> {
+    let valid = prefix.trim_matches('/').split('/').all(|segment| {
+        !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    });
+    if !valid {
         return Err(Error::ConfigParse(format!(
-            "Invalid duration format '{}', expected MM:SS",
-            s
+            "hosting.cloudflare.r2_prefix: '{}' must be letters, digits, '.', '_' or '-', with '/' between parts (e.g. \"v2\" or \"releases/2025\")",
+            prefix
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a color is `#RGB`, `#RRGGBB` or `#RRGGBBAA`
+///
+/// The value is written into the page's CSS, so anything else (e.g.
+/// `#fff; } body { ... }`) is rejected rather than rendered. An empty value
+/// means "use the theme's color" and is allowed.
+fn validate_hex_color(color: &str, field_name: &str) -> Result<()> {
+    if color.is_empty() {
+        return Ok(());
+    }
+    let valid = color.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    });
+    if !valid {
+        return Err(Error::ConfigParse(format!(
+            "{} '{}' must be a hex color (#RGB, #RRGGBB or #RRGGBBAA)",
+            field_name, color
+        )));
+    }
+    Ok(())
+}
+
+/// Validate `[rss.itunes]` against Apple's category list and email rules
+fn validate_itunes(itunes: &ItunesConfig) -> Result<()> {
+    if !is_itunes_category(&itunes.category, itunes.subcategory.as_deref()) {
+        return Err(Error::ConfigParse(match &itunes.subcategory {
+            Some(sub) => format!(
+                "rss.itunes: '{}' is not an Apple Podcasts subcategory of '{}'",
+                sub, itunes.category
+            ),
+            None => format!(
+                "rss.itunes: '{}' is not an Apple Podcasts category",
+                itunes.category
+            ),
+        }));
+    }
+
+    if let Some(email) = &itunes.owner_email
+        && !is_valid_email(email)
+    {
+        return Err(Error::ConfigParse(format!(
+            "rss.itunes.owner_email is not a valid email: '{}'",
+            email
         )));
     }
 
-    let minutes: u64 = parts[0]
+    Ok(())
+}
+
+/// Validate email format
+/// Checks for basic RFC 5322 compliance without full regex
+pub fn is_valid_email(email: &str) -> bool {
+    // Must have exactly one @ symbol
+    let at_count = email.matches('@').count();
+    if at_count != 1 {
+        return false;
+    }
+
+    let parts: Vec<&str> = email.split('@').collect();
+    let local = parts[0];
+    let domain = parts[1];
+
+    // Local part (before @) checks
+    if local.is_empty() || local.len() > 64 {
+        return false;
+    }
+
+    // Domain checks
+    if domain.is_empty() || domain.len() > 255 {
+        return false;
+    }
+
+    // Domain must have at least one dot
+    if !domain.contains('.') {
+        return false;
+    }
+
+    // Domain can't start/end with dot or hyphen
+    if domain.starts_with('.')
+        || domain.ends_with('.')
+        || domain.starts_with('-')
+        || domain.ends_with('-')
+    {
+        return false;
+    }
+
+    // No consecutive dots
+    if domain.contains("..") {
+        return false;
+    }
+
+    // Domain must have valid TLD (at least 2 chars after last dot)
+    if let Some(last_dot) = domain.rfind('.') {
+        let tld = &domain[last_dot + 1..];
+        if tld.len() < 2 {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Check a musical key: a note A-G, optional `#` or `b`, optional `m` (minor)
+fn is_valid_musical_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    if !chars.next().is_some_and(|c| ('A'..='G').contains(&c)) {
+        return false;
+    }
+    let rest = chars.as_str();
+    let rest = rest
+        .strip_prefix('#')
+        .or_else(|| rest.strip_prefix('b'))
+        .unwrap_or(rest);
+    rest.is_empty() || rest == "m"
+}
+
+/// Parse duration string in format "MM:SS", "M:SS", or "H:MM:SS"
+///
+/// In the two-part form minutes are unbounded (`78:30` is fine); with an
+/// hours part, minutes must be < 60.
+fn parse_duration(s: &str) -> Result<std::time::Duration> {
+    let parts: Vec<&str> = s.split(':').collect();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [minutes, seconds] => (None, *minutes, *seconds),
+        [hours, minutes, seconds] => (Some(*hours), *minutes, *seconds),
+        _ => {
+            return Err(Error::ConfigParse(format!(
+                "Invalid duration format '{}', expected MM:SS or H:MM:SS",
+                s
+            )));
+        }
+    };
+
+    let hours: u64 = match hours {
+        Some(hours) => hours
+            .parse()
+            .map_err(|_| Error::ConfigParse(format!("Invalid hours in duration '{}'", s)))?,
+        None => 0,
+    };
+
+    let minutes: u64 = minutes
         .parse()
         .map_err(|_| Error::ConfigParse(format!("Invalid minutes in duration '{}'", s)))?;
 
-    let seconds: u64 = parts[1]
+    let seconds: u64 = seconds
         .parse()
         .map_err(|_| Error::ConfigParse(format!("Invalid seconds in duration '{}'", s)))?;
 
+    if parts.len() == 3 && minutes >= 60 {
+        return Err(Error::ConfigParse(format!(
+            "Minutes must be < 60 in duration '{}'",
+            s
+        )));
+    }
+
     if seconds >= 60 {
         return Err(Error::ConfigParse(format!(
             "Seconds must be < 60 in duration '{}'",
@@ -230,13 +719,147 @@ fn parse_duration(s: &str) -> Result<std::time::Duration> {
         )));
     }
 
-    Ok(std::time::Duration::from_secs(minutes * 60 + seconds))
+    Ok(std::time::Duration::from_secs(
+        hours * 3600 + minutes * 60 + seconds,
+    ))
+}
+
+/// Format a TOML error as `path:line:col: message` with a source snippet
+///
+/// ```text
+/// album.toml:3:9: invalid string
+///   |
+/// 3 | title = "Unterminated
+///   |         ^
+/// ```
+fn describe_toml_error(source: &str, content: &str, err: &toml::de::Error) -> String {
+    let message = err.message().trim_end();
+    let Some(span) = err.span() else {
+        return format!("{}: {}", source, message);
+    };
+
+    let start = span.start.min(content.len());
+    let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = content[start..]
+        .find('\n')
+        .map(|i| start + i)
+        .unwrap_or(content.len());
+    let line_text = content[line_start..line_end].trim_end_matches('\r');
+
+    let line_number = content[..line_start].matches('\n').count() + 1;
+    let column = content[line_start..start].chars().count() + 1;
+
+    // Underline the span, clamped to the offending line
+    let span_end = span.end.clamp(start, line_end);
+    let caret_len = content[start..span_end].chars().count().max(1);
+
+    let gutter = " ".repeat(line_number.to_string().len());
+    format!(
+        "{}:{}:{}: {}\n{} |\n{} | {}\n{} | {}{}",
+        source,
+        line_number,
+        column,
+        message,
+        gutter,
+        line_number,
+        line_text,
+        gutter,
+        " ".repeat(column - 1),
+        "^".repeat(caret_len)
+    )
+}
+
+/// Parse a preview clip range "M:SS-M:SS", checking it against the track duration if known
+fn parse_preview_clip(s: &str, duration: Option<std::time::Duration>) -> Result<PreviewClip> {
+    let (start, end) = s.split_once('-').ok_or_else(|| {
+        Error::ConfigParse(format!(
+            "Invalid preview_clip '{}', expected START-END (e.g. \"0:30-1:00\")",
+            s
+        ))
+    })?;
+
+    let start = parse_duration(start.trim())?;
+    let end = parse_duration(end.trim())?;
+
+    if start >= end {
+        return Err(Error::ConfigParse(format!(
+            "preview_clip '{}' must end after it starts",
+            s
+        )));
+    }
+
+    if let Some(duration) = duration
+        && end > duration
+    {
+        return Err(Error::ConfigParse(format!(
+            "preview_clip '{}' extends past the track duration ({})",
+            s,
+            format_duration(Some(duration))
+        )));
+    }
+
+    Ok(PreviewClip { start, end })
+}
+
+/// Parse a track's `chapters`, which must be named, in order, and (when the
+/// duration is known) start before the track ends
+fn parse_chapters(
+    title: &str,
+    raw: Vec<RawChapter>,
+    duration: Option<std::time::Duration>,
+) -> Result<Vec<Chapter>> {
+    let mut chapters: Vec<Chapter> = Vec::with_capacity(raw.len());
+    for chapter in raw {
+        if chapter.name.trim().is_empty() {
+            return Err(Error::ConfigParse(format!(
+                "Track '{}': chapter names cannot be empty",
+                title
+            )));
+        }
+        let start = parse_duration(chapter.start.trim())?;
+        if let Some(previous) = chapters.last()
+            && start <= previous.start
+        {
+            return Err(Error::ConfigParse(format!(
+                "Track '{}': chapter '{}' must start after '{}'",
+                title, chapter.name, previous.name
+            )));
+        }
+        if let Some(duration) = duration
+            && start >= duration
+        {
+            return Err(Error::ConfigParse(format!(
+                "Track '{}': chapter '{}' starts past the track duration ({})",
+                title,
+                chapter.name,
+                format_duration(Some(duration))
+            )));
+        }
+        chapters.push(Chapter {
+            name: chapter.name,
+            start,
+        });
+    }
+    Ok(chapters)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_duration_with_hours() {
+        assert_eq!(parse_duration("1:18:30").unwrap().as_secs(), 4710);
+        assert_eq!(parse_duration("0:05:00").unwrap().as_secs(), 300);
+        assert_eq!(parse_duration("78:30").unwrap().as_secs(), 4710);
+
+        let err = parse_duration("1:70:00").unwrap_err().to_string();
+        assert!(err.contains("Minutes must be < 60"), "{}", err);
+        assert!(parse_duration("1:18:60").is_err());
+        assert!(parse_duration("x:18:30").is_err());
+        assert!(parse_duration("1::30").is_err());
+    }
+
     #[test]
     fn test_parse_duration() {
         assert_eq!(parse_duration("5:23").unwrap().as_secs(), 323);
@@ -604,7 +1227,7 @@ enabled = true
         assert!(parse_duration("5:99").is_err()); // Seconds >= 60
         assert!(parse_duration("-5:30").is_err()); // Negative minutes
         assert!(parse_duration("5:-30").is_err()); // Negative seconds
-        assert!(parse_duration("5:30:00").is_err()); // Too many parts
+        assert!(parse_duration("1:5:30:00").is_err()); // Too many parts
         assert!(parse_duration("abc:def").is_err()); // Non-numeric
         assert!(parse_duration("").is_err()); // Empty
         assert!(parse_duration("5.5:30").is_err()); // Decimal minutes
@@ -916,4 +1539,1826 @@ enabled = true
                 .contains("Track title cannot be empty")
         );
     }
+
+    #[test]
+    fn test_parse_config_with_site_verification() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[site.verification]
+google = "abc123"
+bing = "def456"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+        "##;
+
+        let album = parse_album_toml_str(toml).unwrap();
+        assert_eq!(album.site.verification.len(), 2);
+        assert_eq!(album.site.verification["google"], "abc123");
+        assert_eq!(album.site.verification["bing"], "def456");
+    }
+
+    #[test]
+    fn test_parse_config_with_site_footer() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[site.footer.links]]
+label = "Mastodon"
+url = "https://example.social/@artist"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+        "##;
+
+        let album = parse_album_toml_str(toml).unwrap();
+        assert!(album.site.footer.copyright.is_none());
+        assert_eq!(album.copyright(), "© 2025 Test Artist");
+        assert_eq!(album.site.footer.links.len(), 1);
+        assert_eq!(album.site.footer.links[0].label, "Mastodon");
+        assert_eq!(
+            album.site.footer.links[0].url,
+            "https://example.social/@artist"
+        );
+
+        let with_url = |url: &str| toml.replace("https://example.social/@artist", url);
+        let album = parse_album_toml_str(&with_url("mailto:artist@example.com")).unwrap();
+        assert_eq!(album.site.footer.links[0].url, "mailto:artist@example.com");
+        for url in [
+            "javascript:alert(1)",
+            "data:text/html,hi",
+            "https://",
+            "mailto:",
+            "/about",
+        ] {
+            let err = parse_album_toml_str(&with_url(url))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains("must be an http://, https:// or mailto: link"),
+                "{}: {}",
+                url,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_config_explicit_defaults_false() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+
+[[track]]
+file = "audio/02-track.flac"
+title = "Track Two"
+explicit = true
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+        "##;
+
+        let album = parse_album_toml_str(toml).unwrap();
+        assert!(!album.metadata.explicit);
+        assert!(!album.tracks[0].explicit);
+        assert!(album.tracks[1].explicit, "Track override should apply");
+    }
+
+    #[test]
+    fn test_parse_config_explicit_album_with_track_override() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+explicit = true
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+
+[[track]]
+file = "audio/02-track.flac"
+title = "Radio Edit"
+explicit = false
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+        "##;
+
+        let album = parse_album_toml_str(toml).unwrap();
+        assert!(album.metadata.explicit);
+        assert!(album.tracks[0].explicit, "Tracks inherit album flag");
+        assert!(!album.tracks[1].explicit, "Track can opt out as clean");
+    }
+
+    #[test]
+    fn test_parse_config_with_site_language() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+language = "he"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+        "##;
+
+        let album = parse_album_toml_str(toml).unwrap();
+        assert_eq!(album.site.language(), "he");
+        assert!(album.site.is_rtl());
+
+        let mut site = album.site.clone();
+        site.language = None;
+        assert_eq!(site.language(), "en");
+        assert!(!site.is_rtl());
+        site.language = Some("fa_IR".to_string());
+        assert!(site.is_rtl());
+        site.language = Some("fr".to_string());
+        assert!(!site.is_rtl());
+    }
+
+    #[test]
+    fn test_parse_config_distribution_preload() {
+        use crate::types::{AudioPreload, LossyFormat};
+
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+{extra}
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+            )
+        };
+
+        let preload =
+            |extra: &str| parse_album_toml_str(&toml(extra)).map(|a| a.distribution.preload);
+        assert_eq!(preload("").unwrap(), AudioPreload::Metadata);
+        assert_eq!(preload(r#"preload = "none""#).unwrap(), AudioPreload::None);
+        assert_eq!(
+            preload(r#"preload = "metadata""#).unwrap(),
+            AudioPreload::Metadata
+        );
+        assert_eq!(preload(r#"preload = "auto""#).unwrap(), AudioPreload::Auto);
+
+        let err = preload(r#"preload = "eager""#).unwrap_err().to_string();
+        assert!(err.contains("unknown variant `eager`"), "{}", err);
+
+        let gapless =
+            |extra: &str| parse_album_toml_str(&toml(extra)).map(|a| a.distribution.gapless);
+        assert!(!gapless("").unwrap());
+        assert!(gapless("gapless = true").unwrap());
+        assert!(!gapless("gapless = false").unwrap());
+        assert!(gapless(r#"gapless = "yes""#).is_err());
+
+        let stream_format = |extra: &str| {
+            parse_album_toml_str(&toml(extra)).map(|a| a.distribution.stream_copy_format())
+        };
+        assert_eq!(stream_format("").unwrap(), None);
+        assert_eq!(
+            stream_format(r#"stream_format = "opus-128""#).unwrap(),
+            Some(LossyFormat::Opus { kbps: 128 })
+        );
+        assert_eq!(
+            stream_format(r#"stream_format = "mp3-96""#).unwrap(),
+            Some(LossyFormat::Mp3 { kbps: 96 })
+        );
+        let err = stream_format(r#"stream_format = "flac""#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("distribution.stream_format: unknown format 'flac'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_config_distribution_default_track() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-intro.flac"
+title = "Intro"
+
+[[track]]
+file = "audio/02-single.flac"
+title = "The Single"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+{extra}
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+            )
+        };
+        let parse = |extra: &str| parse_album_toml_str(&toml(extra));
+
+        let album = parse("").unwrap();
+        assert_eq!(album.distribution.default_track, None);
+        assert_eq!(album.default_track_index(), None);
+
+        let album = parse("default_track = 1").unwrap();
+        assert_eq!(
+            album.distribution.default_track,
+            Some(DefaultTrack::Index(1))
+        );
+        assert_eq!(album.default_track_index(), Some(1));
+
+        let album = parse(r#"default_track = "the-single""#).unwrap();
+        assert_eq!(
+            album.distribution.default_track,
+            Some(DefaultTrack::Slug("the-single".to_string()))
+        );
+        assert_eq!(album.default_track_index(), Some(1));
+
+        let err = parse("default_track = 2").unwrap_err().to_string();
+        assert!(
+            err.contains("no track at index 2 (0 is the first of 2 tracks)"),
+            "{}",
+            err
+        );
+        let err = parse(r#"default_track = "outro""#).unwrap_err().to_string();
+        assert!(
+            err.contains("no track with slug 'outro' (track slugs: intro, the-single)"),
+            "{}",
+            err
+        );
+        assert!(parse("default_track = -1").is_err());
+    }
+
+    #[test]
+    fn test_remove_drafts_keeps_default_track() {
+        use crate::types::DefaultTrack;
+
+        let toml = |default_track: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-sketch.flac"
+title = "Sketch"
+draft = true
+
+[[track]]
+file = "audio/02-single.flac"
+title = "The Single"
+
+[[track]]
+file = "audio/03-demo.flac"
+title = "Demo"
+draft = true
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+default_track = {default_track}
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+            )
+        };
+
+        let mut album = parse_album_toml_str(&toml("1")).unwrap();
+        let drafts: Vec<bool> = album.tracks.iter().map(|t| t.draft).collect();
+        assert_eq!(drafts, vec![true, false, true]);
+
+        assert_eq!(album.remove_drafts(), 2);
+        let titles: Vec<&str> = album.tracks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["The Single"]);
+        // Index 1 was The Single, now first
+        assert_eq!(
+            album.distribution.default_track,
+            Some(DefaultTrack::Index(0))
+        );
+        assert_eq!(album.remove_drafts(), 0);
+
+        // A draft default track is dropped with it
+        let mut album = parse_album_toml_str(&toml("2")).unwrap();
+        album.remove_drafts();
+        assert_eq!(album.distribution.default_track, None);
+        let mut album = parse_album_toml_str(&toml(r#""sketch""#)).unwrap();
+        album.remove_drafts();
+        assert_eq!(album.distribution.default_track, None);
+        let mut album = parse_album_toml_str(&toml(r#""the-single""#)).unwrap();
+        album.remove_drafts();
+        assert_eq!(album.default_track_index(), Some(0));
+    }
+
+    #[test]
+    fn test_lossy_format_parse() {
+        use crate::types::LossyFormat;
+
+        assert_eq!(
+            LossyFormat::parse("mp3-320"),
+            Some(LossyFormat::Mp3 { kbps: 320 })
+        );
+        assert_eq!(
+            LossyFormat::parse("MP3"),
+            Some(LossyFormat::Mp3 { kbps: 320 })
+        );
+        assert_eq!(
+            LossyFormat::parse("ogg-128"),
+            Some(LossyFormat::Ogg { kbps: 128 })
+        );
+        assert_eq!(
+            LossyFormat::parse("ogg"),
+            Some(LossyFormat::Ogg { kbps: 192 })
+        );
+        assert_eq!(
+            LossyFormat::parse("opus"),
+            Some(LossyFormat::Opus { kbps: 128 })
+        );
+        assert_eq!(
+            LossyFormat::Opus { kbps: 96 }.stream_file_name("disc1/01-track.flac"),
+            "disc1/01-track.stream.opus"
+        );
+        assert_eq!(LossyFormat::parse("flac"), None);
+        assert_eq!(LossyFormat::parse("mp3-fast"), None);
+        assert_eq!(LossyFormat::parse("mp3-0"), None);
+    }
+
+    #[test]
+    fn test_theme_from_name() {
+        use crate::types::Theme;
+
+        assert_eq!(Theme::from_name("default"), Some(Theme::Default));
+        assert_eq!(Theme::from_name(""), Some(Theme::Default));
+        assert_eq!(Theme::from_name("Light"), Some(Theme::Light));
+        assert_eq!(Theme::from_name("minimal"), Some(Theme::Minimal));
+        assert_eq!(Theme::from_name("high-contrast"), Some(Theme::HighContrast));
+        assert_eq!(Theme::from_name("high_contrast"), Some(Theme::HighContrast));
+        assert_eq!(Theme::from_name("HighContrast"), Some(Theme::HighContrast));
+        assert_eq!(Theme::from_name("neon"), None);
+    }
+
+    #[test]
+    fn test_validate_hex_color() {
+        for color in ["#fff", "#FF6B35", "#ff6b35", "#ff6b3580", ""] {
+            assert!(
+                validate_hex_color(color, "site.accent_color").is_ok(),
+                "{color}"
+            );
+        }
+        for color in [
+            "#fff; } body { background: url(evil)",
+            "red",
+            "ff6b35",
+            "#ff6b3",
+            "#12345g",
+            "#ff6b35ff0",
+            " #ff6b35",
+        ] {
+            assert!(
+                validate_hex_color(color, "site.accent_color").is_err(),
+                "{color}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_config_rejects_css_in_accent_color() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#fff; } body { background: url(evil)"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##;
+        let err = parse_album_toml_str(toml).unwrap_err().to_string();
+        assert!(err.contains("site.accent_color"));
+        assert!(err.contains("#RGB, #RRGGBB or #RRGGBBAA"));
+    }
+
+    #[test]
+    fn test_parse_config_site_seek_seconds() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+{extra}
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+            )
+        };
+
+        assert_eq!(
+            parse_album_toml_str(&toml("")).unwrap().site.seek_seconds,
+            10
+        );
+        assert_eq!(
+            parse_album_toml_str(&toml("seek_seconds = 30"))
+                .unwrap()
+                .site
+                .seek_seconds,
+            30
+        );
+        let err = parse_album_toml_str(&toml("seek_seconds = 0"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("site.seek_seconds must be at least 1"));
+    }
+
+    #[test]
+    fn test_parse_config_with_preview_clip() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+duration = "3:45"
+preview_clip = "0:30-1:00"
+
+[[track]]
+file = "audio/02-track.flac"
+title = "Track Two"
+
+[distribution]
+streaming_enabled = true
+download_enabled = true
+download_price = 7.0
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+        "##;
+
+        let album = parse_album_toml_str(toml).unwrap();
+        let clip = album.tracks[0].preview_clip.unwrap();
+        assert_eq!(clip.start, std::time::Duration::from_secs(30));
+        assert_eq!(clip.end, std::time::Duration::from_secs(60));
+        assert_eq!(clip.length(), std::time::Duration::from_secs(30));
+        assert!(album.tracks[1].preview_clip.is_none());
+        assert!(album.distribution.is_paid());
+    }
+
+    #[test]
+    fn test_parse_preview_clip_validation() {
+        let three_min = Some(std::time::Duration::from_secs(180));
+
+        assert!(parse_preview_clip("0:30-1:00", three_min).is_ok());
+        assert!(parse_preview_clip("0:30-1:00", None).is_ok());
+
+        // Malformed or inverted ranges
+        assert!(parse_preview_clip("0:30", three_min).is_err());
+        assert!(parse_preview_clip("1:00-0:30", three_min).is_err());
+        assert!(parse_preview_clip("0:30-0:30", three_min).is_err());
+        assert!(parse_preview_clip("0:30-abc", three_min).is_err());
+
+        // Range past the end of the track
+        let err = parse_preview_clip("2:30-3:30", three_min).unwrap_err();
+        assert!(err.to_string().contains("past the track duration (3:00)"));
+    }
+
+    #[test]
+    fn test_parse_track_chapters() {
+        let toml = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-mix.flac"
+title = "The Mix"
+duration = "1:02:00"
+
+[[track.chapters]]
+name = "Intro"
+start = "0:00"
+
+[[track.chapters]]
+name = "Drop"
+start = "12:30"
+
+[[track.chapters]]
+name = "Outro"
+start = "1:00:05"
+
+[[track]]
+file = "audio/02-short.flac"
+title = "Short"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##;
+        let album = parse_album_toml_str(toml).unwrap();
+        let chapters = &album.tracks[0].chapters;
+        assert_eq!(
+            chapters
+                .iter()
+                .map(|c| (c.name.as_str(), c.start.as_secs()))
+                .collect::<Vec<_>>(),
+            vec![("Intro", 0), ("Drop", 750), ("Outro", 3605)]
+        );
+        assert!(album.tracks[1].chapters.is_empty());
+
+        let err = parse_album_toml_str(&toml.replace("start = \"12:30\"", "start = \"12:75\""))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Seconds must be < 60"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_chapters_validation() {
+        let one_hour = Some(std::time::Duration::from_secs(3600));
+        let chapters = |starts: &[(&str, &str)]| {
+            starts
+                .iter()
+                .map(|(name, start)| RawChapter {
+                    name: name.to_string(),
+                    start: start.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            parse_chapters("Mix", chapters(&[("A", "0:00"), ("B", "59:59")]), one_hour).is_ok()
+        );
+        assert!(parse_chapters("Mix", chapters(&[("A", "2:00:00")]), None).is_ok());
+
+        for (raw, expected) in [
+            (
+                chapters(&[("A", "5:00"), ("B", "5:00")]),
+                "Track 'Mix': chapter 'B' must start after 'A'",
+            ),
+            (
+                chapters(&[("A", "5:00"), ("B", "1:00")]),
+                "chapter 'B' must start after 'A'",
+            ),
+            (
+                chapters(&[("A", "1:00:00")]),
+                "chapter 'A' starts past the track duration (1:00:00)",
+            ),
+            (chapters(&[(" ", "0:00")]), "chapter names cannot be empty"),
+            (chapters(&[("A", "soon")]), "Invalid duration format 'soon'"),
+        ] {
+            let err = parse_chapters("Mix", raw, one_hour)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_album_toml_syntax_error_reports_path_and_line() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("album.toml");
+        fs::write(
+            &path,
+            "[album]\ntitle = \"Test Album\"\nartist = \"Unterminated\n",
+        )
+        .unwrap();
+
+        let err = parse_album_toml(&path).unwrap_err().to_string();
+
+        assert!(
+            err.contains(&format!("{}:3:", path.display())),
+            "error should mention path and line: {}",
+            err
+        );
+        assert!(err.contains("3 | artist = \"Unterminated"), "{}", err);
+        assert!(err.contains("  | "), "{}", err);
+        assert!(err.contains('^'), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_album_toml_missing_file_mentions_path() {
+        let err = parse_album_toml("/nonexistent/release-kit/album.toml")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("/nonexistent/release-kit/album.toml"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_describe_toml_error_caret_position() {
+        let content = "[album]\ntitle = = \"x\"\n";
+        let err = toml::from_str::<toml::Value>(content).unwrap_err();
+        let described = describe_toml_error("album.toml", content, &err);
+
+        let lines: Vec<&str> = described.lines().collect();
+        assert!(lines[0].starts_with("album.toml:2:"), "{}", described);
+        assert_eq!(lines[2], "2 | title = = \"x\"");
+        // Caret sits under the reported column
+        let column: usize = lines[0].split(':').nth(2).unwrap().parse().unwrap();
+        assert_eq!(lines[3].find('^').unwrap(), "2 | ".len() + column - 1);
+    }
+
+    #[test]
+    fn test_parse_hosting_s3() {
+        let base = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##;
+
+        let album = parse_album_toml_str(base).unwrap();
+        assert!(album.hosting.s3.is_none());
+
+        let toml = format!(
+            "{}\n[hosting.s3]\nendpoint = \"https://s3.us-west-004.backblazeb2.com/\"\nbucket = \"my-audio\"\n",
+            base
+        );
+        let album = parse_album_toml_str(&toml).unwrap();
+        let s3 = album.hosting.s3.unwrap();
+        assert_eq!(s3.region, "us-east-1");
+        assert_eq!(
+            s3.public_base_url(),
+            "https://s3.us-west-004.backblazeb2.com/my-audio"
+        );
+
+        let toml = format!(
+            "{}\n[hosting.s3]\nendpoint = \"http://localhost:9000\"\nregion = \"eu-central-1\"\nbucket = \"b\"\npublic_url = \"https://cdn.example.com/\"\n",
+            base
+        );
+        let s3 = parse_album_toml_str(&toml).unwrap().hosting.s3.unwrap();
+        assert_eq!(s3.region, "eu-central-1");
+        assert_eq!(s3.public_base_url(), "https://cdn.example.com");
+    }
+
+    #[test]
+    fn test_parse_rss_limits() {
+        let base = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##;
+
+        let album = parse_album_toml_str(base).unwrap();
+        assert_eq!(album.rss.max_items, None);
+        assert_eq!(album.rss.since, None);
+
+        let toml = format!("{}max_items = 20\nsince = \"2024-01-01\"\n", base);
+        let album = parse_album_toml_str(&toml).unwrap();
+        assert_eq!(album.rss.max_items, Some(20));
+        assert_eq!(album.rss.since, chrono::NaiveDate::from_ymd_opt(2024, 1, 1));
+
+        let toml = format!("{}max_items = 0\n", base);
+        let err = parse_album_toml_str(&toml).unwrap_err();
+        assert!(err.to_string().contains("rss.max_items"));
+
+        let toml = format!("{}since = \"January 2024\"\n", base);
+        assert!(parse_album_toml_str(&toml).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_email() {
+        // Valid emails
+        assert!(is_valid_email("user@example.com"));
+        assert!(is_valid_email("test.user@domain.co.uk"));
+        assert!(is_valid_email("name+tag@example.org"));
+
+        // Invalid emails - missing @
+        assert!(!is_valid_email("user"));
+        assert!(!is_valid_email(""));
+
+        // Invalid emails - multiple @
+        assert!(!is_valid_email("user@@example.com"));
+        assert!(!is_valid_email("user@name@example.com"));
+
+        // Invalid emails - missing parts
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("user@"));
+
+        // Invalid emails - invalid domain
+        assert!(!is_valid_email("user@domain")); // No TLD
+        assert!(!is_valid_email("user@.com")); // Domain starts with dot
+        assert!(!is_valid_email("user@domain.")); // Domain ends with dot
+        assert!(!is_valid_email("user@domain.c")); // TLD too short
+        assert!(!is_valid_email("user@domain..com")); // Consecutive dots
+
+        // Invalid emails - local part too long
+        let long_local = "a".repeat(65);
+        assert!(!is_valid_email(&format!("{}@example.com", long_local)));
+    }
+
+    #[test]
+    fn test_parse_rss_itunes() {
+        let base = r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##;
+
+        assert!(parse_album_toml_str(base).unwrap().rss.itunes.is_none());
+
+        let toml = format!(
+            "{}\n[rss.itunes]\ncategory = \"Music\"\nsubcategory = \"Music Commentary\"\nsubtitle = \"Album notes\"\nowner_name = \"Test Artist\"\nowner_email = \"owner@example.com\"\n",
+            base
+        );
+        let itunes = parse_album_toml_str(&toml).unwrap().rss.itunes.unwrap();
+        assert_eq!(itunes.category, "Music");
+        assert_eq!(itunes.subcategory.as_deref(), Some("Music Commentary"));
+        assert_eq!(itunes.owner_email.as_deref(), Some("owner@example.com"));
+        assert!(itunes.image.is_none());
+
+        let toml = format!("{}\n[rss.itunes]\ncategory = \"Jazz\"\n", base);
+        let err = parse_album_toml_str(&toml).unwrap_err().to_string();
+        assert!(err.contains("'Jazz' is not an Apple Podcasts category"));
+
+        let toml = format!(
+            "{}\n[rss.itunes]\ncategory = \"Music\"\nsubcategory = \"Jazz\"\n",
+            base
+        );
+        let err = parse_album_toml_str(&toml).unwrap_err().to_string();
+        assert!(err.contains("not an Apple Podcasts subcategory of 'Music'"));
+
+        let toml = format!(
+            "{}\n[rss.itunes]\ncategory = \"Music\"\nowner_email = \"not-an-email\"\n",
+            base
+        );
+        let err = parse_album_toml_str(&toml).unwrap_err().to_string();
+        assert!(err.contains("rss.itunes.owner_email"));
+    }
+
+    #[test]
+    fn test_parse_track_numbers() {
+        let toml = |one: &str, two: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+{one}
+
+[[track]]
+file = "audio/02-track.flac"
+title = "Track Two"
+{two}
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+            )
+        };
+
+        let album = parse_album_toml_str(&toml("", "")).unwrap();
+        assert_eq!(album.track_numbers(), vec![1, 2]);
+
+        let album = parse_album_toml_str(&toml("track_number = 7", "track_number = 8")).unwrap();
+        assert_eq!(album.tracks[0].track_number, Some(7));
+        assert_eq!(album.track_numbers(), vec![7, 8]);
+
+        // Explicit numbers must be unique, including against positional ones
+        let err = parse_album_toml_str(&toml("track_number = 3", "track_number = 3"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Duplicate track number 3 (track 'Track Two')"));
+
+        let err = parse_album_toml_str(&toml("track_number = 2", ""))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Duplicate track number 2"));
+
+        let err = parse_album_toml_str(&toml("track_number = 0", ""))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("track_number must be positive"));
+
+        assert!(parse_album_toml_str(&toml("track_number = -1", "")).is_err());
+    }
+
+    #[test]
+    fn test_parse_two_disc_album() {
+        let toml = |tracks: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+{tracks}
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##
+            )
+        };
+        let two_discs = r#"
+[[track]]
+file = "audio/disc1/01.flac"
+title = "One"
+
+[[track]]
+file = "audio/disc1/02.flac"
+title = "Two"
+disc = 1
+
+[[track]]
+file = "audio/disc2/01.flac"
+title = "Three"
+disc = 2
+
+[[track]]
+file = "audio/disc2/02.flac"
+title = "Four"
+disc = 2
+"#;
+
+        let album = parse_album_toml_str(&toml(two_discs)).unwrap();
+        assert_eq!(album.tracks[0].disc, None);
+        assert_eq!(album.tracks[0].disc_number(), 1);
+        assert_eq!(album.tracks[2].disc, Some(2));
+        assert!(album.is_multi_disc());
+        // Numbering restarts on each disc
+        assert_eq!(album.track_numbers(), vec![1, 2, 1, 2]);
+
+        let album = parse_album_toml_str(&toml(
+            "[[track]]\nfile = \"audio/01.flac\"\ntitle = \"One\"\ndisc = 1\n",
+        ))
+        .unwrap();
+        assert!(!album.is_multi_disc());
+
+        let err = parse_album_toml_str(&toml(&two_discs.replace(
+            "title = \"Four\"\ndisc = 2",
+            "title = \"Four\"\ndisc = 2\ntrack_number = 1",
+        )))
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("Duplicate track number 1 on disc 2 (track 'Four')"));
+
+        let err = parse_album_toml_str(&toml(
+            &two_discs.replace("title = \"Four\"\ndisc = 2", "title = \"Four\"\ndisc = 1"),
+        ))
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("list tracks in disc order"));
+
+        let err = parse_album_toml_str(&toml(&two_discs.replace("disc = 2", "disc = 0")))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("disc must be positive"));
+    }
+
+    #[test]
+    fn test_parse_track_bpm_and_key() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+{}
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##,
+                extra
+            )
+        };
+
+        let album = parse_album_toml_str(&toml("")).unwrap();
+        assert_eq!(album.tracks[0].bpm, None);
+        assert_eq!(album.tracks[0].key, None);
+
+        let album = parse_album_toml_str(&toml("bpm = 128\nkey = \"Am\"")).unwrap();
+        assert_eq!(album.tracks[0].bpm, Some(128.0));
+        assert_eq!(album.tracks[0].key.as_deref(), Some("Am"));
+
+        for key in ["C", "F#", "Bb", "C#m", "Ebm", "G"] {
+            let album = parse_album_toml_str(&toml(&format!("key = \"{}\"", key))).unwrap();
+            assert_eq!(album.tracks[0].key.as_deref(), Some(key));
+        }
+        assert_eq!(
+            parse_album_toml_str(&toml("bpm = 92.5")).unwrap().tracks[0].bpm,
+            Some(92.5)
+        );
+
+        for key in ["H", "am", "A#b", "Amaj", "F##", "", "8A"] {
+            let err = parse_album_toml_str(&toml(&format!("key = \"{}\"", key)))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains("invalid key"),
+                "Key '{}' should be rejected",
+                key
+            );
+        }
+
+        for bpm in ["0", "-120", "nan"] {
+            let err = parse_album_toml_str(&toml(&format!("bpm = {}", bpm)))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains("bpm must be a positive number"),
+                "bpm = {}",
+                bpm
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_album_credits() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+{}
+"##,
+                extra
+            )
+        };
+
+        assert!(parse_album_toml_str(&toml("")).unwrap().credits.is_empty());
+
+        let album = parse_album_toml_str(&toml(
+            "[credits]\nengineer = \"Jo\"\nart_by = [\"Ana\", \"Bo\"]\nlabel = \"Tape Co\"",
+        ))
+        .unwrap();
+        assert_eq!(album.credits.len(), 3);
+        assert_eq!(album.credits["engineer"], vec!["Jo"]);
+        assert_eq!(album.credits["art_by"], vec!["Ana", "Bo"]);
+
+        for (credits, expected) in [
+            ("\"\" = \"Jo\"", "role names cannot be empty"),
+            (
+                "engineer = \"  \"",
+                "credits.engineer: names cannot be empty",
+            ),
+            ("engineer = []", "credits.engineer: names cannot be empty"),
+            (
+                "engineer = [\"Jo\", \"\"]",
+                "credits.engineer: names cannot be empty",
+            ),
+        ] {
+            let err = parse_album_toml_str(&toml(&format!("[credits]\n{}", credits)))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "{}: {}", credits, err);
+        }
+    }
+
+    #[test]
+    fn test_parse_merch_items() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+{}
+"##,
+                extra
+            )
+        };
+
+        assert!(
+            parse_album_toml_str(&toml(""))
+                .unwrap()
+                .merch
+                .items
+                .is_empty()
+        );
+
+        let album = parse_album_toml_str(&toml(
+            r#"[[merch.items]]
+name = "Vinyl LP"
+url = "https://shop.example.com/lp"
+image = "merch/vinyl.jpg"
+price = "$25"
+
+[[merch.items]]
+name = "T-shirt"
+url = "http://shop.example.com/shirt""#,
+        ))
+        .unwrap();
+        assert_eq!(album.merch.items.len(), 2);
+        assert_eq!(album.merch.items[0].name, "Vinyl LP");
+        assert_eq!(album.merch.items[0].price.as_deref(), Some("$25"));
+        assert_eq!(
+            album.merch.items[0].image_file_name().as_deref(),
+            Some("vinyl.jpg")
+        );
+        assert!(album.merch.items[1].image.is_none());
+
+        for (item, expected) in [
+            (
+                "name = \" \"\nurl = \"https://shop.example.com\"",
+                "merch: item names cannot be empty",
+            ),
+            (
+                "name = \"LP\"\nurl = \"javascript:alert(1)\"",
+                "must be an http:// or https:// link",
+            ),
+            (
+                "name = \"LP\"\nurl = \"https://\"",
+                "must be an http:// or https:// link",
+            ),
+            (
+                "name = \"LP\"\nurl = \"https://shop.example.com\"\nimage = \"../secret.jpg\"",
+                "Parent directory references (..) not allowed in 'merch.items.image'",
+            ),
+            (
+                "name = \"LP\"\nurl = \"https://shop.example.com\"\nimage = \"/etc/lp.jpg\"",
+                "Absolute paths not allowed in 'merch.items.image'",
+            ),
+        ] {
+            let err = parse_album_toml_str(&toml(&format!("[[merch.items]]\n{}", item)))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "{}: {}", item, err);
+        }
+
+        // Both would land on merch/shirt.jpg
+        let err = parse_album_toml_str(&toml(
+            r#"[[merch.items]]
+name = "Red shirt"
+url = "https://shop.example.com/red"
+image = "shop/a/shirt.jpg"
+
+[[merch.items]]
+name = "Blue shirt"
+url = "https://shop.example.com/blue"
+image = "shop/b/Shirt.jpg""#,
+        ))
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("merch.items: more than one image is named 'Shirt.jpg'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_r2_prefix() {
+        let toml = |prefix: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+r2_prefix = "{}"
+
+[rss]
+enabled = true
+"##,
+                prefix
+            )
+        };
+
+        let album = parse_album_toml_str(&toml("releases/2025/")).unwrap();
+        assert_eq!(
+            album.audio_key("01-intro.flac"),
+            "releases/2025/audio/01-intro.flac"
+        );
+        assert_eq!(
+            album.bucket_key("release-kit/manifest.json"),
+            "releases/2025/release-kit/manifest.json"
+        );
+
+        for prefix in ["", "v2/../other", "v 2", "a//b", "v2?x"] {
+            let err = parse_album_toml_str(&toml(prefix)).unwrap_err().to_string();
+            assert!(
+                err.contains("hosting.cloudflare.r2_prefix"),
+                "{}: {}",
+                prefix,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_artwork_gallery() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+{}
+"##,
+                extra
+            )
+        };
+
+        assert!(
+            parse_album_toml_str(&toml(""))
+                .unwrap()
+                .artwork
+                .gallery
+                .is_empty()
+        );
+
+        let album = parse_album_toml_str(&toml(
+            r#"[artwork]
+gallery = ["artwork/1.jpg", "photos/studio session.PNG", "artwork/back.jpeg"]"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            album.artwork.gallery,
+            vec![
+                PathBuf::from("artwork/1.jpg"),
+                PathBuf::from("photos/studio session.PNG"),
+                PathBuf::from("artwork/back.jpeg"),
+            ]
+        );
+        assert_eq!(
+            album.artwork.gallery_file_names(),
+            vec!["1.jpg", "studio session.PNG", "back.jpeg"]
+        );
+
+        for (gallery, expected) in [
+            (
+                r#"["../secret.jpg"]"#,
+                "Parent directory references (..) not allowed in 'artwork.gallery'",
+            ),
+            (
+                r#"["/etc/photo.jpg"]"#,
+                "Absolute paths not allowed in 'artwork.gallery'",
+            ),
+            (
+                r#"["artwork/booklet.pdf"]"#,
+                "artwork.gallery: 'artwork/booklet.pdf' is not a JPEG or PNG image",
+            ),
+            (
+                r#"["front/1.jpg", "back/1.JPG"]"#,
+                "more than one image is named '1.JPG'",
+            ),
+        ] {
+            let err = parse_album_toml_str(&toml(&format!("[artwork]\ngallery = {}", gallery)))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "{}: {}", gallery, err);
+        }
+    }
+
+    #[test]
+    fn test_parse_track_credits() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Test Album"
+artist = "Test Artist"
+release_date = "2025-11-15"
+summary = "A test album"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Test Artist"
+rss_author_email = "test@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-track.flac"
+title = "Track One"
+{}
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##,
+                extra
+            )
+        };
+
+        let album = parse_album_toml_str(&toml("")).unwrap();
+        assert!(album.tracks[0].credits().is_empty());
+
+        let album = parse_album_toml_str(&toml(
+            "producer = [\"Ada\", \"Lin\"]\nmastered_by = [\"Sam\"]\nwritten_by = [\"Kim\"]",
+        ))
+        .unwrap();
+        let track = &album.tracks[0];
+        assert_eq!(track.producer, vec!["Ada", "Lin"]);
+        assert_eq!(track.mastered_by, vec!["Sam"]);
+        assert_eq!(track.written_by, vec!["Kim"]);
+        let roles: Vec<&str> = track.credits().iter().map(|(role, _)| *role).collect();
+        assert_eq!(roles, vec!["Produced by", "Written by", "Mastered by"]);
+
+        let err = parse_album_toml_str(&toml("written_by = [\" \"]"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("written_by names cannot be empty"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_compilation_track_artists() {
+        let toml = |extra: &str| {
+            format!(
+                r##"
+[album]
+title = "Various Artists Vol. 1"
+artist = "Various Artists"
+release_date = "2025-11-15"
+summary = "A compilation"
+genre = ["experimental"]
+license = "CC BY-NC-SA 4.0"
+
+[artist]
+name = "Label Name"
+rss_author_email = "label@example.com"
+
+[site]
+domain = "test.example.com"
+theme = "default"
+accent_color = "#ff6b35"
+
+[[track]]
+file = "audio/01-hum.flac"
+title = "Hum"
+artist = "Ada Lovelace"
+
+[[track]]
+file = "audio/02-decay.flac"
+title = "Decay"
+artist = "Kim & The Lins feat. Sam"
+
+[[track]]
+file = "audio/03-outro.flac"
+title = "Outro"
+{}
+
+[distribution]
+streaming_enabled = true
+download_enabled = false
+pay_what_you_want = false
+tip_jar_enabled = false
+download_formats = ["flac"]
+
+[hosting.cloudflare]
+
+[rss]
+enabled = true
+"##,
+                extra
+            )
+        };
+
+        let album = parse_album_toml_str(&toml("")).unwrap();
+        let artists: Vec<Option<&str>> = album.tracks.iter().map(|t| t.artist.as_deref()).collect();
+        assert_eq!(
+            artists,
+            vec![Some("Ada Lovelace"), Some("Kim & The Lins feat. Sam"), None]
+        );
+
+        let err = parse_album_toml_str(&toml("artist = \" \""))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Track 'Outro': artist cannot be empty"),
+            "{}",
+            err
+        );
+    }
 }
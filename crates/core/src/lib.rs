@@ -1,5 +1,7 @@
+pub mod artwork;
 pub mod config;
 pub mod error;
+pub mod rss;
 pub mod types;
 
 pub use config::parse_album_toml;
@@ -0,0 +1,430 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use std::cmp::Reverse;
+
+use crate::types::ItunesConfig;
+
+/// XML namespace for the `itunes:` channel tags
+pub const ITUNES_NAMESPACE: &str = "http://www.itunes.com/dtds/podcast-1.0.dtd";
+
+/// Apple Podcasts categories and their subcategories
+pub const ITUNES_CATEGORIES: &[(&str, &[&str])] = &[
+    (
+        "Arts",
+        &[
+            "Books",
+            "Design",
+            "Fashion & Beauty",
+            "Food",
+            "Performing Arts",
+            "Visual Arts",
+        ],
+    ),
+    (
+        "Business",
+        &[
+            "Careers",
+            "Entrepreneurship",
+            "Investing",
+            "Management",
+            "Marketing",
+            "Non-Profit",
+        ],
+    ),
+    ("Comedy", &["Comedy Interviews", "Improv", "Stand-Up"]),
+    (
+        "Education",
+        &["Courses", "How To", "Language Learning", "Self-Improvement"],
+    ),
+    ("Fiction", &["Comedy Fiction", "Drama", "Science Fiction"]),
+    ("Government", &[]),
+    ("History", &[]),
+    (
+        "Health & Fitness",
+        &[
+            "Alternative Health",
+            "Fitness",
+            "Medicine",
+            "Mental Health",
+            "Nutrition",
+            "Sexuality",
+        ],
+    ),
+    (
+        "Kids & Family",
+        &[
+            "Education for Kids",
+            "Parenting",
+            "Pets & Animals",
+            "Stories for Kids",
+        ],
+    ),
+    (
+        "Leisure",
+        &[
+            "Animation & Manga",
+            "Automotive",
+            "Aviation",
+            "Crafts",
+            "Games",
+            "Hobbies",
+            "Home & Garden",
+            "Video Games",
+        ],
+    ),
+    (
+        "Music",
+        &["Music Commentary", "Music History", "Music Interviews"],
+    ),
+    (
+        "News",
+        &[
+            "Business News",
+            "Daily News",
+            "Entertainment News",
+            "News Commentary",
+            "Politics",
+            "Sports News",
+            "Tech News",
+        ],
+    ),
+    (
+        "Religion & Spirituality",
+        &[
+            "Buddhism",
+            "Christianity",
+            "Hinduism",
+            "Islam",
+            "Judaism",
+            "Religion",
+            "Spirituality",
+        ],
+    ),
+    (
+        "Science",
+        &[
+            "Astronomy",
+            "Chemistry",
+            "Earth Sciences",
+            "Life Sciences",
+            "Mathematics",
+            "Natural Sciences",
+            "Nature",
+            "Physics",
+            "Social Sciences",
+        ],
+    ),
+    (
+        "Society & Culture",
+        &[
+            "Documentary",
+            "Personal Journal",
+            "Philosophy",
+            "Places & Travel",
+            "Relationships",
+        ],
+    ),
+    (
+        "Sports",
+        &[
+            "Baseball",
+            "Basketball",
+            "Cricket",
+            "Fantasy Sports",
+            "Football",
+            "Golf",
+            "Hockey",
+            "Rugby",
+            "Running",
+            "Soccer",
+            "Swimming",
+            "Tennis",
+            "Volleyball",
+            "Wilderness",
+            "Wrestling",
+        ],
+    ),
+    ("Technology", &[]),
+    ("True Crime", &[]),
+    (
+        "TV & Film",
+        &[
+            "After Shows",
+            "Film History",
+            "Film Interviews",
+            "Film Reviews",
+            "TV Reviews",
+        ],
+    ),
+];
+
+/// Check a category (and optional subcategory) against `ITUNES_CATEGORIES`
+///
+/// Matching is exact, as Apple rejects feeds with misspelled categories.
+pub fn is_itunes_category(category: &str, subcategory: Option<&str>) -> bool {
+    ITUNES_CATEGORIES
+        .iter()
+        .find(|(name, _)| *name == category)
+        .is_some_and(|(_, subs)| subcategory.is_none_or(|sub| subs.contains(&sub)))
+}
+
+/// Escape text for XML element content and attribute values
+pub fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render the `itunes:` channel tags for `[rss.itunes]`
+///
+/// The channel image falls back to `cover_url` (the cover art's absolute
+/// URL) when `image` is not set. The feed's `<rss>` element must declare
+/// `xmlns:itunes` as `ITUNES_NAMESPACE`.
+pub fn itunes_channel_tags(itunes: &ItunesConfig, cover_url: Option<&str>) -> String {
+    let mut xml = String::new();
+
+    if let Some(subtitle) = &itunes.subtitle {
+        xml.push_str(&format!(
+            "    <itunes:subtitle>{}</itunes:subtitle>\n",
+            xml_escape(subtitle)
+        ));
+    }
+
+    if itunes.owner_name.is_some() || itunes.owner_email.is_some() {
+        xml.push_str("    <itunes:owner>\n");
+        if let Some(name) = &itunes.owner_name {
+            xml.push_str(&format!(
+                "      <itunes:name>{}</itunes:name>\n",
+                xml_escape(name)
+            ));
+        }
+        if let Some(email) = &itunes.owner_email {
+            xml.push_str(&format!(
+                "      <itunes:email>{}</itunes:email>\n",
+                xml_escape(email)
+            ));
+        }
+        xml.push_str("    </itunes:owner>\n");
+    }
+
+    if let Some(image) = itunes.image.as_deref().or(cover_url) {
+        xml.push_str(&format!(
+            "    <itunes:image href=\"{}\"/>\n",
+            xml_escape(image)
+        ));
+    }
+
+    let category = xml_escape(&itunes.category);
+    match &itunes.subcategory {
+        Some(sub) => xml.push_str(&format!(
+            "    <itunes:category text=\"{}\">\n      <itunes:category text=\"{}\"/>\n    </itunes:category>\n",
+            category,
+            xml_escape(sub)
+        )),
+        None => xml.push_str(&format!(
+            "    <itunes:category text=\"{}\"/>\n",
+            category
+        )),
+    }
+
+    xml
+}
+
+/// Spacing between consecutive track `pubDate`s
+///
+/// Many podcast clients sort by `pubDate`, so tracks sharing the album's
+/// release date would come out in arbitrary order.
+pub const TRACK_PUB_DATE_STEP: TimeDelta = TimeDelta::minutes(1);
+
+/// Deterministic `pubDate`s for an album's tracks
+///
+/// The first track is dated midnight UTC on the release date and each
+/// following track one `TRACK_PUB_DATE_STEP` later, so dates are strictly
+/// increasing in track order and stable across rebuilds.
+pub fn track_pub_dates(release_date: NaiveDate, track_count: usize) -> Vec<DateTime<Utc>> {
+    let start = release_date.and_time(NaiveTime::MIN).and_utc();
+    (0..track_count)
+        .map(|i| start + TRACK_PUB_DATE_STEP * i as i32)
+        .collect()
+}
+
+/// Format a timestamp for `pubDate` / `lastBuildDate` (RFC 822, GMT)
+pub fn rss_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Channel `lastBuildDate`: the time the feed is generated
+pub fn last_build_date() -> String {
+    rss_date(Utc::now())
+}
+
+/// Apply the `[rss]` item limits to a list of feed items
+///
+/// Items released before `since` are dropped, then, if more than
+/// `max_items` remain, the oldest are dropped until the cap is met. The
+/// surviving items keep their original relative order. Items sharing a
+/// release date are kept in list order.
+///
+/// Dates can be anything ordered: `NaiveDate` for releases, or the
+/// `DateTime` pubDates from `track_pub_dates` for an album's tracks.
+///
+/// # Arguments
+///
+/// * `items` - Feed items, in the order they should be emitted
+/// * `released` - Release date of an item
+/// * `since` - Earliest release date to keep
+/// * `max_items` - Largest number of items to keep
+pub fn limit_items<T, D: Ord>(
+    items: Vec<T>,
+    released: impl Fn(&T) -> D,
+    since: Option<D>,
+    max_items: Option<usize>,
+) -> Vec<T> {
+    let mut items: Vec<T> = match since {
+        Some(since) => items.into_iter().filter(|i| released(i) >= since).collect(),
+        None => items,
+    };
+
+    if let Some(max) = max_items
+        && items.len() > max
+    {
+        // Newest first; the sort is stable so ties keep list order
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        order.sort_by_key(|&i| Reverse(released(&items[i])));
+        let mut keep = vec![false; items.len()];
+        for i in order.into_iter().take(max) {
+            keep[i] = true;
+        }
+
+        let mut index = 0;
+        items.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn releases() -> Vec<(&'static str, NaiveDate)> {
+        vec![
+            ("Third", date("2025-03-01")),
+            ("First", date("2023-06-15")),
+            ("Fourth", date("2025-11-15")),
+            ("Second", date("2024-01-20")),
+        ]
+    }
+
+    fn titles(items: &[(&'static str, NaiveDate)]) -> Vec<&'static str> {
+        items.iter().map(|(t, _)| *t).collect()
+    }
+
+    #[test]
+    fn test_max_items_keeps_newest_in_order() {
+        let limited = limit_items(releases(), |r| r.1, None, Some(2));
+        assert_eq!(titles(&limited), vec!["Third", "Fourth"]);
+
+        let limited = limit_items(releases(), |r| r.1, None, Some(1));
+        assert_eq!(titles(&limited), vec!["Fourth"]);
+
+        // A cap at or above the item count keeps everything
+        let limited = limit_items(releases(), |r| r.1, None, Some(4));
+        assert_eq!(titles(&limited), vec!["Third", "First", "Fourth", "Second"]);
+    }
+
+    #[test]
+    fn test_since_drops_older_releases() {
+        let limited = limit_items(releases(), |r| r.1, Some(date("2024-01-20")), None);
+        assert_eq!(titles(&limited), vec!["Third", "Fourth", "Second"]);
+
+        // Both limits together
+        let limited = limit_items(releases(), |r| r.1, Some(date("2024-01-01")), Some(2));
+        assert_eq!(titles(&limited), vec!["Third", "Fourth"]);
+
+        let limited = limit_items(releases(), |r| r.1, None, None);
+        assert_eq!(limited.len(), 4);
+    }
+
+    #[test]
+    fn test_track_pub_dates_strictly_increasing() {
+        let dates = track_pub_dates(date("2025-11-15"), 12);
+        assert_eq!(dates.len(), 12);
+        assert!(dates.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(rss_date(dates[0]), "Sat, 15 Nov 2025 00:00:00 GMT");
+        assert_eq!(rss_date(dates[1]), "Sat, 15 Nov 2025 00:01:00 GMT");
+
+        // Deterministic across calls
+        assert_eq!(dates, track_pub_dates(date("2025-11-15"), 12));
+        assert!(track_pub_dates(date("2025-11-15"), 0).is_empty());
+    }
+
+    fn itunes() -> ItunesConfig {
+        ItunesConfig {
+            category: "Music".to_string(),
+            subcategory: Some("Music History".to_string()),
+            subtitle: Some("Liner notes & outtakes".to_string()),
+            owner_name: Some("Test Artist".to_string()),
+            owner_email: Some("test@example.com".to_string()),
+            image: None,
+        }
+    }
+
+    #[test]
+    fn test_itunes_channel_tags_render() {
+        let xml = itunes_channel_tags(
+            &itunes(),
+            Some("https://test.example.com/artwork/cover.jpg"),
+        );
+        assert!(xml.contains("<itunes:subtitle>Liner notes &amp; outtakes</itunes:subtitle>"));
+        assert!(xml.contains(
+            "<itunes:owner>\n      <itunes:name>Test Artist</itunes:name>\n      <itunes:email>test@example.com</itunes:email>\n    </itunes:owner>"
+        ));
+        assert!(
+            xml.contains(r#"<itunes:image href="https://test.example.com/artwork/cover.jpg"/>"#)
+        );
+        assert!(xml.contains(
+            "<itunes:category text=\"Music\">\n      <itunes:category text=\"Music History\"/>\n    </itunes:category>"
+        ));
+
+        // Explicit image wins over the cover; unset fields are omitted
+        let config = ItunesConfig {
+            category: "Health & Fitness".to_string(),
+            subcategory: None,
+            subtitle: None,
+            owner_name: None,
+            owner_email: None,
+            image: Some("https://cdn.example.com/podcast.png".to_string()),
+        };
+        let xml = itunes_channel_tags(&config, Some("https://test.example.com/artwork/cover.jpg"));
+        assert!(xml.contains(r#"<itunes:image href="https://cdn.example.com/podcast.png"/>"#));
+        assert!(xml.contains(r#"<itunes:category text="Health &amp; Fitness"/>"#));
+        assert!(!xml.contains("itunes:owner"));
+        assert!(!xml.contains("itunes:subtitle"));
+
+        let config = ItunesConfig {
+            image: None,
+            ..config
+        };
+        assert!(!itunes_channel_tags(&config, None).contains("itunes:image"));
+    }
+
+    #[test]
+    fn test_is_itunes_category() {
+        assert!(is_itunes_category("Music", None));
+        assert!(is_itunes_category("Music", Some("Music Interviews")));
+        assert!(is_itunes_category("True Crime", None));
+        assert!(!is_itunes_category("music", None));
+        assert!(!is_itunes_category("Music", Some("Jazz")));
+        assert!(!is_itunes_category("True Crime", Some("Music History")));
+    }
+}
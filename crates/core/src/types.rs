@@ -1,6 +1,8 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Complete album configuration
@@ -12,7 +14,191 @@ pub struct Album {
     pub tracks: Vec<Track>,
     pub distribution: Distribution,
     pub hosting: HostingConfig,
+    /// `[limits]`, enforced by the streaming worker
+    pub limits: Option<Limits>,
     pub rss: RssConfig,
+    /// `[credits]`: album personnel as role -> names (e.g. `engineer = "Jo"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub credits: BTreeMap<String, Vec<String>>,
+    /// `[merch]`: physical releases and merchandise linked from the page
+    #[serde(default)]
+    pub merch: Merch,
+    /// `[artwork]`: the optional image gallery (the cover is auto-detected)
+    #[serde(default)]
+    pub artwork: Artwork,
+}
+
+impl Album {
+    /// Index of `[distribution] default_track` in the tracklist, if set and present
+    pub fn default_track_index(&self) -> Option<usize> {
+        match self.distribution.default_track.as_ref()? {
+            DefaultTrack::Index(index) => Some(*index).filter(|i| *i < self.tracks.len()),
+            DefaultTrack::Slug(slug) => self.tracks.iter().position(|t| t.slug() == *slug),
+        }
+    }
+
+    /// Drop `draft = true` tracks, as builds and deploys do without `--include-drafts`
+    ///
+    /// `[distribution] default_track` follows its track to the new position,
+    /// and is cleared when it was a draft.
+    ///
+    /// # Returns
+    ///
+    /// How many tracks were dropped
+    pub fn remove_drafts(&mut self) -> usize {
+        let default_track = self.default_track_index();
+        if let (Some(DefaultTrack::Index(index)), Some(position)) =
+            (&mut self.distribution.default_track, default_track)
+        {
+            *index = self.tracks[..position].iter().filter(|t| !t.draft).count();
+        }
+        if default_track.is_some_and(|i| self.tracks[i].draft) {
+            self.distribution.default_track = None;
+        }
+
+        let before = self.tracks.len();
+        self.tracks.retain(|track| !track.draft);
+        before - self.tracks.len()
+    }
+
+    /// Get a URL-safe slug from the album title (e.g. for the download zip)
+    pub fn slug(&self) -> String {
+        self.metadata
+            .title
+            .to_lowercase()
+            .replace(char::is_whitespace, "-")
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-')
+            .collect()
+    }
+
+    /// Site path of the full-album download zip, relative to the site root
+    pub fn download_zip_path(&self) -> String {
+        self.site
+            .layout
+            .asset_path("download", &format!("{}.zip", self.slug()))
+    }
+
+    /// Get the footer copyright line, falling back to "© {year} {artist}"
+    pub fn copyright(&self) -> String {
+        self.site.footer.copyright.clone().unwrap_or_else(|| {
+            format!(
+                "© {} {}",
+                self.metadata.release_date.year(),
+                self.metadata.artist
+            )
+        })
+    }
+
+    /// Get the displayed number of each track, in track order
+    ///
+    /// A track's `track_number` overrides its 1-based position on its disc
+    /// (e.g. a continued series starting at 7). Positions restart on each
+    /// disc of a multi-disc album.
+    pub fn track_numbers(&self) -> Vec<u32> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .map(|(i, track)| {
+                let position = self.tracks[..i]
+                    .iter()
+                    .filter(|t| t.disc_number() == track.disc_number())
+                    .count() as u32
+                    + 1;
+                track.track_number.unwrap_or(position)
+            })
+            .collect()
+    }
+
+    /// Track indices in the order `[site] track_sort` displays them
+    ///
+    /// Only the page's track list follows this; file numbering and the feed
+    /// keep config order. Multi-disc albums are sorted within each disc, and
+    /// tracks without a duration go last under "duration".
+    pub fn track_display_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.tracks.len()).collect();
+        let tracks = &self.tracks;
+        match self.site.track_sort {
+            TrackSort::Config => {}
+            TrackSort::Title => order
+                .sort_by_cached_key(|&i| (tracks[i].disc_number(), tracks[i].title.to_lowercase())),
+            TrackSort::Duration => order.sort_by_key(|&i| {
+                (
+                    tracks[i].disc_number(),
+                    tracks[i].duration.is_none(),
+                    tracks[i].duration,
+                )
+            }),
+        }
+        order
+    }
+
+    /// Whether the tracks span more than one disc
+    pub fn is_multi_disc(&self) -> bool {
+        self.tracks.first().is_some_and(|first| {
+            self.tracks
+                .iter()
+                .any(|t| t.disc_number() != first.disc_number())
+        })
+    }
+
+    /// Get the output filename for each track's audio, in track order
+    ///
+    /// Audio is flattened into a single `audio/` directory keyed by basename,
+    /// so tracks with identical basenames in different source subdirectories
+    /// would overwrite each other. Colliding names are prefixed with the
+    /// 1-based track number (e.g. `02-track.flac`); unique names are kept as-is.
+    ///
+    /// With `[site] preserve_audio_paths`, each track instead keeps its path
+    /// relative to the album's `audio/` directory (e.g. `disc1/track.flac`),
+    /// which is already unique.
+    ///
+    /// Build copying, R2 keys, and player URLs must all use these names.
+    pub fn audio_file_names(&self) -> Vec<String> {
+        if self.site.preserve_audio_paths {
+            return self.tracks.iter().map(Track::audio_relative_path).collect();
+        }
+
+        let names: Vec<String> = self.tracks.iter().map(Track::file_name).collect();
+
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                if names.iter().filter(|n| *n == name).count() > 1 {
+                    format!("{:02}-{}", i + 1, name)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Bucket key of the audio object `name` (an `audio_file_names` entry)
+    ///
+    /// `audio/<name>`, under `[hosting.cloudflare] r2_prefix` when the audio
+    /// goes to R2 rather than `[hosting.s3]`. Uploads, the deploy manifest,
+    /// and CDN URLs in the site and feed must all use these keys.
+    pub fn audio_key(&self, name: &str) -> String {
+        self.bucket_key(&format!("audio/{}", name))
+    }
+
+    /// `key` under `[hosting.cloudflare] r2_prefix` in the same way as
+    /// `audio_key`, for the other objects a publish keeps in the bucket
+    pub fn bucket_key(&self, key: &str) -> String {
+        let prefix = self
+            .hosting
+            .cloudflare
+            .r2_prefix
+            .as_deref()
+            .filter(|_| self.hosting.s3.is_none())
+            .map(|prefix| prefix.trim_matches('/'))
+            .filter(|prefix| !prefix.is_empty());
+        match prefix {
+            Some(prefix) => format!("{}/{}", prefix, key),
+            None => key.to_string(),
+        }
+    }
 }
 
 /// Album metadata and description
@@ -28,6 +214,19 @@ pub struct AlbumMetadata {
     pub license: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub liner_notes: Option<PathBuf>,
+    /// Contains explicit content (default for tracks without an override)
+    #[serde(default)]
+    pub explicit: bool,
+}
+
+impl AlbumMetadata {
+    /// `genre` entries to show, trimmed, with blank ones left out
+    pub fn genres(&self) -> impl Iterator<Item = &str> {
+        self.genre
+            .iter()
+            .map(|genre| genre.trim())
+            .filter(|genre| !genre.is_empty())
+    }
 }
 
 /// Artist information
@@ -42,11 +241,207 @@ pub struct Artist {
 }
 
 /// Site configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SiteConfig {
     pub domain: String,
     pub theme: String,
     pub accent_color: String,
+    /// Site ownership verification tokens, keyed by provider
+    /// (e.g. `google = "abc123"` -> `<meta name="google-site-verification">`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub verification: BTreeMap<String, String>,
+    #[serde(default)]
+    pub footer: FooterConfig,
+    /// Keep nested audio paths (`audio/disc1/track.flac`) instead of flattening to basenames
+    #[serde(default)]
+    pub preserve_audio_paths: bool,
+    /// BCP 47 language tag for the page (e.g. "en", "ar", "he-IL"), defaults to "en"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Where built assets go: subdirectories (default) or all at the site root
+    #[serde(default)]
+    pub layout: SiteLayout,
+    /// Page background: solid color, gradient, or the default diagonal stripes
+    #[serde(default)]
+    pub background: SiteBackground,
+    /// Glowing text-shadows on headings and the progress bar (default: on)
+    #[serde(default = "default_true")]
+    pub glow: bool,
+    /// "?" button and overlay listing the player's keyboard shortcuts (default: on)
+    #[serde(default = "default_true")]
+    pub keyboard_help: bool,
+    /// Seconds the player's seek keys jump back or forward (default: 10)
+    #[serde(default = "default_seek_seconds")]
+    pub seek_seconds: u32,
+    /// Let search engines index the production site (default: on). Preview
+    /// deploys are never indexed.
+    #[serde(default = "default_true")]
+    pub allow_indexing: bool,
+    /// Order of the page's track list: "config" (default), "title" or "duration"
+    #[serde(default)]
+    pub track_sort: TrackSort,
+    /// Static bar chart of each track's peaks behind the progress bar
+    /// (default: off; the build needs ffmpeg)
+    #[serde(default)]
+    pub peak_bars: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_seek_seconds() -> u32 {
+    10
+}
+
+/// Color palette named by `[site] theme`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Theme {
+    /// "Metallic Analog Lab": dark metal with a green signal color
+    #[default]
+    Default,
+    /// Light paper background, dark text
+    Light,
+    /// Near-black and grey, white highlights
+    Minimal,
+    /// Black and white with a yellow highlight
+    HighContrast,
+}
+
+impl Theme {
+    /// Theme for a `site.theme` name (case-insensitive; `-`, `_` or nothing
+    /// between words), or `None` if unknown
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', '_'], "")
+            .as_str()
+        {
+            "" | "default" => Some(Self::Default),
+            "light" => Some(Self::Light),
+            "minimal" => Some(Self::Minimal),
+            "highcontrast" => Some(Self::HighContrast),
+            _ => None,
+        }
+    }
+}
+
+/// Page background style
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SiteBackground {
+    /// Flat base color
+    Solid,
+    /// Diagonal fade between the base shades
+    Gradient,
+    /// Base color with metallic diagonal stripes
+    #[default]
+    Pattern,
+}
+
+/// Display order of the page's track list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackSort {
+    /// As listed in album.toml
+    #[default]
+    Config,
+    /// Alphabetically by title, ignoring case
+    Title,
+    /// Shortest first
+    Duration,
+}
+
+/// Output layout for built audio, artwork, and liner notes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SiteLayout {
+    /// `audio/`, `artwork/`, and `notes/` subdirectories
+    #[default]
+    Nested,
+    /// Everything at the site root, prefixed by kind (`audio-01-track.flac`)
+    Flat,
+}
+
+impl SiteLayout {
+    /// Path of an asset relative to the site root
+    ///
+    /// `kind` is the nested directory name (`audio`, `artwork`, `notes`). In
+    /// the flat layout any `/` in `name` (from `preserve_audio_paths`) becomes
+    /// `-`, so every asset sits directly at the root.
+    pub fn asset_path(self, kind: &str, name: &str) -> String {
+        match self {
+            SiteLayout::Nested => format!("{}/{}", kind, name),
+            SiteLayout::Flat => format!("{}-{}", kind, name.replace('/', "-")),
+        }
+    }
+}
+
+/// Primary language subtags written right-to-left
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "ug", "ur", "yi",
+];
+
+impl SiteConfig {
+    /// Get the page language, defaulting to "en"
+    pub fn language(&self) -> &str {
+        self.language.as_deref().unwrap_or("en")
+    }
+
+    /// Whether the page language is written right-to-left
+    pub fn is_rtl(&self) -> bool {
+        let primary = self.language().split(['-', '_']).next().unwrap_or_default();
+        RTL_LANGUAGES.contains(&primary.to_lowercase().as_str())
+    }
+}
+
+/// Page footer configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FooterConfig {
+    /// Copyright line (defaults to "© {release year} {artist}" when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<FooterLink>,
+}
+
+/// Custom link shown in the page footer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FooterLink {
+    pub label: String,
+    pub url: String,
+}
+
+/// `[merch]`: items shown in a grid below the tracklist
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Merch {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<MerchItem>,
+}
+
+/// One `[[merch.items]]` entry, linking to wherever it is sold
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MerchItem {
+    pub name: String,
+    /// Store page for the item (http or https)
+    pub url: String,
+    /// Product image, relative to the album directory (e.g. `merch/vinyl.jpg`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<PathBuf>,
+    /// Shown as written (e.g. "$25"); nothing is charged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+}
+
+impl MerchItem {
+    /// Filename of the image in the site's `merch` directory
+    pub fn image_file_name(&self) -> Option<String> {
+        self.image
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+    }
 }
 
 /// Individual track
@@ -54,14 +449,100 @@ pub struct SiteConfig {
 pub struct Track {
     pub file: PathBuf,
     pub title: String,
+    /// Track artist when it isn't the album artist (compilations, features)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
     /// Duration in seconds (auto-detected if not specified)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<Duration>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub liner_notes: Option<PathBuf>,
+    /// Explicit content flag, resolved from the track override or the album default
+    #[serde(default)]
+    pub explicit: bool,
+    /// Free sample streamed instead of the full track when downloads are paid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_clip: Option<PreviewClip>,
+    /// Seekable markers within the track, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chapters: Vec<Chapter>,
+    /// Explicit track number; see `Album::track_numbers`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub track_number: Option<u32>,
+    /// Tempo in beats per minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<f64>,
+    /// Musical key, e.g. "Am" or "F#"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Disc of a multi-disc release; see `Track::disc_number`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disc: Option<u32>,
+    /// Unfinished track: previewed, but left out of builds and deploys
+    /// unless drafts are included; see `Album::remove_drafts`
+    #[serde(default)]
+    pub draft: bool,
+    /// Built lossy transcode streamed instead of the original, as an audio
+    /// filename like `audio_file_names` (set by `build --transcode`)
+    #[serde(skip)]
+    pub stream_file: Option<String>,
+    /// Streaming copy for `[distribution] stream_format`, streamed in
+    /// preference to everything else, as an audio filename (set by the build)
+    #[serde(skip)]
+    pub stream_copy: Option<String>,
+    /// Coarse peak levels (0-9) drawn behind the progress bar for
+    /// `[site] peak_bars` (set by the build)
+    #[serde(skip)]
+    pub peaks: Option<Vec<u8>>,
+    /// Credits for this track; see `Track::credits`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub producer: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mastered_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub written_by: Vec<String>,
+}
+
+/// Time range of a track's preview clip (e.g. `preview_clip = "0:30-1:00"`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PreviewClip {
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// A named point in a track (`[[track.chapters]] name = "Intro", start = "0:00"`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub name: String,
+    pub start: Duration,
+}
+
+impl PreviewClip {
+    /// Length of the clip
+    pub fn length(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
 }
 
 impl Track {
+    /// Disc this track is on, defaulting to 1
+    pub fn disc_number(&self) -> u32 {
+        self.disc.unwrap_or(1)
+    }
+
+    /// Credited roles with names, as display labels ("Produced by", ...) in
+    /// a fixed order; roles nobody is credited for are left out
+    pub fn credits(&self) -> Vec<(&'static str, &[String])> {
+        [
+            ("Produced by", self.producer.as_slice()),
+            ("Written by", self.written_by.as_slice()),
+            ("Mastered by", self.mastered_by.as_slice()),
+        ]
+        .into_iter()
+        .filter(|(_, names)| !names.is_empty())
+        .collect()
+    }
+
     /// Get the filename component for use in URLs
     pub fn file_name(&self) -> String {
         self.file
@@ -71,6 +552,23 @@ impl Track {
             .to_string()
     }
 
+    /// Get the track's path relative to the album `audio/` directory, using `/` separators
+    ///
+    /// Files outside `audio/` keep their full album-relative path.
+    pub fn audio_relative_path(&self) -> String {
+        let relative = self.file.strip_prefix("audio").unwrap_or(&self.file);
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Get the output filename of this track's preview clip
+    pub fn preview_file_name(&self) -> String {
+        format!("{}-preview.mp3", self.slug())
+    }
+
     /// Get a URL-safe slug from the title
     pub fn slug(&self) -> String {
         self.title
@@ -83,7 +581,7 @@ impl Track {
 }
 
 /// Distribution settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Distribution {
     pub streaming_enabled: bool,
     pub download_enabled: bool,
@@ -94,24 +592,196 @@ pub struct Distribution {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tip_suggested_amounts: Option<Vec<u32>>,
     pub download_formats: Vec<String>,
+    /// How much of each track the browser fetches before play is pressed
+    #[serde(default)]
+    pub preload: AudioPreload,
+    /// Buffer the next track ahead of time so tracks flow without a gap
+    /// (live albums, concept records)
+    #[serde(default)]
+    pub gapless: bool,
+    /// Track the player starts from (selected, not autoplayed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_track: Option<DefaultTrack>,
+    /// Lower-bitrate copy of every track the player streams instead of the
+    /// original, e.g. "opus-128" (downloads keep the original); see
+    /// `Distribution::stream_copy_format`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_format: Option<String>,
+}
+
+/// `default_track`: a tracklist index (0 is the first track) or a track slug
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum DefaultTrack {
+    Index(usize),
+    /// `Track::slug`, e.g. `"the-single"` for "The Single"
+    Slug(String),
+}
+
+/// The player's `<audio preload>` hint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioPreload {
+    /// Nothing until play (easiest on metered connections)
+    None,
+    /// Duration and headers only
+    #[default]
+    Metadata,
+    /// Let the browser buffer the track (snappiest start for short tracks)
+    Auto,
+}
+
+impl AudioPreload {
+    /// Value of the `preload` attribute
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AudioPreload::None => "none",
+            AudioPreload::Metadata => "metadata",
+            AudioPreload::Auto => "auto",
+        }
+    }
+}
+
+impl Distribution {
+    /// Whether downloads cost money (preview clips are streamed instead of full tracks)
+    pub fn is_paid(&self) -> bool {
+        self.download_enabled && self.download_price.unwrap_or(0.0) > 0.0
+    }
+
+    /// Lossy formats in `download_formats`, in order; lossless and unknown names are skipped
+    pub fn lossy_formats(&self) -> Vec<LossyFormat> {
+        self.download_formats
+            .iter()
+            .filter_map(|name| LossyFormat::parse(name))
+            .collect()
+    }
+
+    /// `stream_format`, parsed (album.toml parsing rejects unknown formats)
+    pub fn stream_copy_format(&self) -> Option<LossyFormat> {
+        self.stream_format.as_deref().and_then(LossyFormat::parse)
+    }
+}
+
+/// A lossy encoding named in `download_formats` or `stream_format`, e.g.
+/// `"mp3-320"`, `"ogg"` or `"opus-128"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossyFormat {
+    /// MP3 at a bitrate in kbps (`"mp3"` alone is 320)
+    Mp3 { kbps: u32 },
+    /// Ogg Vorbis at a bitrate in kbps (`"ogg"` alone is 192)
+    Ogg { kbps: u32 },
+    /// Opus at a bitrate in kbps (`"opus"` alone is 128)
+    Opus { kbps: u32 },
+}
+
+impl LossyFormat {
+    /// Parse a format name: `mp3`, `ogg` or `opus`, optionally with `-<kbps>`
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let (codec, kbps) = match name.split_once('-') {
+            Some((codec, kbps)) => (codec, Some(kbps.parse().ok().filter(|&k| k > 0)?)),
+            None => (name.as_str(), None),
+        };
+        match codec {
+            "mp3" => Some(Self::Mp3 {
+                kbps: kbps.unwrap_or(320),
+            }),
+            "ogg" => Some(Self::Ogg {
+                kbps: kbps.unwrap_or(192),
+            }),
+            "opus" => Some(Self::Opus {
+                kbps: kbps.unwrap_or(128),
+            }),
+            _ => None,
+        }
+    }
+
+    /// File extension of the encoded audio
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp3 { .. } => "mp3",
+            Self::Ogg { .. } => "ogg",
+            Self::Opus { .. } => "opus",
+        }
+    }
+
+    /// Name of a track's streaming copy in this format, next to its audio
+    /// file (`01-track.flac` -> `01-track.stream.opus`); never the name of a
+    /// `download_formats` variant
+    pub fn stream_file_name(&self, audio_file_name: &str) -> String {
+        Path::new(audio_file_name)
+            .with_extension(format!("stream.{}", self.extension()))
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
 }
 
 /// Hosting configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HostingConfig {
     pub cloudflare: CloudflareConfig,
+    /// S3-compatible audio storage used instead of R2, when configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3: Option<S3Config>,
 }
 
 /// Cloudflare-specific hosting config
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CloudflareConfig {
     /// Custom subdomain for album (e.g., "my-album" -> my-album.yourdomain.com)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subdomain: Option<String>,
+    /// Extra origins allowed to fetch the R2 audio (CORS), on top of the
+    /// site's own; `["*"]` lets any site use it. Setting this re-applies the
+    /// policy on every publish.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cors_origins: Vec<String>,
+    /// Streaming worker (crates/worker-template) that serves full tracks
+    /// through short-lived signed URLs instead of the public R2 domain,
+    /// e.g. "https://stream.example.com"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_worker_url: Option<String>,
+    /// Prefix for the audio's R2 keys, e.g. "v2" uploads to `v2/audio/<name>`,
+    /// so versions of an album can sit side by side in one bucket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r2_prefix: Option<String>,
+}
+
+/// S3-compatible storage (Backblaze B2, Wasabi, MinIO, AWS S3, ...)
+///
+/// Credentials are not stored in album.toml; they come from the
+/// `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` environment variables or
+/// the default profile in ~/.aws/credentials.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct S3Config {
+    /// API endpoint (e.g., "https://s3.us-west-004.backblazeb2.com")
+    pub endpoint: String,
+    /// Region name passed to the endpoint for request signing
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    /// Bucket name
+    pub bucket: String,
+    /// Public base URL the audio is served from (defaults to `{endpoint}/{bucket}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+impl S3Config {
+    /// Base URL listeners fetch objects from (no trailing slash)
+    pub fn public_base_url(&self) -> String {
+        match &self.public_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!("{}/{}", self.endpoint.trim_end_matches('/'), self.bucket),
+        }
+    }
 }
 
 /// Bandwidth limits
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Limits {
     pub max_monthly_bandwidth_gb: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -119,27 +789,75 @@ pub struct Limits {
 }
 
 /// RSS feed configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RssConfig {
     pub enabled: bool,
+    /// Keep at most this many items, dropping the oldest first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    /// Drop items released before this date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub since: Option<NaiveDate>,
+    /// iTunes podcast tags for the channel (`[rss.itunes]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub itunes: Option<ItunesConfig>,
 }
 
-/// Artwork files
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// iTunes podcast metadata (`[rss.itunes]`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ItunesConfig {
+    /// Apple Podcasts category, e.g. "Music"
+    pub category: String,
+    /// Apple Podcasts subcategory of `category`, e.g. "Music History"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subcategory: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_email: Option<String>,
+    /// Absolute channel image URL; defaults to the cover art
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// `[artwork]`: images beyond the auto-detected cover
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Artwork {
-    pub cover: PathBuf,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub banner: Option<PathBuf>,
+    /// Extra images (liner photos, artwork variants) shown as a gallery, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gallery: Vec<PathBuf>,
+}
+
+impl Artwork {
+    /// Filenames of the gallery images in the site's `gallery` directory
+    pub fn gallery_file_names(&self) -> Vec<String> {
+        self.gallery
+            .iter()
+            .map(|p| {
+                p.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
 }
 
-/// Helper to format duration as MM:SS
+/// Helper to format duration as M:SS, or H:MM:SS from an hour up
 pub fn format_duration(duration: Option<Duration>) -> String {
     match duration {
         Some(d) => {
             let total_secs = d.as_secs();
-            let mins = total_secs / 60;
+            let hours = total_secs / 3600;
+            let mins = (total_secs % 3600) / 60;
             let secs = total_secs % 60;
-            format!("{}:{:02}", mins, secs)
+            if hours > 0 {
+                format!("{}:{:02}:{:02}", hours, mins, secs)
+            } else {
+                format!("{}:{:02}", mins, secs)
+            }
         }
         None => "?:??".to_string(),
     }
@@ -0,0 +1,367 @@
+// IPFS publishing via a pinning service (Pinata-compatible API)
+
+use crate::{DeployEvent, Deployer, DeploymentResult, ProgressFn};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const DEFAULT_PINNING_API: &str = "https://api.pinata.cloud";
+const DEFAULT_GATEWAY: &str = "dweb.link";
+
+/// Pinning service credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsConfig {
+    /// Pinning service JWT / API token
+    pub api_token: String,
+    /// Pinning API base URL (defaults to Pinata)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinning_api: Option<String>,
+    /// Gateway host used for the returned URL (defaults to dweb.link)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+}
+
+/// A site pinned to IPFS
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinnedSite {
+    pub cid: String,
+    pub gateway_url: String,
+}
+
+/// Adds a built site directory (audio included) to IPFS and pins it
+pub struct IpfsDeployer {
+    config: IpfsConfig,
+    build_dir: PathBuf,
+    /// Name recorded with the pin, and the root folder of the upload
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PinResponse {
+    #[serde(rename = "IpfsHash")]
+    ipfs_hash: String,
+}
+
+impl IpfsDeployer {
+    pub fn new(config: IpfsConfig, build_dir: impl Into<PathBuf>, name: impl Into<String>) -> Self {
+        Self {
+            config,
+            build_dir: build_dir.into(),
+            name: name.into(),
+        }
+    }
+
+    fn pinning_api(&self) -> &str {
+        self.config
+            .pinning_api
+            .as_deref()
+            .unwrap_or(DEFAULT_PINNING_API)
+            .trim_end_matches('/')
+    }
+
+    /// Upload and pin the site, returning its root CID
+    pub async fn pin(&self) -> Result<PinnedSite> {
+        let client = reqwest::Client::new();
+        let request = self.pin_request(&client)?;
+        let response = client.execute(request).await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("IPFS pinning failed ({}): {}", status, response_text);
+        }
+
+        let pinned: PinResponse = serde_json::from_str(&response_text)
+            .context("Failed to parse pinning service response")?;
+        let cid = validate_cid(&pinned.ipfs_hash)?;
+        let gateway = self.config.gateway.as_deref().unwrap_or(DEFAULT_GATEWAY);
+
+        Ok(PinnedSite {
+            gateway_url: gateway_url(&cid, gateway),
+            cid,
+        })
+    }
+
+    /// Build the add+pin request: `POST /pinning/pinFileToIPFS`, one multipart
+    /// `file` part per site file under a common root folder
+    fn pin_request(&self, client: &reqwest::Client) -> Result<reqwest::Request> {
+        let mut form = Form::new();
+        for (relative_path, path) in site_files(&self.build_dir)? {
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("Failed to read file: {}", path.display()))?;
+            let mime = mime_guess::from_path(&path).first_or_octet_stream();
+            let part = Part::bytes(bytes)
+                .file_name(format!("{}/{}", self.name, relative_path))
+                .mime_str(mime.as_ref())?;
+            form = form.part("file", part);
+        }
+
+        // CIDv1 so the site can be served from a subdomain gateway, where
+        // root-relative links (/player.js, /audio/...) resolve correctly
+        form = form
+            .text(
+                "pinataOptions",
+                serde_json::json!({ "cidVersion": 1 }).to_string(),
+            )
+            .text(
+                "pinataMetadata",
+                serde_json::json!({ "name": self.name }).to_string(),
+            );
+
+        client
+            .post(format!("{}/pinning/pinFileToIPFS", self.pinning_api()))
+            .bearer_auth(&self.config.api_token)
+            .multipart(form)
+            .build()
+            .context("Failed to build IPFS pin request")
+    }
+}
+
+#[async_trait]
+impl Deployer for IpfsDeployer {
+    async fn deploy_with_progress(&self, progress: ProgressFn<'_>) -> Result<DeploymentResult> {
+        progress(DeployEvent::UploadingSite { done: 0, total: 1 });
+        let pinned = self.pin().await?;
+        progress(DeployEvent::UploadingSite { done: 1, total: 1 });
+        progress(DeployEvent::Done {
+            site_url: pinned.gateway_url.clone(),
+        });
+
+        Ok(DeploymentResult {
+            feed_url: self
+                .build_dir
+                .join("feed.xml")
+                .exists()
+                .then(|| format!("{}feed.xml", pinned.gateway_url)),
+            site_url: pinned.gateway_url,
+        })
+    }
+}
+
+/// Every file under `dir` as (relative path with `/` separators, full path)
+fn site_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let relative_path = entry
+            .path()
+            .strip_prefix(dir)
+            .context("Failed to get relative path")?
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.push((relative_path, entry.path().to_path_buf()));
+    }
+    Ok(files)
+}
+
+/// Check that a pinning service returned a plausible CID
+///
+/// Accepts CIDv0 (base58btc, `Qm...`, 46 chars) and base32 CIDv1 (`b...`).
+fn validate_cid(cid: &str) -> Result<String> {
+    let cid = cid.trim();
+    let is_base58 = |c: char| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l');
+    let is_base32 = |c: char| c.is_ascii_lowercase() || ('2'..='7').contains(&c);
+
+    let valid = if cid.starts_with("Qm") {
+        cid.len() == 46 && cid.chars().all(is_base58)
+    } else if let Some(rest) = cid.strip_prefix('b') {
+        rest.len() >= 50 && rest.chars().all(is_base32)
+    } else {
+        false
+    };
+
+    if !valid {
+        anyhow::bail!("Pinning service returned an invalid CID: '{}'", cid);
+    }
+    Ok(cid.to_string())
+}
+
+/// Gateway URL for a pinned site (always ends with `/`)
+///
+/// CIDv1 gets a subdomain gateway URL (`https://<cid>.ipfs.<gateway>/`);
+/// CIDv0 is not valid as a DNS label, so it falls back to a path URL.
+fn gateway_url(cid: &str, gateway: &str) -> String {
+    let gateway = gateway
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/');
+    if cid.starts_with("Qm") {
+        format!("https://{}/ipfs/{}/", gateway, cid)
+    } else {
+        format!("https://{}.ipfs.{}/", cid, gateway)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const CID_V1: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+    fn site_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+        fs::write(dir.path().join("audio/01-track.flac"), b"audio").unwrap();
+        dir
+    }
+
+    fn config(pinning_api: Option<String>) -> IpfsConfig {
+        IpfsConfig {
+            api_token: "pin-token".to_string(),
+            pinning_api,
+            gateway: None,
+        }
+    }
+
+    /// Accept one connection, read the whole request, and answer with `status` and `body`
+    async fn mock_server(
+        status: &'static str,
+        body: String,
+    ) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&received).to_lowercase();
+                if let Some(head_end) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| l.strip_prefix("content-length: "))
+                        .and_then(|v| v.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if received.len() >= head_end + 4 + length {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&received).to_string()
+        });
+        (format!("http://{}", addr), handle)
+    }
+
+    #[test]
+    fn test_pin_request_construction() {
+        let dir = site_dir();
+        let deployer = IpfsDeployer::new(config(None), dir.path(), "my-album");
+
+        let request = deployer.pin_request(&reqwest::Client::new()).unwrap();
+
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.pinata.cloud/pinning/pinFileToIPFS"
+        );
+        assert_eq!(
+            request.headers()[reqwest::header::AUTHORIZATION],
+            "Bearer pin-token"
+        );
+        assert!(
+            request.headers()[reqwest::header::CONTENT_TYPE]
+                .to_str()
+                .unwrap()
+                .starts_with("multipart/form-data; boundary=")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pin_against_mock_service() {
+        let body = format!(
+            r#"{{"IpfsHash":"{}","PinSize":1234,"Timestamp":"2025-11-15T00:00:00Z"}}"#,
+            CID_V1
+        );
+        let (api, server) = mock_server("200 OK", body).await;
+
+        let dir = site_dir();
+        let deployer = IpfsDeployer::new(config(Some(format!("{}/", api))), dir.path(), "my-album");
+        let pinned = deployer.pin().await.unwrap();
+
+        assert_eq!(pinned.cid, CID_V1);
+        assert_eq!(
+            pinned.gateway_url,
+            format!("https://{}.ipfs.dweb.link/", CID_V1)
+        );
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /pinning/pinFileToIPFS HTTP/1.1"));
+        assert!(request.contains(r#"filename="my-album/index.html""#));
+        assert!(request.contains(r#"filename="my-album/audio/01-track.flac""#));
+        assert!(request.contains("<html></html>"));
+        assert!(request.contains(r#"{"cidVersion":1}"#));
+        assert!(request.contains(r#"{"name":"my-album"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_rejects_invalid_cid_and_api_errors() {
+        let (api, _server) = mock_server("200 OK", r#"{"IpfsHash":"not-a-cid"}"#.to_string()).await;
+        let dir = site_dir();
+        let err = IpfsDeployer::new(config(Some(api)), dir.path(), "my-album")
+            .deploy()
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("invalid CID"), "{}", err);
+
+        let (api, _server) = mock_server(
+            "401 Unauthorized",
+            r#"{"error":"Invalid authentication"}"#.to_string(),
+        )
+        .await;
+        let err = IpfsDeployer::new(config(Some(api)), dir.path(), "my-album")
+            .pin()
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("401"), "{}", err);
+        assert!(err.contains("Invalid authentication"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_cid() {
+        assert_eq!(validate_cid(CID_V1).unwrap(), CID_V1);
+        assert_eq!(validate_cid(&format!(" {}\n", CID_V0)).unwrap(), CID_V0);
+
+        assert!(validate_cid("").is_err());
+        assert!(validate_cid("QmTooShort").is_err());
+        // '0' and 'l' are not base58
+        assert!(validate_cid("Qm0wAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdl").is_err());
+        // Uppercase is not base32 lowercase
+        assert!(validate_cid(&CID_V1.to_uppercase()).is_err());
+    }
+
+    #[test]
+    fn test_gateway_url() {
+        assert_eq!(
+            gateway_url(CID_V1, "https://w3s.link/"),
+            format!("https://{}.ipfs.w3s.link/", CID_V1)
+        );
+        assert_eq!(
+            gateway_url(CID_V0, "gateway.pinata.cloud"),
+            format!("https://gateway.pinata.cloud/ipfs/{}/", CID_V0)
+        );
+    }
+}
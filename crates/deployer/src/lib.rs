@@ -1,16 +1,51 @@
-// Deployment targets (Cloudflare, future: Netlify, static)
-// TODO: Implement Cloudflare API client, R2 upload, Pages deployment, Worker deployment
+// Deployment targets (Cloudflare, Netlify, IPFS, local directory)
 
 pub mod cloudflare;
+pub mod ipfs;
+pub mod netlify;
+pub mod static_site;
 
 use async_trait::async_trait;
 
 pub struct DeploymentResult {
     pub site_url: String,
-    pub feed_url: String,
+    /// URL of the site's `feed.xml`, if the build produced one
+    pub feed_url: Option<String>,
 }
 
+/// Progress reported while a deploy runs, for callers that render their own UI
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeployEvent {
+    /// `done` of `total` audio files uploaded
+    UploadingAudio {
+        done: usize,
+        total: usize,
+    },
+    BuildingSite,
+    /// `done` of `total` site upload steps (files or batches, per target)
+    UploadingSite {
+        done: usize,
+        total: usize,
+    },
+    CreatingDns,
+    Done {
+        site_url: String,
+    },
+}
+
+/// Progress callback; called from the deploying task, in event order
+pub type ProgressFn<'a> = &'a (dyn Fn(DeployEvent) + Send + Sync);
+
 #[async_trait]
 pub trait Deployer {
-    async fn deploy(&self) -> anyhow::Result<DeploymentResult>;
+    /// Deploy, reporting progress through `progress`
+    async fn deploy_with_progress(
+        &self,
+        progress: ProgressFn<'_>,
+    ) -> anyhow::Result<DeploymentResult>;
+
+    /// Deploy without progress reporting
+    async fn deploy(&self) -> anyhow::Result<DeploymentResult> {
+        self.deploy_with_progress(&|_| {}).await
+    }
 }
@@ -0,0 +1,280 @@
+// Netlify deployment via the zip deploy API
+
+use crate::{DeployEvent, Deployer, DeploymentResult, ProgressFn};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const NETLIFY_API_BASE: &str = "https://api.netlify.com/api/v1";
+
+/// Netlify credentials and target site
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetlifyConfig {
+    /// Personal access token
+    pub api_token: String,
+    /// Site ID (or `<name>.netlify.app`) to deploy to
+    pub site_id: String,
+}
+
+/// Deploys a built site directory to Netlify as a single zip upload
+pub struct NetlifyDeployer {
+    config: NetlifyConfig,
+    build_dir: PathBuf,
+    api_base: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NetlifyDeploy {
+    #[serde(default)]
+    ssl_url: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    deploy_ssl_url: Option<String>,
+}
+
+impl NetlifyDeployer {
+    pub fn new(config: NetlifyConfig, build_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config,
+            build_dir: build_dir.into(),
+            api_base: NETLIFY_API_BASE.to_string(),
+        }
+    }
+
+    /// Override the API base URL (for testing against a mock server)
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Build the zip deploy request: `POST /sites/{site_id}/deploys` with an `application/zip` body
+    fn deploy_request(&self, client: &reqwest::Client, zip: Vec<u8>) -> Result<reqwest::Request> {
+        let url = format!("{}/sites/{}/deploys", self.api_base, self.config.site_id);
+        client
+            .post(url)
+            .bearer_auth(&self.config.api_token)
+            .header(reqwest::header::CONTENT_TYPE, "application/zip")
+            .body(zip)
+            .build()
+            .context("Failed to build Netlify deploy request")
+    }
+}
+
+#[async_trait]
+impl Deployer for NetlifyDeployer {
+    async fn deploy_with_progress(&self, progress: ProgressFn<'_>) -> Result<DeploymentResult> {
+        let zip = zip_directory(&self.build_dir)?;
+
+        // One zip, one upload
+        progress(DeployEvent::UploadingSite { done: 0, total: 1 });
+
+        let client = reqwest::Client::new();
+        let request = self.deploy_request(&client, zip)?;
+        let response = client.execute(request).await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("Netlify deploy failed ({}): {}", status, response_text);
+        }
+
+        let deploy: NetlifyDeploy =
+            serde_json::from_str(&response_text).context("Failed to parse Netlify response")?;
+        let site_url = deploy
+            .ssl_url
+            .or(deploy.url)
+            .or(deploy.deploy_ssl_url)
+            .context("Netlify response did not include a site URL")?;
+        progress(DeployEvent::UploadingSite { done: 1, total: 1 });
+        progress(DeployEvent::Done {
+            site_url: site_url.clone(),
+        });
+
+        Ok(DeploymentResult {
+            feed_url: self
+                .build_dir
+                .join("feed.xml")
+                .exists()
+                .then(|| format!("{}/feed.xml", site_url.trim_end_matches('/'))),
+            site_url,
+        })
+    }
+}
+
+/// Zip every file under `dir`, with paths relative to it
+fn zip_directory(dir: &Path) -> Result<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for entry in WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let name = path
+            .strip_prefix(dir)
+            .context("Failed to get relative path")?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        writer.start_file(name, options)?;
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        writer.write_all(&bytes)?;
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn site_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("audio")).unwrap();
+        fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+        fs::write(dir.path().join("feed.xml"), "<rss></rss>").unwrap();
+        fs::write(dir.path().join("audio/01-track.flac"), b"audio").unwrap();
+        dir
+    }
+
+    fn config() -> NetlifyConfig {
+        NetlifyConfig {
+            api_token: "secret-token".to_string(),
+            site_id: "my-album".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_deploy_request_construction() {
+        let dir = site_dir();
+        let deployer = NetlifyDeployer::new(config(), dir.path());
+        let zip = zip_directory(dir.path()).unwrap();
+
+        let request = deployer
+            .deploy_request(&reqwest::Client::new(), zip.clone())
+            .unwrap();
+
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.netlify.com/api/v1/sites/my-album/deploys"
+        );
+        assert_eq!(
+            request.headers()[reqwest::header::AUTHORIZATION],
+            "Bearer secret-token"
+        );
+        assert_eq!(
+            request.headers()[reqwest::header::CONTENT_TYPE],
+            "application/zip"
+        );
+        assert_eq!(request.body().unwrap().as_bytes().unwrap(), zip.as_slice());
+    }
+
+    #[test]
+    fn test_zip_directory_contains_site_files() {
+        let dir = site_dir();
+        let zip = zip_directory(dir.path()).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(zip)).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(names, vec!["audio/01-track.flac", "feed.xml", "index.html"]);
+
+        let mut index = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("index.html").unwrap(), &mut index)
+            .unwrap();
+        assert_eq!(index, "<html></html>");
+    }
+
+    #[tokio::test]
+    async fn test_deploy_against_mock_api() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Minimal mock: capture the request head, answer with a deploy object
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !received.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..n]);
+            }
+            let body = r#"{"id":"d1","ssl_url":"https://my-album.netlify.app"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&received).to_string()
+        });
+
+        let dir = site_dir();
+        let deployer =
+            NetlifyDeployer::new(config(), dir.path()).with_api_base(format!("http://{}/", addr));
+        let events = std::sync::Mutex::new(Vec::new());
+        let result = deployer
+            .deploy_with_progress(&|event| events.lock().unwrap().push(event))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            events.into_inner().unwrap(),
+            vec![
+                DeployEvent::UploadingSite { done: 0, total: 1 },
+                DeployEvent::UploadingSite { done: 1, total: 1 },
+                DeployEvent::Done {
+                    site_url: "https://my-album.netlify.app".to_string()
+                },
+            ]
+        );
+        assert_eq!(result.site_url, "https://my-album.netlify.app");
+        assert_eq!(
+            result.feed_url.as_deref(),
+            Some("https://my-album.netlify.app/feed.xml")
+        );
+
+        let head = server.await.unwrap().to_lowercase();
+        assert!(head.starts_with("post /sites/my-album/deploys http/1.1"));
+        assert!(head.contains("authorization: bearer secret-token"));
+        assert!(head.contains("content-type: application/zip"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_surfaces_api_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let body = r#"{"code":401,"message":"Access Denied"}"#;
+            let response = format!(
+                "HTTP/1.1 401 Unauthorized\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+
+        let dir = site_dir();
+        let deployer =
+            NetlifyDeployer::new(config(), dir.path()).with_api_base(format!("http://{}", addr));
+        let err = deployer.deploy().await.err().unwrap().to_string();
+        assert!(err.contains("401"), "{}", err);
+        assert!(err.contains("Access Denied"), "{}", err);
+    }
+}
@@ -0,0 +1,178 @@
+// Static deployment: copy the built site into a local directory
+
+use crate::{DeployEvent, Deployer, DeploymentResult, ProgressFn};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Copies a built site directory to a destination on disk
+///
+/// For hosting anywhere else: rsync the destination, point a web server at
+/// it, or commit it. Existing files in the destination are overwritten;
+/// files the build no longer produces are left in place.
+pub struct StaticDeployer {
+    build_dir: PathBuf,
+    destination: PathBuf,
+}
+
+impl StaticDeployer {
+    pub fn new(build_dir: impl Into<PathBuf>, destination: impl Into<PathBuf>) -> Self {
+        Self {
+            build_dir: build_dir.into(),
+            destination: destination.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Deployer for StaticDeployer {
+    async fn deploy_with_progress(&self, progress: ProgressFn<'_>) -> Result<DeploymentResult> {
+        let files = site_files(&self.build_dir)?;
+        let total = files.len();
+        progress(DeployEvent::UploadingSite { done: 0, total });
+
+        fs::create_dir_all(&self.destination).with_context(|| {
+            format!(
+                "Failed to create destination directory {}",
+                self.destination.display()
+            )
+        })?;
+        for (done, relative) in files.iter().enumerate() {
+            let dst = self.destination.join(relative);
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+            }
+            fs::copy(self.build_dir.join(relative), &dst)
+                .with_context(|| format!("Failed to copy {}", relative.display()))?;
+            progress(DeployEvent::UploadingSite {
+                done: done + 1,
+                total,
+            });
+        }
+
+        let root = self.destination.canonicalize().with_context(|| {
+            format!(
+                "Failed to resolve destination {}",
+                self.destination.display()
+            )
+        })?;
+        let site_url = file_url(&root.join("index.html"));
+        progress(DeployEvent::Done {
+            site_url: site_url.clone(),
+        });
+
+        Ok(DeploymentResult {
+            feed_url: self
+                .build_dir
+                .join("feed.xml")
+                .exists()
+                .then(|| file_url(&root.join("feed.xml"))),
+            site_url,
+        })
+    }
+}
+
+/// Every file under `dir`, as paths relative to it, sorted
+fn site_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        if entry.file_type().is_file() {
+            let relative = entry
+                .path()
+                .strip_prefix(dir)
+                .context("Failed to get relative path")?;
+            files.push(relative.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+/// `file://` URL for an absolute path
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        // Windows drive paths (C:/...) need the extra slash
+        format!("file:///{}", path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_deploy_copies_site_to_destination() {
+        let build = TempDir::new().unwrap();
+        fs::create_dir_all(build.path().join("audio")).unwrap();
+        fs::write(build.path().join("index.html"), "<html></html>").unwrap();
+        fs::write(build.path().join("player.js"), "// player").unwrap();
+        fs::write(build.path().join("feed.xml"), "<rss></rss>").unwrap();
+        fs::write(build.path().join("audio/01-track.flac"), b"audio").unwrap();
+
+        let out = TempDir::new().unwrap();
+        let destination = out.path().join("public").join("album");
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let result = StaticDeployer::new(build.path(), &destination)
+            .deploy_with_progress(&|event| events.lock().unwrap().push(event))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(destination.join("index.html")).unwrap(),
+            "<html></html>"
+        );
+        assert_eq!(
+            fs::read_to_string(destination.join("player.js")).unwrap(),
+            "// player"
+        );
+        assert_eq!(
+            fs::read(destination.join("audio/01-track.flac")).unwrap(),
+            b"audio"
+        );
+
+        let root = destination.canonicalize().unwrap();
+        assert_eq!(
+            result.site_url,
+            format!("file://{}/index.html", root.display())
+        );
+        assert_eq!(
+            result.feed_url,
+            Some(format!("file://{}/feed.xml", root.display()))
+        );
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(
+            events.first(),
+            Some(&DeployEvent::UploadingSite { done: 0, total: 4 })
+        );
+        assert_eq!(
+            events.last(),
+            Some(&DeployEvent::Done {
+                site_url: result.site_url.clone()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deploy_without_feed() {
+        let build = TempDir::new().unwrap();
+        fs::write(build.path().join("index.html"), "<html></html>").unwrap();
+        let out = TempDir::new().unwrap();
+
+        let result = StaticDeployer::new(build.path(), out.path())
+            .deploy()
+            .await
+            .unwrap();
+
+        assert!(result.feed_url.is_none());
+        assert!(out.path().join("index.html").exists());
+    }
+}
@@ -1,10 +1,24 @@
 // Validation logic
 // TODO: Implement config validation, file checking, audio metadata detection
 
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
 pub struct ValidationReport {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
     pub info: Vec<String>,
+    /// The album that was checked, when album.toml could be parsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<AlbumSummary>,
+}
+
+/// What was validated, for reports read by CI
+#[derive(Debug, Serialize)]
+pub struct AlbumSummary {
+    pub title: String,
+    pub artist: String,
+    pub tracks: usize,
 }
 
 pub fn validate_album() -> ValidationReport {
@@ -12,5 +26,6 @@ pub fn validate_album() -> ValidationReport {
         errors: vec![],
         warnings: vec![],
         info: vec![],
+        album: None,
     }
 }
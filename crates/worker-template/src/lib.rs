@@ -1,14 +1,471 @@
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
 use worker::*;
 
+mod limits;
+mod signing;
+mod stats;
+
+/// Signed stream URLs stay valid this long unless `SIGNED_URL_TTL_SECONDS` is set
+const DEFAULT_TTL_SECONDS: u64 = 300;
+
 #[event(fetch)]
 async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     Router::new()
-        .get_async("/stream/:track", handle_stream)
+        // Catch-alls: with [site] preserve_audio_paths, names keep their subdirectories
+        .get_async("/sign/*track", handle_sign)
+        .get_async("/stream/*track", handle_stream)
+        .get_async("/stats", handle_stats)
         .run(req, env)
         .await
 }
 
-async fn handle_stream(_req: Request, _ctx: RouteContext<()>) -> Result<Response> {
-    // TODO: Implement streaming from R2 with rate limiting
-    Response::error("Not implemented", 501)
+/// Issue a short-lived signed stream URL for the site's player
+///
+/// Only origins listed in `ALLOWED_ORIGINS` (comma-separated) get one, so
+/// other sites can't embed the audio; unset allows any origin.
+async fn handle_sign(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    // Left percent-encoded: it goes back into the signed /stream/ path as is
+    let track = match ctx
+        .param("track")
+        .map(|track| track.trim_start_matches('/'))
+    {
+        Some(track) if !track.is_empty() => track.to_string(),
+        _ => return Response::error("Missing track", 400),
+    };
+
+    let origin = req.headers().get("Origin")?;
+    let allowed = ctx
+        .var("ALLOWED_ORIGINS")
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    if !allowed.is_empty() {
+        let permitted = origin.as_deref().is_some_and(|origin| {
+            allowed
+                .split(',')
+                .any(|o| o.trim().trim_end_matches('/') == origin)
+        });
+        if !permitted {
+            return Response::error("Origin not allowed", 403);
+        }
+    }
+
+    let secret = ctx.secret("STREAM_SIGNING_SECRET")?.to_string();
+    let ttl = ctx
+        .var("SIGNED_URL_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.to_string().parse().ok())
+        .unwrap_or(DEFAULT_TTL_SECONDS);
+    let expires = now_secs() + ttl;
+    let path = format!("/stream/{}", track);
+    let sig = signing::signature(secret.as_bytes(), &path, expires);
+
+    let mut url = req.url()?;
+    url.set_path(&path);
+    url.set_query(Some(&format!("expires={}&sig={}", expires, sig)));
+
+    let mut response = Response::from_json(&serde_json::json!({ "url": url.as_str() }))?;
+    let headers = response.headers_mut();
+    headers.set("Cache-Control", "no-store")?;
+    if let Some(origin) = origin {
+        headers.set("Access-Control-Allow-Origin", &origin)?;
+        headers.set("Vary", "Origin")?;
+    }
+    Ok(response)
+}
+
+/// Stream a track from R2, if the URL carries a valid, unexpired signature
+async fn handle_stream(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    // Still percent-encoded, as signed; the R2 key and stats use the decoded name
+    let track = match ctx.param("track").and_then(|track| track_name(track)) {
+        Some(track) => track,
+        None => return Response::error("Missing track", 400),
+    };
+
+    let url = req.url()?;
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    let (expires, sig) = match (query("expires").and_then(|e| e.parse().ok()), query("sig")) {
+        (Some(expires), Some(sig)) => (expires, sig),
+        _ => return Response::error("Missing signature", 403),
+    };
+
+    let secret = ctx.secret("STREAM_SIGNING_SECRET")?.to_string();
+    match signing::verify(secret.as_bytes(), url.path(), expires, &sig, now_secs()) {
+        Ok(()) => {}
+        Err(signing::SignatureError::Expired) => return Response::error("Link expired", 410),
+        Err(signing::SignatureError::Invalid) => return Response::error("Invalid signature", 403),
+    }
+
+    // Held until the response body finishes or the client goes away
+    let lease = match acquire_stream(&ctx, &req).await? {
+        Acquired::Unlimited => None,
+        Acquired::Lease(lease) => Some(lease),
+        Acquired::OverLimit => return Response::error("Too many concurrent streams", 429),
+    };
+
+    let prefix = ctx
+        .var("AUDIO_KEY_PREFIX")
+        .ok()
+        .map(|prefix| prefix.to_string());
+    let key = audio_key(prefix.as_deref(), &track);
+    let bucket = ctx.bucket("AUDIO_BUCKET")?;
+    let range_header = req.headers().get("Range")?;
+    // Checked against the object's size first, so a range past the end gets a 416
+    let bounds = match range_header.as_deref().and_then(parse_range) {
+        Some(range) => {
+            let size = match bucket.head(key.clone()).await? {
+                Some(object) => object.size(),
+                None => return Response::error("Track not found", 404),
+            };
+            match range_bounds(&range, size) {
+                Some(bounds) => Some(bounds),
+                None => {
+                    let mut response = Response::error("Range Not Satisfiable", 416)?;
+                    response
+                        .headers_mut()
+                        .set("Content-Range", &format!("bytes */{}", size))?;
+                    return Ok(response);
+                }
+            }
+        }
+        None => None,
+    };
+    let mut get = bucket.get(key);
+    if let Some((start, end)) = bounds {
+        get = get.range(Range::OffsetWithLength {
+            offset: start,
+            length: end - start + 1,
+        });
+    }
+    let object = match get.execute().await? {
+        Some(object) => object,
+        None => return Response::error("Track not found", 404),
+    };
+    record_hit(&ctx, &track, range_header.as_deref()).await;
+
+    let size = object.size();
+    let body = match object.body() {
+        Some(body) => body,
+        None => return Response::error("Track not found", 404),
+    };
+
+    let mut response = Response::from_stream(Leased {
+        inner: Box::pin(body.stream()?),
+        lease,
+    })?;
+    if let Some((start, end)) = bounds {
+        response = response.with_status(206);
+        response.headers_mut().set(
+            "Content-Range",
+            &format!("bytes {}-{}/{}", start, end, size),
+        )?;
+    }
+    let headers = response.headers_mut();
+    let content_type = object
+        .http_metadata()
+        .content_type
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    headers.set("Content-Type", &content_type)?;
+    headers.set("Accept-Ranges", "bytes")?;
+    headers.set("Cache-Control", "private, no-store")?;
+    Ok(response)
+}
+
+/// Outcome of asking the StreamLimiter for a stream slot
+enum Acquired {
+    /// No `MAX_CONCURRENT_STREAMS` or no `STREAM_LIMITER` binding
+    Unlimited,
+    Lease(StreamLease),
+    OverLimit,
+}
+
+/// Take one of the client IP's `MAX_CONCURRENT_STREAMS` slots
+async fn acquire_stream(ctx: &RouteContext<()>, req: &Request) -> Result<Acquired> {
+    let limit: u32 = match ctx
+        .var("MAX_CONCURRENT_STREAMS")
+        .ok()
+        .and_then(|v| v.to_string().parse().ok())
+    {
+        Some(limit) => limit,
+        None => return Ok(Acquired::Unlimited),
+    };
+    let Ok(namespace) = ctx.durable_object("STREAM_LIMITER") else {
+        return Ok(Acquired::Unlimited);
+    };
+
+    // One limiter object per IP; the IP itself is never stored
+    let ip = req
+        .headers()
+        .get("CF-Connecting-IP")?
+        .unwrap_or_else(|| "unknown".to_string());
+    let stub = namespace.id_from_name(&ip)?.get_stub()?;
+    let mut response = stub
+        .fetch_with_str(&format!("https://limiter/acquire?limit={}", limit))
+        .await?;
+    if response.status_code() == 429 {
+        return Ok(Acquired::OverLimit);
+    }
+    let body: serde_json::Value = response.json().await?;
+    let id = body["lease"]
+        .as_u64()
+        .ok_or_else(|| Error::RustError("StreamLimiter returned no lease".to_string()))?;
+    Ok(Acquired::Lease(StreamLease {
+        stub: Some(stub),
+        id,
+    }))
+}
+
+/// A held stream slot, released (in the background) when dropped
+struct StreamLease {
+    stub: Option<Stub>,
+    id: u64,
+}
+
+impl Drop for StreamLease {
+    fn drop(&mut self) {
+        let Some(stub) = self.stub.take() else {
+            return;
+        };
+        let url = format!("https://limiter/release?lease={}", self.id);
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = stub.fetch_with_str(&url).await {
+                console_error!("Failed to release stream slot: {}", e);
+            }
+        });
+    }
+}
+
+/// Response body that gives its stream slot back once it ends or is dropped
+struct Leased<S> {
+    inner: S,
+    lease: Option<StreamLease>,
+}
+
+impl<S: Stream + Unpin> Stream for Leased<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<S::Item>> {
+        let next = Pin::new(&mut self.inner).poll_next(cx);
+        if let Poll::Ready(None) = next {
+            self.lease = None;
+        }
+        next
+    }
+}
+
+/// Tracks active streams for one client IP (see `limits::ActiveStreams`)
+///
+/// State is in memory only: if the object is evicted the counts start over,
+/// which errs on the side of letting listeners play.
+#[durable_object]
+pub struct StreamLimiter {
+    streams: limits::ActiveStreams,
+}
+
+#[durable_object]
+impl DurableObject for StreamLimiter {
+    fn new(_state: State, _env: Env) -> Self {
+        Self {
+            streams: limits::ActiveStreams::default(),
+        }
+    }
+
+    async fn fetch(&mut self, req: Request) -> Result<Response> {
+        let url = req.url()?;
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<u64>().ok())
+        };
+        match url.path() {
+            "/acquire" => {
+                let limit = param("limit").unwrap_or(0) as u32;
+                match self.streams.try_acquire(limit, now_secs()) {
+                    Some(lease) => Response::from_json(&serde_json::json!({ "lease": lease })),
+                    None => Response::error("Too many concurrent streams", 429),
+                }
+            }
+            "/release" => {
+                if let Some(lease) = param("lease") {
+                    self.streams.release(lease);
+                }
+                Response::ok("released")
+            }
+            _ => Response::error("Not found", 404),
+        }
+    }
+}
+
+/// Per-track play and download counts as JSON, from the `STATS` KV namespace
+async fn handle_stats(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let kv = match ctx.kv("STATS") {
+        Ok(kv) => kv,
+        Err(_) => return Response::error("Stats are not enabled", 404),
+    };
+    let counts = stats::collect(&stats::KvCounters(kv)).await?;
+    let mut response = Response::from_json(&counts)?;
+    response.headers_mut().set("Cache-Control", "no-store")?;
+    Ok(response)
+}
+
+/// Count a served request, if the worker has a `STATS` KV binding
+///
+/// Counting never fails the stream itself; errors are only logged.
+async fn record_hit(ctx: &RouteContext<()>, track: &str, range: Option<&str>) {
+    let (Ok(kv), Some(hit)) = (ctx.kv("STATS"), stats::Hit::from_range(range)) else {
+        return;
+    };
+    if let Err(e) = stats::record(&stats::KvCounters(kv), track, hit).await {
+        console_error!("Failed to count {:?} of {}: {}", hit, track, e);
+    }
+}
+
+/// Parse a single `bytes=` range (`a-b`, `a-` or `-n`); anything else streams the whole file
+fn parse_range(header: &str) -> Option<Range> {
+    let spec = header.strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    match (start.trim(), end.trim()) {
+        ("", suffix) => Some(Range::Suffix {
+            suffix: suffix.parse().ok()?,
+        }),
+        (start, "") => Some(Range::OffsetToEnd {
+            offset: start.parse().ok()?,
+        }),
+        (start, end) => {
+            let offset: u64 = start.parse().ok()?;
+            let end: u64 = end.parse().ok()?;
+            (end >= offset).then(|| Range::OffsetWithLength {
+                offset,
+                length: (end - offset).saturating_add(1),
+            })
+        }
+    }
+}
+
+/// Inclusive first and last byte a range selects from an object of `size` bytes
+///
+/// `None` when it selects nothing (it starts past the end, asks for zero
+/// bytes, or the object is empty): a 416 Range Not Satisfiable.
+fn range_bounds(range: &Range, size: u64) -> Option<(u64, u64)> {
+    let last = size.checked_sub(1)?;
+    match *range {
+        Range::OffsetWithLength { offset, length } => (offset <= last && length > 0)
+            .then(|| (offset, offset.saturating_add(length - 1).min(last))),
+        Range::OffsetToEnd { offset } => (offset <= last).then_some((offset, last)),
+        Range::Prefix { length } => (length > 0).then(|| (0, (length - 1).min(last))),
+        Range::Suffix { suffix } => (suffix > 0).then(|| (size.saturating_sub(suffix), last)),
+    }
+}
+
+/// The track name in a `/stream/*track` path, percent-decoded
+///
+/// `None` for an empty name or one that doesn't decode to UTF-8.
+fn track_name(param: &str) -> Option<String> {
+    let param = param.trim_start_matches('/');
+    if param.is_empty() {
+        return None;
+    }
+    percent_encoding::percent_decode_str(param)
+        .decode_utf8()
+        .ok()
+        .map(|name| name.into_owned())
+}
+
+/// R2 key of a track: `audio/<name>`, under publish's AUDIO_KEY_PREFIX when set
+fn audio_key(prefix: Option<&str>, track: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{}/audio/{}", prefix, track),
+        None => format!("audio/{}", track),
+    }
+}
+
+fn now_secs() -> u64 {
+    Date::now().as_millis() / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bytes a `Range` header selects from an object of `size` bytes:
+    /// `Err(())` when it doesn't parse (the whole file streams), `Ok(None)`
+    /// when it is unsatisfiable (416)
+    fn bounds(header: &str, size: u64) -> std::result::Result<Option<(u64, u64)>, ()> {
+        parse_range(header)
+            .map(|range| range_bounds(&range, size))
+            .ok_or(())
+    }
+
+    #[test]
+    fn test_parse_range_forms() {
+        assert_eq!(bounds("bytes=0-99", 1000), Ok(Some((0, 99))));
+        assert_eq!(bounds("bytes= 100 - 199 ", 1000), Ok(Some((100, 199))));
+        assert_eq!(bounds("bytes=500-", 1000), Ok(Some((500, 999))));
+        assert_eq!(bounds("bytes=-100", 1000), Ok(Some((900, 999))));
+
+        // Not a single byte range: served whole
+        for header in [
+            "bytes=0-1,5-9",
+            "items=0-9",
+            "bytes=9-0",
+            "bytes=a-b",
+            "bytes=5",
+        ] {
+            assert_eq!(bounds(header, 1000), Err(()), "{}", header);
+        }
+    }
+
+    #[test]
+    fn test_range_bounds_clamp_to_the_object() {
+        assert_eq!(bounds("bytes=900-5000", 1000), Ok(Some((900, 999))));
+        assert_eq!(bounds("bytes=-5000", 1000), Ok(Some((0, 999))));
+        assert_eq!(bounds("bytes=999-", 1000), Ok(Some((999, 999))));
+        assert_eq!(
+            bounds("bytes=0-18446744073709551615", 1000),
+            Ok(Some((0, 999)))
+        );
+    }
+
+    #[test]
+    fn test_range_bounds_past_the_end_are_unsatisfiable() {
+        assert_eq!(bounds("bytes=1000-", 1000), Ok(None));
+        assert_eq!(bounds("bytes=5000-", 1000), Ok(None));
+        assert_eq!(bounds("bytes=1000-1999", 1000), Ok(None));
+        assert_eq!(bounds("bytes=-0", 1000), Ok(None));
+        assert_eq!(bounds("bytes=0-", 0), Ok(None));
+    }
+
+    #[test]
+    fn test_track_name_keeps_subdirectories() {
+        assert_eq!(
+            track_name("disc1/01-track.flac").as_deref(),
+            Some("disc1/01-track.flac")
+        );
+        assert_eq!(
+            audio_key(None, &track_name("disc1/01-track.flac").unwrap()),
+            "audio/disc1/01-track.flac"
+        );
+        assert_eq!(
+            audio_key(Some("my-album"), "disc1/01-track.flac"),
+            "my-album/audio/disc1/01-track.flac"
+        );
+    }
+
+    #[test]
+    fn test_track_name_is_percent_decoded() {
+        assert_eq!(
+            track_name("disc%201/01%20Caf%C3%A9.flac").as_deref(),
+            Some("disc 1/01 Café.flac")
+        );
+        assert_eq!(track_name("%FF.flac"), None);
+        assert_eq!(track_name(""), None);
+    }
 }
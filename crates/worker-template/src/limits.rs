@@ -0,0 +1,86 @@
+// Concurrent-stream accounting for one client IP (held by the StreamLimiter Durable Object)
+
+use std::collections::BTreeMap;
+
+/// Leases older than this are dropped even if their release never arrived
+/// (the worker was evicted mid-stream), so a client can't stay locked out
+pub const LEASE_TTL_SECS: u64 = 2 * 60 * 60;
+
+/// Streams currently being served to one IP, as leases with expiry times
+#[derive(Debug, Default)]
+pub struct ActiveStreams {
+    /// Lease id -> expiry (Unix seconds)
+    leases: BTreeMap<u64, u64>,
+    next_id: u64,
+}
+
+impl ActiveStreams {
+    /// Start a stream if fewer than `limit` are active at `now`; returns the
+    /// lease to release when it ends, or `None` when over the limit
+    pub fn try_acquire(&mut self, limit: u32, now: u64) -> Option<u64> {
+        self.leases.retain(|_, expires| *expires > now);
+        if self.leases.len() >= limit as usize {
+            return None;
+        }
+        self.next_id += 1;
+        self.leases.insert(self.next_id, now + LEASE_TTL_SECS);
+        Some(self.next_id)
+    }
+
+    /// End a stream; releasing an unknown or expired lease is a no-op
+    pub fn release(&mut self, lease: u64) -> bool {
+        self.leases.remove(&lease).is_some()
+    }
+
+    /// Streams active at `now`
+    pub fn active(&self, now: u64) -> usize {
+        self.leases
+            .values()
+            .filter(|expires| **expires > now)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_acquire_and_release_within_limit() {
+        let mut streams = ActiveStreams::default();
+        let first = streams.try_acquire(2, NOW).unwrap();
+        let second = streams.try_acquire(2, NOW).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(streams.active(NOW), 2);
+
+        // Over the limit until one ends
+        assert_eq!(streams.try_acquire(2, NOW), None);
+        assert!(streams.release(first));
+        assert_eq!(streams.active(NOW), 1);
+        assert!(streams.try_acquire(2, NOW).is_some());
+
+        // Double release doesn't free a second slot
+        assert!(!streams.release(first));
+        assert_eq!(streams.try_acquire(2, NOW), None);
+    }
+
+    #[test]
+    fn test_stale_leases_expire() {
+        let mut streams = ActiveStreams::default();
+        streams.try_acquire(1, NOW).unwrap();
+        assert_eq!(streams.try_acquire(1, NOW + 60), None);
+
+        // The release never came; the lease times out instead
+        let later = NOW + LEASE_TTL_SECS;
+        assert_eq!(streams.active(later), 0);
+        assert!(streams.try_acquire(1, later).is_some());
+    }
+
+    #[test]
+    fn test_zero_limit_rejects_everything() {
+        let mut streams = ActiveStreams::default();
+        assert_eq!(streams.try_acquire(0, NOW), None);
+    }
+}
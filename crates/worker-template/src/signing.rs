@@ -0,0 +1,130 @@
+// Signed stream URLs: HMAC-SHA256 over path + expiry, issued by /sign and checked by /stream
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Why a signed URL was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    Expired,
+    Invalid,
+}
+
+/// Lowercase hex HMAC-SHA256 of `"{path}\n{expires}"`
+pub fn signature(secret: &[u8], path: &str, expires: u64) -> String {
+    mac(secret, path, expires)
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Check a signature for `path` at time `now` (Unix seconds), in constant time
+pub fn verify(
+    secret: &[u8],
+    path: &str,
+    expires: u64,
+    signature: &str,
+    now: u64,
+) -> Result<(), SignatureError> {
+    if expires < now {
+        return Err(SignatureError::Expired);
+    }
+    let bytes = decode_hex(signature).ok_or(SignatureError::Invalid)?;
+    mac(secret, path, expires)
+        .verify_slice(&bytes)
+        .map_err(|_| SignatureError::Invalid)
+}
+
+fn mac(secret: &[u8], path: &str, expires: u64) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(path.as_bytes());
+    mac.update(b"\n");
+    mac.update(expires.to_string().as_bytes());
+    mac
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"stream-secret";
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_sign_verify_round_trip() {
+        let expires = NOW + 300;
+        let sig = signature(SECRET, "/stream/01-track.flac", expires);
+
+        assert_eq!(sig.len(), 64);
+        assert_eq!(
+            verify(SECRET, "/stream/01-track.flac", expires, &sig, NOW),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_urls() {
+        let expires = NOW + 300;
+        let sig = signature(SECRET, "/stream/01-track.flac", expires);
+
+        // Any change to the path, expiry, signature or secret is refused
+        assert_eq!(
+            verify(SECRET, "/stream/02-track.flac", expires, &sig, NOW),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            verify(SECRET, "/stream/01-track.flac", expires + 1, &sig, NOW),
+            Err(SignatureError::Invalid)
+        );
+        let mut flipped = sig.clone().into_bytes();
+        flipped[0] = if flipped[0] == b'0' { b'1' } else { b'0' };
+        assert_eq!(
+            verify(
+                SECRET,
+                "/stream/01-track.flac",
+                expires,
+                std::str::from_utf8(&flipped).unwrap(),
+                NOW
+            ),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            verify(SECRET, "/stream/01-track.flac", expires, "not-hex", NOW),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            verify(b"other-secret", "/stream/01-track.flac", expires, &sig, NOW),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_signature() {
+        let expires = NOW - 1;
+        let sig = signature(SECRET, "/stream/01-track.flac", expires);
+
+        assert_eq!(
+            verify(SECRET, "/stream/01-track.flac", expires, &sig, NOW),
+            Err(SignatureError::Expired)
+        );
+        // Still good up to and including the expiry second
+        assert_eq!(
+            verify(SECRET, "/stream/01-track.flac", expires, &sig, expires),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// Per-track play and download counts, kept in KV (counts only, nothing about listeners)
+
+use std::collections::BTreeMap;
+
+const PLAYS_PREFIX: &str = "plays:";
+const DOWNLOADS_PREFIX: &str = "downloads:";
+
+/// What a served stream request counts as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hit {
+    /// The player's first request for a track (no Range, or one from byte 0)
+    Play,
+    /// A whole-file fetch without a Range header, i.e. a download link
+    Download,
+}
+
+impl Hit {
+    /// Classify a request by its Range header; `None` for seeks and later
+    /// chunks of a play already counted
+    pub fn from_range(range: Option<&str>) -> Option<Self> {
+        match range {
+            None => Some(Self::Download),
+            Some(range) if range.trim().starts_with("bytes=0-") => Some(Self::Play),
+            Some(_) => None,
+        }
+    }
+
+    fn key(&self, track: &str) -> String {
+        match self {
+            Self::Play => format!("{}{}", PLAYS_PREFIX, track),
+            Self::Download => format!("{}{}", DOWNLOADS_PREFIX, track),
+        }
+    }
+}
+
+/// Counter storage; the worker uses the `STATS` KV namespace
+pub trait CounterStore {
+    async fn get(&self, key: &str) -> worker::Result<Option<u64>>;
+    async fn put(&self, key: &str, value: u64) -> worker::Result<()>;
+    /// Every key starting with `prefix`
+    async fn keys(&self, prefix: &str) -> worker::Result<Vec<String>>;
+}
+
+/// Count one hit on `track`
+///
+/// KV has no atomic increment, so concurrent hits on the same track can
+/// occasionally lose a count; fine for rough numbers.
+pub async fn record(store: &impl CounterStore, track: &str, hit: Hit) -> worker::Result<()> {
+    let key = hit.key(track);
+    let count = store.get(&key).await?.unwrap_or(0);
+    store.put(&key, count + 1).await
+}
+
+/// Counts for each track, as served by `/stats`
+#[derive(Debug, Default, PartialEq, Eq, serde::Serialize)]
+pub struct TrackStats {
+    pub plays: u64,
+    pub downloads: u64,
+}
+
+/// All counters, keyed by track file name
+pub async fn collect(store: &impl CounterStore) -> worker::Result<BTreeMap<String, TrackStats>> {
+    let mut stats: BTreeMap<String, TrackStats> = BTreeMap::new();
+    for prefix in [PLAYS_PREFIX, DOWNLOADS_PREFIX] {
+        for key in store.keys(prefix).await? {
+            let count = store.get(&key).await?.unwrap_or(0);
+            let entry = stats.entry(key[prefix.len()..].to_string()).or_default();
+            if prefix == PLAYS_PREFIX {
+                entry.plays = count;
+            } else {
+                entry.downloads = count;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// `CounterStore` over a Workers KV namespace
+pub struct KvCounters(pub worker::kv::KvStore);
+
+impl CounterStore for KvCounters {
+    async fn get(&self, key: &str) -> worker::Result<Option<u64>> {
+        let value = self.0.get(key).text().await?;
+        Ok(value.and_then(|v| v.parse().ok()))
+    }
+
+    async fn put(&self, key: &str, value: u64) -> worker::Result<()> {
+        self.0.put(key, value.to_string())?.execute().await?;
+        Ok(())
+    }
+
+    async fn keys(&self, prefix: &str) -> worker::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut cursor = None;
+        loop {
+            let mut list = self.0.list().prefix(prefix.to_string());
+            if let Some(cursor) = cursor.take() {
+                list = list.cursor(cursor);
+            }
+            let page = list.execute().await?;
+            keys.extend(page.keys.into_iter().map(|k| k.name));
+            match page.cursor {
+                Some(next) if !page.list_complete => cursor = Some(next),
+                _ => return Ok(keys),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MemoryCounters(RefCell<BTreeMap<String, u64>>);
+
+    impl CounterStore for MemoryCounters {
+        async fn get(&self, key: &str) -> worker::Result<Option<u64>> {
+            Ok(self.0.borrow().get(key).copied())
+        }
+
+        async fn put(&self, key: &str, value: u64) -> worker::Result<()> {
+            self.0.borrow_mut().insert(key.to_string(), value);
+            Ok(())
+        }
+
+        async fn keys(&self, prefix: &str) -> worker::Result<Vec<String>> {
+            Ok(self
+                .0
+                .borrow()
+                .keys()
+                .filter(|k| k.starts_with(prefix))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_hit_from_range() {
+        assert_eq!(Hit::from_range(None), Some(Hit::Download));
+        assert_eq!(Hit::from_range(Some("bytes=0-")), Some(Hit::Play));
+        assert_eq!(Hit::from_range(Some("bytes=0-1023")), Some(Hit::Play));
+        assert_eq!(Hit::from_range(Some("bytes=5000-")), None);
+    }
+
+    #[test]
+    fn test_stream_request_increments_stats() {
+        futures::executor::block_on(async {
+            let store = MemoryCounters::default();
+            assert!(collect(&store).await.unwrap().is_empty());
+
+            for range in [
+                Some("bytes=0-"),
+                Some("bytes=65536-"),
+                Some("bytes=0-"),
+                None,
+            ] {
+                if let Some(hit) = Hit::from_range(range) {
+                    record(&store, "01-track.flac", hit).await.unwrap();
+                }
+            }
+
+            let stats = collect(&store).await.unwrap();
+            assert_eq!(
+                stats.get("01-track.flac"),
+                Some(&TrackStats {
+                    plays: 2,
+                    downloads: 1
+                })
+            );
+            assert_eq!(
+                serde_json::to_value(&stats).unwrap(),
+                serde_json::json!({ "01-track.flac": { "plays": 2, "downloads": 1 } })
+            );
+        });
+    }
+}